@@ -7,7 +7,9 @@ use gpui::{
     App, AppContext, AsyncApp, Context, Entity, SharedString, Subscription, Task, WeakEntity,
 };
 use language::{Anchor, Buffer, BufferEvent, Point, ToOffset, ToPoint};
-use project::{Project, ProjectItem, lsp_store::OpenLspBufferHandle};
+use project::{
+    LocalHistoryReason, LocalHistoryStore, Project, ProjectItem, lsp_store::OpenLspBufferHandle,
+};
 use std::{cmp, ops::Range, sync::Arc};
 use text::{Edit, Patch, Rope};
 use util::{RangeExt, ResultExt as _};
@@ -526,13 +528,47 @@ impl ActionLog {
             linked_action_log.update(cx, |log, cx| log.buffer_edited(buffer.clone(), cx));
         }
         let new_version = buffer.read(cx).version();
+        let abs_path = buffer
+            .read(cx)
+            .file()
+            .filter(|file| !file.is_private())
+            .and_then(|file| file.as_local())
+            .map(|file| file.abs_path(cx));
         let tracked_buffer = self.track_buffer_internal(buffer, false, cx);
+        // Keep the contents from before the agent's first unreviewed edit in the local history.
+        let content_before_edits = if tracked_buffer.unreviewed_edits.is_empty() {
+            match &tracked_buffer.status {
+                TrackedBufferStatus::Created {
+                    existing_file_content,
+                } => existing_file_content.clone(),
+                TrackedBufferStatus::Modified | TrackedBufferStatus::Deleted => {
+                    Some(tracked_buffer.diff_base.clone())
+                }
+            }
+        } else {
+            None
+        };
         if let TrackedBufferStatus::Deleted = tracked_buffer.status {
             tracked_buffer.status = TrackedBufferStatus::Modified;
         }
 
         tracked_buffer.version = new_version;
         tracked_buffer.schedule_diff_update(ChangeAuthor::Agent, cx);
+
+        if let Some((abs_path, content)) = abs_path.zip(content_before_edits)
+            && let Some(local_history) = LocalHistoryStore::try_global(cx)
+        {
+            local_history
+                .update(cx, |local_history, cx| {
+                    local_history.snapshot_text(
+                        abs_path.into(),
+                        content,
+                        LocalHistoryReason::BeforeAgentEdit,
+                        cx,
+                    )
+                })
+                .detach_and_log_err(cx);
+        }
     }
 
     pub fn will_delete_buffer(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
@@ -1200,7 +1236,7 @@ mod tests {
     use rand::prelude::*;
     use serde_json::json;
     use settings::SettingsStore;
-    use std::env;
+    use std::{env, path::Path};
     use util::{RandomCharIter, path};
 
     #[ctor::ctor]
@@ -2657,6 +2693,60 @@ mod tests {
         assert!(!action_log.read_with(cx, |log, _| log.has_pending_undo()));
     }
 
+    #[gpui::test]
+    async fn test_local_history_before_agent_edits(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({"file": "abc\ndef", ".env": "SECRET=1"}),
+        )
+        .await;
+        let local_history = cx.update(|cx| LocalHistoryStore::init_global(fs.clone(), cx));
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let action_log = cx.new(|_| ActionLog::new(project.clone()));
+
+        for file_name in ["file", ".env"] {
+            let file_path = project
+                .read_with(cx, |project, cx| {
+                    project.find_project_path(format!("dir/{file_name}"), cx)
+                })
+                .unwrap();
+            let buffer = project
+                .update(cx, |project, cx| project.open_buffer(file_path, cx))
+                .await
+                .unwrap();
+            cx.update(|cx| {
+                action_log.update(cx, |log, cx| log.buffer_read(buffer.clone(), cx));
+                buffer.update(cx, |buffer, cx| {
+                    buffer
+                        .edit([(Point::new(0, 0)..Point::new(0, 1), "A")], None, cx)
+                        .unwrap()
+                });
+                action_log.update(cx, |log, cx| log.buffer_edited(buffer.clone(), cx));
+            });
+        }
+        cx.run_until_parked();
+
+        // Private files are never copied into the local history.
+        local_history.read_with(cx, |local_history, _| {
+            assert_eq!(
+                local_history
+                    .entries_for_path(Path::new(path!("/dir/file")))
+                    .map(|entry| entry.reason)
+                    .collect::<Vec<_>>(),
+                [LocalHistoryReason::BeforeAgentEdit]
+            );
+            assert_eq!(
+                local_history
+                    .entries_for_path(Path::new(path!("/dir/.env")))
+                    .count(),
+                0
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_linked_action_log_buffer_read(cx: &mut TestAppContext) {
         init_test(cx);
@@ -707,6 +707,7 @@ impl EditAgent {
             tools,
             stop: Vec::new(),
             temperature: None,
+            max_tokens: None,
             thinking_allowed: self.thinking_allowed,
             thinking_effort: None,
             speed: None,
@@ -9,6 +9,7 @@ mod config_options;
 pub(crate) mod connection_view;
 mod context;
 mod context_server_configuration;
+mod context_server_requests;
 mod entry_view_state;
 mod favorite_models;
 mod inline_assistant;
@@ -297,6 +298,7 @@ pub fn init(
     assistant_slash_command::init(cx);
     agent_panel::init(cx);
     context_server_configuration::init(language_registry.clone(), fs.clone(), cx);
+    context_server_requests::init(cx);
     TextThreadEditor::init(cx);
 
     register_slash_commands(cx);
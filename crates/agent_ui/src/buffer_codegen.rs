@@ -544,6 +544,7 @@ impl CodegenAlternative {
                 tool_choice,
                 stop: Vec::new(),
                 temperature,
+                max_tokens: None,
                 messages,
                 thinking_allowed: false,
                 thinking_effort: None,
@@ -624,6 +625,7 @@ impl CodegenAlternative {
                 tool_choice: None,
                 stop: Vec::new(),
                 temperature,
+                max_tokens: None,
                 messages: vec![request_message],
                 thinking_allowed: false,
                 thinking_effort: None,
@@ -0,0 +1,562 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use context_server::{
+    ContextServerClientDelegate, ContextServerId,
+    types::{
+        CreateMessageRequest, CreateMessageResult, ElicitAction, ElicitRequestParams, ElicitResult,
+        MessageContent, PrimitiveSchemaDefinition, Role as McpRole,
+    },
+};
+use futures::{StreamExt as _, channel::oneshot};
+use gpui::{
+    AsyncApp, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, PromptLevel, Task,
+};
+use language_model::{
+    LanguageModelImage, LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage,
+    Role,
+};
+use ui::{
+    Banner, Checkbox, KeyBinding, Modal, ModalFooter, ModalHeader, Section, ToggleState, prelude::*,
+};
+use ui_input::InputField;
+use workspace::{ModalView, MultiWorkspace};
+
+pub(crate) fn init(cx: &mut App) {
+    context_server::set_client_delegate(Arc::new(ContextServerRequests), cx);
+}
+
+/// Answers sampling requests with the default language model, after the user allowed it, and
+/// elicitation requests with a form built from the server's schema.
+struct ContextServerRequests;
+
+impl ContextServerClientDelegate for ContextServerRequests {
+    fn create_message(
+        &self,
+        server_id: ContextServerId,
+        request: CreateMessageRequest,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<CreateMessageResult>> {
+        cx.spawn(async move |cx| {
+            let model = cx
+                .update(|cx| LanguageModelRegistry::read_global(cx).default_model())
+                .context("no language model is configured")?;
+
+            let window = cx
+                .update(|cx| cx.active_window().or_else(|| cx.windows().first().copied()))
+                .context("no window is open")?;
+            let message = format!(
+                "The {server_id} MCP server wants to use {}",
+                model.model.name().0
+            );
+            let detail = request
+                .messages
+                .iter()
+                .rev()
+                .find_map(|message| match &message.content {
+                    MessageContent::Text { text, .. } => Some(text.clone()),
+                    _ => None,
+                })
+                .unwrap_or_default();
+            let answer = window.update(cx, |_, window, cx| {
+                window.prompt(
+                    PromptLevel::Info,
+                    &message,
+                    Some(&detail),
+                    &["Allow", "Deny"],
+                    cx,
+                )
+            })?;
+            anyhow::ensure!(
+                matches!(answer.await, Ok(0)),
+                "the user denied the sampling request"
+            );
+
+            let request = language_model_request(request)?;
+            let mut response = model.model.stream_completion_text(request, cx).await?;
+            let mut text = String::new();
+            while let Some(chunk) = response.stream.next().await {
+                text.push_str(&chunk?);
+            }
+
+            Ok(CreateMessageResult {
+                role: McpRole::Assistant,
+                content: MessageContent::Text {
+                    text,
+                    annotations: None,
+                },
+                model: model.model.id().0.to_string(),
+                stop_reason: Some("endTurn".into()),
+            })
+        })
+    }
+
+    fn elicit(
+        &self,
+        server_id: ContextServerId,
+        request: ElicitRequestParams,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<ElicitResult>> {
+        cx.spawn(async move |cx| {
+            let window = cx
+                .update(|cx| {
+                    cx.active_window()
+                        .and_then(|window| window.downcast::<MultiWorkspace>())
+                        .or_else(|| workspace::local_workspace_windows(cx).into_iter().next())
+                })
+                .context("no workspace is open")?;
+
+            let (response_tx, rx) = oneshot::channel();
+            let request = ElicitationRequest {
+                server_id,
+                params: request,
+                response_tx,
+            };
+            window.update(cx, |multi_workspace, window, cx| {
+                multi_workspace.workspace().update(cx, |workspace, cx| {
+                    // Opening another modal would dismiss the open form, so queue the request
+                    // behind it instead.
+                    if let Some(modal) = workspace.active_modal::<ElicitationModal>(cx) {
+                        modal.update(cx, |modal, _| modal.queue.push_back(request));
+                    } else {
+                        workspace.toggle_modal(window, cx, |window, cx| {
+                            ElicitationModal::new(request, window, cx)
+                        })
+                    }
+                })
+            })?;
+
+            // The form being dismissed without an answer counts as the user cancelling it.
+            Ok(rx.await.unwrap_or(ElicitResult {
+                action: ElicitAction::Cancel,
+                content: None,
+            }))
+        })
+    }
+}
+
+fn language_model_request(request: CreateMessageRequest) -> Result<LanguageModelRequest> {
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = request.system_prompt {
+        messages.push(LanguageModelRequestMessage {
+            role: Role::System,
+            content: vec![system_prompt.into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+    for message in request.messages {
+        let content = match message.content {
+            MessageContent::Text { text, .. } => text.into(),
+            MessageContent::Image {
+                data, mime_type, ..
+            } if mime_type == "image/png" => {
+                language_model::MessageContent::Image(LanguageModelImage {
+                    source: data.into(),
+                    size: None,
+                })
+            }
+            MessageContent::Image { mime_type, .. } | MessageContent::Audio { mime_type, .. } => {
+                anyhow::bail!("unsupported sampling content type {mime_type}")
+            }
+            MessageContent::Resource { .. } => {
+                anyhow::bail!("embedded resources are not supported in sampling requests")
+            }
+        };
+        messages.push(LanguageModelRequestMessage {
+            role: match message.role {
+                McpRole::User => Role::User,
+                McpRole::Assistant => Role::Assistant,
+            },
+            content: vec![content],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+
+    Ok(LanguageModelRequest {
+        messages,
+        stop: request.stop_sequences.unwrap_or_default(),
+        temperature: request.temperature.map(|temperature| temperature as f32),
+        max_tokens: Some(request.max_tokens.into()),
+        ..Default::default()
+    })
+}
+
+enum FieldInput {
+    Text(Entity<InputField>),
+    Toggle(ToggleState),
+    Choice {
+        values: Vec<String>,
+        labels: Vec<String>,
+        selected: Option<usize>,
+    },
+}
+
+struct ElicitationField {
+    name: String,
+    label: SharedString,
+    required: bool,
+    schema: PrimitiveSchemaDefinition,
+    input: FieldInput,
+}
+
+impl ElicitationField {
+    fn new(
+        name: String,
+        schema: PrimitiveSchemaDefinition,
+        required: bool,
+        tab_index: isize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self {
+        let label: SharedString = schema.title().unwrap_or(&name).to_string().into();
+        let input = match &schema {
+            PrimitiveSchemaDefinition::Boolean { default, .. } => {
+                FieldInput::Toggle(default.unwrap_or(false).into())
+            }
+            PrimitiveSchemaDefinition::String {
+                enum_values: Some(values),
+                enum_names,
+                ..
+            } => FieldInput::Choice {
+                values: values.clone(),
+                labels: enum_names.clone().unwrap_or_else(|| values.clone()),
+                selected: None,
+            },
+            _ => FieldInput::Text(cx.new(|cx| {
+                InputField::new(window, cx, schema.description().unwrap_or_default())
+                    .label(label.clone())
+                    .tab_index(tab_index)
+                    .tab_stop(true)
+            })),
+        };
+        Self {
+            name,
+            label,
+            required,
+            schema,
+            input,
+        }
+    }
+
+    fn value(&self, cx: &App) -> Result<Option<serde_json::Value>, SharedString> {
+        let text = match &self.input {
+            FieldInput::Toggle(state) => return Ok(Some(state.selected().into())),
+            FieldInput::Choice {
+                values, selected, ..
+            } => {
+                return match selected {
+                    Some(ix) => Ok(Some(values[*ix].clone().into())),
+                    None if self.required => Err(format!("{} is required", self.label).into()),
+                    None => Ok(None),
+                };
+            }
+            FieldInput::Text(input) => input.read(cx).text(cx),
+        };
+        if text.is_empty() {
+            return if self.required {
+                Err(format!("{} is required", self.label).into())
+            } else {
+                Ok(None)
+            };
+        }
+
+        let label = &self.label;
+        let value: serde_json::Value = match &self.schema {
+            PrimitiveSchemaDefinition::String {
+                min_length,
+                max_length,
+                ..
+            } => {
+                let length = text.chars().count() as u32;
+                if min_length.is_some_and(|min_length| length < min_length) {
+                    return Err(format!("{label} is too short").into());
+                }
+                if max_length.is_some_and(|max_length| length > max_length) {
+                    return Err(format!("{label} is too long").into());
+                }
+                text.into()
+            }
+            PrimitiveSchemaDefinition::Number {
+                minimum, maximum, ..
+            }
+            | PrimitiveSchemaDefinition::Integer {
+                minimum, maximum, ..
+            } => {
+                let number = text
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| SharedString::from(format!("{label} must be a number")))?;
+                if minimum.is_some_and(|minimum| number < minimum)
+                    || maximum.is_some_and(|maximum| number > maximum)
+                {
+                    return Err(format!("{label} is out of range").into());
+                }
+                if matches!(self.schema, PrimitiveSchemaDefinition::Integer { .. }) {
+                    if number.fract() != 0. {
+                        return Err(format!("{label} must be an integer").into());
+                    }
+                    (number as i64).into()
+                } else {
+                    number.into()
+                }
+            }
+            PrimitiveSchemaDefinition::Boolean { .. } => unreachable!(),
+        };
+        Ok(Some(value))
+    }
+}
+
+struct ElicitationRequest {
+    server_id: ContextServerId,
+    params: ElicitRequestParams,
+    response_tx: oneshot::Sender<ElicitResult>,
+}
+
+struct ElicitationModal {
+    server_id: ContextServerId,
+    message: SharedString,
+    fields: Vec<ElicitationField>,
+    response_tx: Option<oneshot::Sender<ElicitResult>>,
+    /// Requests received while this form is open, shown once it is answered.
+    queue: VecDeque<ElicitationRequest>,
+    focus_handle: FocusHandle,
+    last_error: Option<SharedString>,
+}
+
+impl ElicitationModal {
+    fn new(request: ElicitationRequest, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            server_id: request.server_id.clone(),
+            message: SharedString::default(),
+            fields: Vec::new(),
+            response_tx: None,
+            queue: VecDeque::new(),
+            focus_handle: cx.focus_handle(),
+            last_error: None,
+        };
+        this.show_request(request, window, cx);
+        this
+    }
+
+    fn show_request(
+        &mut self,
+        request: ElicitationRequest,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let schema = request.params.requested_schema;
+        let mut properties = schema.properties.into_iter().collect::<Vec<_>>();
+        // Schema properties are unordered, so show the required ones first.
+        properties.sort_by_cached_key(|(name, _)| (!schema.required.contains(name), name.clone()));
+        self.fields = properties
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (name, field_schema))| {
+                let required = schema.required.contains(&name);
+                ElicitationField::new(name, field_schema, required, ix as isize, window, cx)
+            })
+            .collect();
+        self.server_id = request.server_id;
+        self.message = request.params.message.into();
+        self.response_tx = Some(request.response_tx);
+        self.last_error = None;
+    }
+
+    fn respond(&mut self, result: ElicitResult, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(response_tx) = self.response_tx.take() {
+            response_tx.send(result).ok();
+        }
+        if let Some(request) = self.queue.pop_front() {
+            self.show_request(request, window, cx);
+            self.focus_handle.focus(window, cx);
+            cx.notify();
+        } else {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let mut content = collections::HashMap::default();
+        for field in &self.fields {
+            match field.value(cx) {
+                Ok(Some(value)) => {
+                    content.insert(field.name.clone(), value);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    self.last_error = Some(error);
+                    cx.notify();
+                    return;
+                }
+            }
+        }
+        self.respond(
+            ElicitResult {
+                action: ElicitAction::Accept,
+                content: Some(content),
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn decline(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(
+            ElicitResult {
+                action: ElicitAction::Decline,
+                content: None,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.respond(
+            ElicitResult {
+                action: ElicitAction::Cancel,
+                content: None,
+            },
+            window,
+            cx,
+        );
+    }
+
+    fn render_field(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let field = &self.fields[ix];
+        match &field.input {
+            FieldInput::Text(input) => input.clone().into_any_element(),
+            FieldInput::Toggle(state) => Checkbox::new(("elicitation-toggle", ix), *state)
+                .label(field.label.clone())
+                .on_click(cx.listener(move |this, checked, _window, cx| {
+                    this.fields[ix].input = FieldInput::Toggle(*checked);
+                    cx.notify();
+                }))
+                .into_any_element(),
+            FieldInput::Choice {
+                labels, selected, ..
+            } => v_flex()
+                .gap_1()
+                .child(Label::new(field.label.clone()).size(LabelSize::Small))
+                .child(
+                    h_flex()
+                        .flex_wrap()
+                        .gap_1()
+                        .children(labels.iter().enumerate().map(|(option_ix, label)| {
+                            Button::new(
+                                SharedString::from(format!("elicitation-choice-{ix}-{option_ix}")),
+                                label.clone(),
+                            )
+                            .style(ButtonStyle::Outlined)
+                            .label_size(LabelSize::Small)
+                            .toggle_state(*selected == Some(option_ix))
+                            .on_click(cx.listener(
+                                move |this, _, _window, cx| {
+                                    if let FieldInput::Choice { selected, .. } =
+                                        &mut this.fields[ix].input
+                                    {
+                                        *selected = Some(option_ix);
+                                    }
+                                    cx.notify();
+                                },
+                            ))
+                        })),
+                )
+                .into_any_element(),
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for ElicitationModal {}
+
+impl Focusable for ElicitationModal {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for ElicitationModal {}
+
+impl Render for ElicitationModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle(cx);
+
+        v_flex()
+            .id("elicitation-modal")
+            .key_context("ElicitationModal")
+            .w(rems(34.))
+            .elevation_3(cx)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .capture_any_mouse_down(cx.listener(|this, _, window, cx| {
+                this.focus_handle(cx).focus(window, cx);
+            }))
+            .child(
+                Modal::new("elicitation", None)
+                    .header(
+                        ModalHeader::new()
+                            .headline(format!("Input Requested by {}", self.server_id))
+                            .description(self.message.clone()),
+                    )
+                    .when_some(self.last_error.clone(), |this, error| {
+                        this.section(
+                            Section::new().child(
+                                Banner::new()
+                                    .severity(Severity::Warning)
+                                    .child(div().text_xs().child(error)),
+                            ),
+                        )
+                    })
+                    .child(
+                        v_flex()
+                            .tab_group()
+                            .pl_3()
+                            .pr_4()
+                            .pb_2()
+                            .gap_2()
+                            .children((0..self.fields.len()).map(|ix| self.render_field(ix, cx))),
+                    )
+                    .footer(
+                        ModalFooter::new().end_slot(
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Button::new("cancel", "Cancel")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Cancel,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.cancel(&menu::Cancel, window, cx)
+                                        })),
+                                )
+                                .child(Button::new("decline", "Decline").on_click(
+                                    cx.listener(|this, _event, window, cx| {
+                                        this.decline(window, cx)
+                                    }),
+                                ))
+                                .child(
+                                    Button::new("submit", "Submit")
+                                        .key_binding(
+                                            KeyBinding::for_action_in(
+                                                &menu::Confirm,
+                                                &focus_handle,
+                                                cx,
+                                            )
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                        )
+                                        .on_click(cx.listener(|this, _event, window, cx| {
+                                            this.confirm(&menu::Confirm, window, cx)
+                                        })),
+                                ),
+                        ),
+                    ),
+            )
+    }
+}
@@ -2273,6 +2273,7 @@ impl TextThread {
             tool_choice: None,
             stop: Vec::new(),
             temperature: model.and_then(|model| AgentSettings::temperature_for_model(model, cx)),
+            max_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
             speed: None,
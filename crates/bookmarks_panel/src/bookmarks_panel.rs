@@ -0,0 +1,676 @@
+mod bookmarks_panel_settings;
+
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use bookmarks_panel_settings::{BookmarksPanelSettings, DockSide};
+use db::kvp::KEY_VALUE_STORE;
+use editor::{
+    Editor, EditorEvent, SelectionEffects,
+    actions::{SelectAll, ToggleBookmark},
+    scroll::Autoscroll,
+};
+use gpui::{
+    Action, App, AsyncWindowContext, ClickEvent, Context, Div, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, Pixels, Render, ScrollStrategy, Stateful, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, uniform_list,
+};
+use language::{Buffer, Point, ToPoint as _};
+use menu::{Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    Fs, Project,
+    bookmark_store::{Bookmark, BookmarkStore},
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, Tab, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    bookmarks_panel,
+    [
+        /// Removes every bookmark in the project.
+        ClearBookmarks,
+        /// Edits the label of the selected bookmark.
+        EditSelectedBookmarkLabel,
+        /// Removes the selected bookmark.
+        RemoveSelectedBookmark,
+        /// Toggles focus on the bookmarks panel.
+        ToggleFocus,
+    ]
+);
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+
+struct BookmarkEntry {
+    path: Arc<Path>,
+    buffer: Entity<Buffer>,
+    bookmark: Bookmark,
+}
+
+pub struct BookmarksPanel {
+    fs: Arc<dyn Fs>,
+    bookmark_store: Entity<BookmarkStore>,
+    workspace: WeakEntity<Workspace>,
+    width: Option<Pixels>,
+    active: bool,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    entries: Vec<BookmarkEntry>,
+    selected_entry: Option<usize>,
+    label_editor: Entity<Editor>,
+    /// The entry whose label is being edited with the label editor.
+    editing_entry: Option<usize>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    width: Option<Pixels>,
+    active: Option<bool>,
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, _: &ClearBookmarks, _, cx| {
+            clear_bookmarks(workspace.project(), cx);
+        });
+    })
+    .detach();
+}
+
+fn clear_bookmarks(project: &Entity<Project>, cx: &mut App) {
+    project
+        .read(cx)
+        .bookmark_store()
+        .update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+}
+
+impl BookmarksPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| {
+                BookmarksPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading bookmarks panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedBookmarksPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update_in(&mut cx, |workspace, window, cx| {
+            let panel = Self::new(workspace, window, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    panel.active = serialized_panel.active.unwrap_or(false);
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let bookmark_store = workspace.project().read(cx).bookmark_store();
+        let workspace = cx.entity().downgrade();
+        cx.new(|cx| {
+            let label_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Bookmark label…", window, cx);
+                editor
+            });
+            let subscriptions = vec![
+                cx.observe(&bookmark_store, |panel, _, cx| panel.update_entries(cx)),
+                cx.subscribe_in(
+                    &label_editor,
+                    window,
+                    |panel, _, event: &EditorEvent, window, cx| {
+                        if let EditorEvent::Blurred = event {
+                            panel.stop_editing_label(true, window, cx);
+                        }
+                    },
+                ),
+            ];
+            let mut panel = Self {
+                fs,
+                bookmark_store,
+                workspace,
+                width: None,
+                active: false,
+                focus_handle: cx.focus_handle(),
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                entries: Vec::new(),
+                selected_entry: None,
+                label_editor,
+                editing_entry: None,
+                _subscriptions: subscriptions,
+            };
+            panel.update_entries(cx);
+            panel
+        })
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", BOOKMARKS_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| {
+                BookmarksPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        let active = Some(self.active);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedBookmarksPanel { width, active })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn dispatch_context(&self, window: &Window, cx: &Context<Self>) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("BookmarksPanel");
+        dispatch_context.add("menu");
+        let identifier = if self.label_editor.focus_handle(cx).is_focused(window) {
+            "editing"
+        } else {
+            "not_editing"
+        };
+        dispatch_context.add(identifier);
+        dispatch_context
+    }
+
+    /// Rebuilds the entries from the bookmark store, keeping the selected and edited bookmarks.
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        let selected = self.selected_entry.and_then(|ix| self.entry_key(ix));
+        let editing = self.editing_entry.and_then(|ix| self.entry_key(ix));
+
+        self.entries = self
+            .bookmark_store
+            .read(cx)
+            .all_bookmarks()
+            .map(|(path, buffer, bookmark)| BookmarkEntry {
+                path: path.clone(),
+                buffer: buffer.clone(),
+                bookmark: bookmark.clone(),
+            })
+            .collect();
+
+        let position_of = |key: Option<(Entity<Buffer>, language::Anchor)>| {
+            let (buffer, position) = key?;
+            self.entries
+                .iter()
+                .position(|entry| entry.buffer == buffer && entry.bookmark.position == position)
+        };
+        self.editing_entry = position_of(editing);
+        self.selected_entry = position_of(selected).or_else(|| {
+            let previous = self.selected_entry?;
+            self.entries
+                .len()
+                .checked_sub(1)
+                .map(|last| previous.min(last))
+        });
+        cx.notify();
+    }
+
+    fn entry_key(&self, ix: usize) -> Option<(Entity<Buffer>, language::Anchor)> {
+        let entry = self.entries.get(ix)?;
+        Some((entry.buffer.clone(), entry.bookmark.position))
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let next = match self.selected_entry {
+            Some(ix) => ix + 1,
+            None => 0,
+        };
+        if next < self.entries.len() {
+            self.select_entry(next, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let previous = match self.selected_entry {
+            Some(ix) => ix.saturating_sub(1),
+            None => self.entries.len().saturating_sub(1),
+        };
+        if previous < self.entries.len() {
+            self.select_entry(previous, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_entry(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(last) = self.entries.len().checked_sub(1) {
+            self.select_entry(last, cx);
+        }
+    }
+
+    fn select_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_entry.is_some() {
+            self.stop_editing_label(true, window, cx);
+        } else if let Some(selected) = self.selected_entry {
+            self.open_entry(selected, window, cx);
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_entry.is_some() {
+            self.stop_editing_label(false, window, cx);
+        }
+    }
+
+    /// Opens the bookmarked line in the editor, focusing it.
+    fn open_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let buffer = entry.buffer.clone();
+        let position = entry.bookmark.position;
+        let editor = workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.open_project_item::<Editor>(pane, buffer, true, true, true, true, window, cx)
+        });
+        editor.update(cx, |editor, cx| {
+            let Some(position) = editor
+                .buffer()
+                .read(cx)
+                .snapshot(cx)
+                .as_singleton_anchor(position)
+            else {
+                return;
+            };
+            editor.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()),
+                window,
+                cx,
+                |selections| selections.select_anchor_ranges([position..position]),
+            );
+        });
+    }
+
+    fn remove_selected_bookmark(
+        &mut self,
+        _: &RemoveSelectedBookmark,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(selected) = self.selected_entry {
+            self.remove_entry(selected, cx);
+        }
+    }
+
+    fn remove_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some((buffer, position)) = self.entry_key(ix) else {
+            return;
+        };
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.remove_bookmark(&buffer, position, cx);
+        });
+    }
+
+    fn edit_selected_bookmark_label(
+        &mut self,
+        _: &EditSelectedBookmarkLabel,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(selected) = self.selected_entry {
+            self.edit_label(selected, window, cx);
+        }
+    }
+
+    fn edit_label(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let label = entry
+            .bookmark
+            .label
+            .as_ref()
+            .map(|label| label.to_string())
+            .unwrap_or_default();
+        self.selected_entry = Some(ix);
+        self.editing_entry = Some(ix);
+        self.label_editor.update(cx, |editor, cx| {
+            editor.set_text(label, window, cx);
+            editor.select_all(&SelectAll, window, cx);
+        });
+        window.focus(&self.label_editor.focus_handle(cx), cx);
+        cx.notify();
+    }
+
+    /// Stops editing the label, saving the edited one if `save` is true.
+    fn stop_editing_label(&mut self, save: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.editing_entry.take() else {
+            return;
+        };
+        if save && let Some((buffer, position)) = self.entry_key(ix) {
+            let label = self.label_editor.read(cx).text(cx);
+            self.bookmark_store.update(cx, |bookmark_store, cx| {
+                bookmark_store.set_label(&buffer, position, Some(label.into()), cx);
+            });
+        }
+        if self.label_editor.focus_handle(cx).is_focused(window) {
+            window.focus(&self.focus_handle, cx);
+        }
+        cx.notify();
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> Div {
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .h(Tab::container_height(cx))
+            .px_2()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new("Bookmarks").color(Color::Muted))
+            .child(
+                IconButton::new("clear-bookmarks", IconName::Trash)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.entries.is_empty())
+                    .tooltip(move |_window, cx| {
+                        Tooltip::for_action_in(
+                            "Clear All Bookmarks",
+                            &ClearBookmarks,
+                            &focus_handle,
+                            cx,
+                        )
+                    })
+                    .on_click(cx.listener(|panel, _, _, cx| {
+                        panel
+                            .bookmark_store
+                            .update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+                    })),
+            )
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> ListItem {
+        let entry = &self.entries[ix];
+        let buffer = entry.buffer.read(cx);
+        let row = entry.bookmark.position.to_point(buffer).row;
+        let file_name = buffer
+            .file()
+            .map(|file| file.file_name(cx).to_string())
+            .or_else(|| {
+                entry
+                    .path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            })
+            .unwrap_or_default();
+        let line = buffer
+            .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+            .collect::<String>();
+        let line = line.trim().to_string();
+        let is_editing = self.editing_entry == Some(ix);
+
+        ListItem::new(("bookmark-entry", ix))
+            .toggle_state(self.selected_entry == Some(ix))
+            .start_slot(
+                Icon::new(IconName::Bookmark)
+                    .size(IconSize::Small)
+                    .color(Color::Accent),
+            )
+            .on_click(cx.listener(move |panel, event: &ClickEvent, window, cx| {
+                panel.select_entry(ix, cx);
+                if event.click_count() > 1 {
+                    panel.open_entry(ix, window, cx);
+                }
+            }))
+            .end_hover_slot(
+                h_flex()
+                    .gap_0p5()
+                    .child(
+                        IconButton::new(("edit-bookmark-label", ix), IconName::Pencil)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Edit Label"))
+                            .on_click(cx.listener(move |panel, _, window, cx| {
+                                panel.edit_label(ix, window, cx);
+                            })),
+                    )
+                    .child(
+                        IconButton::new(("remove-bookmark", ix), IconName::Close)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Remove Bookmark"))
+                            .on_click(cx.listener(move |panel, _, _, cx| {
+                                panel.remove_entry(ix, cx);
+                            })),
+                    ),
+            )
+            .child(
+                v_flex()
+                    .w_full()
+                    .overflow_hidden()
+                    .map(|column| {
+                        if is_editing {
+                            column.child(self.label_editor.clone())
+                        } else if let Some(label) = entry.bookmark.label.clone() {
+                            column.child(Label::new(label).truncate())
+                        } else {
+                            column.child(Label::new(line.clone()).buffer_font(cx).truncate())
+                        }
+                    })
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .child(
+                                Label::new(format!("{file_name}:{}", row + 1))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .when(entry.bookmark.label.is_some() || is_editing, |location| {
+                                location.child(
+                                    Label::new(line)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .buffer_font(cx)
+                                        .truncate(),
+                                )
+                            }),
+                    ),
+            )
+    }
+
+    fn render_empty_state(&self, window: &mut Window, cx: &mut Context<Self>) -> Stateful<Div> {
+        let toggle_bookmark = window.keystroke_text_for(&ToggleBookmark);
+        v_flex()
+            .id("empty-bookmarks-state")
+            .gap_0p5()
+            .flex_1()
+            .justify_center()
+            .size_full()
+            .child(h_flex().justify_center().child(Label::new("No bookmarks")))
+            .child(h_flex().justify_center().child(
+                Label::new(format!("Toggle a Bookmark With {toggle_bookmark}")).color(Color::Muted),
+            ))
+            .child(h_flex().justify_center().child({
+                let keystroke = match self.position(window, cx) {
+                    DockPosition::Left => window.keystroke_text_for(&workspace::ToggleLeftDock),
+                    DockPosition::Bottom => window.keystroke_text_for(&workspace::ToggleBottomDock),
+                    DockPosition::Right => window.keystroke_text_for(&workspace::ToggleRightDock),
+                };
+                Label::new(format!("Toggle Panel With {keystroke}")).color(Color::Muted)
+            }))
+    }
+}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "Bookmarks Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        BOOKMARKS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match BookmarksPanelSettings::get_global(cx).dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.bookmarks_panel.get_or_insert_default().dock = Some(dock);
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| BookmarksPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+        cx.defer_in(window, |this, _, cx| {
+            this.serialize(cx);
+        });
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
+        Some("Bookmarks Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _window: &Window, _: &App) -> bool {
+        self.active
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active != active {
+            self.active = active;
+            self.serialize(cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        8
+    }
+}
+
+impl Focusable for BookmarksPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("bookmarks-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context(window, cx))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::remove_selected_bookmark))
+            .on_action(cx.listener(Self::edit_selected_bookmark_label))
+            .track_focus(&self.focus_handle)
+            .child(self.render_header(cx))
+            .map(|panel| {
+                if self.entries.is_empty() {
+                    panel.child(self.render_empty_state(window, cx))
+                } else {
+                    panel.child(
+                        uniform_list(
+                            "bookmark-entries",
+                            self.entries.len(),
+                            cx.processor(|panel, range: Range<usize>, _, cx| {
+                                range.map(|ix| panel.render_entry(ix, cx)).collect()
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(&self.scroll_handle),
+                    )
+                }
+            })
+    }
+}
@@ -22,6 +22,19 @@ pub enum CliRequest {
         env: Option<HashMap<String, String>>,
         user_data_dir: Option<String>,
     },
+    ListWorkspaces,
+    ListBuffers,
+    DispatchAction {
+        name: String,
+        /// JSON-encoded action arguments, as they would appear in a keymap.
+        arguments: Option<String>,
+    },
+    Diagnostics {
+        path: String,
+    },
+    RunTask {
+        label: String,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
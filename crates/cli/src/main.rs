@@ -8,7 +8,7 @@
 )]
 
 use anyhow::{Context as _, Result};
-use clap::Parser;
+use clap::{ArgGroup, Parser};
 use cli::{CliRequest, CliResponse, IpcHandshake, ipc::IpcOneShotServer};
 use parking_lot::Mutex;
 use std::{
@@ -59,7 +59,11 @@ Examples:
           Open your project in Zed
     `zed -n path-to-file `
           Open file/folder in a new window",
-    after_help = "To read from stdin, append '-', e.g. 'ps axf | zed -'"
+    after_help = "To read from stdin, append '-', e.g. 'ps axf | zed -'",
+    group(
+        ArgGroup::new("query")
+            .conflicts_with_all(["paths_with_position", "diff", "wait", "add", "new", "reuse"])
+    )
 )]
 struct Args {
     /// Wait for all of the given paths to be opened/closed before exiting.
@@ -130,6 +134,27 @@ struct Args {
     #[arg(long)]
     uninstall: bool,
 
+    /// Print the workspaces open in the running Zed instance as JSON.
+    #[arg(long, group = "query")]
+    list_workspaces: bool,
+    /// Print the buffers open in the running Zed instance as JSON.
+    #[arg(long, group = "query")]
+    list_buffers: bool,
+    /// Dispatch an action in the active Zed window, e.g. `workspace::SaveAll`.
+    ///
+    /// Uses the same action names as the command palette and keymaps.
+    #[arg(long, value_name = "ACTION", group = "query")]
+    action: Option<String>,
+    /// JSON arguments for the action passed with `--action`, e.g. `{"save_intent": "skip"}`.
+    #[arg(long, value_name = "JSON", requires = "action")]
+    action_args: Option<String>,
+    /// Print the diagnostics of the given file as JSON.
+    #[arg(long, value_name = "PATH", group = "query")]
+    diagnostics: Option<String>,
+    /// Run the task with the given label in the active workspace and exit with its status.
+    #[arg(long, value_name = "LABEL", group = "query")]
+    run_task: Option<String>,
+
     /// Used for SSH/Git password authentication, to remove the need for netcat as a dependency,
     /// by having Zed act like netcat communicating over a Unix socket.
     #[arg(long, hide = true)]
@@ -183,6 +208,41 @@ fn parse_path_with_position(argument_str: &str) -> anyhow::Result<String> {
     .map(|path_with_pos| path_with_pos.to_string(&|path| path.to_string_lossy().into_owned()))
 }
 
+/// Resolves a path argument to an absolute path, dropping any `:line:column` suffix.
+fn parse_path(argument_str: &str) -> anyhow::Result<String> {
+    let path = PathWithPosition::parse_str(argument_str).path;
+    let path = match fs::canonicalize(&path) {
+        Ok(path) => path,
+        Err(_) => env::current_dir()?.join(path),
+    };
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Returns the request for the query flags passed to the CLI, if any.
+fn query_request(args: &Args) -> anyhow::Result<Option<CliRequest>> {
+    let request = if args.list_workspaces {
+        CliRequest::ListWorkspaces
+    } else if args.list_buffers {
+        CliRequest::ListBuffers
+    } else if let Some(name) = &args.action {
+        CliRequest::DispatchAction {
+            name: name.clone(),
+            arguments: args.action_args.clone(),
+        }
+    } else if let Some(path) = &args.diagnostics {
+        CliRequest::Diagnostics {
+            path: parse_path(path)?,
+        }
+    } else if let Some(label) = &args.run_task {
+        CliRequest::RunTask {
+            label: label.clone(),
+        }
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(request))
+}
+
 fn expand_directory_diff_pairs(
     diff_pairs: Vec<[String; 2]>,
 ) -> anyhow::Result<(Vec<[String; 2]>, Vec<TempDir>)> {
@@ -401,6 +461,51 @@ mod tests {
         .unwrap();
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_query_request() {
+        let request = |args: &[&str]| {
+            let args =
+                Args::try_parse_from(std::iter::once("zed").chain(args.iter().copied())).unwrap();
+            query_request(&args).unwrap()
+        };
+
+        assert!(request(&["file.txt"]).is_none());
+        assert!(matches!(
+            request(&["--list-workspaces"]),
+            Some(CliRequest::ListWorkspaces)
+        ));
+        assert!(matches!(
+            request(&["--run-task", "build"]),
+            Some(CliRequest::RunTask { label }) if label == "build"
+        ));
+        assert!(matches!(
+            request(&["--action", "workspace::SaveAll", "--action-args", "{}"]),
+            Some(CliRequest::DispatchAction { name, arguments })
+                if name == "workspace::SaveAll" && arguments.as_deref() == Some("{}")
+        ));
+        assert!(
+            Args::try_parse_from(["zed", "--list-buffers", "file.txt"]).is_err(),
+            "queries can't be combined with paths"
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_request_drops_position() {
+        let temp_tree = TempTree::new(json!({
+            "file.rs": "",
+        }));
+        let file_path = temp_tree.path().join("file.rs");
+
+        let result = with_cwd(temp_tree.path(), || parse_path("file.rs")).unwrap();
+        assert_path_eq!(result, file_path.to_string_lossy());
+
+        let result = with_cwd(temp_tree.path(), || parse_path("file.rs:10")).unwrap();
+        assert_path_eq!(result, file_path.to_string_lossy());
+
+        let result = parse_path(&format!("{}:10:5", file_path.display())).unwrap();
+        assert_path_eq!(result, file_path.to_string_lossy());
+    }
 }
 
 fn parse_path_in_wsl(source: &str, wsl: &str) -> Result<String> {
@@ -570,6 +675,7 @@ fn main() -> Result<()> {
         }
     };
 
+    let query_request = query_request(&args)?;
     let exit_status = Arc::new(Mutex::new(None));
     let mut paths = vec![];
     let mut urls = vec![];
@@ -659,7 +765,7 @@ fn main() -> Result<()> {
                 #[cfg(not(target_os = "windows"))]
                 let wsl = None;
 
-                tx.send(CliRequest::Open {
+                tx.send(query_request.unwrap_or(CliRequest::Open {
                     paths,
                     urls,
                     diff_paths,
@@ -670,7 +776,7 @@ fn main() -> Result<()> {
                     reuse: args.reuse,
                     env,
                     user_data_dir: user_data_dir_for_thread,
-                })?;
+                }))?;
 
                 while let Ok(response) = rx.recv() {
                     match response {
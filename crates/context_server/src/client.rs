@@ -53,6 +53,7 @@ pub(crate) struct Client {
     name: Arc<str>,
     subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
     io_tasks: Mutex<Option<(Task<Option<()>>, Task<Option<()>>)>>,
@@ -101,7 +102,6 @@ struct AnyResponse<'a> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[allow(dead_code)]
 pub(crate) struct Response<T> {
     pub jsonrpc: &'static str,
     pub id: RequestId,
@@ -114,7 +114,6 @@ pub(crate) struct Response<T> {
 pub(crate) enum CspResult<T> {
     #[serde(rename = "result")]
     Ok(Option<T>),
-    #[allow(dead_code)]
     Error(Option<Error>),
 }
 
@@ -202,12 +201,14 @@ impl Client {
             let response_handlers = response_handlers.clone();
             let request_handlers = request_handlers.clone();
             let transport = transport.clone();
+            let outbound_tx = outbound_tx.clone();
             async move |cx| {
                 Self::handle_input(
                     transport,
                     subscription_set,
                     request_handlers,
                     response_handlers,
+                    outbound_tx,
                     cx,
                 )
                 .log_err()
@@ -238,6 +239,7 @@ impl Client {
             server_id,
             subscription_set,
             response_handlers,
+            request_handlers,
             name: server_name,
             next_id: Default::default(),
             outbound_tx,
@@ -255,11 +257,14 @@ impl Client {
     /// parses them as JSON-RPC responses or notifications, and dispatches them
     /// to the appropriate handlers. It processes both responses (which are matched
     /// to pending requests) and notifications (which trigger registered handlers).
+    /// Requests initiated by the server are answered by the handlers registered with
+    /// [`Client::on_request`], or with a "method not found" error.
     async fn handle_input(
         transport: Arc<dyn Transport>,
         subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
         request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+        outbound_tx: channel::Sender<String>,
         cx: &mut AsyncApp,
     ) -> anyhow::Result<()> {
         let mut receiver = transport.receive();
@@ -274,6 +279,16 @@ impl Client {
                         request.params.unwrap_or(RawValue::NULL),
                         cx.clone(),
                     );
+                } else {
+                    log::debug!("Unhandled request from context server: {}", request.method);
+                    send_response::<()>(
+                        &outbound_tx,
+                        request.id,
+                        Err(Error {
+                            message: format!("method not found: {}", request.method),
+                            code: METHOD_NOT_FOUND,
+                        }),
+                    );
                 }
             } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut()
@@ -465,6 +480,67 @@ impl Client {
             set: self.subscription_set.clone(),
         }
     }
+
+    /// Registers a handler for requests of type `T` sent by the context server.
+    ///
+    /// The task returned by the handler is awaited in the background, and its result is sent
+    /// back to the server as the response to the request.
+    pub fn on_request<T: crate::types::Request>(
+        &self,
+        mut handler: impl 'static + Send + FnMut(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        let outbound_tx = self.outbound_tx.clone();
+        self.request_handlers.lock().insert(
+            T::METHOD,
+            Box::new(move |id, params, cx| {
+                let params = match serde_json::from_str::<T::Params>(params.get()) {
+                    Ok(params) => params,
+                    Err(error) => {
+                        send_response::<()>(
+                            &outbound_tx,
+                            id,
+                            Err(Error {
+                                message: format!("invalid params for {}: {error}", T::METHOD),
+                                code: INVALID_PARAMS,
+                            }),
+                        );
+                        return;
+                    }
+                };
+                let response = handler(params, cx.clone());
+                let outbound_tx = outbound_tx.clone();
+                cx.background_spawn(async move {
+                    let result = response.await.map_err(|error| Error {
+                        message: format!("{error:#}"),
+                        code: INTERNAL_ERROR,
+                    });
+                    send_response(&outbound_tx, id, result);
+                })
+                .detach();
+            }),
+        );
+    }
+}
+
+fn send_response<T: Serialize>(
+    outbound_tx: &channel::Sender<String>,
+    id: RequestId,
+    result: Result<T, Error>,
+) {
+    let value = match result {
+        Ok(result) => CspResult::Ok(Some(result)),
+        Err(error) => CspResult::Error(Some(error)),
+    };
+    let response = serde_json::to_string(&Response {
+        jsonrpc: JSON_RPC_VERSION,
+        id,
+        value,
+    })
+    .unwrap();
+    outbound_tx
+        .try_send(response)
+        .context("failed to write to context server's stdin")
+        .log_err();
 }
 
 #[derive(Debug)]
@@ -15,7 +15,7 @@ use std::{fmt::Display, path::PathBuf};
 
 use anyhow::Result;
 use client::Client;
-use gpui::AsyncApp;
+use gpui::{App, AsyncApp, Global, Task};
 use parking_lot::RwLock;
 pub use settings::ContextServerCommand;
 use url::Url;
@@ -31,6 +31,42 @@ impl Display for ContextServerId {
     }
 }
 
+/// Handles the requests a context server sends to Zed.
+///
+/// Servers are only told that Zed supports sampling and elicitation once a delegate has been
+/// registered with [`set_client_delegate`].
+pub trait ContextServerClientDelegate: 'static + Send + Sync {
+    /// Asks a language model for a completion on behalf of the server.
+    fn create_message(
+        &self,
+        server_id: ContextServerId,
+        request: types::CreateMessageRequest,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<types::CreateMessageResult>>;
+
+    /// Asks the user for the structured input described by the request's schema.
+    fn elicit(
+        &self,
+        server_id: ContextServerId,
+        request: types::ElicitRequestParams,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<types::ElicitResult>>;
+}
+
+struct GlobalContextServerClientDelegate(Arc<dyn ContextServerClientDelegate>);
+
+impl Global for GlobalContextServerClientDelegate {}
+
+/// Sets the delegate used by context servers started from now on.
+pub fn set_client_delegate(delegate: Arc<dyn ContextServerClientDelegate>, cx: &mut App) {
+    cx.set_global(GlobalContextServerClientDelegate(delegate));
+}
+
+fn client_delegate(cx: &App) -> Option<Arc<dyn ContextServerClientDelegate>> {
+    cx.try_global::<GlobalContextServerClientDelegate>()
+        .map(|delegate| delegate.0.clone())
+}
+
 enum ContextServerTransport {
     Stdio(ContextServerCommand, Option<PathBuf>),
     Custom(Arc<dyn crate::transport::Transport>),
@@ -106,7 +142,7 @@ impl ContextServer {
     }
 
     pub async fn start(&self, cx: &AsyncApp) -> Result<()> {
-        self.initialize(self.new_client(cx)?).await
+        self.initialize(self.new_client(cx)?, cx).await
     }
 
     fn new_client(&self, cx: &AsyncApp) -> Result<Client> {
@@ -132,14 +168,34 @@ impl ContextServer {
         })
     }
 
-    async fn initialize(&self, client: Client) -> Result<()> {
+    async fn initialize(&self, client: Client, cx: &AsyncApp) -> Result<()> {
         log::debug!("starting context server {}", self.id);
+        let delegate = cx.update(|cx| client_delegate(cx));
+        if let Some(delegate) = &delegate {
+            client.on_request::<types::requests::CreateMessage>({
+                let delegate = delegate.clone();
+                let server_id = self.id();
+                move |request, mut cx| delegate.create_message(server_id.clone(), request, &mut cx)
+            });
+            client.on_request::<types::requests::Elicit>({
+                let delegate = delegate.clone();
+                let server_id = self.id();
+                move |request, mut cx| delegate.elicit(server_id.clone(), request, &mut cx)
+            });
+        }
+
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
             name: "Zed".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
         };
-        let initialized_protocol = protocol.initialize(client_info).await?;
+        let capabilities = types::ClientCapabilities {
+            experimental: None,
+            sampling: delegate.is_some().then(|| serde_json::json!({})),
+            elicitation: delegate.is_some().then(|| serde_json::json!({})),
+            roots: None,
+        };
+        let initialized_protocol = protocol.initialize(client_info, capabilities).await?;
 
         log::debug!(
             "context server {} initialized: {:?}",
@@ -159,3 +215,206 @@ impl ContextServer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ClientCapability;
+    use crate::test::{FakeTransport, create_fake_transport};
+    use crate::types::{
+        CreateMessageRequest, CreateMessageResult, ElicitAction, ElicitRequestParams, ElicitResult,
+        ElicitationSchema, MessageContent, PrimitiveSchemaDefinition, Role, SamplingMessage,
+        requests,
+    };
+    use gpui::TestAppContext;
+
+    struct FakeClientDelegate;
+
+    impl ContextServerClientDelegate for FakeClientDelegate {
+        fn create_message(
+            &self,
+            server_id: ContextServerId,
+            request: CreateMessageRequest,
+            _cx: &mut AsyncApp,
+        ) -> Task<Result<CreateMessageResult>> {
+            let prompt = request
+                .messages
+                .iter()
+                .filter_map(|message| match &message.content {
+                    MessageContent::Text { text, .. } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Task::ready(Ok(CreateMessageResult {
+                role: Role::Assistant,
+                content: MessageContent::Text {
+                    text: format!("{server_id} asked: {prompt}"),
+                    annotations: None,
+                },
+                model: "fake-model".into(),
+                stop_reason: Some("endTurn".into()),
+            }))
+        }
+
+        fn elicit(
+            &self,
+            _server_id: ContextServerId,
+            request: ElicitRequestParams,
+            _cx: &mut AsyncApp,
+        ) -> Task<Result<ElicitResult>> {
+            if request.message == "decline" {
+                return Task::ready(Ok(ElicitResult {
+                    action: ElicitAction::Decline,
+                    content: None,
+                }));
+            }
+            let content = request
+                .requested_schema
+                .properties
+                .into_iter()
+                .map(|(name, schema)| {
+                    let value = match schema {
+                        PrimitiveSchemaDefinition::Boolean { .. } => serde_json::json!(true),
+                        PrimitiveSchemaDefinition::Number { .. }
+                        | PrimitiveSchemaDefinition::Integer { .. } => serde_json::json!(42),
+                        PrimitiveSchemaDefinition::String { .. } => serde_json::json!(name),
+                    };
+                    (name, value)
+                })
+                .collect();
+            Task::ready(Ok(ElicitResult {
+                action: ElicitAction::Accept,
+                content: Some(content),
+            }))
+        }
+    }
+
+    async fn start_server(cx: &mut TestAppContext) -> (Arc<FakeTransport>, ContextServer) {
+        let transport = Arc::new(create_fake_transport("test-server", cx.executor()));
+        let server = ContextServer::new(ContextServerId("test-server".into()), transport.clone());
+        server.start(&cx.to_async()).await.unwrap();
+        (transport, server)
+    }
+
+    fn text_message(text: &str) -> SamplingMessage {
+        SamplingMessage {
+            role: Role::User,
+            content: MessageContent::Text {
+                text: text.into(),
+                annotations: None,
+            },
+        }
+    }
+
+    #[gpui::test]
+    async fn test_sampling_request(cx: &mut TestAppContext) {
+        cx.update(|cx| set_client_delegate(Arc::new(FakeClientDelegate), cx));
+        let (transport, server) = start_server(cx).await;
+        let client = server.client().unwrap();
+        assert!(client.advertised(ClientCapability::Sampling));
+        assert!(client.advertised(ClientCapability::Elicitation));
+
+        let result = transport
+            .request::<requests::CreateMessage>(CreateMessageRequest {
+                messages: vec![text_message("What is 2 + 2?")],
+                model_preferences: None,
+                system_prompt: None,
+                include_context: None,
+                temperature: None,
+                max_tokens: 100,
+                stop_sequences: None,
+                metadata: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.role, Role::Assistant);
+        assert_eq!(result.model, "fake-model");
+        match result.content {
+            MessageContent::Text { text, .. } => {
+                assert_eq!(text, "test-server asked: What is 2 + 2?")
+            }
+            content => panic!("unexpected content {content:?}"),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_elicitation_request(cx: &mut TestAppContext) {
+        cx.update(|cx| set_client_delegate(Arc::new(FakeClientDelegate), cx));
+        let (transport, _server) = start_server(cx).await;
+
+        let schema = || ElicitationSchema {
+            schema_type: "object".into(),
+            properties: [
+                (
+                    "name".to_string(),
+                    PrimitiveSchemaDefinition::String {
+                        title: Some("Name".into()),
+                        description: None,
+                        min_length: None,
+                        max_length: None,
+                        format: None,
+                        enum_values: None,
+                        enum_names: None,
+                    },
+                ),
+                (
+                    "confirmed".to_string(),
+                    PrimitiveSchemaDefinition::Boolean {
+                        title: None,
+                        description: None,
+                        default: Some(false),
+                    },
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            required: vec!["name".into()],
+        };
+
+        let result = transport
+            .request::<requests::Elicit>(ElicitRequestParams {
+                message: "Who are you?".into(),
+                requested_schema: schema(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.action, ElicitAction::Accept);
+        let content = result.content.unwrap();
+        assert_eq!(content["name"], serde_json::json!("name"));
+        assert_eq!(content["confirmed"], serde_json::json!(true));
+
+        let result = transport
+            .request::<requests::Elicit>(ElicitRequestParams {
+                message: "decline".into(),
+                requested_schema: schema(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.action, ElicitAction::Decline);
+        assert!(result.content.is_none());
+    }
+
+    #[gpui::test]
+    async fn test_server_requests_without_delegate(cx: &mut TestAppContext) {
+        let (transport, server) = start_server(cx).await;
+        let client = server.client().unwrap();
+        assert!(!client.advertised(ClientCapability::Sampling));
+        assert!(!client.advertised(ClientCapability::Elicitation));
+
+        let error = transport
+            .request::<requests::CreateMessage>(CreateMessageRequest {
+                messages: vec![text_message("Hello")],
+                model_preferences: None,
+                system_prompt: None,
+                include_context: None,
+                temperature: None,
+                max_tokens: 100,
+                stop_sequences: None,
+                metadata: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("-32601"), "{error}");
+    }
+}
@@ -34,21 +34,18 @@ impl ModelContextProtocol {
     pub async fn initialize(
         self,
         client_info: types::Implementation,
+        capabilities: types::ClientCapabilities,
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
-            capabilities: types::ClientCapabilities {
-                experimental: None,
-                sampling: None,
-                roots: None,
-            },
+            capabilities,
             meta: None,
             client_info,
         };
 
         let response: types::InitializeResponse = self
             .inner
-            .request(types::requests::Initialize::METHOD, params)
+            .request(types::requests::Initialize::METHOD, &params)
             .await?;
 
         anyhow::ensure!(
@@ -62,6 +59,7 @@ impl ModelContextProtocol {
         let initialized_protocol = InitializedContextServerProtocol {
             inner: self.inner,
             initialize: response,
+            client_capabilities: params.capabilities,
         };
 
         initialized_protocol.notify::<types::notifications::Initialized>(())?;
@@ -73,6 +71,7 @@ impl ModelContextProtocol {
 pub struct InitializedContextServerProtocol {
     inner: Client,
     pub initialize: types::InitializeResponse,
+    pub client_capabilities: types::ClientCapabilities,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -84,6 +83,14 @@ pub enum ServerCapability {
     Tools,
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ClientCapability {
+    Experimental,
+    Sampling,
+    Elicitation,
+    Roots,
+}
+
 impl InitializedContextServerProtocol {
     /// Check if the server supports a specific capability
     pub fn capable(&self, capability: ServerCapability) -> bool {
@@ -96,6 +103,16 @@ impl InitializedContextServerProtocol {
         }
     }
 
+    /// Check if the client advertised a specific capability to the server
+    pub fn advertised(&self, capability: ClientCapability) -> bool {
+        match capability {
+            ClientCapability::Experimental => self.client_capabilities.experimental.is_some(),
+            ClientCapability::Sampling => self.client_capabilities.sampling.is_some(),
+            ClientCapability::Elicitation => self.client_capabilities.elicitation.is_some(),
+            ClientCapability::Roots => self.client_capabilities.roots.is_some(),
+        }
+    }
+
     pub async fn request<T: Request>(&self, params: T::Params) -> Result<T::Response> {
         self.inner.request(T::METHOD, params).await
     }
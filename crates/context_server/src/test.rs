@@ -1,8 +1,16 @@
 use anyhow::Context as _;
 use collections::HashMap;
-use futures::{FutureExt, Stream, StreamExt as _, future::BoxFuture, lock::Mutex};
+use futures::{
+    FutureExt, Stream, StreamExt as _, channel::oneshot, future::BoxFuture, lock::Mutex,
+};
 use gpui::BackgroundExecutor;
-use std::{pin::Pin, sync::Arc};
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering::SeqCst},
+    },
+};
 
 use crate::{
     transport::Transport,
@@ -39,6 +47,8 @@ pub struct FakeTransport {
         &'static str,
         Arc<dyn Send + Sync + Fn(serde_json::Value) -> BoxFuture<'static, serde_json::Value>>,
     >,
+    pending_requests: parking_lot::Mutex<HashMap<String, oneshot::Sender<serde_json::Value>>>,
+    next_request_id: AtomicUsize,
     tx: futures::channel::mpsc::UnboundedSender<String>,
     rx: Arc<Mutex<futures::channel::mpsc::UnboundedReceiver<String>>>,
     executor: BackgroundExecutor,
@@ -49,6 +59,8 @@ impl FakeTransport {
         let (tx, rx) = futures::channel::mpsc::unbounded();
         Self {
             request_handlers: Default::default(),
+            pending_requests: Default::default(),
+            next_request_id: AtomicUsize::new(0),
             tx,
             rx: Arc::new(Mutex::new(rx)),
             executor,
@@ -78,6 +90,40 @@ impl FakeTransport {
         );
         self
     }
+
+    /// Sends a request to the client as if it was initiated by the server, resolving with the
+    /// client's response.
+    pub fn request<T: crate::types::Request>(
+        &self,
+        params: T::Params,
+    ) -> BoxFuture<'static, anyhow::Result<T::Response>> {
+        let id = format!("server-{}", self.next_request_id.fetch_add(1, SeqCst));
+        let (tx, rx) = oneshot::channel();
+        self.pending_requests.lock().insert(id.clone(), tx);
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": T::METHOD,
+            "params": params,
+        });
+        let sent = self
+            .tx
+            .unbounded_send(request.to_string())
+            .context("sending a request");
+        async move {
+            sent?;
+            let response = rx.await.context("no response received")?;
+            if let Some(error) = response.get("error") {
+                anyhow::bail!("request failed: {error}");
+            }
+            let result = response
+                .get("result")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+            Ok(serde_json::from_value(result)?)
+        }
+        .boxed()
+    }
 }
 
 #[async_trait::async_trait]
@@ -101,6 +147,10 @@ impl Transport for FakeTransport {
                 } else {
                     log::debug!("No handler registered for MCP request '{method}'");
                 }
+            } else if let Some(id) = msg.get("id").and_then(|id| id.as_str())
+                && let Some(tx) = self.pending_requests.lock().remove(id)
+            {
+                tx.send(msg).ok();
             }
         }
         Ok(())
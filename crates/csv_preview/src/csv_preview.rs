@@ -1,7 +1,9 @@
+use ::settings::{Settings as _, SettingsStore};
 use editor::{Editor, EditorEvent};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _};
 use gpui::{
-    AppContext, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment, Task, actions,
+    AppContext, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment, Subscription, Task,
+    actions,
 };
 use std::{
     collections::HashMap,
@@ -12,16 +14,26 @@ use crate::table_data_engine::TableDataEngine;
 use ui::{SharedString, TableColumnWidths, TableInteractionState, prelude::*};
 use workspace::{Item, SplitDirection, Workspace};
 
-use crate::{parser::EditorState, settings::CsvPreviewSettings, types::TableLikeContent};
+use crate::{
+    parser::EditorState,
+    settings::{CsvPreviewSettings, CsvPreviewUserSettings},
+    table_editing::CellEditor,
+    types::TableLikeContent,
+};
 
+mod dialect;
 mod parser;
 mod renderer;
 mod settings;
 mod table_data_engine;
+mod table_editing;
 mod types;
 
 actions!(csv, [OpenPreview, OpenPreviewToTheSide]);
 
+/// Extensions of the files that can be previewed as a table.
+const TABULAR_FILE_EXTENSIONS: [&str; 4] = ["csv", "tsv", "tab", "psv"];
+
 pub struct TabularDataPreviewFeatureFlag;
 
 impl FeatureFlag for TabularDataPreviewFeatureFlag {
@@ -42,6 +54,13 @@ pub struct CsvPreviewView {
     pub(crate) list_state: gpui::ListState,
     /// Time when the last parsing operation ended, used for smart debouncing
     pub(crate) last_parse_end_time: Option<std::time::Instant>,
+    /// The cell being edited inline, if any
+    pub(crate) cell_editor: Option<CellEditor>,
+    /// Delimiter chosen for this file in the toolbar, taking precedence over the settings
+    pub(crate) delimiter_override: Option<char>,
+    /// Delimiter from the settings the table was last parsed with
+    delimiter_setting: Option<char>,
+    _settings_subscription: Subscription,
 }
 
 pub fn init(cx: &mut App) {
@@ -132,6 +151,7 @@ impl CsvPreviewView {
                 |this: &mut CsvPreviewView, _editor, event: &EditorEvent, cx| {
                     match event {
                         EditorEvent::Edited { .. }
+                        | EditorEvent::BufferEdited
                         | EditorEvent::DirtyChanged
                         | EditorEvent::ExcerptsEdited { .. } => {
                             this.parse_csv_from_active_editor(true, cx);
@@ -140,6 +160,13 @@ impl CsvPreviewView {
                     };
                 },
             );
+            let settings_subscription = cx.observe_global::<SettingsStore>(|this, cx| {
+                let delimiter_setting = CsvPreviewUserSettings::get_global(cx).delimiter;
+                if this.delimiter_setting != delimiter_setting {
+                    this.delimiter_setting = delimiter_setting;
+                    this.parse_csv_from_active_editor(false, cx);
+                }
+            });
 
             let mut view = CsvPreviewView {
                 focus_handle: cx.focus_handle(),
@@ -154,6 +181,10 @@ impl CsvPreviewView {
                 list_state: gpui::ListState::new(contents.rows.len(), ListAlignment::Top, px(1.)),
                 settings: CsvPreviewSettings::default(),
                 last_parse_end_time: None,
+                cell_editor: None,
+                delimiter_override: None,
+                delimiter_setting: CsvPreviewUserSettings::get_global(cx).delimiter,
+                _settings_subscription: settings_subscription,
                 engine: TableDataEngine::default(),
             };
 
@@ -165,6 +196,23 @@ impl CsvPreviewView {
     pub(crate) fn editor_state(&self) -> &EditorState {
         &self.active_editor_state
     }
+
+    /// The delimiter to parse the file with, or `None` to detect it from the file contents.
+    pub(crate) fn configured_delimiter(&self, cx: &App) -> Option<char> {
+        self.delimiter_override
+            .or(CsvPreviewUserSettings::get_global(cx).delimiter)
+    }
+
+    pub(crate) fn set_delimiter_override(
+        &mut self,
+        delimiter: Option<char>,
+        cx: &mut Context<Self>,
+    ) {
+        self.delimiter_override = delimiter;
+        self.parse_csv_from_active_editor(false, cx);
+        cx.notify();
+    }
+
     pub(crate) fn apply_sort(&mut self) {
         self.performance_metrics.record("Sort", || {
             self.engine.apply_sort();
@@ -203,7 +251,11 @@ impl CsvPreviewView {
                     .read(cx)
                     .file()
                     .and_then(|file| file.path().extension())
-                    .map(|ext| ext.eq_ignore_ascii_case("csv"))
+                    .map(|ext| {
+                        TABULAR_FILE_EXTENSIONS
+                            .iter()
+                            .any(|tabular_ext| ext.eq_ignore_ascii_case(tabular_ext))
+                    })
             })
             .unwrap_or(false)
     }
@@ -0,0 +1,207 @@
+//! Detection of the delimiter and quote character of tabular files.
+
+use ui::SharedString;
+
+/// Delimiters tried when detecting the dialect of a file, in order of preference.
+pub(crate) const COMMON_DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+/// Quote characters tried when detecting the dialect of a file, in order of preference.
+const COMMON_QUOTES: [char; 2] = ['"', '\''];
+/// Number of rows from the start of the file used to detect its dialect.
+const SNIFF_ROWS: usize = 100;
+/// Number of bytes from the start of the file used to detect its dialect.
+const SNIFF_BYTES: usize = 64 * 1024;
+
+/// How the fields of a tabular file are separated and quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub delimiter: char,
+    pub quote: char,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: '"',
+        }
+    }
+}
+
+impl CsvDialect {
+    /// Detects the dialect of the text from its first rows. The delimiter is only detected
+    /// when not given.
+    ///
+    /// The dialect splitting most rows into the same number of fields (more than one) wins,
+    /// with ties going to the more common delimiters and quotes.
+    pub fn detect(text: &str, delimiter: Option<char>) -> Self {
+        let sample = sample(text);
+        let delimiters = match delimiter {
+            Some(delimiter) => vec![delimiter],
+            None => COMMON_DELIMITERS.to_vec(),
+        };
+
+        let mut best = Self {
+            delimiter: delimiters[0],
+            ..Self::default()
+        };
+        let mut best_score = (0, 0);
+        for delimiter in delimiters {
+            for quote in COMMON_QUOTES {
+                if quote == delimiter {
+                    continue;
+                }
+                let dialect = Self { delimiter, quote };
+                let score = dialect.consistency(sample);
+                if score > best_score {
+                    best = dialect;
+                    best_score = score;
+                }
+            }
+        }
+        best
+    }
+
+    /// Human readable name of the delimiter.
+    pub fn delimiter_name(&self) -> SharedString {
+        delimiter_name(self.delimiter)
+    }
+
+    /// Returns the number of rows sharing the most common field count, along with that count.
+    /// Text without any row of several fields scores zero.
+    fn consistency(&self, sample: &str) -> (usize, usize) {
+        let field_counts = self.field_counts(sample);
+        let mut best = (0, 0);
+        for &count in &field_counts {
+            if count < 2 {
+                continue;
+            }
+            let rows = field_counts.iter().filter(|&&other| other == count).count();
+            best = best.max((rows, count));
+        }
+        best
+    }
+
+    /// Counts the fields of each non-blank row, following the quoting rules of the parser.
+    fn field_counts(&self, sample: &str) -> Vec<usize> {
+        let mut counts = Vec::new();
+        let mut fields = 1;
+        let mut row_is_blank = true;
+        let mut field_is_empty = true;
+        let mut in_quotes = false;
+        let mut chars = sample.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if in_quotes {
+                if ch == self.quote && chars.next_if_eq(&self.quote).is_none() {
+                    in_quotes = false;
+                }
+                continue;
+            }
+            match ch {
+                ch if ch == self.quote && field_is_empty => {
+                    in_quotes = true;
+                    field_is_empty = false;
+                    row_is_blank = false;
+                }
+                ch if ch == self.delimiter => {
+                    fields += 1;
+                    field_is_empty = true;
+                    row_is_blank = false;
+                }
+                '\n' | '\r' => {
+                    if !row_is_blank {
+                        counts.push(fields);
+                    }
+                    fields = 1;
+                    field_is_empty = true;
+                    row_is_blank = true;
+                }
+                ch => {
+                    field_is_empty = false;
+                    row_is_blank &= ch.is_whitespace();
+                }
+            }
+        }
+        if !row_is_blank {
+            counts.push(fields);
+        }
+        counts
+    }
+}
+
+/// Human readable name of a delimiter.
+pub fn delimiter_name(delimiter: char) -> SharedString {
+    match delimiter {
+        ',' => "Comma".into(),
+        '\t' => "Tab".into(),
+        ';' => "Semicolon".into(),
+        '|' => "Pipe".into(),
+        ' ' => "Space".into(),
+        other => format!("\"{other}\"").into(),
+    }
+}
+
+/// Returns the first rows of the text, without a trailing row that may have been cut off.
+fn sample(text: &str) -> &str {
+    let mut end = text.len().min(SNIFF_BYTES);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut sample = &text[..end];
+    if let Some((row_end, _)) = sample.match_indices('\n').nth(SNIFF_ROWS - 1) {
+        sample = &sample[..row_end];
+    } else if end < text.len()
+        && let Some(row_end) = sample.rfind('\n')
+    {
+        sample = &sample[..row_end];
+    }
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(text: &str) -> (char, char) {
+        let dialect = CsvDialect::detect(text, None);
+        (dialect.delimiter, dialect.quote)
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect("a,b,c\n1,2,3\n4,5,6"), (',', '"'));
+        assert_eq!(detect("a\tb\tc\n1\t2\t3\n"), ('\t', '"'));
+        assert_eq!(detect("name;price\nApple;1,50\nPear;2,25\n"), (';', '"'));
+        assert_eq!(
+            detect("2024-01-01|INFO|started\n2024-01-01|WARN|slow, retrying\n"),
+            ('|', '"')
+        );
+        // A single column does not have a delimiter to detect.
+        assert_eq!(detect("name\nJohn\nJane"), (',', '"'));
+        assert_eq!(detect(""), (',', '"'));
+    }
+
+    #[test]
+    fn test_detect_with_quoted_fields() {
+        // Delimiters and line breaks inside quotes are not counted.
+        let text = "id;comment\n1;\"first; with a\nline break\"\n2;\"plain\"\n";
+        assert_eq!(detect(text), (';', '"'));
+
+        let text = "id,name\n1,'Doe, John'\n2,'Roe, Jane'\n";
+        assert_eq!(detect(text), (',', '\''));
+
+        // Apostrophes within fields do not make single quotes the quote character.
+        let text = "id,comment\n1,don't\n2,it's fine\n";
+        assert_eq!(detect(text), (',', '"'));
+    }
+
+    #[test]
+    fn test_detect_quote_with_given_delimiter() {
+        let dialect = CsvDialect::detect("a|b\n'x|y'|z\n", Some('|'));
+        assert_eq!(dialect.delimiter, '|');
+        assert_eq!(dialect.quote, '\'');
+
+        let dialect = CsvDialect::detect("a,b", Some(';'));
+        assert_eq!(dialect.delimiter, ';');
+        assert_eq!(dialect.quote, '"');
+    }
+}
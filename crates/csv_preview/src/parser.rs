@@ -1,5 +1,6 @@
 use crate::{
     CsvPreviewView,
+    dialect::CsvDialect,
     types::TableLikeContent,
     types::{LineNumber, TableCell},
 };
@@ -54,13 +55,14 @@ impl CsvPreviewView {
                 }
             }
 
-            let buffer_snapshot = view.update(cx, |_, cx| {
-                editor
+            let (buffer_snapshot, delimiter) = view.update(cx, |view, cx| {
+                let buffer_snapshot = editor
                     .read(cx)
                     .buffer()
                     .read(cx)
                     .as_singleton()
-                    .map(|b| b.read(cx).text_snapshot())
+                    .map(|b| b.read(cx).text_snapshot());
+                (buffer_snapshot, view.configured_delimiter(cx))
             })?;
 
             let Some(buffer_snapshot) = buffer_snapshot else {
@@ -69,7 +71,7 @@ impl CsvPreviewView {
 
             let instant = Instant::now();
             let parsed_csv = cx
-                .background_spawn(async move { from_buffer(&buffer_snapshot) })
+                .background_spawn(async move { from_buffer(&buffer_snapshot, delimiter) })
                 .await;
             let parse_duration = instant.elapsed();
             let parse_end_time: Instant = Instant::now();
@@ -94,22 +96,29 @@ impl CsvPreviewView {
     }
 }
 
-pub fn from_buffer(buffer_snapshot: &BufferSnapshot) -> TableLikeContent {
+/// Parses the buffer as a table. The dialect of the table is detected from the contents of
+/// the buffer, except for the delimiter when one is given.
+pub fn from_buffer(buffer_snapshot: &BufferSnapshot, delimiter: Option<char>) -> TableLikeContent {
     let text = buffer_snapshot.text();
+    let dialect = CsvDialect::detect(&text, delimiter);
+    let empty = TableLikeContent {
+        dialect,
+        ..TableLikeContent::default()
+    };
 
     if text.trim().is_empty() {
-        return TableLikeContent::default();
+        return empty;
     }
 
-    let (parsed_cells_with_positions, line_numbers) = parse_csv_with_positions(&text);
+    let (parsed_cells_with_positions, line_numbers) = parse_csv_with_positions(&text, dialect);
     if parsed_cells_with_positions.is_empty() {
-        return TableLikeContent::default();
+        return empty;
     }
     let raw_headers = parsed_cells_with_positions[0].clone();
 
     // Calculating the longest row, as CSV might have less headers than max row width
     let Some(max_number_of_cols) = parsed_cells_with_positions.iter().map(|r| r.len()).max() else {
-        return TableLikeContent::default();
+        return empty;
     };
 
     // Convert to TableCell objects with buffer positions
@@ -128,12 +137,18 @@ pub fn from_buffer(buffer_snapshot: &BufferSnapshot) -> TableLikeContent {
         rows,
         line_numbers: row_line_numbers,
         number_of_cols: max_number_of_cols,
+        dialect,
     }
 }
 
-/// Parse CSV and track byte positions for each cell
+/// Parse delimiter-separated text and track byte positions for each cell.
+///
+/// Follows RFC 4180, leniently: quotes only have a special meaning at the start of a field,
+/// text following a closing quote is kept, and an unterminated quoted field extends to the
+/// end of the text. Rows may end with `\n`, `\r\n` or `\r`.
 fn parse_csv_with_positions(
     text: &str,
+    dialect: CsvDialect,
 ) -> (
     Vec<Vec<(SharedString, std::ops::Range<usize>)>>,
     Vec<LineNumber>,
@@ -143,156 +158,122 @@ fn parse_csv_with_positions(
     let mut current_row: Vec<(SharedString, std::ops::Range<usize>)> = Vec::new();
     let mut current_field = String::new();
     let mut field_start_offset = 0;
-    let mut current_offset = 0;
     let mut in_quotes = false;
     let mut current_line = 1; // 1-based line numbering
     let mut row_start_line = 1;
-    let mut chars = text.chars().peekable();
+    let mut chars = text.char_indices().peekable();
 
-    while let Some(ch) = chars.next() {
-        let char_byte_len = ch.len_utf8();
+    // A byte order mark is not part of the first field
+    if chars.next_if(|&(_, ch)| ch == '\u{feff}').is_some() {
+        field_start_offset = '\u{feff}'.len_utf8();
+    }
 
-        match ch {
-            '"' => {
-                if in_quotes {
-                    if chars.peek() == Some(&'"') {
-                        // Escaped quote
-                        chars.next();
-                        current_field.push('"');
-                        current_offset += 1; // Skip the second quote
-                    } else {
-                        // End of quoted field
-                        in_quotes = false;
-                    }
+    while let Some((offset, ch)) = chars.next() {
+        let next_offset = offset + ch.len_utf8();
+
+        if in_quotes {
+            if ch == dialect.quote {
+                if chars.next_if(|&(_, next)| next == dialect.quote).is_some() {
+                    // Escaped quote
+                    current_field.push(ch);
                 } else {
-                    // Start of quoted field
-                    in_quotes = true;
-                    if current_field.is_empty() {
-                        // Include the opening quote in the range
-                        field_start_offset = current_offset;
-                    }
+                    // End of quoted field
+                    in_quotes = false;
                 }
-            }
-            ',' if !in_quotes => {
-                // Field separator
-                let field_end_offset = current_offset;
-                if current_field.is_empty() && !in_quotes {
-                    field_start_offset = current_offset;
+            } else {
+                if ch == '\n' || (ch == '\r' && chars.peek().map(|&(_, next)| next) != Some('\n')) {
+                    current_line += 1;
                 }
+                // Line breaks inside quotes are preserved
+                current_field.push(ch);
+            }
+            continue;
+        }
+
+        match ch {
+            ch if ch == dialect.quote && offset == field_start_offset => {
+                // Start of quoted field, the range includes the quotes
+                in_quotes = true;
+            }
+            ch if ch == dialect.delimiter => {
                 current_row.push((
-                    current_field.clone().into(),
-                    field_start_offset..field_end_offset,
+                    std::mem::take(&mut current_field).into(),
+                    field_start_offset..offset,
                 ));
-                current_field.clear();
-                field_start_offset = current_offset + char_byte_len;
+                field_start_offset = next_offset;
             }
-            '\n' => {
+            '\n' | '\r' => {
+                // Row separator, with `\r\n` ending a single row
+                let next_offset = match chars.next_if(|&(_, next)| ch == '\r' && next == '\n') {
+                    Some((newline_offset, _)) => newline_offset + 1,
+                    None => next_offset,
+                };
+                current_row.push((
+                    std::mem::take(&mut current_field).into(),
+                    field_start_offset..offset,
+                ));
+                push_row(
+                    text,
+                    std::mem::take(&mut current_row),
+                    row_start_line,
+                    current_line,
+                    &mut rows,
+                    &mut line_numbers,
+                );
                 current_line += 1;
-                if !in_quotes {
-                    // Row separator (only when not inside quotes)
-                    let field_end_offset = current_offset;
-                    if current_field.is_empty() && current_row.is_empty() {
-                        field_start_offset = 0;
-                    }
-                    current_row.push((
-                        current_field.clone().into(),
-                        field_start_offset..field_end_offset,
-                    ));
-                    current_field.clear();
-
-                    // Only add non-empty rows
-                    if !current_row.is_empty()
-                        && !current_row.iter().all(|(field, _)| field.trim().is_empty())
-                    {
-                        rows.push(current_row);
-                        // Add line number info for this row
-                        let line_info = if row_start_line == current_line - 1 {
-                            LineNumber::Line(row_start_line)
-                        } else {
-                            LineNumber::LineRange(row_start_line, current_line - 1)
-                        };
-                        line_numbers.push(line_info);
-                    }
-                    current_row = Vec::new();
-                    row_start_line = current_line;
-                    field_start_offset = current_offset + char_byte_len;
-                } else {
-                    // Newline inside quotes - preserve it
-                    current_field.push(ch);
-                }
-            }
-            '\r' => {
-                if chars.peek() == Some(&'\n') {
-                    // Handle Windows line endings (\r\n): account for \r byte, let \n be handled next
-                    current_offset += char_byte_len;
-                    continue;
-                } else {
-                    // Standalone \r
-                    current_line += 1;
-                    if !in_quotes {
-                        // Row separator (only when not inside quotes)
-                        let field_end_offset = current_offset;
-                        current_row.push((
-                            current_field.clone().into(),
-                            field_start_offset..field_end_offset,
-                        ));
-                        current_field.clear();
-
-                        // Only add non-empty rows
-                        if !current_row.is_empty()
-                            && !current_row.iter().all(|(field, _)| field.trim().is_empty())
-                        {
-                            rows.push(current_row);
-                            // Add line number info for this row
-                            let line_info = if row_start_line == current_line - 1 {
-                                LineNumber::Line(row_start_line)
-                            } else {
-                                LineNumber::LineRange(row_start_line, current_line - 1)
-                            };
-                            line_numbers.push(line_info);
-                        }
-                        current_row = Vec::new();
-                        row_start_line = current_line;
-                        field_start_offset = current_offset + char_byte_len;
-                    } else {
-                        // \r inside quotes - preserve it
-                        current_field.push(ch);
-                    }
-                }
-            }
-            _ => {
-                if current_field.is_empty() && !in_quotes {
-                    field_start_offset = current_offset;
-                }
-                current_field.push(ch);
+                row_start_line = current_line;
+                field_start_offset = next_offset;
             }
+            _ => current_field.push(ch),
         }
-
-        current_offset += char_byte_len;
     }
 
-    // Add the last field and row if not empty
-    if !current_field.is_empty() || !current_row.is_empty() {
-        let field_end_offset = current_offset;
-        current_row.push((
-            current_field.clone().into(),
-            field_start_offset..field_end_offset,
-        ));
-    }
-    if !current_row.is_empty() && !current_row.iter().all(|(field, _)| field.trim().is_empty()) {
-        rows.push(current_row);
-        // Add line number info for the last row
-        let line_info = if row_start_line == current_line {
-            LineNumber::Line(row_start_line)
-        } else {
-            LineNumber::LineRange(row_start_line, current_line)
-        };
-        line_numbers.push(line_info);
+    // Add the last field and row, unless the text ends with a row separator
+    if field_start_offset < text.len() || !current_row.is_empty() {
+        current_row.push((current_field.into(), field_start_offset..text.len()));
+        push_row(
+            text,
+            current_row,
+            row_start_line,
+            current_line,
+            &mut rows,
+            &mut line_numbers,
+        );
     }
 
     (rows, line_numbers)
 }
 
+/// Adds a row spanning the given lines, unless it is blank.
+fn push_row(
+    text: &str,
+    row: Vec<(SharedString, std::ops::Range<usize>)>,
+    start_line: usize,
+    end_line: usize,
+    rows: &mut Vec<Vec<(SharedString, std::ops::Range<usize>)>>,
+    line_numbers: &mut Vec<LineNumber>,
+) {
+    if is_blank_row(text, &row) {
+        return;
+    }
+    rows.push(row);
+    line_numbers.push(if start_line == end_line {
+        LineNumber::Line(start_line)
+    } else {
+        LineNumber::LineRange(start_line, end_line)
+    });
+}
+
+/// Whether a row comes from a line without any content. Rows of empty fields separated by
+/// delimiters, e.g. `,,`, are not blank.
+fn is_blank_row(text: &str, row: &[(SharedString, std::ops::Range<usize>)]) -> bool {
+    match row {
+        [] => true,
+        [(_, range)] => text[range.clone()].trim().is_empty(),
+        _ => false,
+    }
+}
+
 fn create_table_row(
     buffer_snapshot: &BufferSnapshot,
     max_number_of_cols: usize,
@@ -424,7 +405,7 @@ Jane,"Simple name""#;
     #[test]
     fn test_csv_parsing_quote_offset_handling() {
         let csv_data = r#"first,"se,cond",third"#;
-        let (parsed_cells, _) = parse_csv_with_positions(csv_data);
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, CsvDialect::default());
 
         assert_eq!(parsed_cells.len(), 1); // One row
         assert_eq!(parsed_cells[0].len(), 3); // Three cells
@@ -450,7 +431,7 @@ Jane,"Simple name""#;
         let csv_data = r#"id,"name with spaces","description, with commas",status
 1,"John Doe","A person with ""quotes"" and, commas",active
 2,"Jane Smith","Simple description",inactive"#;
-        let (parsed_cells, _) = parse_csv_with_positions(csv_data);
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, CsvDialect::default());
 
         assert_eq!(parsed_cells.len(), 3); // header + 2 rows
 
@@ -498,6 +479,88 @@ Jane,"Simple name""#;
         assert_eq!(first_row[3].0.as_ref(), "active");
         assert_eq!(first_row[3].1, 108..114);
     }
+
+    #[test]
+    fn test_parsing_with_other_dialects() {
+        let parsed = TableLikeContent::from_str("a\tb\n1\t\"x\ty\"\n".to_string());
+        assert_eq!(parsed.dialect.delimiter, '\t');
+        assert_eq!(parsed.headers.cols(), 2);
+        assert_eq!(parsed.rows[0][1].display_value().unwrap().as_ref(), "x\ty");
+
+        let parsed = TableLikeContent::from_str("name;note\nA;\"1,5\nkg\"\nB;2\n".to_string());
+        assert_eq!(parsed.dialect.delimiter, ';');
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(
+            parsed.rows[0][1].display_value().unwrap().as_ref(),
+            "1,5\nkg"
+        );
+        assert!(matches!(
+            parsed.line_numbers[0],
+            LineNumber::LineRange(2, 3)
+        ));
+        assert!(matches!(parsed.line_numbers[1], LineNumber::Line(4)));
+
+        let dialect = CsvDialect {
+            delimiter: '|',
+            quote: '\'',
+        };
+        let (parsed_cells, _) = parse_csv_with_positions("a|'b|''c'''", dialect);
+        assert_eq!(parsed_cells[0].len(), 2);
+        assert_eq!(parsed_cells[0][1].0.as_ref(), "b|'c'");
+        assert_eq!(parsed_cells[0][1].1, 2..11);
+    }
+
+    #[test]
+    fn test_parsing_rfc4180_edge_cases() {
+        let parse = |text: &str| parse_csv_with_positions(text, CsvDialect::default());
+        let fields = |text: &str| {
+            parse(text)
+                .0
+                .into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|(content, range)| (content.to_string(), range))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Quotes in the middle of a field are kept as they are
+        assert_eq!(
+            fields("a\"b,c"),
+            [[("a\"b".to_string(), 0..3), ("c".to_string(), 4..5)]]
+        );
+        // Empty quoted fields keep their quotes in the range
+        assert_eq!(
+            fields("\"\",x"),
+            [[(String::new(), 0..2), ("x".to_string(), 3..4)]]
+        );
+        // Text following a closing quote is part of the field
+        assert_eq!(
+            fields("\"a\"b,c"),
+            [[("ab".to_string(), 0..4), ("c".to_string(), 5..6)]]
+        );
+        // Line breaks do not belong to the last field of a row
+        assert_eq!(
+            fields("a,b\r\nc,d\r\n"),
+            [
+                [("a".to_string(), 0..1), ("b".to_string(), 2..3)],
+                [("c".to_string(), 5..6), ("d".to_string(), 7..8)]
+            ]
+        );
+        assert_eq!(parse("a\rb").0.len(), 2);
+        // A byte order mark is not part of the first header
+        assert_eq!(fields("\u{feff}id,name")[0][0], ("id".to_string(), 3..5));
+        // An unterminated quoted field extends to the end of the text
+        assert_eq!(
+            fields("a,\"b\nc"),
+            [[("a".to_string(), 0..1), ("b\nc".to_string(), 2..6)]]
+        );
+        // A trailing delimiter ends with an empty field
+        assert_eq!(fields("a,b,\n")[0][2], (String::new(), 4..4));
+        // A single empty quoted field is a row, even at the end of the text
+        assert_eq!(parse("a\n\"\"").0.len(), 2);
+    }
 }
 
 impl TableLikeContent {
@@ -508,6 +571,6 @@ impl TableLikeContent {
         let buffer_id = BufferId::new(1).unwrap();
         let buffer = Buffer::new(ReplicaId::LOCAL, buffer_id, text);
         let snapshot = buffer.snapshot();
-        from_buffer(snapshot)
+        from_buffer(snapshot, None)
     }
 }
@@ -1,11 +1,17 @@
 use std::time::Instant;
 
-use ui::{div, prelude::*};
+use ui::{ContextMenu, PopoverMenu, Tooltip, div, prelude::*};
 
-use crate::{CsvPreviewView, settings::FontType};
+use crate::{
+    CsvPreviewView,
+    dialect::{COMMON_DELIMITERS, delimiter_name},
+    settings::FontType,
+};
 
 impl Render for CsvPreviewView {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let toolbar = self.render_toolbar(cx);
+
         let theme = cx.theme();
 
         self.performance_metrics.rendered_indices.clear();
@@ -15,6 +21,7 @@ impl Render for CsvPreviewView {
             .p_4()
             .bg(theme.colors().editor_background)
             .track_focus(&self.focus_handle)
+            .child(toolbar)
             .child({
                 if self.engine.contents.number_of_cols == 0 {
                     div()
@@ -48,3 +55,80 @@ impl Render for CsvPreviewView {
             .child(table_with_settings)
     }
 }
+
+impl CsvPreviewView {
+    /// Shows the dialect the file is parsed with, and allows overriding its delimiter.
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let dialect = self.engine.contents.dialect;
+        let source = if self.delimiter_override.is_some() {
+            "chosen for this file"
+        } else if self.configured_delimiter(cx).is_some() {
+            "from settings"
+        } else {
+            "detected"
+        };
+
+        let view = cx.entity();
+        let delimiter_override = self.delimiter_override;
+        let menu = PopoverMenu::new("csv-delimiter-menu")
+            .trigger_with_tooltip(
+                Button::new(
+                    "csv-delimiter",
+                    format!("{} ({source})", dialect.delimiter_name()),
+                )
+                .size(ButtonSize::Compact)
+                .icon(IconName::ChevronUpDown)
+                .icon_position(IconPosition::End)
+                .icon_size(IconSize::XSmall)
+                .icon_color(Color::Muted),
+                Tooltip::text("Delimiter separating the fields of a row"),
+            )
+            .menu(move |window, cx| {
+                let view = view.clone();
+                Some(ContextMenu::build(
+                    window,
+                    cx,
+                    move |mut menu, window, _| {
+                        menu = menu.toggleable_entry(
+                            "Detect or Use Settings",
+                            delimiter_override.is_none(),
+                            IconPosition::Start,
+                            None,
+                            window.handler_for(&view, |this, _, cx| {
+                                this.set_delimiter_override(None, cx)
+                            }),
+                        );
+                        for delimiter in COMMON_DELIMITERS {
+                            menu = menu.toggleable_entry(
+                                delimiter_name(delimiter),
+                                delimiter_override == Some(delimiter),
+                                IconPosition::Start,
+                                None,
+                                window.handler_for(&view, move |this, _, cx| {
+                                    this.set_delimiter_override(Some(delimiter), cx)
+                                }),
+                            );
+                        }
+                        menu
+                    },
+                ))
+            });
+
+        h_flex()
+            .pb_2()
+            .gap_2()
+            .text_ui_sm(cx)
+            .child(
+                Label::new("Delimiter")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(menu)
+            .child(
+                Label::new("Quote")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(dialect.quote.to_string()).size(LabelSize::Small))
+    }
+}
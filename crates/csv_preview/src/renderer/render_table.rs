@@ -10,6 +10,7 @@ use ui::{DefiniteLength, div, prelude::*};
 use crate::{
     CsvPreviewView,
     settings::RowRenderMechanism,
+    table_data_engine::table_edits::SourceRow,
     types::{AnyColumn, DisplayCellId, DisplayRow},
 };
 
@@ -155,15 +156,23 @@ impl CsvPreviewView {
 
             let display_cell_id = DisplayCellId::new(display_row, col);
 
-            let cell = div().size_full().whitespace_nowrap().text_ellipsis().child(
-                CsvPreviewView::create_selectable_cell(
-                    display_cell_id,
-                    cell_content,
-                    this.settings.vertical_alignment,
-                    this.settings.font_type,
-                    cx,
-                ),
-            );
+            let cell = this
+                .render_cell_editor(SourceRow::Data(data_row), col, cx)
+                .unwrap_or_else(|| {
+                    div()
+                        .size_full()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .child(CsvPreviewView::create_selectable_cell(
+                            display_cell_id,
+                            data_row,
+                            cell_content,
+                            this.settings.vertical_alignment,
+                            this.settings.font_type,
+                            cx,
+                        ))
+                        .into_any_element()
+                });
 
             elements.push(
                 div()
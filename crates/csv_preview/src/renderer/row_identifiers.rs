@@ -1,7 +1,8 @@
 use ui::{
     ActiveTheme as _, AnyElement, Button, ButtonCommon as _, ButtonSize, ButtonStyle,
-    Clickable as _, Context, ElementId, FluentBuilder as _, IntoElement as _, ParentElement as _,
-    SharedString, Styled as _, StyledTypography as _, Tooltip, div,
+    Clickable as _, Context, ContextMenu, ElementId, FluentBuilder as _, IntoElement as _,
+    ParentElement as _, SharedString, Styled as _, StyledTypography as _, Tooltip, div,
+    right_click_menu,
 };
 
 use crate::{
@@ -184,6 +185,44 @@ impl CsvPreviewView {
             })
             .child(row_identifier)
             .into_any_element();
-        Some(value)
+        if !self.is_editable(cx) {
+            return Some(value);
+        }
+
+        let view = cx.entity();
+        Some(
+            right_click_menu(ElementId::NamedInteger(
+                "csv-row-menu".into(),
+                *data_row as u64,
+            ))
+            .trigger(move |_, _, _| value)
+            .menu(move |window, cx| {
+                ContextMenu::build(window, cx, |menu, window, _| {
+                    menu.entry(
+                        "Insert Row Above",
+                        None,
+                        window.handler_for(&view, move |this, _, cx| {
+                            this.insert_row(*data_row, cx);
+                        }),
+                    )
+                    .entry(
+                        "Insert Row Below",
+                        None,
+                        window.handler_for(&view, move |this, _, cx| {
+                            this.insert_row(*data_row + 1, cx);
+                        }),
+                    )
+                    .separator()
+                    .entry(
+                        "Delete Row",
+                        None,
+                        window.handler_for(&view, move |this, _, cx| {
+                            this.delete_row(data_row, cx);
+                        }),
+                    )
+                })
+            })
+            .into_any_element(),
+        )
     }
 }
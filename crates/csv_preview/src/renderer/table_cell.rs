@@ -1,18 +1,21 @@
 //! Table Cell Rendering
 
-use gpui::{AnyElement, ElementId};
+use gpui::{AnyElement, ClickEvent, ElementId};
 use ui::{SharedString, Tooltip, div, prelude::*};
 
 use crate::{
     CsvPreviewView,
     settings::{FontType, VerticalAlignment},
-    types::DisplayCellId,
+    table_data_engine::table_edits::SourceRow,
+    types::{AnyColumn, DataRow, DisplayCellId},
 };
 
 impl CsvPreviewView {
     /// Create selectable table cell with mouse event handlers.
+    /// Double-clicking the cell starts editing it.
     pub fn create_selectable_cell(
         display_cell_id: DisplayCellId,
+        data_row: DataRow,
         cell_content: SharedString,
         vertical_alignment: VerticalAlignment,
         font_type: FontType,
@@ -25,9 +28,47 @@ impl CsvPreviewView {
             font_type,
             cx,
         )
-        // Mouse events handlers will be here
+        .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+            if event.click_count() == 2 {
+                this.start_editing_cell(SourceRow::Data(data_row), display_cell_id.col, window, cx);
+            }
+        }))
         .into_any_element()
     }
+
+    /// Renders the inline editor in place of the cell, if the cell is being edited.
+    pub(crate) fn render_cell_editor(
+        &self,
+        row: SourceRow,
+        col: AnyColumn,
+        cx: &Context<CsvPreviewView>,
+    ) -> Option<AnyElement> {
+        let cell_editor = self
+            .cell_editor
+            .as_ref()
+            .filter(|cell_editor| cell_editor.row == row && cell_editor.col == col)?;
+        Some(
+            div()
+                .size_full()
+                .flex()
+                .items_center()
+                .px_1()
+                .border_1()
+                .border_color(cx.theme().colors().border_focused)
+                .bg(cx.theme().colors().editor_background)
+                .on_action(cx.listener(|this, _: &menu::Confirm, window, cx| {
+                    this.confirm_cell_edit(window, cx);
+                }))
+                // Captured, so that the first escape does not just clear the selection.
+                .capture_action(
+                    cx.listener(|this, _: &editor::actions::Cancel, window, cx| {
+                        this.cancel_cell_edit(window, cx);
+                    }),
+                )
+                .child(cell_editor.editor.clone())
+                .into_any_element(),
+        )
+    }
 }
 
 /// Create styled table cell div element.
@@ -1,10 +1,13 @@
-use gpui::ElementId;
-use ui::{Tooltip, prelude::*};
+use gpui::{ClickEvent, ElementId};
+use ui::{ContextMenu, Tooltip, prelude::*, right_click_menu};
 
 use crate::{
     CsvPreviewView,
     settings::FontType,
-    table_data_engine::sorting_by_column::{AppliedSorting, SortDirection},
+    table_data_engine::{
+        sorting_by_column::{AppliedSorting, SortDirection},
+        table_edits::SourceRow,
+    },
     types::AnyColumn,
 };
 
@@ -16,8 +19,26 @@ impl CsvPreviewView {
         cx: &mut Context<'_, CsvPreviewView>,
         col_idx: AnyColumn,
     ) -> AnyElement {
+        let header_text = self
+            .render_cell_editor(SourceRow::Header, col_idx, cx)
+            .unwrap_or_else(|| {
+                div()
+                    .id(ElementId::NamedInteger(
+                        "header-text".into(),
+                        *col_idx as u64,
+                    ))
+                    .flex_1()
+                    .child(header_text)
+                    .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                        if event.click_count() == 2 {
+                            this.start_editing_cell(SourceRow::Header, col_idx, window, cx);
+                        }
+                    }))
+                    .into_any_element()
+            });
+
         // CSV data columns: text + filter/sort buttons
-        h_flex()
+        let header = h_flex()
             .justify_between()
             .items_center()
             .w_full()
@@ -25,9 +46,73 @@ impl CsvPreviewView {
                 FontType::Ui => div.font_ui(cx),
                 FontType::Monospace => div.font_buffer(cx),
             })
-            .child(div().child(header_text))
+            .child(header_text)
             .child(h_flex().gap_1().child(self.create_sort_button(cx, col_idx)))
-            .into_any_element()
+            .into_any_element();
+        if !self.is_editable(cx) {
+            return header;
+        }
+
+        let view = cx.entity();
+        let number_of_cols = self.engine.contents.number_of_cols;
+        right_click_menu(ElementId::NamedInteger(
+            "csv-column-menu".into(),
+            *col_idx as u64,
+        ))
+        .trigger(move |_, _, _| header)
+        .menu(move |window, cx| {
+            ContextMenu::build(window, cx, |menu, window, _| {
+                menu.entry(
+                    "Rename Column",
+                    None,
+                    window.handler_for(&view, move |this, window, cx| {
+                        this.start_editing_cell(SourceRow::Header, col_idx, window, cx);
+                    }),
+                )
+                .separator()
+                .entry(
+                    "Insert Column Left",
+                    None,
+                    window.handler_for(&view, move |this, _, cx| {
+                        this.insert_column(col_idx, cx);
+                    }),
+                )
+                .entry(
+                    "Insert Column Right",
+                    None,
+                    window.handler_for(&view, move |this, _, cx| {
+                        this.insert_column(AnyColumn(*col_idx + 1), cx);
+                    }),
+                )
+                .when(*col_idx > 0, |menu| {
+                    menu.entry(
+                        "Move Column Left",
+                        None,
+                        window.handler_for(&view, move |this, _, cx| {
+                            this.move_column(col_idx, AnyColumn(*col_idx - 1), cx);
+                        }),
+                    )
+                })
+                .when(*col_idx + 1 < number_of_cols, |menu| {
+                    menu.entry(
+                        "Move Column Right",
+                        None,
+                        window.handler_for(&view, move |this, _, cx| {
+                            this.move_column(col_idx, AnyColumn(*col_idx + 1), cx);
+                        }),
+                    )
+                })
+                .separator()
+                .entry(
+                    "Delete Column",
+                    None,
+                    window.handler_for(&view, move |this, _, cx| {
+                        this.delete_column(col_idx, cx);
+                    }),
+                )
+            })
+        })
+        .into_any_element()
     }
 
     fn create_sort_button(
@@ -1,3 +1,5 @@
+use ::settings::{RegisterSetting, Settings};
+
 #[derive(Default, Clone, Copy)]
 pub enum RowRenderMechanism {
     /// Default behaviour
@@ -44,3 +46,34 @@ pub(crate) struct CsvPreviewSettings {
     pub(crate) show_debug_info: bool,
     pub(crate) multiline_cells_enabled: bool,
 }
+
+/// User settings of the CSV preview.
+#[derive(Clone, Debug, RegisterSetting)]
+pub(crate) struct CsvPreviewUserSettings {
+    /// The delimiter to parse tabular files with, or `None` to detect it for each file.
+    pub(crate) delimiter: Option<char>,
+}
+
+impl Settings for CsvPreviewUserSettings {
+    fn from_settings(content: &::settings::SettingsContent) -> Self {
+        let delimiter = content.csv_preview.clone().unwrap().delimiter.unwrap();
+        Self {
+            delimiter: parse_delimiter(&delimiter),
+        }
+    }
+}
+
+/// Parses the `delimiter` setting, where anything but a single character (other than a quote
+/// or a line break) means detecting the delimiter.
+fn parse_delimiter(delimiter: &str) -> Option<char> {
+    let mut chars = delimiter.chars();
+    match (chars.next(), chars.next()) {
+        (Some(delimiter), None) if !matches!(delimiter, '"' | '\n' | '\r') => Some(delimiter),
+        _ => {
+            if delimiter != "auto" {
+                log::warn!("Invalid CSV preview delimiter {delimiter:?}, detecting it instead");
+            }
+            None
+        }
+    }
+}
@@ -15,6 +15,7 @@ use crate::{
 };
 
 pub mod sorting_by_column;
+pub mod table_edits;
 
 #[derive(Default)]
 pub(crate) struct TableDataEngine {
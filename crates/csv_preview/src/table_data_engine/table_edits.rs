@@ -0,0 +1,469 @@
+//! Translates table operations (editing cells, inserting/deleting/reordering rows and columns)
+//! into edits of the source buffer.
+//!
+//! Edits are computed from the buffer positions of the parsed cells, so they address source
+//! rows (`DataRow`) and are not affected by sorting of the displayed rows.
+
+use std::{borrow::Cow, ops::Range};
+
+use text::{BufferSnapshot, ToOffset as _};
+use ui::table_row::TableRow;
+
+use crate::{
+    dialect::CsvDialect,
+    types::{AnyColumn, DataRow, TableCell, TableLikeContent},
+};
+
+/// Replacement of a byte range in the source buffer.
+pub(crate) type BufferEdit = (Range<usize>, String);
+
+/// A row of the table as stored in the source buffer, including the header row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourceRow {
+    Header,
+    Data(DataRow),
+}
+
+/// Quotes a field value if it contains a delimiter, a quote or a line break.
+pub(crate) fn quote_field(value: &str, dialect: CsvDialect) -> Cow<'_, str> {
+    let CsvDialect { delimiter, quote } = dialect;
+    if value.contains([delimiter, quote, '\n', '\r']) {
+        let escaped_quote = format!("{quote}{quote}");
+        Cow::Owned(format!(
+            "{quote}{}{quote}",
+            value.replace(quote, &escaped_quote)
+        ))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Sets the value of a cell. Cells padding a short row are created by appending fields to it.
+pub(crate) fn set_cell(
+    content: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    row: SourceRow,
+    col: AnyColumn,
+    value: &str,
+) -> Vec<BufferEdit> {
+    let Some(cells) = source_row(content, row) else {
+        return Vec::new();
+    };
+    let value = quote_field(value, content.dialect);
+    if let Some(range) = cells.get(col).and_then(|cell| cell_range(cell, snapshot)) {
+        return vec![(range, value.into_owned())];
+    }
+
+    let real_cells = real_cell_count(cells);
+    let Some(row_range) = row_range(cells, snapshot) else {
+        return Vec::new();
+    };
+    let separators = content
+        .dialect
+        .delimiter
+        .to_string()
+        .repeat(*col + 1 - real_cells);
+    vec![(row_range.end..row_range.end, format!("{separators}{value}"))]
+}
+
+/// Inserts an empty row, so that it becomes the data row at `index`. The row is separated with
+/// the buffer's line ending.
+pub(crate) fn insert_row(
+    content: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    index: usize,
+) -> Vec<BufferEdit> {
+    let empty_row = empty_row(content.number_of_cols, content.dialect);
+    let line_ending = snapshot.line_ending().as_str();
+    if let Some(range) = content
+        .rows
+        .get(index)
+        .and_then(|cells| row_range(cells, snapshot))
+    {
+        return vec![(
+            range.start..range.start,
+            format!("{empty_row}{line_ending}"),
+        )];
+    }
+
+    let last_row = content.rows.last().unwrap_or(&content.headers);
+    match row_range(last_row, snapshot) {
+        Some(range) => vec![(range.end..range.end, format!("{line_ending}{empty_row}"))],
+        None => Vec::new(),
+    }
+}
+
+/// Deletes a data row, along with its line break.
+pub(crate) fn delete_row(
+    content: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    row: DataRow,
+) -> Vec<BufferEdit> {
+    let Some(mut range) = content
+        .get_row(row)
+        .and_then(|cells| row_range(cells, snapshot))
+    else {
+        return Vec::new();
+    };
+    if snapshot.chars_at(range.end).next() == Some('\n') {
+        range.end += 1;
+    } else if range.start > 0 && snapshot.reversed_chars_at(range.start).next() == Some('\n') {
+        range.start -= 1;
+    }
+    vec![(range, String::new())]
+}
+
+/// Inserts an empty column, so that it becomes the column at `col`.
+pub(crate) fn insert_column(
+    content: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    col: AnyColumn,
+) -> Vec<BufferEdit> {
+    all_rows(content)
+        .filter_map(|cells| {
+            let real_cells = real_cell_count(cells);
+            if *col < real_cells {
+                let range = cell_range(cells.get(col)?, snapshot)?;
+                Some((
+                    range.start..range.start,
+                    content.dialect.delimiter.to_string(),
+                ))
+            } else if *col == real_cells {
+                let range = row_range(cells, snapshot)?;
+                Some((range.end..range.end, content.dialect.delimiter.to_string()))
+            } else {
+                // The row is too short to reach the new column, which is padded anyway.
+                None
+            }
+        })
+        .collect()
+}
+
+/// Deletes a column, along with the delimiter separating it from its neighbor.
+pub(crate) fn delete_column(
+    content: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    col: AnyColumn,
+) -> Vec<BufferEdit> {
+    all_rows(content)
+        .filter_map(|cells| {
+            let range = cell_range(cells.get(col)?, snapshot)?;
+            let range = if *col > 0 {
+                let previous = cell_range(cells.get(AnyColumn(*col - 1))?, snapshot)?;
+                previous.end..range.end
+            } else if let Some(next) = cells
+                .get(AnyColumn(1))
+                .and_then(|cell| cell_range(cell, snapshot))
+            {
+                range.start..next.start
+            } else {
+                range
+            };
+            Some((range, String::new()))
+        })
+        .collect()
+}
+
+/// Moves the column at `from` so that it becomes the column at `to`, rewriting every row.
+pub(crate) fn move_column(
+    content: &TableLikeContent,
+    snapshot: &BufferSnapshot,
+    from: AnyColumn,
+    to: AnyColumn,
+) -> Vec<BufferEdit> {
+    if from == to || *from >= content.number_of_cols || *to >= content.number_of_cols {
+        return Vec::new();
+    }
+
+    all_rows(content)
+        .filter_map(|cells| {
+            let range = row_range(cells, snapshot)?;
+            let mut fields = cells
+                .as_slice()
+                .iter()
+                .map(|cell| {
+                    let range = cell_range(cell, snapshot)?;
+                    Some(snapshot.text_for_range(range).collect::<String>())
+                })
+                .collect::<Vec<_>>();
+            let field = fields.remove(*from);
+            fields.insert(*to, field);
+            while fields.last().is_some_and(Option::is_none) {
+                fields.pop();
+            }
+
+            let new_text = fields
+                .into_iter()
+                .map(Option::unwrap_or_default)
+                .collect::<Vec<_>>()
+                .join(&content.dialect.delimiter.to_string());
+            let old_text = snapshot.text_for_range(range.clone()).collect::<String>();
+            (new_text != old_text).then_some((range, new_text))
+        })
+        .collect()
+}
+
+fn source_row(content: &TableLikeContent, row: SourceRow) -> Option<&TableRow<TableCell>> {
+    match row {
+        SourceRow::Header => Some(&content.headers),
+        SourceRow::Data(row) => content.get_row(row),
+    }
+}
+
+fn all_rows(content: &TableLikeContent) -> impl Iterator<Item = &TableRow<TableCell>> {
+    std::iter::once(&content.headers).chain(&content.rows)
+}
+
+fn empty_row(number_of_cols: usize, dialect: CsvDialect) -> String {
+    if number_of_cols > 1 {
+        dialect.delimiter.to_string().repeat(number_of_cols - 1)
+    } else {
+        // A blank line would not be parsed as a row.
+        format!("{0}{0}", dialect.quote)
+    }
+}
+
+fn cell_range(cell: &TableCell, snapshot: &BufferSnapshot) -> Option<Range<usize>> {
+    match cell {
+        TableCell::Real { position, .. } => {
+            Some(position.start.to_offset(snapshot)..position.end.to_offset(snapshot))
+        }
+        TableCell::Virtual => None,
+    }
+}
+
+/// Cells present in the source come first in a row, followed by the virtual cells padding it.
+fn real_cell_count(cells: &TableRow<TableCell>) -> usize {
+    cells
+        .as_slice()
+        .iter()
+        .take_while(|cell| matches!(cell, TableCell::Real { .. }))
+        .count()
+}
+
+/// Returns the range from the start of the first cell of a row to the end of its last cell.
+fn row_range(cells: &TableRow<TableCell>, snapshot: &BufferSnapshot) -> Option<Range<usize>> {
+    let real_cells = real_cell_count(cells);
+    let first = cell_range(cells.get(AnyColumn(0))?, snapshot)?;
+    let last = cell_range(cells.get(AnyColumn(real_cells.checked_sub(1)?))?, snapshot)?;
+    Some(first.start..last.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::from_buffer;
+    use text::{Buffer, BufferId, ReplicaId};
+
+    fn apply(
+        text: &str,
+        edit: impl FnOnce(&TableLikeContent, &BufferSnapshot) -> Vec<BufferEdit>,
+    ) -> String {
+        let mut buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), text);
+        let content = from_buffer(buffer.snapshot(), None);
+        let edits = edit(&content, buffer.snapshot());
+        buffer.edit(edits);
+        buffer.text()
+    }
+
+    #[test]
+    fn test_quote_field() {
+        let dialect = CsvDialect::default();
+        assert_eq!(quote_field("plain", dialect), "plain");
+        assert_eq!(quote_field("a,b", dialect), "\"a,b\"");
+        assert_eq!(quote_field("say \"hi\"", dialect), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_field("two\nlines", dialect), "\"two\nlines\"");
+
+        let dialect = CsvDialect {
+            delimiter: ';',
+            quote: '\'',
+        };
+        assert_eq!(quote_field("a,b", dialect), "a,b");
+        assert_eq!(quote_field("a;b", dialect), "'a;b'");
+        assert_eq!(quote_field("don't", dialect), "'don''t'");
+    }
+
+    #[test]
+    fn test_set_cell() {
+        let text = "name,city\nJohn,\"New York\"\nJane\n";
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                set_cell(
+                    content,
+                    snapshot,
+                    SourceRow::Data(DataRow(0)),
+                    AnyColumn(1),
+                    "Paris, FR",
+                )
+            }),
+            "name,city\nJohn,\"Paris, FR\"\nJane\n"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                set_cell(
+                    content,
+                    snapshot,
+                    SourceRow::Header,
+                    AnyColumn(0),
+                    "first name",
+                )
+            }),
+            "first name,city\nJohn,\"New York\"\nJane\n"
+        );
+        // Jane's row has no city field yet.
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                set_cell(
+                    content,
+                    snapshot,
+                    SourceRow::Data(DataRow(1)),
+                    AnyColumn(1),
+                    "Rome",
+                )
+            }),
+            "name,city\nJohn,\"New York\"\nJane,Rome\n"
+        );
+    }
+
+    #[test]
+    fn test_insert_and_delete_rows() {
+        let text = "a,b\n1,2\n3,4";
+        assert_eq!(
+            apply(text, |content, snapshot| insert_row(content, snapshot, 1)),
+            "a,b\n1,2\n,\n3,4"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| insert_row(content, snapshot, 2)),
+            "a,b\n1,2\n3,4\n,"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| delete_row(
+                content,
+                snapshot,
+                DataRow(0)
+            )),
+            "a,b\n3,4"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| delete_row(
+                content,
+                snapshot,
+                DataRow(1)
+            )),
+            "a,b\n1,2"
+        );
+
+        // Rows are inserted with the line ending of the file.
+        let mut buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), "a,b\r\n1,2");
+        let content = from_buffer(buffer.snapshot(), None);
+        let edits = insert_row(&content, buffer.snapshot(), 1);
+        assert_eq!(edits[0].1, "\r\n,");
+        buffer.edit(edits);
+        assert_eq!(
+            text::chunks_with_line_ending(buffer.as_rope(), buffer.line_ending())
+                .collect::<String>(),
+            "a,b\r\n1,2\r\n,"
+        );
+
+        // Inserted rows are parsed as rows, even though they are empty.
+        let text = apply(text, |content, snapshot| insert_row(content, snapshot, 0));
+        assert_eq!(TableLikeContent::from_str(text).rows.len(), 3);
+    }
+
+    #[test]
+    fn test_insert_and_delete_columns() {
+        let text = "a,b\n1,2\n3";
+        assert_eq!(
+            apply(text, |content, snapshot| insert_column(
+                content,
+                snapshot,
+                AnyColumn(0)
+            )),
+            ",a,b\n,1,2\n,3"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| insert_column(
+                content,
+                snapshot,
+                AnyColumn(1)
+            )),
+            "a,,b\n1,,2\n3,"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| insert_column(
+                content,
+                snapshot,
+                AnyColumn(2)
+            )),
+            "a,b,\n1,2,\n3"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| delete_column(
+                content,
+                snapshot,
+                AnyColumn(0)
+            )),
+            "b\n2\n"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| delete_column(
+                content,
+                snapshot,
+                AnyColumn(1)
+            )),
+            "a\n1\n3"
+        );
+    }
+
+    #[test]
+    fn test_move_column() {
+        let text = "a,b,\"c,d\"\n1,2,3\n4";
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                move_column(content, snapshot, AnyColumn(0), AnyColumn(2))
+            }),
+            "b,\"c,d\",a\n2,3,1\n,,4"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                move_column(content, snapshot, AnyColumn(2), AnyColumn(1))
+            }),
+            "a,\"c,d\",b\n1,3,2\n4"
+        );
+    }
+
+    #[test]
+    fn test_edits_use_the_dialect_of_the_table() {
+        let text = "a;b\n1;2\n";
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                set_cell(
+                    content,
+                    snapshot,
+                    SourceRow::Data(DataRow(0)),
+                    AnyColumn(1),
+                    "2;5",
+                )
+            }),
+            "a;b\n1;\"2;5\"\n"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| insert_row(content, snapshot, 0)),
+            "a;b\n;\n1;2\n"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| insert_column(
+                content,
+                snapshot,
+                AnyColumn(1)
+            )),
+            "a;;b\n1;;2\n"
+        );
+        assert_eq!(
+            apply(text, |content, snapshot| {
+                move_column(content, snapshot, AnyColumn(1), AnyColumn(0))
+            }),
+            "b;a\n2;1\n"
+        );
+    }
+}
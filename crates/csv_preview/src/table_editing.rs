@@ -0,0 +1,221 @@
+//! Write-back editing of the table.
+//!
+//! Every change is applied as an edit of the source buffer, so it can be undone and is shared
+//! with collaborators. The table is then parsed again from the edited buffer.
+
+use editor::{Editor, EditorEvent};
+use gpui::{Entity, Subscription};
+use text::BufferSnapshot;
+use ui::{SharedString, prelude::*};
+
+use crate::{
+    CsvPreviewView,
+    table_data_engine::{
+        sorting_by_column::AppliedSorting,
+        table_edits::{self, BufferEdit, SourceRow},
+    },
+    types::{AnyColumn, DataRow, TableLikeContent},
+};
+
+/// A table cell being edited inline.
+pub(crate) struct CellEditor {
+    pub row: SourceRow,
+    pub col: AnyColumn,
+    pub editor: Entity<Editor>,
+    original_value: SharedString,
+    _subscription: Subscription,
+}
+
+impl CsvPreviewView {
+    /// Whether the source buffer of the table can be edited.
+    pub(crate) fn is_editable(&self, cx: &App) -> bool {
+        self.editor_state()
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+            .is_some_and(|buffer| !buffer.read(cx).read_only())
+    }
+
+    pub(crate) fn start_editing_cell(
+        &mut self,
+        row: SourceRow,
+        col: AnyColumn,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_editable(cx) {
+            return;
+        }
+
+        let cells = match row {
+            SourceRow::Header => Some(&self.engine.contents.headers),
+            SourceRow::Data(row) => self.engine.contents.get_row(row),
+        };
+        let original_value = cells
+            .and_then(|cells| cells.get(col))
+            .and_then(|cell| cell.display_value())
+            .cloned()
+            .unwrap_or_default();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_text(original_value.as_ref(), window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        let subscription = cx.subscribe_in(
+            &editor,
+            window,
+            |this, _, event: &EditorEvent, window, cx| {
+                if let EditorEvent::Blurred = event {
+                    this.confirm_cell_edit(window, cx);
+                }
+            },
+        );
+        window.focus(&editor.focus_handle(cx), cx);
+        self.cell_editor = Some(CellEditor {
+            row,
+            col,
+            editor,
+            original_value,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    /// Writes the value of the cell being edited back to the buffer.
+    pub(crate) fn confirm_cell_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(cell_editor) = self.cell_editor.take() else {
+            return;
+        };
+        let value = cell_editor.editor.read(cx).text(cx);
+        if value != cell_editor.original_value.as_ref() {
+            self.apply_table_edits(
+                |content, snapshot| {
+                    table_edits::set_cell(
+                        content,
+                        snapshot,
+                        cell_editor.row,
+                        cell_editor.col,
+                        &value,
+                    )
+                },
+                cx,
+            );
+        }
+        window.focus(&self.focus_handle, cx);
+        cx.notify();
+    }
+
+    pub(crate) fn cancel_cell_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_editor.take().is_some() {
+            window.focus(&self.focus_handle, cx);
+            cx.notify();
+        }
+    }
+
+    /// Inserts an empty row, so that it becomes the data row at `index`.
+    pub(crate) fn insert_row(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.apply_table_edits(
+            |content, snapshot| table_edits::insert_row(content, snapshot, index),
+            cx,
+        );
+    }
+
+    pub(crate) fn delete_row(&mut self, row: DataRow, cx: &mut Context<Self>) {
+        self.apply_table_edits(
+            |content, snapshot| table_edits::delete_row(content, snapshot, row),
+            cx,
+        );
+    }
+
+    /// Inserts an empty column, so that it becomes the column at `col`.
+    pub(crate) fn insert_column(&mut self, col: AnyColumn, cx: &mut Context<Self>) {
+        if self.apply_table_edits(
+            |content, snapshot| table_edits::insert_column(content, snapshot, col),
+            cx,
+        ) {
+            self.remap_sorted_column(|sorted| {
+                Some(if sorted >= col {
+                    AnyColumn(*sorted + 1)
+                } else {
+                    sorted
+                })
+            });
+        }
+    }
+
+    pub(crate) fn delete_column(&mut self, col: AnyColumn, cx: &mut Context<Self>) {
+        if self.apply_table_edits(
+            |content, snapshot| table_edits::delete_column(content, snapshot, col),
+            cx,
+        ) {
+            self.remap_sorted_column(|sorted| match sorted.cmp(&col) {
+                std::cmp::Ordering::Less => Some(sorted),
+                std::cmp::Ordering::Equal => None,
+                std::cmp::Ordering::Greater => Some(AnyColumn(*sorted - 1)),
+            });
+        }
+    }
+
+    /// Moves the column at `from` so that it becomes the column at `to`.
+    pub(crate) fn move_column(&mut self, from: AnyColumn, to: AnyColumn, cx: &mut Context<Self>) {
+        if self.apply_table_edits(
+            |content, snapshot| table_edits::move_column(content, snapshot, from, to),
+            cx,
+        ) {
+            self.remap_sorted_column(|sorted| {
+                Some(if sorted == from {
+                    to
+                } else if from < sorted && sorted <= to {
+                    AnyColumn(*sorted - 1)
+                } else if to <= sorted && sorted < from {
+                    AnyColumn(*sorted + 1)
+                } else {
+                    sorted
+                })
+            });
+        }
+    }
+
+    /// Keeps sorting by the same data after the columns of the table changed.
+    fn remap_sorted_column(&mut self, remap: impl FnOnce(AnyColumn) -> Option<AnyColumn>) {
+        self.engine.applied_sorting = self.engine.applied_sorting.and_then(|sorting| {
+            remap(sorting.col_idx).map(|col_idx| AppliedSorting { col_idx, ..sorting })
+        });
+    }
+
+    /// Applies the edits computed from the parsed table to the source buffer, as a single
+    /// transaction. Returns whether the buffer was edited.
+    fn apply_table_edits(
+        &mut self,
+        edits: impl FnOnce(&TableLikeContent, &BufferSnapshot) -> Vec<BufferEdit>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.is_editable(cx) {
+            return false;
+        }
+        let Some(buffer) = self
+            .editor_state()
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+        else {
+            return false;
+        };
+
+        let snapshot = buffer.read(cx).text_snapshot();
+        let edits = edits(&self.engine.contents, &snapshot);
+        if edits.is_empty() {
+            return false;
+        }
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit(edits, None, cx);
+        });
+        self.parse_csv_from_active_editor(false, cx);
+        true
+    }
+}
@@ -1,6 +1,9 @@
 use ui::table_row::TableRow;
 
-use crate::types::{DataRow, LineNumber, TableCell};
+use crate::{
+    dialect::CsvDialect,
+    types::{DataRow, LineNumber, TableCell},
+};
 
 /// Generic container struct of table-like data (CSV, TSV, etc)
 #[derive(Clone)]
@@ -12,6 +15,8 @@ pub struct TableLikeContent {
     pub rows: Vec<TableRow<TableCell>>,
     /// Follows the same indices as `rows`
     pub line_numbers: Vec<LineNumber>,
+    /// The dialect the content was parsed with, also used when writing edits back
+    pub dialect: CsvDialect,
 }
 
 impl Default for TableLikeContent {
@@ -21,6 +26,7 @@ impl Default for TableLikeContent {
             headers: TableRow::<TableCell>::from_vec(vec![], 0),
             rows: vec![],
             line_numbers: vec![],
+            dialect: CsvDialect::default(),
         }
     }
 }
@@ -395,6 +395,29 @@ pub trait DebugAdapter: 'static + Send + Sync {
     fn prefer_thread_name(&self) -> bool {
         false
     }
+
+    /// Whether [`DebugAdapter::config_for_core_dump`] is implemented for this adapter.
+    fn supports_core_dumps(&self) -> bool {
+        false
+    }
+
+    /// Builds a scenario that loads `core_file` for `program` for post-mortem debugging, without running the program.
+    async fn config_for_core_dump(
+        &self,
+        _program: &Path,
+        _core_file: &Path,
+        _label: SharedString,
+    ) -> Result<DebugScenario> {
+        Err(anyhow!(
+            "{} does not support debugging core dumps",
+            self.name()
+        ))
+    }
+
+    /// Whether the given configuration inspects a core dump rather than a live process.
+    fn is_core_dump(&self, _config: &serde_json::Value) -> bool {
+        false
+    }
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -1,17 +1,30 @@
-use std::{path::PathBuf, sync::OnceLock};
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 use anyhow::{Context as _, Result};
 use async_trait::async_trait;
 use collections::HashMap;
 use dap::adapters::{DebugTaskDefinition, latest_github_release};
 use futures::StreamExt;
-use gpui::AsyncApp;
+use gpui::{AsyncApp, SharedString};
 use serde_json::Value;
 use task::{DebugRequest, DebugScenario, ZedDebugConfig};
 use util::fs::remove_matching;
 
 use crate::*;
 
+/// Quotes a path so that it's passed to an LLDB command as a single argument.
+fn lldb_quote(path: &Path) -> String {
+    format!(
+        "\"{}\"",
+        path.to_string_lossy()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+    )
+}
+
 #[derive(Default)]
 pub(crate) struct CodeLldbDebugAdapter {
     path_to_codelldb: OnceLock<String>,
@@ -133,6 +146,50 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 
+    fn supports_core_dumps(&self) -> bool {
+        true
+    }
+
+    async fn config_for_core_dump(
+        &self,
+        program: &Path,
+        core_file: &Path,
+        label: SharedString,
+    ) -> Result<DebugScenario> {
+        // A custom launch: LLDB creates the target from the core file and no process is started.
+        let configuration = json!({
+            "request": "launch",
+            "name": label.as_ref(),
+            "targetCreateCommands": [format!(
+                "target create --core {} {}",
+                lldb_quote(core_file),
+                lldb_quote(program)
+            )],
+            "processCreateCommands": [],
+        });
+
+        Ok(DebugScenario {
+            adapter: Self::ADAPTER_NAME.into(),
+            label,
+            config: configuration,
+            build: None,
+            tcp_connection: None,
+        })
+    }
+
+    fn is_core_dump(&self, config: &Value) -> bool {
+        config
+            .get("targetCreateCommands")
+            .and_then(Value::as_array)
+            .is_some_and(|commands| {
+                commands.iter().filter_map(Value::as_str).any(|command| {
+                    command
+                        .split_whitespace()
+                        .any(|arg| arg == "--core" || arg == "-c")
+                })
+            })
+    }
+
     fn dap_schema(&self) -> serde_json::Value {
         json!({
             "properties": {
@@ -424,3 +481,32 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_core_dump_config_creates_target_from_core_file() {
+        let adapter = CodeLldbDebugAdapter::default();
+        let scenario = adapter
+            .config_for_core_dump(
+                Path::new("/tmp/test/my server"),
+                Path::new("/tmp/test/core.1234"),
+                "server core dump".into(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            scenario.config["targetCreateCommands"],
+            json!([r#"target create --core "/tmp/test/core.1234" "/tmp/test/my server""#])
+        );
+        assert_eq!(scenario.config["processCreateCommands"], json!([]));
+        assert!(adapter.is_core_dump(&scenario.config));
+        assert!(!adapter.is_core_dump(&json!({
+            "request": "launch",
+            "program": "/tmp/test/my server",
+        })));
+    }
+}
@@ -1083,6 +1083,7 @@ impl SearchableItem for DapLogView {
             word: true,
             regex: true,
             find_in_results: true,
+            scope: false,
             // DAP log is read-only.
             replacement: false,
             selection: false,
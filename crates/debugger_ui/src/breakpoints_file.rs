@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use gpui::{Context, SharedString, Window};
+use project::{WorktreeId, debugger::breakpoint_store::ExportedBreakpoint};
+use serde::{Deserialize, Serialize};
+use workspace::{OpenOptions, Workspace, notifications::DetachAndPromptErr};
+
+use crate::debugger_panel::DebugPanel;
+
+/// The contents of `.zed/breakpoints.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BreakpointsFile {
+    #[serde(default)]
+    breakpoints: Vec<ExportedBreakpoint>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    watches: Vec<String>,
+}
+
+/// Writes the project's breakpoints and watch expressions to `.zed/breakpoints.json` in the
+/// first worktree, then opens the file so it can be reviewed before committing it.
+pub(crate) fn export_breakpoints(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let (worktree_id, path) = match breakpoints_file_path(workspace, cx) {
+        Ok(location) => location,
+        Err(error) => {
+            workspace.show_error(&error, cx);
+            return;
+        }
+    };
+
+    let breakpoints = workspace
+        .project()
+        .read(cx)
+        .breakpoint_store()
+        .read(cx)
+        .export_breakpoints(worktree_id, cx);
+    let watches = workspace
+        .panel::<DebugPanel>(cx)
+        .map(|debug_panel| debug_panel.read(cx).watch_expressions(cx))
+        .unwrap_or_default();
+    let contents = serde_json::to_string_pretty(&BreakpointsFile {
+        breakpoints,
+        watches: watches.iter().map(ToString::to_string).collect(),
+    });
+    let fs = workspace.app_state().fs.clone();
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let contents = contents?;
+        if let Some(parent) = path.parent() {
+            fs.create_dir(parent).await?;
+        }
+        fs.write(&path, contents.as_bytes()).await?;
+
+        workspace
+            .update_in(cx, |workspace, window, cx| {
+                workspace.open_abs_path(path, OpenOptions::default(), window, cx)
+            })?
+            .await?;
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to export breakpoints", window, cx, |_, _, _| None);
+}
+
+/// Adds the breakpoints and watch expressions from `.zed/breakpoints.json` in the first
+/// worktree, re-anchoring the breakpoints in the files they refer to.
+pub(crate) fn import_breakpoints(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let (worktree_id, path) = match breakpoints_file_path(workspace, cx) {
+        Ok(location) => location,
+        Err(error) => {
+            workspace.show_error(&error, cx);
+            return;
+        }
+    };
+
+    let breakpoint_store = workspace.project().read(cx).breakpoint_store();
+    let debug_panel = workspace.panel::<DebugPanel>(cx);
+    let fs = workspace.app_state().fs.clone();
+
+    cx.spawn_in(window, async move |_, cx| {
+        let contents = fs
+            .load(&path)
+            .await
+            .with_context(|| format!("reading {path:?}"))?;
+        let file: BreakpointsFile =
+            serde_json_lenient::from_str(&contents).with_context(|| format!("parsing {path:?}"))?;
+
+        breakpoint_store
+            .update(cx, |breakpoint_store, cx| {
+                breakpoint_store.import_breakpoints(worktree_id, file.breakpoints, cx)
+            })
+            .await?;
+
+        if let Some(debug_panel) = debug_panel {
+            debug_panel.update(cx, |debug_panel, cx| {
+                debug_panel.import_watches(
+                    file.watches.into_iter().map(SharedString::from).collect(),
+                    cx,
+                );
+            });
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_prompt_err("Failed to import breakpoints", window, cx, |_, _, _| None);
+}
+
+fn breakpoints_file_path(
+    workspace: &Workspace,
+    cx: &Context<Workspace>,
+) -> Result<(WorktreeId, PathBuf)> {
+    anyhow::ensure!(
+        workspace.project().read(cx).is_local(),
+        "Breakpoints can only be exported and imported in local projects"
+    );
+    let worktree = workspace
+        .visible_worktrees(cx)
+        .next()
+        .context("Breakpoints can only be exported and imported in a project with a folder")?;
+    let worktree = worktree.read(cx);
+    let path = worktree
+        .abs_path()
+        .join(paths::local_breakpoints_file_relative_path().as_std_path());
+    Ok((worktree.id(), path))
+}
@@ -0,0 +1,133 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use dap::{DapRegistry, adapters::DebugAdapter};
+use gpui::{
+    AsyncWindowContext, Context, PathPromptOptions, PromptLevel, SharedString, WeakEntity, Window,
+};
+use project::DirectoryLister;
+use task::SharedTaskContext;
+use workspace::{Workspace, notifications::DetachAndPromptErr};
+
+use crate::debugger_panel::DebugPanel;
+
+/// Asks for an executable and a core file it produced, then opens a stopped
+/// session to inspect the threads, stack frames and variables at the time of the crash.
+pub(crate) fn debug_core_dump(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let adapters = core_dump_adapters(cx);
+    if adapters.is_empty() {
+        workspace.show_error(
+            &anyhow::anyhow!("No installed debug adapter can debug core dumps"),
+            cx,
+        );
+        return;
+    }
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(adapter) = pick_adapter(adapters, cx).await else {
+            return Ok(());
+        };
+        let Some(program) = prompt_for_file(&workspace, "Select Executable".into(), cx).await?
+        else {
+            return Ok(());
+        };
+        let Some(core_file) = prompt_for_file(&workspace, "Select Core Dump".into(), cx).await?
+        else {
+            return Ok(());
+        };
+
+        let label = format!(
+            "Core dump: {}",
+            core_file
+                .file_name()
+                .unwrap_or(core_file.as_os_str())
+                .to_string_lossy()
+        );
+        let scenario = adapter
+            .config_for_core_dump(&program, &core_file, label.into())
+            .await?;
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let debug_panel = workspace
+                .panel::<DebugPanel>(cx)
+                .context("Debug panel is not available")?;
+            workspace.focus_panel::<DebugPanel>(window, cx);
+            debug_panel.update(cx, |debug_panel, cx| {
+                debug_panel.start_session(
+                    scenario,
+                    SharedTaskContext::default(),
+                    None,
+                    None,
+                    window,
+                    cx,
+                );
+            });
+            anyhow::Ok(())
+        })?
+    })
+    .detach_and_prompt_err("Failed to debug core dump", window, cx, |_, _, _| None);
+}
+
+fn core_dump_adapters(cx: &mut Context<Workspace>) -> Vec<Arc<dyn DebugAdapter>> {
+    let registry = DapRegistry::global(cx);
+    let mut adapters = registry
+        .enumerate_adapters::<Vec<_>>()
+        .into_iter()
+        .filter_map(|name| registry.adapter(&name))
+        .filter(|adapter| adapter.supports_core_dumps())
+        .collect::<Vec<_>>();
+    adapters.sort_by_key(|adapter| adapter.name());
+    adapters
+}
+
+/// Lets the user choose the debugger when more than one of them can open core dumps.
+async fn pick_adapter(
+    mut adapters: Vec<Arc<dyn DebugAdapter>>,
+    cx: &mut AsyncWindowContext,
+) -> Option<Arc<dyn DebugAdapter>> {
+    if adapters.len() == 1 {
+        return adapters.pop();
+    }
+
+    let mut answers = adapters
+        .iter()
+        .map(|adapter| adapter.name().to_string())
+        .collect::<Vec<_>>();
+    answers.push("Cancel".to_string());
+    let answers = answers.iter().map(String::as_str).collect::<Vec<_>>();
+    let response = cx
+        .prompt(
+            PromptLevel::Info,
+            "Which debugger should open the core dump?",
+            None,
+            &answers,
+        )
+        .await
+        .ok()?;
+    (response < adapters.len()).then(|| adapters.swap_remove(response))
+}
+
+async fn prompt_for_file(
+    workspace: &WeakEntity<Workspace>,
+    prompt: SharedString,
+    cx: &mut AsyncWindowContext,
+) -> Result<Option<PathBuf>> {
+    let paths = workspace.update_in(cx, |workspace, window, cx| {
+        workspace.prompt_for_open_path(
+            PathPromptOptions {
+                files: true,
+                directories: false,
+                multiple: false,
+                prompt: Some(prompt),
+            },
+            DirectoryLister::Project(workspace.project().clone()),
+            window,
+            cx,
+        )
+    })?;
+    Ok(paths.await?.and_then(|mut paths| paths.pop()))
+}
@@ -24,7 +24,9 @@ use gpui::{
 
 use itertools::Itertools as _;
 use language::Buffer;
-use project::debugger::session::{Session, SessionQuirks, SessionState, SessionStateEvent};
+use project::debugger::session::{
+    Session, SessionEvent, SessionQuirks, SessionState, SessionStateEvent,
+};
 use project::{DebugScenarioContext, Fs, ProjectPath, TaskSourceKind, WorktreeId};
 use project::{Project, debugger::session::ThreadStatus};
 use rpc::proto::{self};
@@ -68,6 +70,8 @@ pub struct DebugPanel {
     pub(crate) session_picker_menu_handle: PopoverMenuHandle<ContextMenu>,
     fs: Arc<dyn Fs>,
     is_zoomed: bool,
+    /// Watch expressions imported from `.zed/breakpoints.json`, added to every new session.
+    imported_watches: Vec<SharedString>,
     _subscriptions: [Subscription; 1],
     breakpoint_list: Entity<BreakpointList>,
 }
@@ -111,6 +115,7 @@ impl DebugPanel {
                 thread_picker_menu_handle,
                 session_picker_menu_handle,
                 is_zoomed: false,
+                imported_watches: Vec::new(),
                 _subscriptions: [focus_subscription],
                 debug_scenario_scheduled_last: true,
             }
@@ -190,6 +195,7 @@ impl DebugPanel {
         let quirks = SessionQuirks {
             compact: adapter.compact_child_session(),
             prefer_thread_name: adapter.prefer_thread_name(),
+            post_mortem: adapter.is_core_dump(&scenario.config),
         };
         let session = dap_store.update(cx, |dap_store, cx| {
             dap_store.new_session(
@@ -385,6 +391,11 @@ impl DebugPanel {
         let adapter = curr_session.read(cx).adapter();
         let binary = curr_session.read(cx).binary().cloned().unwrap();
         let task_context = curr_session.read(cx).task_context().clone();
+        let instruction_breakpoints = curr_session
+            .read(cx)
+            .instruction_breakpoints()
+            .cloned()
+            .collect::<Vec<_>>();
 
         let curr_session_id = curr_session.read(cx).session_id();
         self.sessions_with_children
@@ -400,6 +411,7 @@ impl DebugPanel {
                 let session = dap_store.new_session(label, adapter, task_context, None, quirks, cx);
 
                 let task = session.update(cx, |session, cx| {
+                    session.restore_instruction_breakpoints(instruction_breakpoints);
                     session.boot(binary, worktree, dap_store_handle.downgrade(), cx)
                 });
                 (session, task)
@@ -462,6 +474,7 @@ impl DebugPanel {
                 );
 
                 let task = session.update(cx, |session, cx| {
+                    session.restore_instruction_breakpoints(instruction_breakpoints);
                     session.boot(binary, worktree, dap_store_handle.downgrade(), cx)
                 });
                 (session, task)
@@ -676,6 +689,11 @@ impl DebugPanel {
             .map(|session| session.read(cx).running_state())
             .and_then(|state| state.read(cx).thread_status(cx))
             .unwrap_or(project::debugger::session::ThreadStatus::Exited);
+        // Core dumps have no live process, so execution can't move.
+        let can_step = thread_status == ThreadStatus::Stopped
+            && !active_session
+                .as_ref()
+                .is_some_and(|session| session.read(cx).quirks.post_mortem);
 
         Some(
             div.w_full()
@@ -735,7 +753,7 @@ impl DebugPanel {
                                                     running_state,
                                                     |this, _, _window, cx| this.continue_thread(cx),
                                                 ))
-                                                .disabled(thread_status != ThreadStatus::Stopped)
+                                                .disabled(!can_step)
                                                 .tooltip({
                                                     let focus_handle = focus_handle.clone();
                                                     move |_window, cx| {
@@ -759,7 +777,7 @@ impl DebugPanel {
                                                     this.step_over(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(!can_step)
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |_window, cx| {
@@ -781,7 +799,7 @@ impl DebugPanel {
                                                     this.step_in(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(!can_step)
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |_window, cx| {
@@ -803,7 +821,7 @@ impl DebugPanel {
                                                     this.step_out(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(!can_step)
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |_window, cx| {
@@ -1333,6 +1351,49 @@ impl DebugPanel {
         None
     }
 
+    /// Returns the watch expressions of all sessions, along with the imported ones.
+    pub(crate) fn watch_expressions(&self, cx: &App) -> Vec<SharedString> {
+        let mut watches = self
+            .sessions_with_children
+            .keys()
+            .flat_map(|session| {
+                let session = session.read(cx).running_state().read(cx).session();
+                session
+                    .read(cx)
+                    .watchers()
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .chain(self.imported_watches.iter().cloned())
+            .collect::<Vec<_>>();
+        watches.sort();
+        watches.dedup();
+        watches
+    }
+
+    /// Adds watch expressions to the running sessions and to any session started later.
+    pub(crate) fn import_watches(&mut self, watches: Vec<SharedString>, cx: &mut Context<Self>) {
+        for debug_session in self.sessions_with_children.keys() {
+            let running_state = debug_session.read(cx).running_state().read(cx);
+            let stack_frame_id = running_state.selected_stack_frame_id(cx);
+            running_state.session().clone().update(cx, |session, cx| {
+                for watch in &watches {
+                    session.add_pending_watcher(watch.clone(), cx);
+                }
+                if let Some(stack_frame_id) = stack_frame_id {
+                    session.refresh_watchers(stack_frame_id, cx);
+                }
+            });
+        }
+
+        for watch in watches {
+            if !self.imported_watches.contains(&watch) {
+                self.imported_watches.push(watch);
+            }
+        }
+    }
+
     fn retain_sessions(&mut self, keep: &dyn Fn(&Entity<DebugSession>) -> bool) {
         self.sessions_with_children
             .retain(|session, _| keep(session));
@@ -1463,6 +1524,21 @@ async fn register_session_inner(
     .ok();
     let serialized_layout = persistence::get_serialized_layout(adapter_name).await;
     let debug_session = this.update_in(cx, |this, window, cx| {
+        session.update(cx, |session, cx| {
+            for watch in &this.imported_watches {
+                session.add_pending_watcher(watch.clone(), cx);
+            }
+        });
+        // Imported watches stop being added to new sessions once the user removes them.
+        cx.subscribe(&session, |this, session, event: &SessionEvent, cx| {
+            if let SessionEvent::Watchers = event {
+                let watchers = session.read(cx).watchers();
+                this.imported_watches
+                    .retain(|watch| watchers.contains_key(watch));
+            }
+        })
+        .detach();
+
         let parent_session = this
             .sessions_with_children
             .keys()
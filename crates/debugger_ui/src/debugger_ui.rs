@@ -8,6 +8,7 @@ use project::debugger::{self, breakpoint_store::SourceBreakpoint, session::Threa
 use schemars::JsonSchema;
 use serde::Deserialize;
 use session::DebugSession;
+use step_in_targets_modal::StepInTargetsModal;
 
 use tasks_ui::{Spawn, TaskOverrides};
 use ui::{FluentBuilder, InteractiveElement};
@@ -16,11 +17,14 @@ use workspace::{ShutdownDebugAdapters, Workspace};
 use zed_actions::debug_panel::{Toggle, ToggleFocus};
 
 pub mod attach_modal;
+mod breakpoints_file;
+mod core_dump;
 pub mod debugger_panel;
 mod dropdown_menus;
 mod new_process_modal;
 mod persistence;
 pub(crate) mod session;
+mod step_in_targets_modal;
 
 #[cfg(any(test, feature = "test-support"))]
 pub mod tests;
@@ -31,6 +35,8 @@ actions!(
     [
         /// Starts a new debugging session.
         Start,
+        /// Opens a core dump of a crashed program for post-mortem debugging.
+        DebugCoreDump,
         /// Continues execution until the next breakpoint.
         Continue,
         /// Detaches the debugger from the running process.
@@ -49,12 +55,22 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Steps over the current machine instruction.
+        StepOverInstruction,
+        /// Steps into the current machine instruction.
+        StepIntoInstruction,
+        /// Picks which call on the current line to step into.
+        StepIntoTarget,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
         ToggleIgnoreBreakpoints,
         /// Clears all breakpoints in the project.
         ClearAllBreakpoints,
+        /// Writes the project's breakpoints and watch expressions to `.zed/breakpoints.json`.
+        ExportBreakpoints,
+        /// Adds the breakpoints and watch expressions from `.zed/breakpoints.json`.
+        ImportBreakpoints,
         /// Focuses on the debugger console panel.
         FocusConsole,
         /// Focuses on the variables panel.
@@ -123,6 +139,19 @@ pub fn init(cx: &mut App) {
             .register_action(|workspace: &mut Workspace, _: &Start, window, cx| {
                 NewProcessModal::show(workspace, window, NewProcessMode::Debug, None, cx);
             })
+            .register_action(|workspace: &mut Workspace, _: &DebugCoreDump, window, cx| {
+                core_dump::debug_core_dump(workspace, window, cx);
+            })
+            .register_action(
+                |workspace: &mut Workspace, _: &ExportBreakpoints, window, cx| {
+                    breakpoints_file::export_breakpoints(workspace, window, cx);
+                },
+            )
+            .register_action(
+                |workspace: &mut Workspace, _: &ImportBreakpoints, window, cx| {
+                    breakpoints_file::import_breakpoints(workspace, window, cx);
+                },
+            )
             .register_action(|workspace: &mut Workspace, _: &Rerun, window, cx| {
                 let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
                     return;
@@ -159,8 +188,16 @@ pub fn init(cx: &mut App) {
 
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
+                let supports_instruction_stepping =
+                    caps.supports_stepping_granularity.unwrap_or_default();
+                let supports_step_in_targets =
+                    caps.supports_step_in_targets_request.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
+                let post_mortem = running_state.session().read(cx).quirks().post_mortem;
                 let status = running_state.thread_status(cx);
+                // Core dumps have no live process, so execution can't move.
+                let can_step = status == Some(ThreadStatus::Stopped) && !post_mortem;
+                let weak_workspace = workspace.weak_handle();
 
                 let active_item = active_item.downgrade();
                 div.when(status == Some(ThreadStatus::Running), |div| {
@@ -171,7 +208,7 @@ pub fn init(cx: &mut App) {
                             .ok();
                     })
                 })
-                .when(status == Some(ThreadStatus::Stopped), |div| {
+                .when(can_step, |div| {
                     div.on_action({
                         let active_item = active_item.clone();
                         move |_: &StepInto, _, cx| {
@@ -196,6 +233,35 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_back(cx)).ok();
                         })
                     })
+                    .when(supports_instruction_stepping, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOverInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_over_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepIntoInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_in_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                    })
+                    .when(supports_step_in_targets, |div| {
+                        let active_item = active_item.clone();
+                        div.on_action(move |_: &StepIntoTarget, window, cx| {
+                            StepInTargetsModal::toggle(
+                                weak_workspace.clone(),
+                                active_item.clone(),
+                                window,
+                                cx,
+                            );
+                        })
+                    })
                     .on_action({
                         let active_item = active_item.clone();
                         move |_: &Continue, _, cx| {
@@ -286,10 +352,16 @@ pub fn init(cx: &mut App) {
                         return;
                     }
 
+                    let can_move_execution =
+                        session.any_stopped_thread() && !session.quirks().post_mortem;
+                    let supports_goto_targets = session
+                        .capabilities()
+                        .supports_goto_targets_request
+                        .unwrap_or_default();
                     let editor = cx.entity().downgrade();
 
                     window.on_action_when(
-                        session.any_stopped_thread(),
+                        can_move_execution,
                         TypeId::of::<editor::actions::RunToCursor>(),
                         {
                             let editor = editor.clone();
@@ -347,6 +419,60 @@ pub fn init(cx: &mut App) {
                         },
                     );
 
+                    window.on_action_when(
+                        can_move_execution && supports_goto_targets,
+                        TypeId::of::<editor::actions::JumpToCursor>(),
+                        {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, _, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (buffer, position, _) = editor
+                                        .update(cx, |editor, cx| {
+                                            let cursor_point: language::Point = editor
+                                                .selections
+                                                .newest(&editor.display_snapshot(cx))
+                                                .head();
+
+                                            editor
+                                                .buffer()
+                                                .read(cx)
+                                                .point_to_buffer_point(cursor_point, cx)
+                                        })
+                                        .ok()??;
+
+                                    let path =
+                                debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
+                                    &buffer, cx,
+                                )?;
+
+                                    active_session.update(cx, |session, cx| {
+                                        session.running_state().update(cx, |state, cx| {
+                                            if let Some(thread_id) = state.selected_thread_id() {
+                                                state.session().update(cx, |session, cx| {
+                                                    session
+                                                        .jump_to_position(
+                                                            &path,
+                                                            position.row,
+                                                            position.column,
+                                                            thread_id,
+                                                            cx,
+                                                        )
+                                                        .detach_and_log_err(cx);
+                                                })
+                                            }
+                                        });
+                                    });
+
+                                    Some(())
+                                });
+                            }
+                        },
+                    );
+
                     window.on_action(
                         TypeId::of::<editor::actions::EvaluateSelectedText>(),
                         move |_, _, window, cx| {
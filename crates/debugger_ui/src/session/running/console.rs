@@ -5,21 +5,23 @@ use super::{
 use alacritty_terminal::vte::ansi;
 use anyhow::Result;
 use collections::HashMap;
-use dap::{CompletionItem, CompletionItemType, OutputEvent};
+use dap::OutputEvent;
 use editor::{
-    Bias, CompletionProvider, Editor, EditorElement, EditorMode, EditorStyle, ExcerptId,
-    HighlightKey, MultiBufferOffset, SizingBehavior,
+    CompletionProvider, Editor, EditorElement, EditorMode, EditorStyle, ExcerptId, HighlightKey,
+    MultiBufferOffset, SizingBehavior,
 };
 use fuzzy::StringMatchCandidate;
 use gpui::{
     Action as _, AppContext, Context, Corner, Entity, FocusHandle, Focusable, HighlightStyle, Hsla,
     Render, Subscription, Task, TextStyle, WeakEntity, actions,
 };
-use language::{Anchor, Buffer, CharScopeContext, CodeLabel, TextBufferSnapshot, ToOffset};
+use language::{Buffer, CharScopeContext, CodeLabel};
 use menu::{Confirm, SelectNext, SelectPrevious};
 use project::{
     CompletionDisplayOptions, CompletionResponse,
-    debugger::session::{CompletionsQuery, OutputToken, Session},
+    debugger::session::{
+        CompletionsQuery, OutputToken, Session, completion_replace_range, completions_from_dap,
+    },
     lsp_store::CompletionDocumentation,
     search_history::{SearchHistory, SearchHistoryCursor},
 };
@@ -656,7 +658,7 @@ impl ConsoleQueryBarCompletionProvider {
                     let variable_value = variables.get(&string_match.string)?;
 
                     Some(project::Completion {
-                        replace_range: Self::replace_range_for_completion(
+                        replace_range: completion_replace_range(
                             &buffer_text,
                             buffer_position,
                             string_match.string.as_bytes(),
@@ -685,54 +687,6 @@ impl ConsoleQueryBarCompletionProvider {
         })
     }
 
-    fn replace_range_for_completion(
-        buffer_text: &String,
-        buffer_position: Anchor,
-        new_bytes: &[u8],
-        snapshot: &TextBufferSnapshot,
-    ) -> Range<Anchor> {
-        let buffer_offset = buffer_position.to_offset(snapshot);
-        let buffer_bytes = &buffer_text.as_bytes()[0..buffer_offset];
-
-        let mut prefix_len = 0;
-        for i in (0..new_bytes.len()).rev() {
-            if buffer_bytes.ends_with(&new_bytes[0..i]) {
-                prefix_len = i;
-                break;
-            }
-        }
-
-        let start = snapshot.clip_offset(buffer_offset - prefix_len, Bias::Left);
-
-        snapshot.anchor_before(start)..buffer_position
-    }
-
-    const fn completion_type_score(completion_type: CompletionItemType) -> usize {
-        match completion_type {
-            CompletionItemType::Field | CompletionItemType::Property => 0,
-            CompletionItemType::Variable | CompletionItemType::Value => 1,
-            CompletionItemType::Method
-            | CompletionItemType::Function
-            | CompletionItemType::Constructor => 2,
-            CompletionItemType::Class
-            | CompletionItemType::Interface
-            | CompletionItemType::Module => 3,
-            _ => 4,
-        }
-    }
-
-    fn completion_item_sort_text(completion_item: &CompletionItem) -> String {
-        completion_item.sort_text.clone().unwrap_or_else(|| {
-            format!(
-                "{:03}_{}",
-                Self::completion_type_score(
-                    completion_item.type_.unwrap_or(CompletionItemType::Text)
-                ),
-                completion_item.label.to_ascii_lowercase()
-            )
-        })
-    }
-
     fn client_completions(
         &self,
         console: &Entity<Console>,
@@ -754,42 +708,8 @@ impl ConsoleQueryBarCompletionProvider {
         cx.background_executor().spawn(async move {
             let completions = completion_task.await?;
 
-            let buffer_text = snapshot.text();
-
-            let completions = completions
-                .into_iter()
-                .map(|completion| {
-                    let sort_text = Self::completion_item_sort_text(&completion);
-                    let new_text = completion
-                        .text
-                        .as_ref()
-                        .unwrap_or(&completion.label)
-                        .to_owned();
-
-                    project::Completion {
-                        replace_range: Self::replace_range_for_completion(
-                            &buffer_text,
-                            buffer_position,
-                            new_text.as_bytes(),
-                            &snapshot,
-                        ),
-                        new_text,
-                        label: CodeLabel::plain(completion.label, None),
-                        icon_path: None,
-                        documentation: completion.detail.map(|detail| {
-                            CompletionDocumentation::MultiLineMarkdown(detail.into())
-                        }),
-                        match_start: None,
-                        snippet_deduplication_key: None,
-                        confirm: None,
-                        source: project::CompletionSource::Dap { sort_text },
-                        insert_text_mode: None,
-                    }
-                })
-                .collect();
-
             Ok(vec![project::CompletionResponse {
-                completions,
+                completions: completions_from_dap(completions, buffer_position, &snapshot),
                 display_options: CompletionDisplayOptions::default(),
                 is_incomplete: false,
             }])
@@ -970,7 +890,7 @@ mod tests {
 
         let snapshot = &cx.buffer_snapshot();
 
-        let replace_range = ConsoleQueryBarCompletionProvider::replace_range_for_completion(
+        let replace_range = completion_replace_range(
             &cx.buffer_text(),
             snapshot.anchor_before(buffer_position),
             replacement.as_bytes(),
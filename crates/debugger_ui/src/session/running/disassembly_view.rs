@@ -0,0 +1,518 @@
+use std::{ops::Range, path::Path};
+
+use anyhow::Result;
+
+use collections::HashMap;
+use gpui::{
+    AnyElement, AsyncApp, Entity, FocusHandle, Focusable, ScrollStrategy, Subscription, Task,
+    UniformList, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::{
+    Project, ProjectPath,
+    debugger::session::{Session, SessionEvent, ThreadStatus},
+};
+use ui::{Tooltip, WithScrollbar, prelude::*};
+use util::ResultExt;
+use workspace::Workspace;
+
+use crate::{
+    StepIntoInstruction, StepOverInstruction,
+    session::running::{
+        RunningState,
+        stack_frame_list::{StackFrameList, StackFrameListEvent},
+    },
+};
+
+/// How many instructions are disassembled on each side of the program counter.
+const INSTRUCTIONS_AROUND_PROGRAM_COUNTER: u64 = 64;
+
+enum DisassemblyEntry {
+    /// The source line that the instructions below it were generated from.
+    Source {
+        location: SharedString,
+        text: Option<SharedString>,
+    },
+    Instruction(dap::DisassembledInstruction),
+}
+
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    stack_frame_list: Entity<StackFrameList>,
+    running_state: WeakEntity<RunningState>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    entries: Vec<DisassemblyEntry>,
+    program_counter: Option<String>,
+    /// The address of the program counter, to find its instruction regardless of how the adapter
+    /// formats addresses.
+    program_counter_address: Option<u64>,
+    /// Whether the view has been rendered, i.e. whether it needs to follow the selected stack frame.
+    shown: bool,
+    _refresh_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        running_state: WeakEntity<RunningState>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(_) => {
+                    if this.shown {
+                        this.schedule_refresh(cx);
+                    }
+                }
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) | SessionEvent::HistoricSnapshotSelected => {
+                    this.program_counter = None;
+                    this.program_counter_address = None;
+                    if this.shown {
+                        this.schedule_refresh(cx);
+                    }
+                }
+                _ => {}
+            }),
+        ];
+
+        Self {
+            session,
+            stack_frame_list,
+            running_state,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            entries: Vec::new(),
+            program_counter: None,
+            program_counter_address: None,
+            shown: false,
+            _refresh_task: None,
+            _subscriptions,
+        }
+    }
+
+    fn schedule_refresh(&mut self, cx: &mut Context<Self>) {
+        let program_counter = self
+            .stack_frame_list
+            .update(cx, |stack_frame_list, cx| {
+                stack_frame_list.opened_stack_frame(cx)
+            })
+            .and_then(|stack_frame| stack_frame.dap.instruction_pointer_reference);
+        if program_counter.is_some() && program_counter == self.program_counter {
+            return;
+        }
+
+        self.program_counter = program_counter.clone();
+        self.program_counter_address = program_counter.as_deref().and_then(parse_address);
+        let Some(program_counter) = program_counter else {
+            self._refresh_task = None;
+            self.entries.clear();
+            cx.notify();
+            return;
+        };
+
+        let disassembly = self.session.update(cx, |session, cx| {
+            session.disassemble(
+                program_counter,
+                -(INSTRUCTIONS_AROUND_PROGRAM_COUNTER as i64),
+                INSTRUCTIONS_AROUND_PROGRAM_COUNTER * 2,
+                cx,
+            )
+        });
+        let project = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.project().clone())
+            .ok();
+        self._refresh_task = Some(cx.spawn(async move |this, cx| {
+            let instructions = disassembly.await.unwrap_or_default();
+
+            let mut source_files = HashMap::default();
+            if let Some(project) = project {
+                for instruction in &instructions {
+                    let Some(path) = instruction
+                        .location
+                        .as_ref()
+                        .and_then(|source| source.path.as_ref())
+                    else {
+                        continue;
+                    };
+                    if source_files.contains_key(path) {
+                        continue;
+                    }
+                    let text = load_source_text(&project, Path::new(path), cx)
+                        .await
+                        .log_err();
+                    source_files.insert(path.clone(), text);
+                }
+            }
+
+            this.update(cx, |this, cx| {
+                this.entries = disassembly_entries(instructions, &source_files);
+                this.scroll_to_program_counter();
+                cx.notify();
+            })
+            .log_err();
+        }));
+    }
+
+    #[cfg(test)]
+    pub(crate) fn entries(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                DisassemblyEntry::Source { location, text } => match text {
+                    Some(text) => format!("{location} {text}"),
+                    None => location.to_string(),
+                },
+                DisassemblyEntry::Instruction(instruction) => {
+                    format!("{} {}", instruction.address, instruction.instruction)
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn program_counter(&self) -> Option<&str> {
+        self.program_counter.as_deref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn program_counter_instruction(&self) -> Option<&str> {
+        self.entries.iter().find_map(|entry| match entry {
+            DisassemblyEntry::Instruction(instruction) if self.is_program_counter(instruction) => {
+                Some(instruction.address.as_str())
+            }
+            _ => None,
+        })
+    }
+
+    fn is_program_counter(&self, instruction: &dap::DisassembledInstruction) -> bool {
+        self.program_counter_address.is_some()
+            && parse_address(&instruction.address) == self.program_counter_address
+    }
+
+    fn scroll_to_program_counter(&self) {
+        if let Some(ix) = self.entries.iter().position(|entry| {
+            matches!(
+                entry,
+                DisassemblyEntry::Instruction(instruction) if self.is_program_counter(instruction)
+            )
+        }) {
+            self.scroll_handle
+                .scroll_to_item(ix, ScrollStrategy::Center);
+        }
+    }
+
+    pub(crate) fn toggle_instruction_breakpoint(
+        &mut self,
+        address: String,
+        cx: &mut Context<Self>,
+    ) {
+        self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(address, cx);
+        });
+        cx.notify();
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            DisassemblyEntry::Source { location, text } => h_flex()
+                .id(("disassembly-source", ix))
+                .w_full()
+                .gap_2()
+                .pl_6()
+                .pt_1()
+                .child(
+                    Label::new(location.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Accent),
+                )
+                .when_some(text.clone(), |this, text| {
+                    this.child(
+                        Label::new(text)
+                            .buffer_font(cx)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                })
+                .into_any(),
+            DisassemblyEntry::Instruction(instruction) => {
+                let session = self.session.read(cx);
+                let has_breakpoint = session.has_instruction_breakpoint(&instruction.address);
+                let supports_breakpoints = session
+                    .capabilities()
+                    .supports_instruction_breakpoints
+                    .unwrap_or_default();
+                let is_program_counter = self.is_program_counter(instruction);
+                let address = instruction.address.clone();
+
+                h_flex()
+                    .id(("disassembly-instruction", ix))
+                    .w_full()
+                    .gap_2()
+                    .when(is_program_counter, |this| {
+                        this.bg(cx.theme().colors().editor_debugger_active_line_background)
+                    })
+                    .child(
+                        div()
+                            .id(("disassembly-gutter", ix))
+                            .w_4()
+                            .flex_none()
+                            .when(supports_breakpoints, |this| {
+                                this.cursor_pointer()
+                                    .tooltip(Tooltip::text("Toggle Instruction Breakpoint"))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.toggle_instruction_breakpoint(address.clone(), cx);
+                                    }))
+                            })
+                            .map(|this| {
+                                if has_breakpoint {
+                                    this.child(
+                                        Icon::new(IconName::DebugBreakpoint)
+                                            .size(IconSize::XSmall)
+                                            .color(Color::Debugger),
+                                    )
+                                } else if is_program_counter {
+                                    this.child(
+                                        Icon::new(IconName::ArrowRight)
+                                            .size(IconSize::XSmall)
+                                            .color(Color::Debugger),
+                                    )
+                                } else {
+                                    this
+                                }
+                            }),
+                    )
+                    .child(
+                        Label::new(instruction.address.clone())
+                            .buffer_font(cx)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                        this.child(
+                            Label::new(bytes)
+                                .buffer_font(cx)
+                                .size(LabelSize::Small)
+                                .color(Color::Disabled),
+                        )
+                    })
+                    .child(
+                        Label::new(instruction.instruction.clone())
+                            .buffer_font(cx)
+                            .size(LabelSize::Small),
+                    )
+                    .when_some(instruction.symbol.clone(), |this, symbol| {
+                        this.child(
+                            Label::new(format!("<{symbol}>"))
+                                .buffer_font(cx)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .into_any()
+            }
+        }
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> Div {
+        let thread_status = self
+            .running_state
+            .read_with(cx, |running_state, cx| running_state.thread_status(cx))
+            .ok()
+            .flatten();
+        let supports_instruction_stepping = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_stepping_granularity
+            .unwrap_or_default();
+        let disabled = thread_status != Some(ThreadStatus::Stopped)
+            || self.session.read(cx).quirks().post_mortem;
+
+        h_flex()
+            .w_full()
+            .gap_1()
+            .pb_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .when(supports_instruction_stepping, |this| {
+                this.child(
+                    IconButton::new("step-over-instruction", IconName::DebugStepOver)
+                        .icon_size(IconSize::Small)
+                        .disabled(disabled)
+                        .tooltip(Tooltip::for_action_title(
+                            "Step Over Instruction",
+                            &StepOverInstruction,
+                        ))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.running_state
+                                .update(cx, |running_state, cx| {
+                                    running_state.step_over_instruction(cx)
+                                })
+                                .ok();
+                        })),
+                )
+                .child(
+                    IconButton::new("step-into-instruction", IconName::DebugStepInto)
+                        .icon_size(IconSize::Small)
+                        .disabled(disabled)
+                        .tooltip(Tooltip::for_action_title(
+                            "Step Into Instruction",
+                            &StepIntoInstruction,
+                        ))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.running_state
+                                .update(cx, |running_state, cx| {
+                                    running_state.step_in_instruction(cx)
+                                })
+                                .ok();
+                        })),
+                )
+            })
+            .child(
+                IconButton::new("go-to-program-counter", IconName::Crosshair)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.program_counter.is_none())
+                    .tooltip(Tooltip::text("Go to Current Instruction"))
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.scroll_to_program_counter();
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_list(&self, cx: &mut Context<Self>) -> UniformList {
+        uniform_list(
+            "disassembly-view",
+            self.entries.len(),
+            cx.processor(|this, range: Range<usize>, _window, cx| {
+                range.map(|ix| this.render_entry(ix, cx)).collect()
+            }),
+        )
+        .track_scroll(&self.scroll_handle)
+        .size_full()
+    }
+}
+
+async fn load_source_text(
+    project: &Entity<Project>,
+    abs_path: &Path,
+    cx: &mut AsyncApp,
+) -> Result<String> {
+    let (worktree, relative_path) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(abs_path, false, cx)
+        })
+        .await?;
+    let buffer = project
+        .update(cx, |project, cx| {
+            let worktree_id = worktree.read(cx).id();
+            project.open_buffer(
+                ProjectPath {
+                    worktree_id,
+                    path: relative_path,
+                },
+                cx,
+            )
+        })
+        .await?;
+    Ok(buffer.read_with(cx, |buffer, _| buffer.text()))
+}
+
+/// Interleaves the instructions with the source lines they were generated from.
+///
+/// Adapters only report the source location of an instruction when it differs from the
+/// previous one, so the last seen location applies until a new one is reported.
+/// Parses a memory address of the debug adapter protocol, which is hexadecimal when prefixed
+/// with `0x`, and decimal otherwise.
+pub(crate) fn parse_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
+fn disassembly_entries(
+    instructions: Vec<dap::DisassembledInstruction>,
+    source_files: &HashMap<String, Option<String>>,
+) -> Vec<DisassemblyEntry> {
+    let mut entries = Vec::with_capacity(instructions.len());
+    let mut current_path = None;
+    let mut last_location = None;
+    for instruction in instructions {
+        if let Some(path) = instruction
+            .location
+            .as_ref()
+            .and_then(|source| source.path.clone())
+        {
+            current_path = Some(path);
+        }
+        if let Some((path, line)) = current_path.clone().zip(instruction.line)
+            && last_location.as_ref() != Some(&(path.clone(), line))
+        {
+            let file_name = Path::new(&path)
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            let text = source_files
+                .get(&path)
+                .and_then(|text| text.as_deref())
+                .and_then(|text| text.lines().nth(line.saturating_sub(1) as usize))
+                .map(|line| SharedString::from(line.trim().to_string()));
+            entries.push(DisassemblyEntry::Source {
+                location: format!("{file_name}:{line}").into(),
+                text,
+            });
+            last_location = Some((path, line));
+        }
+        entries.push(DisassemblyEntry::Instruction(instruction));
+    }
+    entries
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !self.shown {
+            self.shown = true;
+            self.schedule_refresh(cx);
+        }
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .child(self.render_toolbar(cx))
+            .when(self.entries.is_empty(), |this| {
+                this.child(
+                    div().p_2().child(
+                        Label::new("No disassembly available for the selected stack frame")
+                            .color(Color::Muted),
+                    ),
+                )
+            })
+            .child(
+                div()
+                    .size_full()
+                    .child(self.render_list(cx))
+                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+            )
+    }
+}
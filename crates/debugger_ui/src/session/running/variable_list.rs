@@ -662,7 +662,8 @@ impl VariableList {
                     session
                         .capabilities()
                         .supports_set_variable
-                        .unwrap_or_default(),
+                        .unwrap_or_default()
+                        && !session.quirks().post_mortem,
                     session
                         .capabilities()
                         .supports_data_breakpoints
@@ -896,6 +897,9 @@ impl VariableList {
     }
 
     fn edit_variable(&mut self, _: &EditVariable, window: &mut Window, cx: &mut Context<Self>) {
+        if self.session.read(cx).quirks().post_mortem {
+            return;
+        }
         let Some(selection) = self.selection.as_ref() else {
             return;
         };
@@ -965,8 +969,8 @@ impl VariableList {
             return;
         };
 
-        self.session.update(cx, |session, _| {
-            session.remove_watcher(watcher.expression.clone());
+        self.session.update(cx, |session, cx| {
+            session.remove_watcher(watcher.expression.clone(), cx);
         });
         self.build_entries(cx);
     }
@@ -1128,7 +1132,8 @@ impl VariableList {
                                         .read(cx)
                                         .capabilities()
                                         .supports_set_variable
-                                        .unwrap_or_default(),
+                                        .unwrap_or_default()
+                                    && !self.session.read(cx).quirks().post_mortem,
                                 |this| {
                                     let path = entry.path.clone();
                                     let variable_value = value.clone();
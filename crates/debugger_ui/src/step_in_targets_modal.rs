@@ -0,0 +1,217 @@
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{AppContext, DismissEvent, Entity, EventEmitter, Focusable, Render, Subscription};
+use gpui::{Task, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use std::sync::Arc;
+use ui::{Context, ListItem, ListItemSpacing, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::session::running::RunningState;
+
+pub(crate) struct StepInTargetsModalDelegate {
+    selected_index: usize,
+    matches: Vec<StringMatch>,
+    targets: Arc<[dap::StepInTarget]>,
+    running_state: WeakEntity<RunningState>,
+}
+
+pub(crate) struct StepInTargetsModal {
+    _subscription: Subscription,
+    picker: Entity<Picker<StepInTargetsModalDelegate>>,
+}
+
+impl StepInTargetsModal {
+    /// Asks the debug adapter which calls the selected stack frame can step into,
+    /// and lets the user pick one when there is more than one.
+    pub(crate) fn toggle(
+        workspace: WeakEntity<Workspace>,
+        running_state: WeakEntity<RunningState>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(targets) = running_state
+            .update(cx, |running_state, cx| {
+                let stack_frame_id = running_state.selected_stack_frame_id(cx)?;
+                Some(running_state.session().update(cx, |session, cx| {
+                    session.step_in_targets(stack_frame_id, cx)
+                }))
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        window
+            .spawn(cx, async move |cx| {
+                let targets = targets.await.unwrap_or_default();
+                match targets.as_slice() {
+                    [] => running_state.update(cx, |running_state, cx| running_state.step_in(cx)),
+                    [target] => running_state.update(cx, |running_state, cx| {
+                        running_state.step_in_target(target.id, cx)
+                    }),
+                    _ => workspace.update_in(cx, |workspace, window, cx| {
+                        workspace.toggle_modal(window, cx, |window, cx| {
+                            Self::new(targets.into(), running_state, window, cx)
+                        })
+                    }),
+                }
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn new(
+        targets: Arc<[dap::StepInTarget]>,
+        running_state: WeakEntity<RunningState>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = StepInTargetsModalDelegate {
+            selected_index: 0,
+            matches: Vec::new(),
+            targets,
+            running_state,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self {
+            _subscription: cx.subscribe(&picker, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }),
+            picker,
+        }
+    }
+}
+
+impl Render for StepInTargetsModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("StepInTargetsModal")
+            .track_focus(&self.focus_handle(cx))
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for StepInTargetsModal {}
+
+impl Focusable for StepInTargetsModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for StepInTargetsModal {}
+
+impl PickerDelegate for StepInTargetsModalDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select the call to step into".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .targets
+            .iter()
+            .enumerate()
+            .map(|(id, target)| StringMatchCandidate::new(id, &target.label))
+            .collect::<Vec<_>>();
+        cx.spawn(async move |this, cx| {
+            let matches = fuzzy::match_strings(
+                &candidates,
+                &query,
+                true,
+                true,
+                100,
+                &Default::default(),
+                cx.background_executor().clone(),
+            )
+            .await;
+
+            this.update(cx, |this, _| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _secondary: bool, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let target = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|current_match| self.targets.get(current_match.candidate_id));
+        if let Some(target) = target {
+            self.running_state
+                .update(cx, |running_state, cx| {
+                    running_state.step_in_target(target.id, cx)
+                })
+                .ok();
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let target = self.targets.get(hit.candidate_id)?;
+        let location = target.line.map(|line| match target.column {
+            Some(column) => format!("{line}:{column}"),
+            None => line.to_string(),
+        });
+
+        Some(
+            ListItem::new(("step-in-target", ix))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .justify_between()
+                        .child(Label::new(target.label.clone()))
+                        .when_some(location, |this, location| {
+                            this.child(
+                                Label::new(location)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        }),
+                ),
+        )
+    }
+}
@@ -9,18 +9,21 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, Disconnect, Goto, GotoTargets, Launch, Next, RunInTerminal, SetBreakpoints,
+        StackTrace, StartDebugging, StepBack, StepIn, StepInTargets, StepOut, Threads,
     },
 };
 use editor::{
     ActiveDebugLine, Editor, EditorMode, MultiBuffer,
     actions::{self},
 };
-use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use gpui::{BackgroundExecutor, SharedString, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::BreakpointSessionState,
+        session::{ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
@@ -1246,6 +1249,160 @@ async fn test_send_breakpoints_when_editor_has_been_saved(
     );
 }
 
+#[gpui::test]
+async fn test_breakpoint_rejection_message_is_shown(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = project.update(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("main.rs")), cx)
+        })
+        .await
+        .unwrap();
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer.clone(), cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![dap::StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.rs".into()),
+                    path: Some(path!("/project/main.rs").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+    client.on_request::<SetBreakpoints, _>(move |_, args| {
+        Ok(dap::SetBreakpointsResponse {
+            breakpoints: args
+                .breakpoints
+                .unwrap_or_default()
+                .into_iter()
+                .map(|breakpoint| {
+                    serde_json::from_value(json!({
+                        "id": breakpoint.line,
+                        "verified": false,
+                        "message": "Unable to parse condition",
+                        "line": breakpoint.line,
+                    }))
+                    .unwrap()
+                })
+                .collect(),
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .read_with(cx, |item, _| item.running_state().clone());
+    running_state.update_in(cx, |running_state, window, cx| {
+        running_state.select_current_thread(
+            &running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx)),
+            window,
+            cx,
+        );
+    });
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_down(&zed_actions::editor::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+    cx.run_until_parked();
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    let states = breakpoint_store.read_with(cx, |breakpoint_store, cx| {
+        let snapshot = buffer.read(cx).snapshot();
+        breakpoint_store
+            .breakpoints(&buffer, None, &snapshot, cx)
+            .map(|(_, state)| state)
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        states,
+        vec![Some(BreakpointSessionState {
+            id: Some(2),
+            verified: false,
+            message: Some("Unable to parse condition".into()),
+        })]
+    );
+
+    editor.update(cx, |editor, cx| {
+        let cursor = editor.selections.newest_anchor().head();
+        assert_eq!(
+            editor.breakpoint_rejection(cursor, cx),
+            Some("Unable to parse condition".into())
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
     executor: BackgroundExecutor,
@@ -2481,3 +2638,183 @@ async fn test_adapter_shutdown_with_child_sessions_on_app_quit(
         "Child session should have received disconnect request"
     );
 }
+
+#[gpui::test]
+async fn test_step_in_target(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_step_in_targets_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StepInTargets, _>(move |_, args| {
+        assert_eq!(args.frame_id, 7);
+        Ok(dap::StepInTargetsResponse {
+            targets: serde_json::from_value(json!([
+                { "id": 1, "label": "first()" },
+                { "id": 2, "label": "second()" },
+            ]))
+            .unwrap(),
+        })
+    });
+    let stepped_into = Arc::new(parking_lot::Mutex::new(None));
+    client.on_request::<StepIn, _>({
+        let stepped_into = stepped_into.clone();
+        move |_, args| {
+            *stepped_into.lock() = Some((args.thread_id, args.target_id));
+            Ok(())
+        }
+    });
+
+    let targets = session
+        .update(cx, |session, cx| session.step_in_targets(7, cx))
+        .await
+        .expect("step in targets should be returned");
+    assert_eq!(
+        targets
+            .iter()
+            .map(|target| (target.id, target.label.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(1, "first()"), (2, "second()")]
+    );
+
+    session.update(cx, |session, cx| {
+        session.step_in_target(
+            ThreadId(1),
+            targets[1].id,
+            dap::SteppingGranularity::Statement,
+            cx,
+        );
+    });
+    cx.run_until_parked();
+
+    assert_eq!(*stepped_into.lock(), Some((1, Some(2))));
+}
+
+#[gpui::test]
+async fn test_jump_to_position(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({ "main.rs": "fn main() {\n    let a = 1; let b = 2;\n}" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_goto_targets_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<GotoTargets, _>(move |_, args| {
+        assert_eq!(args.source.path.as_deref(), Some(path!("/project/main.rs")));
+        assert_eq!(args.line, 2);
+        Ok(dap::GotoTargetsResponse {
+            targets: serde_json::from_value(json!([
+                { "id": 1, "label": "let a", "line": 2, "column": 5, "endColumn": 14 },
+                { "id": 2, "label": "let b", "line": 2, "column": 16, "endColumn": 25 },
+            ]))
+            .unwrap(),
+        })
+    });
+    let jumped_to = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Goto, _>({
+        let jumped_to = jumped_to.clone();
+        move |_, args| {
+            jumped_to.lock().push((args.thread_id, args.target_id));
+            Ok(())
+        }
+    });
+
+    let abs_path = Path::new(path!("/project/main.rs"));
+    // The cursor is on `let b`, so its target is picked over the first one on the line.
+    session
+        .update(cx, |session, cx| {
+            session.jump_to_position(abs_path, 1, 19, ThreadId(1), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    // Outside of any target's range, the first target on the line is used.
+    session
+        .update(cx, |session, cx| {
+            session.jump_to_position(abs_path, 1, 0, ThreadId(1), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    assert_eq!(*jumped_to.lock(), vec![(1, 2), (1, 1)]);
+}
+
+#[gpui::test]
+async fn test_removed_imported_watch_is_not_added_to_new_sessions(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let debug_panel = workspace
+        .update(cx, |workspace, _window, cx| {
+            workspace.panel::<DebugPanel>(cx).unwrap()
+        })
+        .unwrap();
+
+    debug_panel.update(cx, |debug_panel, cx| {
+        debug_panel.import_watches(vec!["first".into(), "second".into()], cx);
+    });
+    cx.run_until_parked();
+
+    session.update(cx, |session, cx| {
+        session.remove_watcher("first".into(), cx);
+    });
+    cx.run_until_parked();
+
+    debug_panel.update(cx, |debug_panel, cx| {
+        assert_eq!(
+            debug_panel.watch_expressions(cx),
+            vec![SharedString::from("second")]
+        );
+    });
+
+    let new_session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    new_session.update(cx, |session, _| {
+        assert!(!session.watchers().contains_key("first"));
+        assert!(session.watchers().contains_key("second"));
+    });
+}
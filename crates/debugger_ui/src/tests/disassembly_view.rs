@@ -0,0 +1,301 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    session::running::disassembly_view::parse_address,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StackFrame,
+    requests::{Disassemble, Initialize, SetInstructionBreakpoints, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use parking_lot::Mutex;
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::sync::Arc;
+use unindent::Unindent as _;
+use util::path;
+
+fn capabilities() -> dap::Capabilities {
+    dap::Capabilities {
+        supports_disassemble_request: Some(true),
+        supports_instruction_breakpoints: Some(true),
+        ..Default::default()
+    }
+}
+
+fn stack_frame() -> StackFrame {
+    StackFrame {
+        id: 1,
+        name: "main".into(),
+        source: Some(dap::Source {
+            name: Some("main.c".into()),
+            path: Some(path!("/project/src/main.c").into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line: 3,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        // Adapters may format the program counter differently than the disassembled addresses.
+        instruction_pointer_reference: Some("0x0000000000001004".into()),
+        module_id: None,
+        presentation_hint: None,
+    }
+}
+
+fn instructions() -> Vec<dap::DisassembledInstruction> {
+    serde_json::from_value(json!([
+        {
+            "address": "0x1000",
+            "instruction": "push rbp",
+            "location": { "path": path!("/project/src/main.c") },
+            "line": 2,
+        },
+        {
+            "address": "0x1004",
+            "instruction": "mov eax, 1",
+            "line": 3,
+        },
+        {
+            "address": "0x1008",
+            "instruction": "add eax, 2",
+            "line": 3,
+        },
+        {
+            "address": "0x100C",
+            "instruction": "ret",
+            "line": 4,
+        },
+    ]))
+    .unwrap()
+}
+
+#[gpui::test]
+async fn test_disassembly_view(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "src": {
+                "main.c": r#"
+                    int main() {
+                        int x = 1;
+                        x += 2;
+                        return x;
+                    }
+                "#.unindent(),
+            }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| Ok(capabilities()));
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![stack_frame()],
+            total_frames: None,
+        })
+    });
+    client.on_request::<Disassemble, _>(move |_, args| {
+        assert_eq!(args.memory_reference, "0x0000000000001004");
+        assert_eq!(args.instruction_offset, Some(-64));
+        assert_eq!(args.instruction_count, 128);
+        Ok(dap::DisassembleResponse {
+            instructions: instructions(),
+        })
+    });
+    let sent_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<SetInstructionBreakpoints, _>({
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |_, args| {
+            sent_breakpoints.lock().push(
+                args.breakpoints
+                    .iter()
+                    .map(|breakpoint| breakpoint.instruction_reference.clone())
+                    .collect::<Vec<_>>(),
+            );
+            Ok(dap::SetInstructionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+    running_state.update_in(cx, |running_state, window, cx| {
+        running_state.select_current_thread(
+            &running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx)),
+            window,
+            cx,
+        );
+        running_state.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    let disassembly_view = running_state.update(cx, |running_state, _| {
+        running_state.disassembly_view().clone()
+    });
+    disassembly_view.update(cx, |view, _| {
+        assert_eq!(view.program_counter(), Some("0x0000000000001004"));
+        assert_eq!(view.program_counter_instruction(), Some("0x1004"));
+        assert_eq!(
+            view.entries(),
+            vec![
+                "main.c:2 int x = 1;",
+                "0x1000 push rbp",
+                "main.c:3 x += 2;",
+                "0x1004 mov eax, 1",
+                "0x1008 add eax, 2",
+                "main.c:4 return x;",
+                "0x100C ret",
+            ]
+        );
+    });
+
+    disassembly_view.update(cx, |view, cx| {
+        view.toggle_instruction_breakpoint("0x1008".into(), cx);
+    });
+    cx.run_until_parked();
+    disassembly_view.update(cx, |view, cx| {
+        view.toggle_instruction_breakpoint("0x100c".into(), cx);
+    });
+    cx.run_until_parked();
+    disassembly_view.update(cx, |view, cx| {
+        view.toggle_instruction_breakpoint("0x1008".into(), cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        *sent_breakpoints.lock(),
+        vec![
+            vec!["0x1008".to_string()],
+            vec!["0x1008".to_string(), "0x100c".to_string()],
+            vec!["0x100c".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_parse_address() {
+    assert_eq!(parse_address("0x1004"), Some(0x1004));
+    assert_eq!(parse_address("0X00000000000010AB"), Some(0x10ab));
+    assert_eq!(parse_address("0x10ab"), Some(0x10ab));
+    assert_eq!(parse_address("4100"), Some(4100));
+    assert_eq!(parse_address("0xnope"), None);
+}
+
+#[gpui::test]
+async fn test_instruction_breakpoints_are_sent_again_after_restart(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.c": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| Ok(capabilities()));
+        client.on_request::<SetInstructionBreakpoints, _>(move |_, _| {
+            Ok(dap::SetInstructionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        });
+    })
+    .unwrap();
+
+    session.update(cx, |session, cx| {
+        session.toggle_instruction_breakpoint("0x1004".into(), cx);
+    });
+    cx.run_until_parked();
+
+    let sent_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = project::debugger::test::intercept_debug_sessions(cx, {
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |client| {
+            client.on_request::<Initialize, _>(move |_, _| Ok(capabilities()));
+            client.on_request::<SetInstructionBreakpoints, _>({
+                let sent_breakpoints = sent_breakpoints.clone();
+                move |_, args| {
+                    sent_breakpoints.lock().extend(
+                        args.breakpoints
+                            .into_iter()
+                            .map(|breakpoint| breakpoint.instruction_reference),
+                    );
+                    Ok(dap::SetInstructionBreakpointsResponse {
+                        breakpoints: Vec::new(),
+                    })
+                }
+            });
+        }
+    });
+
+    session.update(cx, |session, cx| session.restart(None, cx));
+    cx.run_until_parked();
+
+    let restarted_session = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).session().clone()
+    });
+    assert_ne!(restarted_session.entity_id(), session.entity_id());
+    restarted_session.update(cx, |session, _| {
+        assert!(session.has_instruction_breakpoint("0x1004"));
+    });
+    assert_eq!(*sent_breakpoints.lock(), vec!["0x1004".to_string()]);
+}
@@ -375,6 +375,8 @@ actions!(
     [
         /// Runs program execution to the current cursor position.
         RunToCursor,
+        /// Moves program execution to the current cursor position without running the code in between.
+        JumpToCursor,
         /// Evaluates the selected text in the debugger context.
         EvaluateSelectedText
     ]
@@ -554,6 +556,10 @@ actions!(
         GoToDefinition,
         /// Goes to definition in a split pane.
         GoToDefinitionSplit,
+        /// Goes to the next bookmark, continuing in the files after this one.
+        GoToNextBookmark,
+        /// Goes to the previous bookmark, continuing in the files before this one.
+        GoToPreviousBookmark,
         /// Goes to the next diff hunk.
         GoToHunk,
         /// Goes to the previous diff hunk.
@@ -803,6 +809,8 @@ actions!(
         Tab,
         /// Removes a tab character or outdents.
         Backtab,
+        /// Toggles a bookmark at the current line.
+        ToggleBookmark,
         /// Toggles a breakpoint at the current line.
         ToggleBreakpoint,
         /// Toggles the case of selected text.
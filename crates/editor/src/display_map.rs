@@ -150,6 +150,7 @@ pub enum HighlightKey {
     SemanticToken,
     // below is sorted lexicographically, as there is no relevant ordering for these aside from coming after the above
     BufferSearchHighlights,
+    CallHierarchy,
     ConsoleAnsiHighlight(usize),
     DebugStackFrameLine,
     DocumentHighlightRead,
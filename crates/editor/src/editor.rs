@@ -39,6 +39,7 @@ mod rust_analyzer_ext;
 pub mod scroll;
 mod selections_collection;
 pub mod semantic_tokens;
+mod snippet_variables;
 mod split;
 pub mod split_editor_view;
 pub mod tasks;
@@ -97,7 +98,7 @@ use code_context_menus::{
 };
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
-use dap::TelemetrySpawnLocation;
+use dap::{StackFrameId, TelemetrySpawnLocation};
 use display_map::*;
 use document_colors::LspColorData;
 use edit_prediction_types::{
@@ -156,15 +157,16 @@ use parking_lot::Mutex;
 use persistence::DB;
 use project::{
     BreakpointWithPosition, CodeAction, Completion, CompletionDisplayOptions, CompletionIntent,
-    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, InlayHint, InlayId,
-    InvalidationStrategy, Location, LocationLink, LspAction, PrepareRenameResponse, Project,
-    ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    CompletionResponse, CompletionSource, DisableAiSettings, DocumentHighlight, DocumentLink,
+    InlayHint, InlayId, InvalidationStrategy, Location, LocationLink, LspAction,
+    PrepareRenameResponse, Project, ProjectItem, ProjectPath, ProjectTransaction, TaskSourceKind,
+    bookmark_store::{Bookmark, BookmarkStore},
     debugger::{
         breakpoint_store::{
             Breakpoint, BreakpointEditAction, BreakpointSessionState, BreakpointState,
             BreakpointStore, BreakpointStoreEvent,
         },
-        session::{Session, SessionEvent},
+        session::{CompletionsQuery, Session, SessionEvent, completions_from_dap},
     },
     git_store::GitStoreEvent,
     lsp_store::{
@@ -185,6 +187,7 @@ use settings::{
 };
 use smallvec::{SmallVec, smallvec};
 use snippet::Snippet;
+use snippet_variables::SnippetVariables;
 use std::{
     any::{Any, TypeId},
     borrow::Cow,
@@ -1296,6 +1299,7 @@ pub struct Editor {
     tasks: BTreeMap<(BufferId, BufferRow), RunnableTasks>,
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
+    bookmark_store: Option<Entity<BookmarkStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
     pub(crate) gutter_diff_review_indicator: (Option<PhantomDiffReviewIndicator>, Option<Task<()>>),
     pub(crate) diff_review_drag_state: Option<DiffReviewDragState>,
@@ -2356,6 +2360,10 @@ impl Editor {
             (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).breakpoint_store()),
             _ => None,
         };
+        let bookmark_store = match (&mode, project.as_ref()) {
+            (EditorMode::Full { .. }, Some(project)) => Some(project.read(cx).bookmark_store()),
+            _ => None,
+        };
 
         let mut code_action_providers = Vec::new();
         let mut load_uncommitted_diff = None;
@@ -2529,6 +2537,7 @@ impl Editor {
             tasks: BTreeMap::default(),
 
             breakpoint_store,
+            bookmark_store,
             gutter_breakpoint_indicator: (None, None),
             gutter_diff_review_indicator: (None, None),
             diff_review_drag_state: None,
@@ -2629,6 +2638,13 @@ impl Editor {
                     cx.notify();
                 }));
         }
+        if let Some(bookmarks) = editor.bookmark_store.as_ref() {
+            editor
+                ._subscriptions
+                .push(cx.observe(bookmarks, |_, _, cx| {
+                    cx.notify();
+                }));
+        }
         editor.tasks_update_task = Some(editor.refresh_runnables(window, cx));
         editor._subscriptions.extend(project_subscriptions);
 
@@ -6464,6 +6480,7 @@ impl Editor {
             intent,
             &buffer_handle,
             &completions_menu.initial_position.text_anchor,
+            &SnippetVariables::new(self, cx),
             cx,
         );
 
@@ -8895,6 +8912,7 @@ impl Editor {
         };
 
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
+        let jump_to_cursor = window.is_action_available(&JumpToCursor, cx);
 
         let toggle_state_msg = breakpoint.as_ref().map_or(None, |bp| match bp.1.state {
             BreakpointState::Enabled => Some("Disable"),
@@ -8923,8 +8941,25 @@ impl Editor {
 
                         window.dispatch_action(Box::new(RunToCursor), cx);
                     })
-                    .separator()
                 })
+                .when(jump_to_cursor, |this| {
+                    let weak_editor = weak_editor.clone();
+                    this.entry("Jump to Cursor", None, move |window, cx| {
+                        weak_editor
+                            .update(cx, |editor, cx| {
+                                editor.change_selections(
+                                    SelectionEffects::no_scroll(),
+                                    window,
+                                    cx,
+                                    |s| s.select_ranges([Point::new(row, 0)..Point::new(row, 0)]),
+                                );
+                            })
+                            .ok();
+
+                        window.dispatch_action(Box::new(JumpToCursor), cx);
+                    })
+                })
+                .when(run_to_cursor || jump_to_cursor, |this| this.separator())
                 .when_some(toggle_state_msg, |this, msg| {
                     this.entry(msg, None, {
                         let weak_editor = weak_editor.clone();
@@ -9017,7 +9052,8 @@ impl Editor {
         state: Option<BreakpointSessionState>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        let is_rejected = state.is_some_and(|s| !s.verified);
+        let rejection = state.filter(|s| !s.verified);
+        let is_rejected = rejection.is_some();
         // Is it a breakpoint that shows up when hovering over gutter?
         let (is_phantom, collides_with_existing) = self.gutter_breakpoint_indicator.0.map_or(
             (false, false),
@@ -9077,8 +9113,10 @@ impl Editor {
         };
         let focus_handle = self.focus_handle.clone();
 
-        let meta = if is_rejected {
-            SharedString::from("No executable code is associated with this line.")
+        let meta = if let Some(rejection) = rejection {
+            rejection.message.unwrap_or_else(|| {
+                SharedString::from("No executable code is associated with this line.")
+            })
         } else if collides_with_existing && !breakpoint.is_disabled() {
             SharedString::from(format!(
                 "{alt_as_text}-click to disable,\nright-click for more options."
@@ -9132,6 +9170,103 @@ impl Editor {
             })
     }
 
+    /// Get all display points of bookmarks that will be rendered within editor.
+    fn active_bookmarks(
+        &self,
+        range: Range<DisplayRow>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> HashMap<DisplayRow, (Anchor, Bookmark)> {
+        let mut bookmark_display_points = HashMap::default();
+
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return bookmark_display_points;
+        };
+        if bookmark_store.read(cx).is_empty() {
+            return bookmark_display_points;
+        }
+
+        let snapshot = self.snapshot(window, cx);
+        let multi_buffer_snapshot = snapshot.buffer_snapshot();
+        let Some(project) = self.project() else {
+            return bookmark_display_points;
+        };
+
+        let range = snapshot.display_point_to_point(DisplayPoint::new(range.start, 0), Bias::Left)
+            ..snapshot.display_point_to_point(DisplayPoint::new(range.end, 0), Bias::Right);
+
+        for (buffer_snapshot, range, excerpt_id) in
+            multi_buffer_snapshot.range_to_buffer_ranges(range.start..=range.end)
+        {
+            let Some(buffer) = project
+                .read(cx)
+                .buffer_for_id(buffer_snapshot.remote_id(), cx)
+            else {
+                continue;
+            };
+            let bookmarks = bookmark_store.read(cx).bookmarks_in_buffer(
+                &buffer,
+                Some(
+                    buffer_snapshot.anchor_before(range.start)
+                        ..buffer_snapshot.anchor_after(range.end),
+                ),
+                buffer_snapshot,
+            );
+            for bookmark in bookmarks {
+                let multi_buffer_anchor = Anchor::in_buffer(excerpt_id, bookmark.position);
+                let position = multi_buffer_anchor
+                    .to_point(&multi_buffer_snapshot)
+                    .to_display_point(&snapshot);
+
+                bookmark_display_points
+                    .insert(position.row(), (multi_buffer_anchor, bookmark.clone()));
+            }
+        }
+
+        bookmark_display_points
+    }
+
+    fn render_bookmark(
+        &self,
+        position: Anchor,
+        row: DisplayRow,
+        bookmark: &Bookmark,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let focus_handle = self.focus_handle.clone();
+        let label = bookmark.label.clone();
+
+        IconButton::new(("bookmark_indicator", row.0 as usize), IconName::Bookmark)
+            .icon_size(IconSize::XSmall)
+            .size(ui::ButtonSize::None)
+            .icon_color(Color::Accent)
+            .style(ButtonStyle::Transparent)
+            .on_click(cx.listener(move |editor, _: &ClickEvent, window, cx| {
+                window.focus(&editor.focus_handle(cx), cx);
+                let Some(bookmark_store) = editor.bookmark_store.clone() else {
+                    return;
+                };
+                let Some(buffer) = editor.buffer.read(cx).buffer_for_anchor(position, cx) else {
+                    return;
+                };
+                bookmark_store.update(cx, |bookmark_store, cx| {
+                    bookmark_store.remove_bookmark(&buffer, position.text_anchor, cx);
+                });
+            }))
+            .tooltip(move |_window, cx| match label.clone() {
+                Some(label) => Tooltip::with_meta_in(
+                    "Remove bookmark",
+                    Some(&ToggleBookmark),
+                    label,
+                    &focus_handle,
+                    cx,
+                ),
+                None => {
+                    Tooltip::for_action_in("Remove bookmark", &ToggleBookmark, &focus_handle, cx)
+                }
+            })
+    }
+
     fn build_tasks_context(
         project: &Entity<Project>,
         buffer: &Entity<Buffer>,
@@ -11775,20 +11910,33 @@ impl Editor {
         cx: &mut Context<Self>,
     ) {
         let weak_editor = cx.weak_entity();
+        let language = self
+            .buffer
+            .read(cx)
+            .snapshot(cx)
+            .language_at(anchor)
+            .cloned();
+        let project = self.project.as_ref().map(|project| project.downgrade());
+        let rejection = self.breakpoint_rejection(anchor, cx);
         let bp_prompt = cx.new(|cx| {
             BreakpointPromptEditor::new(
                 weak_editor,
                 anchor,
                 breakpoint.clone(),
                 edit_action,
+                language,
+                project,
+                rejection,
                 window,
                 cx,
             )
         });
 
         let height = bp_prompt.update(cx, |this, cx| {
+            let rejection_height = u32::from(this.rejection.is_some());
             this.prompt
                 .update(cx, |prompt, cx| prompt.max_point(cx).row().0 + 1 + 2)
+                + rejection_height
         });
         let cloned_prompt = bp_prompt.clone();
         let blocks = vec![BlockProperties {
@@ -11811,6 +11959,22 @@ impl Editor {
         });
     }
 
+    /// Returns the reason the active debug session gave for not verifying the breakpoint on
+    /// `anchor`'s line, such as a condition the adapter failed to parse.
+    pub fn breakpoint_rejection(&self, anchor: Anchor, cx: &App) -> Option<SharedString> {
+        let buffer = self.buffer.read(cx).buffer_for_anchor(anchor, cx)?;
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        let row = anchor.text_anchor.to_point(&buffer_snapshot).row;
+        let line = buffer_snapshot.anchor_before(Point::new(row, 0))
+            ..buffer_snapshot.anchor_after(Point::new(row, buffer_snapshot.line_len(row)));
+
+        self.breakpoint_store
+            .as_ref()?
+            .read(cx)
+            .breakpoints(&buffer, Some(line), &buffer_snapshot, cx)
+            .find_map(|(_, state)| state.filter(|state| !state.verified)?.message)
+    }
+
     pub(crate) fn breakpoint_at_row(
         &self,
         row: u32,
@@ -12078,6 +12242,119 @@ impl Editor {
         self.breakpoint_store.clone()
     }
 
+    pub fn toggle_bookmark(
+        &mut self,
+        _: &crate::actions::ToggleBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+
+        let snapshot = self.snapshot(window, cx);
+        let mut toggled_rows = HashSet::default();
+        for selection in self.selections.disjoint_anchors_arc().iter() {
+            let row = selection.head().to_point(snapshot.buffer_snapshot()).row;
+            let Some((buffer, position)) = self
+                .buffer
+                .read(cx)
+                .text_anchor_for_position(Point::new(row, 0), cx)
+            else {
+                continue;
+            };
+            // Multiple cursors on the same line should toggle its bookmark only once.
+            if toggled_rows.insert(MultiBufferRow(row)) {
+                bookmark_store.update(cx, |bookmark_store, cx| {
+                    bookmark_store.toggle_bookmark(buffer, position, cx);
+                });
+            }
+        }
+    }
+
+    pub fn go_to_next_bookmark(
+        &mut self,
+        _: &crate::actions::GoToNextBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_adjacent_bookmark(Direction::Next, window, cx);
+    }
+
+    pub fn go_to_previous_bookmark(
+        &mut self,
+        _: &crate::actions::GoToPreviousBookmark,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.go_to_adjacent_bookmark(Direction::Prev, window, cx);
+    }
+
+    fn go_to_adjacent_bookmark(
+        &mut self,
+        direction: Direction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark_store) = self.bookmark_store.as_ref() else {
+            return;
+        };
+        let head = self.selections.newest_anchor().head();
+        let Some((buffer, position)) = self.buffer.read(cx).text_anchor_for_position(head, cx)
+        else {
+            return;
+        };
+        let bookmark = match direction {
+            Direction::Next => bookmark_store.read(cx).next_bookmark(&buffer, position, cx),
+            Direction::Prev => bookmark_store
+                .read(cx)
+                .previous_bookmark(&buffer, position, cx),
+        };
+        let Some((target_buffer, bookmark)) = bookmark else {
+            return;
+        };
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::MovementAction, cx);
+        if let Some(anchor) =
+            self.buffer
+                .read(cx)
+                .buffer_anchor_to_anchor(&target_buffer, bookmark.position, cx)
+        {
+            self.change_selections(
+                SelectionEffects::scroll(Autoscroll::center()).nav_history(true),
+                window,
+                cx,
+                |s| s.select_anchor_ranges([anchor..anchor]),
+            );
+            return;
+        }
+
+        let Some(workspace) = self.workspace() else {
+            return;
+        };
+        let point = target_buffer
+            .read(cx)
+            .summary_for_anchor::<Point>(&bookmark.position);
+        window.defer(cx, move |window, cx| {
+            let target_editor = workspace.update(cx, |workspace, cx| {
+                let pane = workspace.active_pane().clone();
+                workspace.open_project_item::<Self>(
+                    pane,
+                    target_buffer,
+                    true,
+                    true,
+                    true,
+                    true,
+                    window,
+                    cx,
+                )
+            });
+            target_editor.update(cx, |target_editor, cx| {
+                target_editor.go_to_singleton_buffer_point(point, window, cx);
+            });
+        });
+    }
+
     pub fn prepare_restore_change(
         &self,
         revert_changes: &mut HashMap<BufferId, Vec<(Range<text::Anchor>, Rope)>>,
@@ -15677,9 +15954,10 @@ impl Editor {
             .map(|selection| selection.range())
             .collect_vec();
 
+        let variables = SnippetVariables::new(self, cx);
         let snippet = if let Some(snippet_body) = &action.snippet {
             if action.language.is_none() && action.name.is_none() {
-                Snippet::parse(snippet_body)?
+                Snippet::parse_with_variables(snippet_body, &variables)?
             } else {
                 bail!("`snippet` is mutually exclusive with `language` and `name`")
             }
@@ -15691,7 +15969,7 @@ impl Editor {
                 .into_iter()
                 .find(|snippet| snippet.name == *name)
                 .context("snippet not found")?;
-            Snippet::parse(&snippet.body)?
+            Snippet::parse_with_variables(&snippet.body, &variables)?
         } else {
             // todo(andrew): open modal to select snippet
             bail!("`name` or `snippet` is required")
@@ -18172,6 +18450,18 @@ impl Editor {
                     first_url_or_file = Some(Either::Left(url));
                     None
                 }
+                HoverLink::DocumentLink(target, server_id) => {
+                    let url = target.to_string();
+                    if url.starts_with("file:") {
+                        let lsp_location = hover_links::document_link_location(target);
+                        let computation =
+                            self.compute_target_location(lsp_location, server_id, window, cx);
+                        Some(cx.background_spawn(computation))
+                    } else {
+                        first_url_or_file = Some(Either::Left(url));
+                        None
+                    }
+                }
                 HoverLink::File(path) => {
                     first_url_or_file = Some(Either::Right(path));
                     None
@@ -25726,7 +26016,8 @@ fn process_completion_for_edit(
     intent: CompletionIntent,
     buffer: &Entity<Buffer>,
     cursor_position: &text::Anchor,
-    cx: &mut Context<Editor>,
+    snippet_variables: &SnippetVariables,
+    cx: &App,
 ) -> CompletionEdit {
     let buffer = buffer.read(cx);
     let buffer_snapshot = buffer.snapshot();
@@ -25751,7 +26042,7 @@ fn process_completion_for_edit(
         {
             snippet_source = label;
         }
-        match Snippet::parse(&snippet_source).log_err() {
+        match Snippet::parse_with_variables(&snippet_source, snippet_variables).log_err() {
             Some(parsed_snippet) => (Some(parsed_snippet.clone()), parsed_snippet.text),
             None => (None, completion.new_text.clone()),
         }
@@ -26830,6 +27121,8 @@ pub trait SemanticsProvider {
 
     fn supports_semantic_tokens(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
 
+    fn supports_document_links(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool;
+
     fn document_highlights(
         &self,
         buffer: &Entity<Buffer>,
@@ -26845,6 +27138,19 @@ pub trait SemanticsProvider {
         cx: &mut App,
     ) -> Option<Task<Result<Option<Vec<LocationLink>>>>>;
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Shared<Task<std::result::Result<Option<Vec<DocumentLink>>, Arc<anyhow::Error>>>>>;
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>>;
+
     fn range_for_rename(
         &self,
         buffer: &Entity<Buffer>,
@@ -27345,6 +27651,34 @@ impl SemanticsProvider for WeakEntity<Project> {
         .ok()
     }
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Shared<Task<std::result::Result<Option<Vec<DocumentLink>>, Arc<anyhow::Error>>>>>
+    {
+        self.update(cx, |project, cx| {
+            project
+                .lsp_store()
+                .update(cx, |lsp_store, cx| lsp_store.document_links(buffer, cx))
+        })
+        .ok()
+    }
+
+    fn resolve_document_link(
+        &self,
+        buffer: &Entity<Buffer>,
+        link: DocumentLink,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>> {
+        self.update(cx, |project, cx| {
+            project.lsp_store().update(cx, |lsp_store, cx| {
+                lsp_store.resolve_document_link(link, buffer, cx)
+            })
+        })
+        .ok()
+    }
+
     fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
         self.update(cx, |project, cx| {
             if project
@@ -27370,6 +27704,15 @@ impl SemanticsProvider for WeakEntity<Project> {
         .unwrap_or(false)
     }
 
+    fn supports_document_links(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
+        self.update(cx, |project, cx| {
+            buffer.update(cx, |buffer, cx| {
+                project.any_language_server_supports_document_links(buffer, cx)
+            })
+        })
+        .unwrap_or(false)
+    }
+
     fn inline_values(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -27742,7 +28085,7 @@ impl EditorSnapshot {
             let mut left_padding = git_blame_entries_width.unwrap_or(Pixels::ZERO);
             left_padding += if !is_singleton {
                 ch_width * 4.0
-            } else if show_runnables || show_breakpoints {
+            } else if show_runnables || show_breakpoints || gutter_settings.bookmarks {
                 ch_width * 3.0
             } else if show_git_gutter && show_line_numbers {
                 ch_width * 2.0
@@ -28838,12 +29181,56 @@ enum BreakpointPromptEditAction {
     HitCondition,
 }
 
+/// Hit condition operators that most debug adapters understand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HitConditionMode {
+    AtLeast,
+    Exactly,
+    EveryNth,
+}
+
+impl HitConditionMode {
+    const ALL: [Self; 3] = [Self::AtLeast, Self::Exactly, Self::EveryNth];
+
+    fn operator(self) -> &'static str {
+        match self {
+            Self::AtLeast => ">=",
+            Self::Exactly => "==",
+            Self::EveryNth => "%",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::AtLeast => "Break once the breakpoint has been hit this many times",
+            Self::Exactly => "Break only when the breakpoint is hit for this exact time",
+            Self::EveryNth => "Break every time the hit count is a multiple of this number",
+        }
+    }
+
+    /// Splits a hit condition such as `">= 5"` into its mode and count. Conditions without a
+    /// known operator are returned as-is, to be interpreted by the debug adapter.
+    pub(crate) fn parse(hit_condition: &str) -> (Option<Self>, &str) {
+        let hit_condition = hit_condition.trim();
+        Self::ALL
+            .into_iter()
+            .find_map(|mode| {
+                let count = hit_condition.strip_prefix(mode.operator())?;
+                Some((Some(mode), count.trim_start()))
+            })
+            .unwrap_or((None, hit_condition))
+    }
+}
+
 struct BreakpointPromptEditor {
     pub(crate) prompt: Entity<Editor>,
     editor: WeakEntity<Editor>,
     breakpoint_anchor: Anchor,
     breakpoint: Breakpoint,
     edit_action: BreakpointPromptEditAction,
+    hit_condition_mode: Option<HitConditionMode>,
+    /// Why the active debug session didn't verify this breakpoint.
+    rejection: Option<SharedString>,
     block_ids: HashSet<CustomBlockId>,
     editor_margins: Arc<Mutex<EditorMargins>>,
     _subscriptions: Vec<Subscription>,
@@ -28857,18 +29244,35 @@ impl BreakpointPromptEditor {
         breakpoint_anchor: Anchor,
         breakpoint: Breakpoint,
         edit_action: BreakpointPromptEditAction,
+        language: Option<Arc<Language>>,
+        project: Option<WeakEntity<Project>>,
+        rejection: Option<SharedString>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
+        let mut hit_condition_mode = None;
         let base_text = match edit_action {
-            BreakpointPromptEditAction::Log => breakpoint.message.as_ref(),
-            BreakpointPromptEditAction::Condition => breakpoint.condition.as_ref(),
-            BreakpointPromptEditAction::HitCondition => breakpoint.hit_condition.as_ref(),
+            BreakpointPromptEditAction::Log => breakpoint.message.as_deref(),
+            BreakpointPromptEditAction::Condition => breakpoint.condition.as_deref(),
+            BreakpointPromptEditAction::HitCondition => {
+                breakpoint.hit_condition.as_deref().map(|hit_condition| {
+                    let (mode, count) = HitConditionMode::parse(hit_condition);
+                    hit_condition_mode = mode;
+                    count
+                })
+            }
         }
         .map(|msg| msg.to_string())
         .unwrap_or_default();
 
-        let buffer = cx.new(|cx| Buffer::local(base_text, cx));
+        let buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local(base_text, cx);
+            // Conditions are expressions in the language being debugged.
+            if matches!(edit_action, BreakpointPromptEditAction::Condition) {
+                buffer.set_language(language, cx);
+            }
+            buffer
+        });
         let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
 
         let prompt = cx.new(|cx| {
@@ -28884,6 +29288,13 @@ impl BreakpointPromptEditor {
             );
             prompt.set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
             prompt.set_show_cursor_when_unfocused(false, cx);
+            if !matches!(edit_action, BreakpointPromptEditAction::HitCondition)
+                && let Some(project) = project
+            {
+                prompt.set_completion_provider(Some(Rc::new(
+                    BreakpointPromptCompletionProvider { project },
+                )));
+            }
             prompt.set_placeholder_text(
                 match edit_action {
                     BreakpointPromptEditAction::Log => "Message to log when a breakpoint is hit. Expressions within {} are interpolated.",
@@ -28903,6 +29314,8 @@ impl BreakpointPromptEditor {
             breakpoint_anchor,
             breakpoint,
             edit_action,
+            hit_condition_mode,
+            rejection,
             editor_margins: Arc::new(Mutex::new(EditorMargins::default())),
             block_ids: Default::default(),
             _subscriptions: vec![],
@@ -28925,6 +29338,12 @@ impl BreakpointPromptEditor {
                 .read(cx)
                 .as_rope()
                 .to_string();
+            let message = match self.hit_condition_mode {
+                Some(mode) if !message.trim().is_empty() => {
+                    format!("{}{}", mode.operator(), message.trim())
+                }
+                _ => message,
+            };
 
             editor.update(cx, |editor, cx| {
                 editor.edit_breakpoint_at_anchor(
@@ -28980,10 +29399,49 @@ impl BreakpointPromptEditor {
                 background: cx.theme().colors().editor_background,
                 local_player: cx.theme().players().local(),
                 text: text_style,
+                syntax: cx.theme().syntax().clone(),
                 ..Default::default()
             },
         )
     }
+
+    fn render_hit_condition_modes(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .gap_0p5()
+            .children(HitConditionMode::ALL.into_iter().map(|mode| {
+                Button::new(mode.operator(), mode.operator())
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.hit_condition_mode == Some(mode))
+                    .tooltip(Tooltip::text(mode.description()))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.hit_condition_mode = if this.hit_condition_mode == Some(mode) {
+                            None
+                        } else {
+                            Some(mode)
+                        };
+                        cx.notify();
+                    }))
+            }))
+    }
+
+    fn render_rejection(&self) -> Option<impl IntoElement> {
+        let rejection = self.rejection.clone()?;
+        Some(
+            h_flex()
+                .gap_1()
+                .child(
+                    Icon::new(IconName::Warning)
+                        .size(IconSize::Small)
+                        .color(Color::Warning),
+                )
+                .child(
+                    Label::new(rejection)
+                        .size(LabelSize::Small)
+                        .color(Color::Warning)
+                        .truncate(),
+                ),
+        )
+    }
 }
 
 impl Render for BreakpointPromptEditor {
@@ -29000,7 +29458,115 @@ impl Render for BreakpointPromptEditor {
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::cancel))
             .child(h_flex().w(gutter_dimensions.full_width() + (gutter_dimensions.margin / 2.0)))
-            .child(div().flex_1().child(self.render_prompt_editor(cx)))
+            .child(
+                v_flex()
+                    .flex_1()
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .when(
+                                matches!(
+                                    self.edit_action,
+                                    BreakpointPromptEditAction::HitCondition
+                                ),
+                                |this| this.child(self.render_hit_condition_modes(cx)),
+                            )
+                            .child(div().flex_1().child(self.render_prompt_editor(cx))),
+                    )
+                    .children(self.render_rejection()),
+            )
+    }
+}
+
+/// Completes expressions in breakpoint conditions and log messages using the `completions`
+/// request of the session that is currently stopped.
+struct BreakpointPromptCompletionProvider {
+    project: WeakEntity<Project>,
+}
+
+impl BreakpointPromptCompletionProvider {
+    fn stopped_session(&self, cx: &App) -> Option<(Entity<Session>, StackFrameId)> {
+        let project = self.project.upgrade()?;
+        let project = project.read(cx);
+        let breakpoint_store = project.breakpoint_store();
+        let active_frame = breakpoint_store.read(cx).active_position()?;
+        let session = project
+            .dap_store()
+            .read(cx)
+            .session_by_id(active_frame.session_id)?;
+        let stack_frame_id = active_frame.stack_frame_id;
+        session
+            .read(cx)
+            .capabilities()
+            .supports_completions_request
+            .unwrap_or_default()
+            .then_some((session, stack_frame_id))
+    }
+}
+
+impl CompletionProvider for BreakpointPromptCompletionProvider {
+    fn completions(
+        &self,
+        _excerpt_id: ExcerptId,
+        buffer: &Entity<Buffer>,
+        buffer_position: text::Anchor,
+        _trigger: CompletionContext,
+        _window: &mut Window,
+        cx: &mut Context<Editor>,
+    ) -> Task<Result<Vec<CompletionResponse>>> {
+        let Some((session, stack_frame_id)) = self.stopped_session(cx) else {
+            return Task::ready(Ok(Vec::new()));
+        };
+
+        let completions = session.update(cx, |session, cx| {
+            session.completions(
+                CompletionsQuery::new(buffer.read(cx), buffer_position, Some(stack_frame_id)),
+                cx,
+            )
+        });
+        let snapshot = buffer.read(cx).text_snapshot();
+
+        cx.background_spawn(async move {
+            let completions = completions.await?;
+
+            Ok(vec![CompletionResponse {
+                completions: completions_from_dap(completions, buffer_position, &snapshot),
+                display_options: CompletionDisplayOptions::default(),
+                is_incomplete: false,
+            }])
+        })
+    }
+
+    fn is_completion_trigger(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: language::Anchor,
+        text: &str,
+        trigger_in_words: bool,
+        cx: &mut Context<Editor>,
+    ) -> bool {
+        let Some((session, _)) = self.stopped_session(cx) else {
+            return false;
+        };
+        let Some(char) = text.chars().next() else {
+            return false;
+        };
+
+        let classifier = buffer
+            .read(cx)
+            .snapshot()
+            .char_classifier_at(position)
+            .scope_context(Some(CharScopeContext::Completion));
+        if trigger_in_words && classifier.is_word(char) {
+            return true;
+        }
+
+        session
+            .read(cx)
+            .capabilities()
+            .completion_trigger_characters
+            .as_ref()
+            .is_some_and(|triggers| triggers.iter().any(|trigger| trigger.as_str() == text))
     }
 }
 
@@ -12519,6 +12519,34 @@ async fn test_snippet_indentation(cx: &mut TestAppContext) {
         ˇ"});
 }
 
+#[gpui::test]
+fn test_snippet_variables_in_untitled_buffer(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let editor = cx.add_window(|window, cx| {
+        let buffer = MultiBuffer::build_simple("", cx);
+        build_editor(buffer, window, cx)
+    });
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.insert_snippet_at_selections(
+            &InsertSnippet {
+                language: None,
+                name: None,
+                snippet: Some("$CURRENT_YEAR ${UNKNOWN:default} [$TM_FILENAME]".to_string()),
+            },
+            window,
+            cx,
+        );
+
+        // Only the file variables are empty without a file.
+        let year = time::OffsetDateTime::now_local()
+            .unwrap_or_else(|_| time::OffsetDateTime::now_utc())
+            .year();
+        assert_eq!(editor.text(cx), format!("{year} default []"));
+    });
+}
+
 #[gpui::test]
 async fn test_snippet_with_multi_word_prefix(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -25198,6 +25226,122 @@ async fn test_breakpoint_toggling(cx: &mut TestAppContext) {
     assert_breakpoint(&breakpoints, &abs_path, vec![]);
 }
 
+#[gpui::test]
+async fn test_bookmark_toggling_and_navigation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let sample_text = "First line\nSecond line\nThird line\nFourth line".to_string();
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": sample_text,
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let window = cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+    let workspace = window
+        .read_with(cx, |mw, _| mw.workspace().clone())
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*window, cx);
+    let worktree_id = workspace.update_in(cx, |workspace, _window, cx| {
+        workspace.project().update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        })
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, rel_path("main.rs")), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer.clone(), cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+    let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+    let bookmark_rows = |cx: &mut VisualTestContext| {
+        bookmark_store.read_with(cx, |bookmark_store, cx| {
+            let snapshot = buffer.read(cx).snapshot();
+            bookmark_store
+                .bookmarks_in_buffer(&buffer, None, &snapshot)
+                .map(|bookmark| snapshot.summary_for_anchor::<Point>(&bookmark.position).row)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // Cursors on the same line toggle its bookmark once.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(SelectionEffects::no_scroll(), window, cx, |s| {
+            s.select_ranges([
+                Point::new(1, 0)..Point::new(1, 0),
+                Point::new(1, 4)..Point::new(1, 4),
+                Point::new(3, 2)..Point::new(3, 2),
+            ])
+        });
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+    });
+    assert_eq!(bookmark_rows(cx), vec![1, 3]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_to_beginning(&MoveToBeginning, window, cx);
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+        assert_eq!(
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head(),
+            Point::new(1, 0)
+        );
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+        assert_eq!(
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head(),
+            Point::new(3, 0)
+        );
+        editor.go_to_next_bookmark(&actions::GoToNextBookmark, window, cx);
+        assert_eq!(
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head(),
+            Point::new(1, 0)
+        );
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
+        assert_eq!(
+            editor
+                .selections
+                .newest::<Point>(&editor.display_snapshot(cx))
+                .head(),
+            Point::new(3, 0)
+        );
+    });
+
+    // Bookmarks stay on their lines when lines are inserted above them.
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_to_beginning(&MoveToBeginning, window, cx);
+        editor.handle_input("Zeroth line\n", window, cx);
+    });
+    assert_eq!(bookmark_rows(cx), vec![2, 4]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+        editor.go_to_previous_bookmark(&actions::GoToPreviousBookmark, window, cx);
+        editor.toggle_bookmark(&actions::ToggleBookmark, window, cx);
+    });
+    assert_eq!(bookmark_rows(cx), vec![1, 2]);
+}
+
 #[gpui::test]
 async fn test_log_breakpoint_editing(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -25354,6 +25498,25 @@ async fn test_log_breakpoint_editing(cx: &mut TestAppContext) {
     );
 }
 
+#[test]
+fn test_hit_condition_mode_parsing() {
+    assert_eq!(
+        HitConditionMode::parse(">= 5"),
+        (Some(HitConditionMode::AtLeast), "5")
+    );
+    assert_eq!(
+        HitConditionMode::parse("==3"),
+        (Some(HitConditionMode::Exactly), "3")
+    );
+    assert_eq!(
+        HitConditionMode::parse(" % 2 "),
+        (Some(HitConditionMode::EveryNth), "2")
+    );
+    // Conditions in an adapter-specific syntax are left for the adapter to interpret.
+    assert_eq!(HitConditionMode::parse("> 10"), (None, "> 10"));
+    assert_eq!(HitConditionMode::parse("7"), (None, "7"));
+}
+
 /// This also tests that Editor::breakpoint_at_cursor_head is working properly
 /// we had some issues where we wouldn't find a breakpoint at Point {row: 0, col: 0}
 /// or when breakpoints were placed out of order. This tests for a regression too
@@ -1,7 +1,7 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToDefinitionSplit, GoToTypeDefinition, GoToTypeDefinitionSplit, GotoDefinitionKind,
-    HighlightKey, Navigated, PointForPosition, SelectPhase,
+    HighlightKey, Navigated, PointForPosition, SelectPhase, SemanticsProvider,
     editor_settings::GoToDefinitionFallback, scroll::ScrollAmount,
 };
 use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
@@ -11,7 +11,7 @@ use lsp::LanguageServerId;
 use project::{InlayId, LocationLink, Project, ResolvedPath};
 use regex::Regex;
 use settings::Settings;
-use std::{ops::Range, sync::LazyLock};
+use std::{ops::Range, str::FromStr as _, sync::LazyLock};
 use text::OffsetRangeExt;
 use theme::ActiveTheme as _;
 use util::{ResultExt, TryFutureExt as _, maybe};
@@ -66,6 +66,7 @@ pub enum HoverLink {
     File(ResolvedPath),
     Text(LocationLink),
     InlayHint(lsp::Location, LanguageServerId),
+    DocumentLink(lsp::Uri, LanguageServerId),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -336,7 +337,7 @@ pub fn show_link_definition(
         || hovered_link_state
             .links
             .first()
-            .is_some_and(|d| matches!(d, HoverLink::Url(_)));
+            .is_some_and(|d| matches!(d, HoverLink::Url(_) | HoverLink::DocumentLink(..)));
 
     if same_kind {
         if is_cached && (hovered_link_state.last_trigger_point == trigger_point)
@@ -361,7 +362,21 @@ pub fn show_link_definition(
         async move {
             let result = match &trigger_point {
                 TriggerPoint::Text(_) => {
-                    if let Some((url_range, url)) = find_url(&buffer, text_anchor, cx.clone()) {
+                    let document_link = match &provider {
+                        Some(provider) => {
+                            find_document_link(provider.as_ref(), &buffer, text_anchor, cx).await
+                        }
+                        None => None,
+                    };
+                    if let Some((link_range, link)) = document_link {
+                        let range = maybe!({
+                            let range = snapshot.anchor_range_in_excerpt(excerpt_id, link_range)?;
+                            Some(RangeInEditor::Text(range))
+                        });
+                        Some((range, vec![link]))
+                    } else if let Some((url_range, url)) =
+                        find_url(&buffer, text_anchor, cx.clone())
+                    {
                         this.read_with(cx, |_, _| {
                             let range = maybe!({
                                 let range =
@@ -485,6 +500,63 @@ pub fn show_link_definition(
     editor.hovered_link_state = Some(hovered_link_state);
 }
 
+/// Finds the link a language server computed at the position, resolving its target if the server
+/// didn't send it with the link.
+async fn find_document_link(
+    provider: &dyn SemanticsProvider,
+    buffer: &Entity<language::Buffer>,
+    position: text::Anchor,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let supported = cx
+        .update(|_, cx| provider.supports_document_links(buffer, cx))
+        .ok()?;
+    if !supported {
+        return None;
+    }
+    let links = cx
+        .update(|_, cx| provider.document_links(buffer, cx))
+        .ok()??
+        .await
+        .ok()??;
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    let link = links.into_iter().find(|link| {
+        link.range.start.cmp(&position, &snapshot).is_le()
+            && link.range.end.cmp(&position, &snapshot).is_ge()
+    })?;
+    let range = link.range.clone();
+    let link = cx
+        .update(|_, cx| provider.resolve_document_link(buffer, link, cx))
+        .ok()??
+        .await
+        .log_err()?;
+    Some((range, HoverLink::DocumentLink(link.target?, link.server_id)))
+}
+
+/// Returns the location a `file:` document link points to.
+///
+/// The link may point into the file with a `#L<line>,<column>` fragment, both one-based, where the
+/// column can be omitted.
+pub(crate) fn document_link_location(target: lsp::Uri) -> lsp::Location {
+    let target_str = target.to_string();
+    let location = target_str.split_once('#').and_then(|(uri, fragment)| {
+        let position = fragment.strip_prefix('L')?;
+        let (line, column) = position.split_once(',').unwrap_or((position, "1"));
+        let position = lsp::Position::new(
+            line.parse::<u32>().ok()?.saturating_sub(1),
+            column.parse::<u32>().ok()?.saturating_sub(1),
+        );
+        Some(lsp::Location {
+            uri: lsp::Uri::from_str(uri).ok()?,
+            range: lsp::Range::new(position, position),
+        })
+    });
+    location.unwrap_or(lsp::Location {
+        uri: target,
+        range: lsp::Range::default(),
+    })
+}
+
 pub(crate) fn find_url(
     buffer: &Entity<language::Buffer>,
     position: text::Anchor,
@@ -1362,6 +1434,146 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            use serde::Deserialize;ˇ
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            use «serde»::Deserialize;
+        "});
+        let mut link_requests = cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: link_range,
+                    target: None,
+                    tooltip: None,
+                    data: None,
+                }]))
+            },
+        );
+        // The server only tells where the link goes once it's resolved.
+        let mut resolve_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+                |link, _, _| async move {
+                    Ok(lsp::DocumentLink {
+                        target: Some("https://docs.rs/serde".parse().unwrap()),
+                        ..link
+                    })
+                },
+            );
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            use serˇde::Deserialize;
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        link_requests.next().await;
+        resolve_requests.next().await;
+        cx.run_until_parked();
+        cx.assert_editor_text_highlights(
+            HighlightKey::HoveredLinkState,
+            indoc! {"
+            use «serde»::Deserialize;
+        "},
+        );
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://docs.rs/serde".into()));
+    }
+
+    #[gpui::test]
+    async fn test_file_document_link_with_position(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: None,
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            // See mainˇ.
+            fn main() {
+                let x = 1;
+            }
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            // See «main».
+            fn main() {
+                let x = 1;
+            }
+        "});
+        let target: lsp::Uri = format!("{}#L3,9", cx.buffer_lsp_url.as_str())
+            .parse()
+            .unwrap();
+        let mut link_requests =
+            cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(move |_, _, _| {
+                let target = target.clone();
+                async move {
+                    Ok(Some(vec![lsp::DocumentLink {
+                        range: link_range,
+                        target: Some(target),
+                        tooltip: None,
+                        data: None,
+                    }]))
+                }
+            });
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            // See maˇin.
+            fn main() {
+                let x = 1;
+            }
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        link_requests.next().await;
+        cx.run_until_parked();
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        cx.run_until_parked();
+
+        cx.assert_editor_state(indoc! {"
+            // See main.
+            fn main() {
+                let ˇx = 1;
+            }
+        "});
+    }
+
+    #[test]
+    fn test_document_link_location() {
+        let location = |target: &str| document_link_location(target.parse().unwrap());
+
+        let link = location("file:///a/b.rs#L12,5");
+        assert_eq!(link.uri.as_str(), "file:///a/b.rs");
+        assert_eq!(link.range.start, lsp::Position::new(11, 4));
+
+        let link = location("file:///a/b.rs#L12");
+        assert_eq!(link.uri.as_str(), "file:///a/b.rs");
+        assert_eq!(link.range.start, lsp::Position::new(11, 0));
+
+        let link = location("file:///a/b.rs#section");
+        assert_eq!(link.uri.as_str(), "file:///a/b.rs#section");
+        assert_eq!(link.range, lsp::Range::default());
+    }
+
     #[gpui::test]
     async fn test_urls_at_beginning_of_buffer(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
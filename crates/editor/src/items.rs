@@ -1627,6 +1627,7 @@ impl SearchableItem for Editor {
                 replacement: false,
                 selection: false,
                 find_in_results: true,
+                scope: true,
             }
         } else {
             SearchOptions {
@@ -1636,6 +1637,7 @@ impl SearchableItem for Editor {
                 replacement: true,
                 selection: true,
                 find_in_results: false,
+                scope: true,
             }
         }
     }
@@ -1715,6 +1717,14 @@ impl SearchableItem for Editor {
     ) {
         let text = self.buffer.read(cx);
         let text = text.snapshot(cx);
+        if query.is_structural() {
+            if let Some(replacement) = structural_replacement_for(identifier, query, &text) {
+                self.transact(window, cx, |this, _, cx| {
+                    this.edit([(identifier.clone(), Arc::from(replacement))], cx);
+                });
+            }
+            return;
+        }
         let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
         let text: Cow<_> = if text.len() == 1 {
             text.first().cloned().unwrap().into()
@@ -1741,9 +1751,16 @@ impl SearchableItem for Editor {
         let text = text.snapshot(cx);
         let mut edits = vec![];
 
-        // A regex might have replacement variables so we cannot apply
-        // the same replacement to all matches
-        if query.is_regex() {
+        // Structural and regex queries might have replacement variables,
+        // so we cannot apply the same replacement to all matches
+        if query.is_structural() {
+            edits = matches
+                .filter_map(|m| {
+                    structural_replacement_for(m, query, &text)
+                        .map(|replacement| (m.clone(), Arc::from(replacement)))
+                })
+                .collect();
+        } else if query.is_regex() {
             edits = matches
                 .filter_map(|m| {
                     let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
@@ -1909,6 +1926,18 @@ impl SearchableItem for Editor {
     }
 }
 
+fn structural_replacement_for(
+    range: &Range<Anchor>,
+    query: &SearchQuery,
+    snapshot: &MultiBufferSnapshot,
+) -> Option<String> {
+    let (buffer, buffer_range, _) = snapshot
+        .range_to_buffer_ranges(range.clone())
+        .into_iter()
+        .next()?;
+    query.structural_replacement_for(buffer, buffer_range.start.0..buffer_range.end.0)
+}
+
 pub fn active_match_index(
     direction: Direction,
     ranges: &[Range<Anchor>],
@@ -0,0 +1,167 @@
+use gpui::App;
+use multi_buffer::{MultiBufferRow, ToOffset as _, ToPoint as _};
+use snippet::VariableResolver;
+use std::path::Path;
+use text::Point;
+use time::OffsetDateTime;
+
+use crate::Editor;
+
+/// Resolves the variables of snippets inserted into an editor, using the same names as VS Code:
+/// https://code.visualstudio.com/docs/editing/userdefinedsnippets#_variables
+///
+/// Selection and line variables are resolved relative to the newest selection.
+pub(crate) struct SnippetVariables<'a> {
+    editor: &'a Editor,
+    now: OffsetDateTime,
+    cx: &'a App,
+}
+
+impl<'a> SnippetVariables<'a> {
+    pub(crate) fn new(editor: &'a Editor, cx: &'a App) -> Self {
+        Self {
+            editor,
+            now: OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc()),
+            cx,
+        }
+    }
+
+    fn resolve_file_variable(&self, name: &str) -> Option<String> {
+        let cx = self.cx;
+        let snapshot = self.editor.buffer.read(cx).snapshot(cx);
+        let head = self.editor.selections.newest_anchor().head();
+        let Some(file) = snapshot.file_at(head) else {
+            // Untitled buffers have empty file variables, other variables are left unresolved.
+            return matches!(
+                name,
+                "TM_FILENAME"
+                    | "TM_FILENAME_BASE"
+                    | "TM_DIRECTORY"
+                    | "TM_FILEPATH"
+                    | "RELATIVE_FILEPATH"
+            )
+            .then(String::new);
+        };
+        let worktree = self
+            .editor
+            .project
+            .as_ref()
+            .and_then(|project| project.read(cx).worktree_for_id(file.worktree_id(cx), cx));
+        let abs_path = match (file.as_local(), &worktree) {
+            (Some(file), _) => file.abs_path(cx),
+            (None, Some(worktree)) => worktree.read(cx).absolutize(file.path()),
+            (None, None) => file.full_path(cx),
+        };
+
+        let value = match name {
+            "TM_FILENAME" => file.file_name(cx).to_string(),
+            "TM_FILENAME_BASE" => Path::new(file.file_name(cx))
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "TM_DIRECTORY" => abs_path
+                .parent()
+                .map(|parent| parent.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "TM_FILEPATH" => abs_path.to_string_lossy().into_owned(),
+            "RELATIVE_FILEPATH" => file.path().display(file.path_style(cx)).into_owned(),
+            "WORKSPACE_NAME" => worktree
+                .map(|worktree| worktree.read(cx).root_name_str().to_string())
+                .unwrap_or_default(),
+            "WORKSPACE_FOLDER" => worktree
+                .map(|worktree| worktree.read(cx).abs_path().to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn resolve_date_variable(&self, name: &str) -> Option<String> {
+        let now = self.now;
+        let value = match name {
+            "CURRENT_YEAR" => now.year().to_string(),
+            "CURRENT_YEAR_SHORT" => format!("{:02}", now.year() % 100),
+            "CURRENT_MONTH" => format!("{:02}", u8::from(now.month())),
+            "CURRENT_MONTH_NAME" => now.month().to_string(),
+            "CURRENT_MONTH_NAME_SHORT" => now.month().to_string()[..3].to_string(),
+            "CURRENT_DATE" => format!("{:02}", now.day()),
+            "CURRENT_DAY_NAME" => now.weekday().to_string(),
+            "CURRENT_DAY_NAME_SHORT" => now.weekday().to_string()[..3].to_string(),
+            "CURRENT_HOUR" => format!("{:02}", now.hour()),
+            "CURRENT_MINUTE" => format!("{:02}", now.minute()),
+            "CURRENT_SECOND" => format!("{:02}", now.second()),
+            "CURRENT_SECONDS_UNIX" => now.unix_timestamp().to_string(),
+            "CURRENT_TIMEZONE_OFFSET" => {
+                let offset = now.offset();
+                let sign = if offset.is_negative() { '-' } else { '+' };
+                format!(
+                    "{sign}{:02}:{:02}",
+                    offset.whole_hours().unsigned_abs(),
+                    offset.minutes_past_hour().unsigned_abs()
+                )
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl VariableResolver for SnippetVariables<'_> {
+    fn resolve(&self, name: &str) -> Option<String> {
+        let cx = self.cx;
+        let snapshot = self.editor.buffer.read(cx).snapshot(cx);
+        let selection = self.editor.selections.newest_anchor();
+        let head = selection.head().to_point(&snapshot);
+
+        match name {
+            "TM_SELECTED_TEXT" => Some(
+                snapshot
+                    .text_for_range(selection.start..selection.end)
+                    .collect(),
+            ),
+            "TM_CURRENT_LINE" => {
+                let line_end = Point::new(head.row, snapshot.line_len(MultiBufferRow(head.row)));
+                Some(
+                    snapshot
+                        .text_for_range(Point::new(head.row, 0)..line_end)
+                        .collect(),
+                )
+            }
+            "TM_CURRENT_WORD" => {
+                let (range, _) = snapshot.surrounding_word(head.to_offset(&snapshot), None);
+                Some(snapshot.text_for_range(range).collect())
+            }
+            "TM_LINE_INDEX" => Some(head.row.to_string()),
+            "TM_LINE_NUMBER" => Some((head.row + 1).to_string()),
+            "CLIPBOARD" => Some(
+                cx.read_from_clipboard()
+                    .and_then(|item| item.text())
+                    .unwrap_or_default(),
+            ),
+            "RANDOM" => Some(format!("{:06}", rand::random_range(0..1_000_000))),
+            "RANDOM_HEX" => Some(format!("{:06x}", rand::random_range(0..0x1000000))),
+            "UUID" => Some(uuid::Uuid::new_v4().to_string()),
+            "LINE_COMMENT" | "BLOCK_COMMENT_START" | "BLOCK_COMMENT_END" => {
+                let scope = snapshot.language_scope_at(head);
+                let value = match name {
+                    "LINE_COMMENT" => scope
+                        .as_ref()
+                        .and_then(|scope| scope.line_comment_prefixes().first())
+                        .map(|prefix| prefix.trim_end().to_string()),
+                    "BLOCK_COMMENT_START" => scope
+                        .as_ref()
+                        .and_then(|scope| scope.block_comment())
+                        .map(|comment| comment.start.trim_end().to_string()),
+                    _ => scope
+                        .as_ref()
+                        .and_then(|scope| scope.block_comment())
+                        .map(|comment| comment.end.trim_start().to_string()),
+                };
+                Some(value.unwrap_or_default())
+            }
+            _ => self
+                .resolve_file_variable(name)
+                .or_else(|| self.resolve_date_variable(name)),
+        }
+    }
+}
@@ -0,0 +1,801 @@
+//! Recording and replaying macros in editors.
+//!
+//! A macro is recorded from the actions handled while the recording editor is focused, no matter
+//! whether they came from keystrokes, the command palette or menus, and from the text typed into
+//! it. Replaying dispatches the same actions on the editor and types the same text, so a macro
+//! behaves the same way with any number of selections.
+//! Macros can be saved under a name in the `macros` setting, and replayed from the keymap.
+mod save_macro_modal;
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::{BTreeSet, HashMap};
+use editor::{Anchor, Editor, EditorEvent, SelectionEffects};
+use gpui::{
+    Action, App, AppContext as _, Context, Entity, Focusable, Global, Subscription, WeakEntity,
+    Window, actions,
+};
+use language::{Point, TransactionId};
+use save_macro_modal::SaveMacroModal;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use settings::{MacroStepContent, RegisterSetting, Settings};
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    iter,
+    ops::Range,
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+use workspace::{Toast, notifications::NotificationId};
+
+actions!(
+    editor_macros,
+    [
+        /// Starts recording a macro in the focused editor, or stops the current recording.
+        ToggleRecording,
+        /// Saves the last recorded macro to the settings under a name.
+        SaveMacro,
+    ]
+);
+
+/// Replays a macro in the focused editor.
+#[derive(PartialEq, Clone, Deserialize, JsonSchema, Action)]
+#[action(namespace = editor_macros)]
+#[serde(deny_unknown_fields)]
+pub struct Replay {
+    /// The name of a macro from the `macros` setting. The last recorded macro is replayed
+    /// when this is omitted.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// How many times to replay the macro.
+    #[serde(default = "default_times")]
+    pub times: usize,
+    /// Whether to replay the macro with all selections at once, or separately for each
+    /// selection or line.
+    #[serde(default)]
+    pub mode: ReplayMode,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self {
+            name: None,
+            times: default_times(),
+            mode: ReplayMode::default(),
+        }
+    }
+}
+
+fn default_times() -> usize {
+    1
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayMode {
+    /// Replays the macro with all selections at once.
+    #[default]
+    Once,
+    /// Replays the macro separately for each selection, starting with only that selection.
+    EachSelection,
+    /// Replays the macro separately for each line spanned by the selections, starting with
+    /// the cursor at the beginning of that line.
+    EachLine,
+}
+
+/// Replays that would run more steps than this are refused.
+const MAX_REPLAY_STEPS: usize = 10_000;
+
+#[derive(Clone, Debug, RegisterSetting)]
+pub struct MacroSettings {
+    pub macros: HashMap<String, Vec<MacroStepContent>>,
+}
+
+impl Settings for MacroSettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        Self {
+            macros: content.macros.clone().unwrap(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum MacroStep {
+    Action {
+        action: Box<dyn Action>,
+        /// The action as it's written in the keymap, when it can be built from that.
+        content: Option<serde_json::Value>,
+    },
+    Text {
+        text: Arc<str>,
+        utf16_range_to_replace: Option<Range<isize>>,
+    },
+}
+
+impl Clone for MacroStep {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Action { action, content } => Self::Action {
+                action: action.boxed_clone(),
+                content: content.clone(),
+            },
+            Self::Text {
+                text,
+                utf16_range_to_replace,
+            } => Self::Text {
+                text: text.clone(),
+                utf16_range_to_replace: utf16_range_to_replace.clone(),
+            },
+        }
+    }
+}
+
+struct Recording {
+    editor: WeakEntity<Editor>,
+    steps: Vec<MacroStep>,
+    _subscription: Subscription,
+}
+
+#[derive(Default)]
+struct EditorMacros {
+    recording: Option<Recording>,
+    last_macro: Option<Arc<[MacroStep]>>,
+    /// Alive for as long as a replay is running.
+    replaying: Weak<()>,
+    /// Set after an action is recorded until the end of the current effect cycle, so that
+    /// text inserted by the action itself isn't recorded too.
+    ignore_input: bool,
+}
+
+impl Global for EditorMacros {}
+
+pub fn init(cx: &mut App) {
+    cx.set_global(EditorMacros::default());
+    cx.observe_actions(observe_action).detach();
+    cx.observe_new(register).detach();
+}
+
+fn register(editor: &mut Editor, _window: Option<&mut Window>, cx: &mut Context<Editor>) {
+    if !editor.mode().is_full() {
+        return;
+    }
+
+    let handle = cx.entity().downgrade();
+    editor
+        .register_action({
+            let handle = handle.clone();
+            move |_: &ToggleRecording, _, cx| {
+                if let Some(editor) = handle.upgrade() {
+                    toggle_recording(&editor, cx);
+                }
+            }
+        })
+        .detach();
+    editor
+        .register_action({
+            let handle = handle.clone();
+            move |action: &Replay, window, cx| {
+                if let Some(editor) = handle.upgrade() {
+                    replay(&editor, action, window, cx);
+                }
+            }
+        })
+        .detach();
+    editor
+        .register_action(move |_: &SaveMacro, window, cx| {
+            if let Some(editor) = handle.upgrade() {
+                save_macro(&editor, window, cx);
+            }
+        })
+        .detach();
+}
+
+fn toggle_recording(editor: &Entity<Editor>, cx: &mut App) {
+    let macros = cx.global_mut::<EditorMacros>();
+    let message = if let Some(recording) = macros.recording.take() {
+        let step_count = recording.steps.len();
+        if step_count > 0 {
+            macros.last_macro = Some(recording.steps.into());
+        }
+        match step_count {
+            0 => "Stopped recording an empty macro".to_string(),
+            1 => "Recorded a macro with 1 step".to_string(),
+            _ => format!("Recorded a macro with {step_count} steps"),
+        }
+    } else {
+        let subscription = cx.subscribe(editor, |_, event: &EditorEvent, cx| {
+            if let EditorEvent::InputHandled {
+                text,
+                utf16_range_to_replace,
+            } = event
+            {
+                cx.global_mut::<EditorMacros>()
+                    .observe_input(text, utf16_range_to_replace.clone());
+            }
+        });
+        cx.global_mut::<EditorMacros>().recording = Some(Recording {
+            editor: editor.downgrade(),
+            steps: Vec::new(),
+            _subscription: subscription,
+        });
+        "Recording a macro".to_string()
+    };
+
+    struct MacroRecordingToast;
+    if let Some(workspace) = editor.read(cx).workspace() {
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<MacroRecordingToast>(), message).autohide(),
+                cx,
+            )
+        });
+    }
+}
+
+fn observe_action(action: &dyn Action, window: &mut Window, cx: &mut App) {
+    let macros = cx.global::<EditorMacros>();
+    if macros.is_replaying() || is_macro_action(action) {
+        return;
+    }
+    let Some(editor) = macros
+        .recording
+        .as_ref()
+        .and_then(|recording| recording.editor.upgrade())
+    else {
+        return;
+    };
+    if !editor.focus_handle(cx).is_focused(window) {
+        return;
+    }
+
+    let content = action_content(action, window, cx);
+    let macros = cx.global_mut::<EditorMacros>();
+    if let Some(recording) = macros.recording.as_mut() {
+        recording.steps.push(MacroStep::Action {
+            action: action.boxed_clone(),
+            content,
+        });
+    }
+    macros.ignore_input = true;
+    cx.defer(|cx| cx.global_mut::<EditorMacros>().ignore_input = false);
+}
+
+impl EditorMacros {
+    fn is_replaying(&self) -> bool {
+        self.replaying.strong_count() > 0
+    }
+
+    fn observe_input(&mut self, text: &Arc<str>, utf16_range_to_replace: Option<Range<isize>>) {
+        if self.ignore_input || self.is_replaying() {
+            return;
+        }
+        if let Some(recording) = self.recording.as_mut() {
+            recording.steps.push(MacroStep::Text {
+                text: text.clone(),
+                utf16_range_to_replace,
+            });
+        }
+    }
+}
+
+fn is_macro_action(action: &dyn Action) -> bool {
+    action.name().starts_with("editor_macros::")
+}
+
+/// Returns the action as it's written in the keymap, if building it from that results in the
+/// same action. Actions with arguments can only be written down when they were dispatched from
+/// a key binding, whose arguments are then reused.
+fn action_content(action: &dyn Action, window: &Window, cx: &App) -> Option<serde_json::Value> {
+    let name = action.name();
+    if cx
+        .build_action(name, None)
+        .is_ok_and(|built| built.partial_eq(action))
+    {
+        return Some(name.into());
+    }
+    window
+        .bindings_for_action(action)
+        .iter()
+        .rev()
+        .find_map(|binding| {
+            let input = serde_json::from_str::<serde_json::Value>(&binding.action_input()?).ok()?;
+            let built = cx.build_action(name, Some(input.clone())).ok()?;
+            built
+                .partial_eq(action)
+                .then(|| serde_json::json!([name, input]))
+        })
+}
+
+fn macro_to_content(steps: &[MacroStep]) -> Result<Vec<MacroStepContent>> {
+    let mut content = Vec::new();
+    for step in steps {
+        match step {
+            MacroStep::Action {
+                action,
+                content: None,
+            } => {
+                bail!(
+                    "the macro can't be saved, because the arguments of {} are unknown",
+                    action.name()
+                )
+            }
+            MacroStep::Action {
+                content: Some(action),
+                ..
+            } => content.push(MacroStepContent::Action {
+                action: action.clone(),
+            }),
+            MacroStep::Text { text, .. } => {
+                if let Some(MacroStepContent::Text { text: previous }) = content.last_mut() {
+                    previous.push_str(text);
+                } else {
+                    content.push(MacroStepContent::Text {
+                        text: text.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(content)
+}
+
+fn macro_from_content(content: &[MacroStepContent], cx: &App) -> Result<Vec<MacroStep>> {
+    content
+        .iter()
+        .map(|step| match step {
+            MacroStepContent::Action { action } => {
+                let (name, input) = match action {
+                    serde_json::Value::String(name) => (name.as_str(), None),
+                    serde_json::Value::Array(items) => match items.as_slice() {
+                        [serde_json::Value::String(name), input] => {
+                            (name.as_str(), Some(input.clone()))
+                        }
+                        _ => bail!("expected an action name and its arguments, got {action}"),
+                    },
+                    _ => bail!("expected an action, got {action}"),
+                };
+                Ok(MacroStep::Action {
+                    action: cx.build_action(name, input)?,
+                    content: Some(action.clone()),
+                })
+            }
+            MacroStepContent::Text { text } => Ok(MacroStep::Text {
+                text: text.as_str().into(),
+                utf16_range_to_replace: None,
+            }),
+        })
+        .collect()
+}
+
+fn replay(editor: &Entity<Editor>, action: &Replay, window: &mut Window, cx: &mut App) {
+    if cx.global::<EditorMacros>().is_replaying() {
+        return;
+    }
+
+    let steps: Result<Arc<[MacroStep]>> = match &action.name {
+        Some(name) => MacroSettings::get_global(cx)
+            .macros
+            .get(name)
+            .with_context(|| format!("no macro named {name:?} in the settings"))
+            .and_then(|content| macro_from_content(content, cx))
+            .with_context(|| format!("replaying macro {name:?}"))
+            .map(Arc::from),
+        None => cx
+            .global::<EditorMacros>()
+            .last_macro
+            .clone()
+            .context("no macro has been recorded"),
+    };
+    let steps = match steps {
+        Ok(steps) => steps,
+        Err(error) => {
+            show_error(editor, error, cx);
+            return;
+        }
+    };
+
+    let starting_selections = editor.update(cx, |editor, cx| {
+        editor.finalize_last_transaction(cx);
+        starting_selections(editor, action.mode, cx)
+    });
+    let step_count = steps
+        .len()
+        .saturating_mul(action.times)
+        .saturating_mul(starting_selections.len().max(1));
+    if step_count > MAX_REPLAY_STEPS {
+        show_error(
+            editor,
+            anyhow!(
+                "refusing to replay a macro with {step_count} steps, the maximum is {MAX_REPLAY_STEPS}"
+            ),
+            cx,
+        );
+        return;
+    }
+
+    let repeated_steps = || {
+        iter::repeat_n(steps.iter(), action.times)
+            .flatten()
+            .map(|step| ReplayStep::Macro(step.clone()))
+    };
+    let replay_steps: VecDeque<_> = match action.mode {
+        ReplayMode::Once => repeated_steps().collect(),
+        ReplayMode::EachSelection | ReplayMode::EachLine => starting_selections
+            .into_iter()
+            .flat_map(|range| {
+                iter::once(ReplayStep::Select(range))
+                    .chain(repeated_steps())
+                    .chain(iter::once(ReplayStep::CollectSelections))
+            })
+            .chain(iter::once(ReplayStep::RestoreSelections))
+            .collect(),
+    };
+
+    let replaying = Rc::new(());
+    cx.global_mut::<EditorMacros>().replaying = Rc::downgrade(&replaying);
+    let first_transaction = Rc::new(Cell::new(None));
+    let subscription = cx.subscribe(editor, {
+        let first_transaction = first_transaction.clone();
+        move |_, event: &EditorEvent, _| {
+            if let EditorEvent::TransactionBegun { transaction_id } = event
+                && first_transaction.get().is_none()
+            {
+                first_transaction.set(Some(*transaction_id));
+            }
+        }
+    });
+    let replayer = Replayer {
+        editor: editor.downgrade(),
+        steps: replay_steps,
+        collected_selections: Vec::new(),
+        first_transaction,
+        _subscription: subscription,
+        _replaying: replaying,
+    };
+    window.defer(cx, move |window, cx| replayer.next(window, cx));
+}
+
+/// Returns the selections that a macro starts with when it's replayed separately for each of
+/// them.
+fn starting_selections(
+    editor: &mut Editor,
+    mode: ReplayMode,
+    cx: &mut Context<Editor>,
+) -> Vec<Range<Anchor>> {
+    let display_snapshot = editor.display_snapshot(cx);
+    match mode {
+        ReplayMode::Once => Vec::new(),
+        ReplayMode::EachSelection => editor
+            .selections
+            .all_anchors(&display_snapshot)
+            .iter()
+            .map(|selection| selection.tail()..selection.head())
+            .collect(),
+        ReplayMode::EachLine => {
+            let mut rows = BTreeSet::default();
+            for selection in editor.selections.all::<Point>(&display_snapshot) {
+                let mut end_row = selection.end.row;
+                if end_row > selection.start.row && selection.end.column == 0 {
+                    end_row -= 1;
+                }
+                rows.extend(selection.start.row..=end_row);
+            }
+            let buffer = display_snapshot.buffer_snapshot();
+            rows.into_iter()
+                .map(|row| {
+                    let anchor = buffer.anchor_before(Point::new(row, 0));
+                    anchor..anchor
+                })
+                .collect()
+        }
+    }
+}
+
+fn show_error(editor: &Entity<Editor>, error: anyhow::Error, cx: &mut App) {
+    if let Some(workspace) = editor.read(cx).workspace() {
+        workspace.update(cx, |workspace, cx| workspace.show_error(&error, cx));
+    } else {
+        log::error!("{error:#}");
+    }
+}
+
+enum ReplayStep {
+    Macro(MacroStep),
+    /// Replaces the selections with the given one.
+    Select(Range<Anchor>),
+    /// Remembers the current selections, to restore them once the replay is finished.
+    CollectSelections,
+    RestoreSelections,
+}
+
+struct Replayer {
+    editor: WeakEntity<Editor>,
+    steps: VecDeque<ReplayStep>,
+    collected_selections: Vec<Range<Anchor>>,
+    first_transaction: Rc<Cell<Option<TransactionId>>>,
+    _subscription: Subscription,
+    _replaying: Rc<()>,
+}
+
+impl Replayer {
+    fn next(mut self, window: &mut Window, cx: &mut App) {
+        let Some(editor) = self.editor.upgrade() else {
+            return;
+        };
+        let Some(step) = self.steps.pop_front() else {
+            // Group the edits of the whole replay, so that they're undone at once.
+            editor.update(cx, |editor, cx| {
+                if let Some(transaction_id) = self.first_transaction.get() {
+                    editor.group_until_transaction(transaction_id, cx);
+                }
+                editor.finalize_last_transaction(cx);
+            });
+            return;
+        };
+
+        match step {
+            ReplayStep::Macro(MacroStep::Action { action, .. }) => {
+                if !is_macro_action(action.as_ref()) {
+                    editor
+                        .focus_handle(cx)
+                        .dispatch_action(action.as_ref(), window, cx);
+                }
+            }
+            ReplayStep::Macro(MacroStep::Text {
+                text,
+                utf16_range_to_replace,
+            }) => editor.update(cx, |editor, cx| {
+                editor.replay_insert_event(&text, utf16_range_to_replace, window, cx)
+            }),
+            ReplayStep::Select(range) => editor.update(cx, |editor, cx| {
+                editor.change_selections(SelectionEffects::default(), window, cx, |selections| {
+                    selections.select_anchor_ranges([range])
+                })
+            }),
+            ReplayStep::CollectSelections => {
+                let display_snapshot = editor.update(cx, |editor, cx| editor.display_snapshot(cx));
+                self.collected_selections.extend(
+                    editor
+                        .read(cx)
+                        .selections
+                        .all_anchors(&display_snapshot)
+                        .iter()
+                        .map(|selection| selection.tail()..selection.head()),
+                );
+            }
+            ReplayStep::RestoreSelections => {
+                let selections = std::mem::take(&mut self.collected_selections);
+                if !selections.is_empty() {
+                    editor.update(cx, |editor, cx| {
+                        editor.change_selections(SelectionEffects::default(), window, cx, |s| {
+                            s.select_anchor_ranges(selections)
+                        })
+                    });
+                }
+            }
+        }
+        window.defer(cx, move |window, cx| self.next(window, cx));
+    }
+}
+
+fn save_macro(editor: &Entity<Editor>, window: &mut Window, cx: &mut App) {
+    let Some(workspace) = editor.read(cx).workspace() else {
+        return;
+    };
+    let content = cx
+        .global::<EditorMacros>()
+        .last_macro
+        .as_deref()
+        .context("no macro has been recorded")
+        .and_then(macro_to_content);
+    let content = match content {
+        Ok(content) => content,
+        Err(error) => {
+            show_error(editor, error, cx);
+            return;
+        }
+    };
+    let fs = workspace.read(cx).app_state().fs.clone();
+    workspace.update(cx, |workspace, cx| {
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            SaveMacroModal::new(content, fs, window, cx)
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::{
+        actions::{MoveDown, MoveLeft},
+        test::editor_test_context::EditorTestContext,
+    };
+    use gpui::{KeyBinding, TestAppContext};
+    use indoc::indoc;
+    use settings::SettingsStore;
+    use workspace::AppState;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            AppState::test(cx);
+            editor::init(cx);
+            crate::init(cx);
+            cx.bind_keys([
+                KeyBinding::new("ctrl-r", ToggleRecording, Some("Editor")),
+                KeyBinding::new("left", MoveLeft, Some("Editor")),
+                KeyBinding::new("down", MoveDown, Some("Editor")),
+            ]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_record_and_replay(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = EditorTestContext::new(cx).await;
+
+        cx.set_state(indoc! {"
+            ˇa
+            b
+            c
+            d"});
+        cx.simulate_keystrokes("ctrl-r - space left left down ctrl-r");
+        cx.assert_editor_state(indoc! {"
+            - a
+            ˇb
+            c
+            d"});
+
+        cx.dispatch_action(Replay {
+            times: 2,
+            ..Replay::default()
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            - a
+            - b
+            - c
+            ˇd"});
+
+        // The edits of a replay are undone at once.
+        cx.update_editor(|editor, window, cx| editor.undo(&Default::default(), window, cx));
+        cx.assert_editor_state(indoc! {"
+            - a
+            ˇb
+            c
+            d"});
+    }
+
+    #[gpui::test]
+    async fn test_record_dispatched_actions(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = EditorTestContext::new(cx).await;
+
+        cx.set_state(indoc! {"
+            ˇa
+            b
+            c"});
+        // Actions dispatched without a keystroke, like the ones run from the command palette,
+        // are recorded too.
+        cx.dispatch_action(ToggleRecording);
+        cx.simulate_keystrokes("- space");
+        cx.dispatch_action(MoveLeft);
+        cx.dispatch_action(MoveLeft);
+        cx.dispatch_action(MoveDown);
+        cx.dispatch_action(ToggleRecording);
+        cx.assert_editor_state(indoc! {"
+            - a
+            ˇb
+            c"});
+
+        cx.dispatch_action(Replay::default());
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            - a
+            - b
+            ˇc"});
+    }
+
+    #[gpui::test]
+    async fn test_replay_for_each_selection_and_line(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = EditorTestContext::new(cx).await;
+
+        cx.set_state("ˇa");
+        cx.simulate_keystrokes("ctrl-r - space ctrl-r");
+        cx.assert_editor_state("- ˇa");
+
+        cx.set_state(indoc! {"
+            a
+            ˇb
+            c ˇd
+            e"});
+        cx.dispatch_action(Replay {
+            mode: ReplayMode::EachSelection,
+            ..Replay::default()
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            a
+            - ˇb
+            c - ˇd
+            e"});
+
+        cx.set_state(indoc! {"
+            a
+            «b
+            cˇ»
+            d"});
+        cx.dispatch_action(Replay {
+            mode: ReplayMode::EachLine,
+            ..Replay::default()
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            a
+            - ˇb
+            - ˇc
+            d"});
+    }
+
+    #[gpui::test]
+    async fn test_replay_macro_from_settings(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = EditorTestContext::new(cx).await;
+        cx.update(|_, cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings.macros = Some(HashMap::from_iter([(
+                        "bullet".to_string(),
+                        vec![
+                            MacroStepContent::Text {
+                                text: "- ".to_string(),
+                            },
+                            MacroStepContent::Action {
+                                action: "editor::MoveDown".into(),
+                            },
+                        ],
+                    )]));
+                });
+            });
+        });
+
+        cx.set_state(indoc! {"
+            ˇa
+            b
+            c"});
+        cx.dispatch_action(Replay {
+            name: Some("bullet".to_string()),
+            times: 2,
+            ..Replay::default()
+        });
+        cx.run_until_parked();
+        cx.assert_editor_state(indoc! {"
+            - a
+            - b
+            ˇc"});
+    }
+
+    #[gpui::test]
+    async fn test_recorded_macro_content(cx: &mut TestAppContext) {
+        init_test(cx);
+        let mut cx = EditorTestContext::new(cx).await;
+
+        cx.set_state("ˇa");
+        cx.simulate_keystrokes("ctrl-r - space down ctrl-r");
+        let content = cx.update(|_, cx| {
+            macro_to_content(cx.global::<EditorMacros>().last_macro.as_deref().unwrap()).unwrap()
+        });
+        assert_eq!(
+            content,
+            [
+                MacroStepContent::Text {
+                    text: "- ".to_string(),
+                },
+                MacroStepContent::Action {
+                    action: "editor::MoveDown".into(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::MacroSettings;
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    App, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription, div,
+    prelude::*,
+};
+use settings::{MacroStepContent, Settings as _, update_settings_file};
+use std::sync::Arc;
+use ui::prelude::*;
+use workspace::ModalView;
+
+/// Asks for the name under which the last recorded macro is saved to the settings.
+pub struct SaveMacroModal {
+    name_editor: Entity<Editor>,
+    steps: Vec<MacroStepContent>,
+    fs: Arc<dyn Fs>,
+    _subscription: Subscription,
+}
+
+impl ModalView for SaveMacroModal {}
+
+impl Focusable for SaveMacroModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for SaveMacroModal {}
+
+impl SaveMacroModal {
+    pub fn new(
+        steps: Vec<MacroStepContent>,
+        fs: Arc<dyn Fs>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Macro name", window, cx);
+            editor
+        });
+        let subscription =
+            cx.subscribe(&name_editor, |_, _, event: &EditorEvent, cx| match event {
+                EditorEvent::Blurred => cx.emit(DismissEvent),
+                EditorEvent::BufferEdited => cx.notify(),
+                _ => {}
+            });
+
+        Self {
+            name_editor,
+            steps,
+            fs,
+            _subscription: subscription,
+        }
+    }
+
+    fn name(&self, cx: &App) -> String {
+        self.name_editor.read(cx).text(cx).trim().to_string()
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name(cx);
+        if name.is_empty() {
+            return;
+        }
+
+        let steps = self.steps.clone();
+        update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            settings.macros.get_or_insert_default().insert(name, steps);
+        });
+        cx.emit(DismissEvent);
+    }
+}
+
+impl Render for SaveMacroModal {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let name = self.name(cx);
+        let step_count = self.steps.len();
+        let help_text: SharedString = if name.is_empty() {
+            match step_count {
+                1 => "Save the last macro, with 1 step, to the settings".into(),
+                _ => {
+                    format!("Save the last macro, with {step_count} steps, to the settings").into()
+                }
+            }
+        } else if MacroSettings::get_global(cx).macros.contains_key(&name) {
+            format!("Replace the macro \"{name}\" in the settings").into()
+        } else {
+            format!("Save the macro as \"{name}\"").into()
+        };
+
+        v_flex()
+            .w(rems(24.))
+            .elevation_2(cx)
+            .key_context("SaveMacroModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                div()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .px_2()
+                    .py_1()
+                    .child(self.name_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .child(Label::new(help_text).color(Color::Muted)),
+            )
+    }
+}
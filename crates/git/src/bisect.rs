@@ -0,0 +1,225 @@
+//! State of `git bisect` sessions.
+
+use gpui::SharedString;
+
+/// File of the git directory that exists while bisecting, naming what to check out on reset.
+pub const BISECT_START: &str = "BISECT_START";
+/// File of the git directory recording the commits marked while bisecting.
+pub const BISECT_LOG: &str = "BISECT_LOG";
+
+/// How a commit is marked while bisecting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BisectMark {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectMark {
+    /// The `git bisect` subcommand marking a commit.
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Good => "Good",
+            Self::Bad => "Bad",
+            Self::Skip => "Skip",
+        }
+    }
+
+    /// Marks a commit from the exit code of the command testing it, like `git bisect run`.
+    /// Returns `None` for the codes that mean the test itself failed.
+    pub fn from_exit_code(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(Self::Good),
+            125 => Some(Self::Skip),
+            1..=127 => Some(Self::Bad),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BisectCommit {
+    pub sha: SharedString,
+    pub subject: SharedString,
+}
+
+impl BisectCommit {
+    /// Parses a commit formatted with `%H%x00%s`.
+    pub fn from_log_line(line: &str) -> Option<Self> {
+        let (sha, subject) = line.split_once('\0')?;
+        Some(Self {
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+        })
+    }
+}
+
+/// A commit marked while bisecting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BisectLogEntry {
+    pub mark: BisectMark,
+    pub commit: BisectCommit,
+}
+
+/// A bisect session in progress.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BisectState {
+    /// The branch or commit checked out before bisecting, which resetting goes back to.
+    pub start: SharedString,
+    /// The commits marked so far, in order.
+    pub log: Vec<BisectLogEntry>,
+    /// The commits that may still be the first bad one, from the newest to the oldest. Empty
+    /// until both a good and a bad commit are known.
+    pub candidates: Vec<BisectCommit>,
+    /// The commit checked out to be tested.
+    pub current: Option<BisectCommit>,
+    /// The first bad commit, once it was found.
+    pub first_bad: Option<BisectCommit>,
+}
+
+impl BisectState {
+    /// Estimates how many commits are left to test, the way git does.
+    pub fn steps_left(&self) -> u32 {
+        match self.candidates.len() {
+            0 | 1 => 0,
+            count => usize::BITS - (count - 1).leading_zeros(),
+        }
+    }
+
+    /// How the commit was marked, if it was.
+    pub fn mark_of(&self, sha: &str) -> Option<BisectMark> {
+        self.log
+            .iter()
+            .rev()
+            .find(|entry| &*entry.commit.sha == sha)
+            .map(|entry| entry.mark)
+    }
+}
+
+/// Parses the marked commits, and the first bad commit once found, from a bisect log.
+pub fn parse_bisect_log(text: &str) -> (Vec<BisectLogEntry>, Option<BisectCommit>) {
+    let mut log = Vec::new();
+    let mut first_bad = None;
+    // Git comments each command with the commit it marked and its subject.
+    for line in text.lines() {
+        let Some((kind, commit)) = line
+            .strip_prefix("# ")
+            .and_then(|line| line.split_once(": "))
+        else {
+            continue;
+        };
+        let Some(commit) = parse_commit(commit) else {
+            continue;
+        };
+        let mark = match kind {
+            "good" => BisectMark::Good,
+            "bad" => BisectMark::Bad,
+            "skip" => BisectMark::Skip,
+            "first bad commit" => {
+                first_bad = Some(commit);
+                continue;
+            }
+            _ => continue,
+        };
+        log.push(BisectLogEntry { mark, commit });
+    }
+    (log, first_bad)
+}
+
+/// Parses a commit formatted as `[<sha>] <subject>`.
+fn parse_commit(text: &str) -> Option<BisectCommit> {
+    let (sha, subject) = text.strip_prefix('[')?.split_once(']')?;
+    Some(BisectCommit {
+        sha: sha.to_string().into(),
+        subject: subject.trim().to_string().into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn commit(sha: &str, subject: &str) -> BisectCommit {
+        BisectCommit {
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_bisect_log() {
+        let text = "\
+# bad: [99c44c7] Release 2.0
+# good: [4de9fcd] Release 1.0
+git bisect start 'HEAD' 'v1.0'
+# skip: [d0652bd] Flaky test
+git bisect skip d0652bd
+# good: [fb1d95c] Add the parser
+git bisect good fb1d95c
+# bad: [8ecbd33] Speed up the parser: cache tokens
+git bisect bad 8ecbd33
+# first bad commit: [8ecbd33] Speed up the parser: cache tokens
+";
+        let (log, first_bad) = parse_bisect_log(text);
+        let marks = log
+            .iter()
+            .map(|entry| (entry.mark, &*entry.commit.sha))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            marks,
+            [
+                (BisectMark::Bad, "99c44c7"),
+                (BisectMark::Good, "4de9fcd"),
+                (BisectMark::Skip, "d0652bd"),
+                (BisectMark::Good, "fb1d95c"),
+                (BisectMark::Bad, "8ecbd33"),
+            ]
+        );
+        assert_eq!(
+            first_bad,
+            Some(commit("8ecbd33", "Speed up the parser: cache tokens"))
+        );
+
+        let (log, first_bad) = parse_bisect_log("git bisect start\n# status: waiting\n");
+        assert!(log.is_empty());
+        assert_eq!(first_bad, None);
+    }
+
+    #[test]
+    fn test_steps_left() {
+        let state = |candidates: usize| BisectState {
+            start: "main".into(),
+            log: Vec::new(),
+            candidates: (0..candidates)
+                .map(|ix| commit(&ix.to_string(), ""))
+                .collect(),
+            current: None,
+            first_bad: None,
+        };
+        assert_eq!(state(0).steps_left(), 0);
+        assert_eq!(state(1).steps_left(), 0);
+        assert_eq!(state(2).steps_left(), 1);
+        assert_eq!(state(4).steps_left(), 2);
+        assert_eq!(state(5).steps_left(), 3);
+        assert_eq!(state(1000).steps_left(), 10);
+    }
+
+    #[test]
+    fn test_mark_from_exit_code() {
+        assert_eq!(BisectMark::from_exit_code(0), Some(BisectMark::Good));
+        assert_eq!(BisectMark::from_exit_code(1), Some(BisectMark::Bad));
+        assert_eq!(BisectMark::from_exit_code(125), Some(BisectMark::Skip));
+        assert_eq!(BisectMark::from_exit_code(127), Some(BisectMark::Bad));
+        assert_eq!(BisectMark::from_exit_code(128), None);
+        assert_eq!(BisectMark::from_exit_code(-1), None);
+    }
+}
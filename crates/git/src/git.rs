@@ -1,6 +1,8 @@
+pub mod bisect;
 pub mod blame;
 pub mod commit;
 mod hosting_provider;
+pub mod rebase;
 mod remote;
 pub mod repository;
 pub mod stash;
@@ -101,6 +103,10 @@ actions!(
         Clone,
         /// Adds a file to .gitignore.
         AddToGitignore,
+        /// Opens the bisect view, to find the commit that introduced a regression.
+        Bisect,
+        /// Shows the changes to the active Jupyter notebook cell by cell.
+        DiffNotebook,
     ]
 );
 
@@ -116,6 +122,18 @@ pub struct RenameBranch {
     pub branch: Option<String>,
 }
 
+/// Interactively rebases the commits of the current branch, choosing what to do with each.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
+#[action(namespace = git)]
+#[serde(deny_unknown_fields)]
+pub struct InteractiveRebase {
+    /// The branch or commit to rebase onto.
+    ///
+    /// Default: the upstream of the current branch, or the default branch.
+    #[serde(default)]
+    pub onto: Option<String>,
+}
+
 /// Restores a file to its last committed state, discarding local changes.
 #[derive(Clone, Debug, Default, PartialEq, Deserialize, JsonSchema, Action)]
 #[action(namespace = git, deprecated_aliases = ["editor::RevertFile"])]
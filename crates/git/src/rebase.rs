@@ -0,0 +1,347 @@
+//! Todo lists of interactive rebases, and the state of rebases in progress.
+
+use anyhow::{Context as _, Result};
+use gpui::SharedString;
+use std::path::{Path, PathBuf};
+
+/// Directory of the git directory holding the state of a rebase in progress.
+pub const REBASE_MERGE_DIR: &str = "rebase-merge";
+/// Directory of the git directory holding the todo list and commit messages of an interactive
+/// rebase started by Zed.
+pub const ZED_REBASE_DIR: &str = "zed-rebase";
+
+/// What an interactive rebase does with a commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RebaseAction {
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [Self; 6] = [
+        Self::Pick,
+        Self::Reword,
+        Self::Edit,
+        Self::Squash,
+        Self::Fixup,
+        Self::Drop,
+    ];
+
+    /// The command of the action in a todo list.
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Edit => "edit",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Parses the command of a todo list, which may be abbreviated.
+    pub fn from_command(command: &str) -> Option<Self> {
+        Some(match command {
+            "pick" | "p" => Self::Pick,
+            "reword" | "r" => Self::Reword,
+            "edit" | "e" => Self::Edit,
+            "squash" | "s" => Self::Squash,
+            "fixup" | "f" => Self::Fixup,
+            "drop" | "d" => Self::Drop,
+            _ => return None,
+        })
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pick => "Pick",
+            Self::Reword => "Reword",
+            Self::Edit => "Edit",
+            Self::Squash => "Squash",
+            Self::Fixup => "Fixup",
+            Self::Drop => "Drop",
+        }
+    }
+
+    /// Whether the commit is melded into the one before it.
+    pub fn melds_into_previous(self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+/// A commit of the todo list of an interactive rebase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: SharedString,
+    pub subject: SharedString,
+    /// The new message of a reworded commit. Reworded commits without one keep their message.
+    pub message: Option<String>,
+}
+
+/// A rebase in progress.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RebaseState {
+    /// The branch being rebased, unless HEAD was detached.
+    pub head_name: Option<SharedString>,
+    /// The commit being rebased onto.
+    pub onto: SharedString,
+    /// The entries of the todo list that were already applied.
+    pub done: Vec<RebaseTodoEntry>,
+    /// The entries of the todo list that are left to apply.
+    pub todo: Vec<RebaseTodoEntry>,
+    /// The commit the rebase stopped at, to be edited or to have its conflicts resolved.
+    pub stopped_sha: Option<SharedString>,
+}
+
+/// How to go on with a rebase in progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebaseCommand {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseCommand {
+    pub fn flag(self) -> &'static str {
+        match self {
+            Self::Continue => "--continue",
+            Self::Skip => "--skip",
+            Self::Abort => "--abort",
+        }
+    }
+}
+
+/// Parses a todo list, skipping comments and the commands that don't apply a commit.
+pub fn parse_todo(text: &str) -> Vec<RebaseTodoEntry> {
+    text.lines().filter_map(parse_todo_line).collect()
+}
+
+fn parse_todo_line(line: &str) -> Option<RebaseTodoEntry> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    let (command, rest) = line.split_once(char::is_whitespace)?;
+    let action = RebaseAction::from_command(command)?;
+    let mut rest = rest.trim_start();
+    if action == RebaseAction::Fixup
+        && let Some(after_flag) = rest
+            .strip_prefix("-C ")
+            .or_else(|| rest.strip_prefix("-c "))
+    {
+        rest = after_flag.trim_start();
+    }
+    let (sha, subject) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if sha.is_empty() {
+        return None;
+    }
+    // Recent versions of git separate the subject from the commit with a comment marker.
+    let subject = subject.trim();
+    let subject = subject.strip_prefix("# ").unwrap_or(subject);
+    Some(RebaseTodoEntry {
+        action,
+        sha: sha.to_string().into(),
+        subject: subject.to_string().into(),
+        message: None,
+    })
+}
+
+/// Formats the todo list given to git. Reworded commits with a new message are picked, then
+/// amended with the message stored in `message_file(ix)` by `git_binary`, so that the rebase
+/// doesn't need an editor.
+pub fn format_todo(
+    entries: &[RebaseTodoEntry],
+    git_binary: &Path,
+    message_file: impl Fn(usize) -> PathBuf,
+) -> String {
+    let mut text = String::new();
+    for (ix, entry) in entries.iter().enumerate() {
+        let reworded = entry.action == RebaseAction::Reword && entry.message.is_some();
+        let command = if reworded {
+            RebaseAction::Pick.command()
+        } else {
+            entry.action.command()
+        };
+        text.push_str(&format!("{command} {} {}\n", entry.sha, entry.subject));
+        if reworded {
+            text.push_str(&format!(
+                "exec {} commit --amend --only --no-verify --file {}\n",
+                shell_quote(&git_binary.to_string_lossy()),
+                shell_quote(&message_file(ix).to_string_lossy()),
+            ));
+        }
+    }
+    text
+}
+
+/// Quotes an argument of the shell git runs `exec` commands and editors with.
+pub fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// Reads the state of the rebase in progress in the given git directory, if any.
+/// Removes the files written for an interactive rebase, once it has finished or was aborted.
+pub fn remove_zed_rebase_files(git_dir: &Path) -> Result<()> {
+    if git_dir.join(REBASE_MERGE_DIR).exists() {
+        return Ok(());
+    }
+    match std::fs::remove_dir_all(git_dir.join(ZED_REBASE_DIR)) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error).with_context(|| format!("removing {ZED_REBASE_DIR}")),
+    }
+}
+
+pub fn read_rebase_state(git_dir: &Path) -> Result<Option<RebaseState>> {
+    let dir = git_dir.join(REBASE_MERGE_DIR);
+    let read = |name: &str| -> Result<Option<String>> {
+        match std::fs::read_to_string(dir.join(name)) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error).with_context(|| format!("reading {REBASE_MERGE_DIR}/{name}")),
+        }
+    };
+
+    // Git writes the commit being rebased onto first, so a rebase that's only being set up
+    // isn't reported.
+    let Some(onto) = read("onto")? else {
+        return Ok(None);
+    };
+    let head_name = read("head-name")?
+        .map(|name| name.trim().to_string())
+        .filter(|name| name != "detached HEAD")
+        .map(|name| {
+            name.strip_prefix("refs/heads/")
+                .map(ToString::to_string)
+                .unwrap_or(name)
+                .into()
+        });
+    Ok(Some(RebaseState {
+        head_name,
+        onto: onto.trim().to_string().into(),
+        done: parse_todo(&read("done")?.unwrap_or_default()),
+        todo: parse_todo(&read("git-rebase-todo")?.unwrap_or_default()),
+        stopped_sha: read("stopped-sha")?
+            .map(|sha| sha.trim().to_string())
+            .filter(|sha| !sha.is_empty())
+            .map(Into::into),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(action: RebaseAction, sha: &str, subject: &str) -> RebaseTodoEntry {
+        RebaseTodoEntry {
+            action,
+            sha: sha.to_string().into(),
+            subject: subject.to_string().into(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_todo() {
+        let text = "\
+pick 1a2b3c4 Add the parser
+r 2b3c4d5 Fix a typo
+exec cargo test
+f -C 3c4d5e6 Tidy up
+squash 4d5e6f7 # Merge the tests
+break
+
+# Rebase 0f1e2d3..4d5e6f7 onto 0f1e2d3 (4 commands)
+#
+# Commands:
+# p, pick <commit> = use commit
+";
+        assert_eq!(
+            parse_todo(text),
+            vec![
+                entry(RebaseAction::Pick, "1a2b3c4", "Add the parser"),
+                entry(RebaseAction::Reword, "2b3c4d5", "Fix a typo"),
+                entry(RebaseAction::Fixup, "3c4d5e6", "Tidy up"),
+                entry(RebaseAction::Squash, "4d5e6f7", "Merge the tests"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_todo() {
+        let mut reworded = entry(RebaseAction::Reword, "2b3c4d5", "Fix a typo");
+        reworded.message = Some("Fix a typo in the docs".into());
+        let entries = vec![
+            entry(RebaseAction::Pick, "1a2b3c4", "Add the parser"),
+            reworded,
+            entry(RebaseAction::Reword, "3c4d5e6", "Keep this message"),
+            entry(RebaseAction::Drop, "4d5e6f7", "Debug logging"),
+        ];
+        let text = format_todo(&entries, Path::new("/usr/bin/git"), |ix| {
+            PathBuf::from(format!("/repo/.git/message-{ix}"))
+        });
+        assert_eq!(
+            text,
+            "\
+pick 1a2b3c4 Add the parser
+pick 2b3c4d5 Fix a typo
+exec '/usr/bin/git' commit --amend --only --no-verify --file '/repo/.git/message-1'
+reword 3c4d5e6 Keep this message
+drop 4d5e6f7 Debug logging
+"
+        );
+
+        let mut parsed = parse_todo(&text);
+        parsed[1].action = RebaseAction::Reword;
+        parsed[1].message = entries[1].message.clone();
+        assert_eq!(parsed, entries);
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/tmp/a b"), "'/tmp/a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_read_rebase_state() {
+        let git_dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_rebase_state(git_dir.path()).unwrap(), None);
+
+        let dir = git_dir.path().join(REBASE_MERGE_DIR);
+        std::fs::create_dir(&dir).unwrap();
+        assert_eq!(read_rebase_state(git_dir.path()).unwrap(), None);
+
+        std::fs::write(dir.join("onto"), "0f1e2d3c\n").unwrap();
+        std::fs::write(dir.join("head-name"), "refs/heads/feature\n").unwrap();
+        std::fs::write(dir.join("done"), "pick 1a2b3c4 Add the parser\n").unwrap();
+        std::fs::write(
+            dir.join("git-rebase-todo"),
+            "fixup 2b3c4d5 Fix a typo\n\n# Commands:\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("stopped-sha"), "1a2b3c4\n").unwrap();
+        assert_eq!(
+            read_rebase_state(git_dir.path()).unwrap(),
+            Some(RebaseState {
+                head_name: Some("feature".into()),
+                onto: "0f1e2d3c".into(),
+                done: vec![entry(RebaseAction::Pick, "1a2b3c4", "Add the parser")],
+                todo: vec![entry(RebaseAction::Fixup, "2b3c4d5", "Fix a typo")],
+                stopped_sha: Some("1a2b3c4".into()),
+            })
+        );
+
+        std::fs::write(dir.join("head-name"), "detached HEAD\n").unwrap();
+        std::fs::remove_file(dir.join("stopped-sha")).unwrap();
+        let state = read_rebase_state(git_dir.path()).unwrap().unwrap();
+        assert_eq!(state.head_name, None);
+        assert_eq!(state.stopped_sha, None);
+    }
+}
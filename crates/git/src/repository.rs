@@ -1,4 +1,8 @@
+use crate::bisect::{self, BisectCommit, BisectMark, BisectState};
 use crate::commit::parse_git_diff_name_status;
+use crate::rebase::{
+    self, RebaseAction, RebaseCommand, RebaseState, RebaseTodoEntry, format_todo, shell_quote,
+};
 use crate::stash::GitStash;
 use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
 use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
@@ -870,6 +874,62 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Returns the todo list of an interactive rebase of HEAD onto `onto`, picking every commit.
+    fn rebase_todo(&self, onto: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>>;
+
+    /// Starts an interactive rebase of HEAD onto `onto`, following the given todo list
+    /// instead of asking for one.
+    fn rebase_interactive(
+        &self,
+        onto: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Continues, skips the current commit of, or aborts the rebase in progress.
+    fn rebase_command(
+        &self,
+        command: RebaseCommand,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_state(&self) -> BoxFuture<'_, Result<Option<RebaseState>>>;
+
+    /// Applies the changes of the given commits or commit ranges onto HEAD, committing each.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Reverts the changes of the given commits or commit ranges, committing each revert.
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Starts bisecting between a bad commit and the good ones, checking out a commit to test.
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks a commit of the bisect session, HEAD by default, and checks out the next one.
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect session, checking out what was checked out before it started.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    fn bisect_state(&self) -> BoxFuture<'_, Result<Option<BisectState>>>;
+
     fn push(
         &self,
         branch_name: String,
@@ -1036,6 +1096,67 @@ impl RealGitRepository {
         *self.any_git_binary_help_output.lock() = Some(output.clone());
         output
     }
+
+    fn run_bisect_command(
+        &self,
+        args: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_command(git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .arg("bisect")
+                    .args(&args);
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git bisect {}:\n{}{}",
+                    args[0],
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    /// Runs `git cherry-pick` or `git revert` with the given commits. When a commit conflicts,
+    /// git stops and leaves the conflicts to be resolved, which fails the command.
+    fn apply_commits(
+        &self,
+        command: &'static [&'static str],
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(!commits.is_empty(), "No commits given");
+                let mut cmd = new_command(git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .args(command)
+                    .arg("--")
+                    .args(&commits);
+                let output = cmd.output().await?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git {}:\n{}{}",
+                    command[0],
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -2295,6 +2416,234 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn rebase_todo(&self, onto: String) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let git = GitBinary::new(git_binary_path, working_directory?, executor);
+                let output = git
+                    .run(&[
+                        "log".to_string(),
+                        "--reverse".to_string(),
+                        "--topo-order".to_string(),
+                        "--no-merges".to_string(),
+                        // Like git, leave out the commits already applied to `onto`.
+                        "--right-only".to_string(),
+                        "--cherry-pick".to_string(),
+                        "--format=%H%x00%s".to_string(),
+                        format!("{onto}...HEAD"),
+                    ])
+                    .await?;
+                Ok(output
+                    .lines()
+                    .filter_map(|line| {
+                        let (sha, subject) = line.split_once('\0')?;
+                        Some(RebaseTodoEntry {
+                            action: RebaseAction::Pick,
+                            sha: sha.to_string().into(),
+                            subject: subject.to_string().into(),
+                            message: None,
+                        })
+                    })
+                    .collect())
+            })
+            .boxed()
+    }
+
+    fn rebase_interactive(
+        &self,
+        onto: String,
+        todo: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let git_dir = self.path();
+        let state_dir = git_dir.join(rebase::ZED_REBASE_DIR);
+        self.executor
+            .spawn(async move {
+                anyhow::ensure!(!todo.is_empty(), "Nothing to rebase");
+                smol::fs::create_dir_all(&state_dir).await?;
+                let message_file = |ix: usize| state_dir.join(format!("message-{ix}"));
+                for (ix, entry) in todo.iter().enumerate() {
+                    if let Some(message) = &entry.message {
+                        smol::fs::write(message_file(ix), message).await?;
+                    }
+                }
+                let todo_file = state_dir.join("git-rebase-todo");
+                smol::fs::write(
+                    &todo_file,
+                    format_todo(&todo, &git_binary_path, message_file),
+                )
+                .await?;
+
+                let mut cmd = new_command(&git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env(
+                        "GIT_SEQUENCE_EDITOR",
+                        format!("cp {}", shell_quote(&todo_file.to_string_lossy())),
+                    )
+                    .env("GIT_EDITOR", "true")
+                    .args(["rebase", "--interactive", &onto]);
+                let output = cmd.output().await;
+                // The files are still needed if the rebase stopped for a conflict or an edit.
+                rebase::remove_zed_rebase_files(&git_dir).log_err();
+                let output = output?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to rebase:\n{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_command(
+        &self,
+        command: RebaseCommand,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git_dir = self.path();
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        self.executor
+            .spawn(async move {
+                let mut cmd = new_command(git_binary_path);
+                cmd.current_dir(&working_directory?)
+                    .envs(env.iter())
+                    .env("GIT_EDITOR", "true")
+                    .args(["rebase", command.flag()]);
+                let output = cmd.output().await;
+                rebase::remove_zed_rebase_files(&git_dir).log_err();
+                let output = output?;
+
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to run git rebase {}:\n{}{}",
+                    command.flag(),
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                Ok(())
+            })
+            .boxed()
+    }
+
+    fn rebase_state(&self) -> BoxFuture<'_, Result<Option<RebaseState>>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { rebase::read_rebase_state(&git_dir) })
+            .boxed()
+    }
+
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commits(&["cherry-pick"], commits, env)
+    }
+
+    fn revert(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.apply_commits(&["revert", "--no-edit"], commits, env)
+    }
+
+    fn bisect_start(
+        &self,
+        bad: String,
+        good: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec!["start".to_string(), bad];
+        args.extend(good);
+        // Anything after the commits would limit bisecting to the commits changing those paths.
+        args.push("--".to_string());
+        self.run_bisect_command(args, env)
+    }
+
+    fn bisect_mark(
+        &self,
+        mark: BisectMark,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let mut args = vec![mark.command().to_string()];
+        args.extend(commit);
+        self.run_bisect_command(args, env)
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.run_bisect_command(vec!["reset".to_string()], env)
+    }
+
+    fn bisect_state(&self) -> BoxFuture<'_, Result<Option<BisectState>>> {
+        let git_dir = self.path();
+        let working_directory = self.working_directory();
+        let git_binary_path = self.any_git_binary_path.clone();
+        let executor = self.executor.clone();
+        self.executor
+            .spawn(async move {
+                let start_path = git_dir.join(bisect::BISECT_START);
+                let start = match smol::fs::read_to_string(&start_path).await {
+                    Ok(start) => start,
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(error) => return Err(error).context("reading BISECT_START"),
+                };
+                let log = smol::fs::read_to_string(git_dir.join(bisect::BISECT_LOG))
+                    .await
+                    .unwrap_or_default();
+                let (log, first_bad) = bisect::parse_bisect_log(&log);
+
+                let git = GitBinary::new(git_binary_path, working_directory?, executor);
+                let current = git
+                    .run(["log", "-1", "--format=%H%x00%s", "HEAD"])
+                    .await
+                    .ok()
+                    .and_then(|line| BisectCommit::from_log_line(&line));
+                let refs = git
+                    .run(["for-each-ref", "--format=%(refname)", "refs/bisect/"])
+                    .await?;
+                let has_bad = refs.lines().any(|name| name == "refs/bisect/bad");
+                let has_good = refs
+                    .lines()
+                    .any(|name| name.starts_with("refs/bisect/good-"));
+                let candidates = if has_bad && has_good {
+                    git.run([
+                        "log",
+                        "--format=%H%x00%s",
+                        "refs/bisect/bad",
+                        "--not",
+                        "--glob=refs/bisect/good-*",
+                    ])
+                    .await?
+                    .lines()
+                    .filter_map(BisectCommit::from_log_line)
+                    .collect()
+                } else {
+                    Vec::new()
+                };
+
+                Ok(Some(BisectState {
+                    start: start.trim().to_string().into(),
+                    log,
+                    candidates,
+                    current,
+                    first_bad,
+                }))
+            })
+            .boxed()
+    }
+
     fn commit(
         &self,
         message: SharedString,
@@ -3596,6 +3945,261 @@ mod tests {
         // );
     }
 
+    #[gpui::test]
+    async fn test_interactive_rebase(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        for name in ["a", "b", "c", "d", "e"] {
+            smol::fs::write(repo_dir.path().join(name), name)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(name)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Add {name}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let mut todo = repo.rebase_todo("HEAD~4".into()).await.unwrap();
+        assert_eq!(
+            todo.iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<&str>>(),
+            ["Add b", "Add c", "Add d", "Add e"]
+        );
+        assert!(todo.iter().all(|entry| entry.action == RebaseAction::Pick));
+
+        // Reword d and move it first, drop c, meld b into d, and stop to edit e.
+        todo.swap(0, 2);
+        todo[0].action = RebaseAction::Reword;
+        todo[0].message = Some("Add d and b".into());
+        todo[1].action = RebaseAction::Drop;
+        todo[2].action = RebaseAction::Fixup;
+        todo[3].action = RebaseAction::Edit;
+        repo.rebase_interactive("HEAD~4".into(), todo, env.clone())
+            .await
+            .unwrap();
+        let zed_rebase_dir = repo_dir.path().join(".git").join(rebase::ZED_REBASE_DIR);
+        assert!(zed_rebase_dir.exists());
+
+        let state = repo.rebase_state().await.unwrap().unwrap();
+        assert_eq!(
+            state.done.last().map(|entry| entry.action),
+            Some(RebaseAction::Edit)
+        );
+        assert!(state.todo.is_empty());
+        assert!(state.stopped_sha.is_some());
+        assert_eq!(
+            repo.show("HEAD".into()).await.unwrap().message.trim(),
+            "Add e"
+        );
+
+        repo.rebase_command(RebaseCommand::Continue, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_state().await.unwrap(), None);
+        assert!(!zed_rebase_dir.exists());
+        assert_eq!(
+            repo.show("HEAD~1".into()).await.unwrap().message.trim(),
+            "Add d and b"
+        );
+        assert_eq!(
+            repo.show("HEAD~2".into()).await.unwrap().message.trim(),
+            "Add a"
+        );
+        assert!(repo_dir.path().join("b").exists());
+        assert!(!repo_dir.path().join("c").exists());
+
+        // Aborting a rebase removes its files as well.
+        let mut todo = repo.rebase_todo("HEAD~1".into()).await.unwrap();
+        todo[0].action = RebaseAction::Edit;
+        repo.rebase_interactive("HEAD~1".into(), todo, env.clone())
+            .await
+            .unwrap();
+        assert!(zed_rebase_dir.exists());
+        repo.rebase_command(RebaseCommand::Abort, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_state().await.unwrap(), None);
+        assert!(!zed_rebase_dir.exists());
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        for version in 1..=8 {
+            smol::fs::write(repo_dir.path().join("version"), version.to_string())
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path("version")], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Release {version}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        }
+        let head = repo.head_sha().await.unwrap();
+        assert_eq!(repo.bisect_state().await.unwrap(), None);
+
+        repo.bisect_start("HEAD".into(), vec!["HEAD~7".into()], env.clone())
+            .await
+            .unwrap();
+        let mut state = repo.bisect_state().await.unwrap().unwrap();
+        assert_eq!(state.candidates.len(), 7);
+        assert_eq!(state.steps_left(), 3);
+        assert_eq!(
+            state.log.iter().map(|entry| entry.mark).collect::<Vec<_>>(),
+            [BisectMark::Bad, BisectMark::Good]
+        );
+
+        // Release 5 introduced the regression.
+        while state.first_bad.is_none() {
+            let current = state.current.as_ref().unwrap();
+            let version = current
+                .subject
+                .strip_prefix("Release ")
+                .unwrap()
+                .parse::<u32>()
+                .unwrap();
+            let mark = if version >= 5 {
+                BisectMark::Bad
+            } else {
+                BisectMark::Good
+            };
+            repo.bisect_mark(mark, None, env.clone()).await.unwrap();
+            state = repo.bisect_state().await.unwrap().unwrap();
+        }
+        assert_eq!(&*state.first_bad.unwrap().subject, "Release 5");
+        assert_eq!(state.candidates.len(), 1);
+
+        repo.bisect_reset(env.clone()).await.unwrap();
+        assert_eq!(repo.bisect_state().await.unwrap(), None);
+        assert_eq!(repo.head_sha().await, Some(head));
+    }
+
+    #[gpui::test]
+    async fn test_cherry_pick_and_revert(cx: &mut TestAppContext) {
+        disable_git_global_config();
+
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(checkpoint_author_envs());
+
+        for name in ["a", "b", "c"] {
+            smol::fs::write(repo_dir.path().join(name), name)
+                .await
+                .unwrap();
+            repo.stage_paths(vec![repo_path(name)], env.clone())
+                .await
+                .unwrap();
+            repo.commit(
+                format!("Add {name}").into(),
+                None,
+                CommitOptions::default(),
+                AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+                env.clone(),
+            )
+            .await
+            .unwrap();
+        }
+        let add_b = repo.show("HEAD~1".into()).await.unwrap().sha.to_string();
+
+        repo.revert(vec![add_b.clone()], env.clone()).await.unwrap();
+        assert!(!repo_dir.path().join("b").exists());
+        assert_eq!(
+            repo.show("HEAD".into()).await.unwrap().message.trim(),
+            format!("Revert \"Add b\"\n\nThis reverts commit {add_b}.")
+        );
+
+        repo.cherry_pick(vec![add_b.clone()], env.clone())
+            .await
+            .unwrap();
+        assert!(repo_dir.path().join("b").exists());
+        assert_eq!(
+            repo.show("HEAD".into()).await.unwrap().message.trim(),
+            "Add b"
+        );
+
+        // Reverting a range reverts its commits from the newest one.
+        repo.revert(vec!["HEAD~2..HEAD".into()], env.clone())
+            .await
+            .unwrap();
+        let picked_b = repo.show("HEAD~2".into()).await.unwrap().sha;
+        assert!(repo_dir.path().join("b").exists());
+        assert_eq!(
+            repo.show("HEAD~1".into()).await.unwrap().message.trim(),
+            format!("Revert \"Add b\"\n\nThis reverts commit {picked_b}.")
+        );
+
+        // A conflict stops the cherry-pick, leaving it to be resolved.
+        smol::fs::write(repo_dir.path().join("b"), "changed")
+            .await
+            .unwrap();
+        repo.stage_paths(vec![repo_path("b")], env.clone())
+            .await
+            .unwrap();
+        repo.commit(
+            "Change b".into(),
+            None,
+            CommitOptions::default(),
+            AskPassDelegate::new(&mut cx.to_async(), |_, _, _| {}),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(repo.cherry_pick(vec![add_b], env.clone()).await.is_err());
+        assert!(repo_dir.path().join(".git/CHERRY_PICK_HEAD").exists());
+    }
+
     #[gpui::test]
     async fn test_checkpoint_empty_repo(cx: &mut TestAppContext) {
         disable_git_global_config();
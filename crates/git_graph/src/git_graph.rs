@@ -1,4 +1,4 @@
-use collections::{BTreeMap, HashMap};
+use collections::{BTreeMap, HashMap, HashSet};
 use feature_flags::{FeatureFlagAppExt as _, GitGraphFeatureFlag};
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
@@ -6,13 +6,17 @@ use git::{
     repository::{CommitDiff, CommitFile, InitialGraphCommitData, LogOrder, LogSource, RepoPath},
     status::{FileStatus, StatusCode, TrackedStatus},
 };
-use git_ui::{commit_tooltip::CommitAvatar, commit_view::CommitView, git_status_icon};
+use git_ui::{
+    commit_tooltip::CommitAvatar,
+    commit_view::{CommitOperation, CommitView},
+    git_status_icon,
+};
 use gpui::{
-    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Corner, DefiniteLength, DragMoveEvent,
-    ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable, Hsla, PathBuilder, Pixels,
-    Point, ScrollStrategy, ScrollWheelEvent, SharedString, Subscription, Task,
-    UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, point, prelude::*,
-    px, uniform_list,
+    AnyElement, App, Bounds, ClickEvent, ClipboardItem, Corner, DefiniteLength, DismissEvent,
+    DragMoveEvent, ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    MouseButton, PathBuilder, Pixels, Point, ScrollStrategy, ScrollWheelEvent, SharedString,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred,
+    point, prelude::*, px, uniform_list,
 };
 use language::line_diff;
 use menu::{Cancel, SelectNext, SelectPrevious};
@@ -583,6 +587,30 @@ impl GraphData {
         })
     }
 
+    /// Returns the commits between the rows `newer` and `older`, newest first, that are reachable
+    /// from the commit at `newer`.
+    ///
+    /// This matches `git rev-list --no-merges older^..newer` for linear histories, but also works
+    /// when `older` is a root commit. Merge commits are left out, as cherry-picking or reverting
+    /// them would fail without choosing a mainline parent. Returns `None` when the commit at
+    /// `older` isn't an ancestor of the one at `newer`.
+    fn commit_range(&self, newer: usize, older: usize) -> Option<Vec<Oid>> {
+        let rows = self.commits.get(newer..=older)?;
+        let mut reachable = HashSet::default();
+        reachable.insert(rows.first()?.data.sha);
+        // Rows are ordered topologically, so every parent comes after its children.
+        let mut range = Vec::new();
+        for commit in rows {
+            if reachable.contains(&commit.data.sha) {
+                if commit.data.parents.len() <= 1 {
+                    range.push(commit.data.sha);
+                }
+                reachable.extend(commit.data.parents.iter().copied());
+            }
+        }
+        reachable.contains(&rows.last()?.data.sha).then_some(range)
+    }
+
     fn add_commits(&mut self, commits: &[Arc<InitialGraphCommitData>]) {
         self.commits.reserve(commits.len());
         self.lines.reserve(commits.len() / 2);
@@ -1289,6 +1317,99 @@ impl GitGraph {
         );
     }
 
+    fn deploy_context_menu(
+        &mut self,
+        entry_index: usize,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let Some(repository) = self.get_selected_repository(cx) else {
+            return;
+        };
+
+        let sha = commit.data.sha.to_string();
+        // Rows are ordered from the newest commit to the oldest one, so the range between the
+        // selected commit and this one goes from the lower row to the upper one.
+        let range = self
+            .selected_entry_idx
+            .filter(|&selected| selected != entry_index)
+            .and_then(|selected| {
+                let newer = selected.min(entry_index);
+                let older = selected.max(entry_index);
+                let range = self
+                    .graph_data
+                    .commit_range(newer, older)
+                    .filter(|range| !range.is_empty())?;
+                let label = format!(
+                    "{}..{}",
+                    self.graph_data.commits[older].data.sha.display_short(),
+                    self.graph_data.commits[newer].data.sha.display_short()
+                );
+                Some((
+                    range.iter().map(|sha| sha.to_string()).collect::<Vec<_>>(),
+                    label,
+                ))
+            });
+
+        let weak_self = cx.weak_entity();
+        let context_menu = ContextMenu::build(window, cx, move |mut menu, _, _| {
+            menu = menu.entry("Open Commit", None, move |window, cx| {
+                weak_self
+                    .update(cx, |this, cx| {
+                        this.open_commit_view(entry_index, window, cx)
+                    })
+                    .ok();
+            });
+            menu = menu.separator();
+            for operation in [CommitOperation::CherryPick, CommitOperation::Revert] {
+                let sha = sha.clone();
+                let repository = repository.clone();
+                menu = menu.entry(operation.label(), None, move |window, cx| {
+                    operation.run(vec![sha.clone()], repository.clone(), window, cx);
+                });
+            }
+            if let Some((range, range_label)) = range {
+                menu = menu.separator();
+                // Commits are picked from the oldest one, and reverted from the newest one.
+                let oldest_first = range.iter().rev().cloned().collect::<Vec<_>>();
+                for (operation, label, commits) in [
+                    (
+                        CommitOperation::CherryPick,
+                        "Cherry-Pick Range",
+                        oldest_first,
+                    ),
+                    (CommitOperation::Revert, "Revert Range", range),
+                ] {
+                    let repository = repository.clone();
+                    menu = menu.entry(format!("{label} {range_label}"), None, move |window, cx| {
+                        operation.run(commits.clone(), repository.clone(), window, cx);
+                    });
+                }
+            }
+            menu
+        });
+
+        let subscription = cx.subscribe_in(
+            &context_menu,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.context_menu.as_ref().is_some_and(|context_menu| {
+                    context_menu.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.context_menu.take();
+                cx.notify();
+            },
+        );
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
     fn get_remote(
         &self,
         repository: &Repository,
@@ -2216,6 +2337,20 @@ impl Render for GitGraph {
                                             })
                                             .ok();
                                     })
+                                    .on_mouse_down(MouseButton::Right, {
+                                        let weak = weak.clone();
+                                        move |event, window, cx| {
+                                            weak.update(cx, |this, cx| {
+                                                this.deploy_context_menu(
+                                                    index,
+                                                    event.position,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                            .ok();
+                                        }
+                                    })
                                     .on_click(move |event, window, cx| {
                                         let click_count = event.click_count();
                                         weak.update(cx, |this, cx| {
@@ -3011,6 +3146,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_git_graph_commit_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let merge = Oid::random(&mut rng);
+        let main = Oid::random(&mut rng);
+        let branch = Oid::random(&mut rng);
+        let root = Oid::random(&mut rng);
+
+        let commits = vec![
+            Arc::new(InitialGraphCommitData {
+                sha: merge,
+                parents: smallvec![main, branch],
+                ref_names: vec!["HEAD".into()],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: main,
+                parents: smallvec![root],
+                ref_names: vec![],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: branch,
+                parents: smallvec![root],
+                ref_names: vec![],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: root,
+                parents: smallvec![],
+                ref_names: vec![],
+            }),
+        ];
+
+        let mut graph_data = GraphData::new(8);
+        graph_data.add_commits(&commits);
+
+        // Merge commits are skipped.
+        assert_eq!(
+            graph_data.commit_range(0, 3),
+            Some(vec![main, branch, root])
+        );
+        // Commits of other branches between the two rows aren't part of the range.
+        assert_eq!(graph_data.commit_range(1, 3), Some(vec![main, root]));
+        assert_eq!(graph_data.commit_range(0, 1), Some(vec![main]));
+        // The older commit has to be an ancestor of the newer one.
+        assert_eq!(graph_data.commit_range(1, 2), None);
+        assert_eq!(graph_data.commit_range(2, 4), None);
+    }
+
     #[test]
     fn test_git_graph_random_commits() {
         for seed in 0..100 {
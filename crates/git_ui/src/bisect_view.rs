@@ -0,0 +1,908 @@
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use git::SHORT_SHA_LENGTH;
+use git::bisect::{BisectCommit, BisectMark, BisectState};
+use gpui::{
+    AnyEntity, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, Subscription, Task, WeakEntity, Window, actions, uniform_list,
+};
+use project::{
+    Project, ProjectPath, TaskSourceKind,
+    git_store::{Repository, RepositoryEvent},
+};
+use std::any::{Any, TypeId};
+use std::ops::Range;
+use std::sync::Arc;
+use task::{TaskContext, TaskTemplate, TaskVariables, VariableName};
+use ui::{ContextMenu, DropdownMenu, ListItem, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+    notifications::DetachAndPromptErr,
+};
+
+use crate::commit_view::CommitView;
+
+actions!(
+    git_bisect,
+    [
+        /// Starts bisecting between the bad and the good commits.
+        Start,
+        /// Marks the commit being tested as good.
+        MarkGood,
+        /// Marks the commit being tested as bad.
+        MarkBad,
+        /// Skips the commit being tested, when it can't be tested.
+        Skip,
+        /// Ends bisecting, checking out what was checked out before it started.
+        Reset,
+        /// Tests each commit with the test task until the first bad commit is found, or stops
+        /// testing.
+        ToggleTestRun,
+    ]
+);
+
+/// Finds the commit that introduced a regression with `git bisect`, testing the commits by
+/// hand or with a task.
+pub struct BisectView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    state: Option<BisectState>,
+    bad_editor: Entity<Editor>,
+    good_editor: Entity<Editor>,
+    /// The tasks that can test a commit.
+    tasks: Vec<(TaskSourceKind, TaskTemplate)>,
+    test_task: Option<usize>,
+    /// Whether a bisect command started from the view is running.
+    running: bool,
+    /// Tests the commits with the test task, until the first bad commit is found.
+    test_run: Option<Task<()>>,
+    focus_handle: FocusHandle,
+    _load_state: Task<()>,
+    _load_tasks: Task<()>,
+    _repository_subscription: Subscription,
+}
+
+impl BisectView {
+    pub fn open(
+        repository: Entity<Repository>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let pane = workspace.active_pane().clone();
+        let existing = pane.read(cx).items().find_map(|item| {
+            item.downcast::<Self>()
+                .filter(|view| view.read(cx).repository == repository)
+        });
+        if let Some(view) = existing {
+            view.update(cx, |view, cx| view.load_state(cx));
+            pane.update(cx, |pane, cx| {
+                if let Some(ix) = pane.index_for_item(&view) {
+                    pane.activate_item(ix, true, true, window, cx);
+                }
+            });
+        } else {
+            let project = workspace.project().clone();
+            let workspace = workspace.weak_handle();
+            let view = cx.new(|cx| Self::new(repository, project, workspace, window, cx));
+            pane.update(cx, |pane, cx| {
+                pane.add_item(Box::new(view), true, true, None, window, cx);
+            });
+        }
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let bad_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("HEAD", window, cx);
+            editor
+        });
+        let good_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(
+                "Tags, branches or commits, separated by spaces",
+                window,
+                cx,
+            );
+            editor
+        });
+        // Marking a commit checks out the next one to test.
+        let repository_subscription =
+            cx.subscribe(&repository, |this, _, event: &RepositoryEvent, cx| {
+                if matches!(event, RepositoryEvent::BranchChanged) {
+                    this.load_state(cx);
+                }
+            });
+
+        let mut this = Self {
+            repository,
+            workspace,
+            state: None,
+            bad_editor,
+            good_editor,
+            tasks: Vec::new(),
+            test_task: None,
+            running: false,
+            test_run: None,
+            focus_handle: cx.focus_handle(),
+            _load_state: Task::ready(()),
+            _load_tasks: Task::ready(()),
+            _repository_subscription: repository_subscription,
+        };
+        this.load_state(cx);
+        this.load_tasks(project, cx);
+        this
+    }
+
+    fn load_state(&mut self, cx: &mut Context<Self>) {
+        let state = self
+            .repository
+            .update(cx, |repository, _| repository.bisect_state());
+        self._load_state = cx.spawn(async move |this, cx| {
+            let Some(state) = state
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|state| state)
+                .log_err()
+            else {
+                return;
+            };
+            this.update(cx, |this, cx| {
+                this.state = state;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn load_tasks(&mut self, project: Entity<Project>, cx: &mut Context<Self>) {
+        let project = project.read(cx);
+        let work_directory = self.repository.read(cx).work_directory_abs_path.clone();
+        let worktree_id = project
+            .find_worktree(&work_directory, cx)
+            .map(|(worktree, _)| worktree)
+            .or_else(|| project.visible_worktrees(cx).next())
+            .map(|worktree| worktree.read(cx).id());
+        let Some(inventory) = project.task_store().read(cx).task_inventory().cloned() else {
+            return;
+        };
+        let tasks = inventory.read(cx).list_tasks(None, None, worktree_id, cx);
+        self._load_tasks = cx.spawn(async move |this, cx| {
+            let tasks = tasks.await;
+            this.update(cx, |this, cx| {
+                this.tasks = tasks;
+                this.test_task = None;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// The context tasks testing the commits run in, at the root of the repository.
+    fn task_context(&self, cx: &App) -> TaskContext {
+        let work_directory = self.repository.read(cx).work_directory_abs_path.clone();
+        TaskContext {
+            cwd: Some(work_directory.to_path_buf()),
+            task_variables: TaskVariables::from_iter([(
+                VariableName::WorktreeRoot,
+                work_directory.to_string_lossy().into_owned(),
+            )]),
+            project_env: Default::default(),
+        }
+    }
+
+    fn start(&mut self, _: &Start, window: &mut Window, cx: &mut Context<Self>) {
+        if self.running || self.state.is_some() {
+            return;
+        }
+        let bad = self.bad_editor.read(cx).text(cx).trim().to_string();
+        let bad = if bad.is_empty() {
+            "HEAD".to_string()
+        } else {
+            bad
+        };
+        let good = self
+            .good_editor
+            .read(cx)
+            .text(cx)
+            .split_whitespace()
+            .map(ToString::to_string)
+            .collect();
+        let task = self
+            .repository
+            .update(cx, |repository, cx| repository.bisect_start(bad, good, cx));
+        self.run(task, window, cx);
+    }
+
+    fn mark_good(&mut self, _: &MarkGood, window: &mut Window, cx: &mut Context<Self>) {
+        self.mark(BisectMark::Good, window, cx);
+    }
+
+    fn mark_bad(&mut self, _: &MarkBad, window: &mut Window, cx: &mut Context<Self>) {
+        self.mark(BisectMark::Bad, window, cx);
+    }
+
+    fn skip(&mut self, _: &Skip, window: &mut Window, cx: &mut Context<Self>) {
+        self.mark(BisectMark::Skip, window, cx);
+    }
+
+    fn mark(&mut self, mark: BisectMark, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.can_mark() {
+            return;
+        }
+        let task = self
+            .repository
+            .update(cx, |repository, cx| repository.bisect_mark(mark, None, cx));
+        self.run(task, window, cx);
+    }
+
+    fn can_mark(&self) -> bool {
+        !self.running
+            && self.test_run.is_none()
+            && self
+                .state
+                .as_ref()
+                .is_some_and(|state| state.first_bad.is_none())
+    }
+
+    fn reset(&mut self, _: &Reset, window: &mut Window, cx: &mut Context<Self>) {
+        if self.running || self.state.is_none() {
+            return;
+        }
+        self.test_run = None;
+        let task = self
+            .repository
+            .update(cx, |repository, cx| repository.bisect_reset(cx));
+        self.run(task, window, cx);
+    }
+
+    /// Runs a bisect command, then reloads the state of the session.
+    fn run(&mut self, task: Task<Result<()>>, window: &mut Window, cx: &mut Context<Self>) {
+        self.running = true;
+        cx.notify();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                this.running = false;
+                this.load_state(cx);
+            })?;
+            result
+        })
+        .detach_and_prompt_err("Failed to bisect", window, cx, |_, _, _| None);
+    }
+
+    fn toggle_test_run(&mut self, _: &ToggleTestRun, window: &mut Window, cx: &mut Context<Self>) {
+        if self.test_run.take().is_some() {
+            cx.notify();
+            return;
+        }
+        if !self.can_mark() {
+            return;
+        }
+        let Some((source_kind, template)) = self.test_task.and_then(|ix| self.tasks.get(ix)) else {
+            return;
+        };
+        let source_kind = source_kind.clone();
+        let template = template.clone();
+        let workspace = self.workspace.clone();
+        self.test_run = Some(cx.spawn_in(window, async move |this, cx| {
+            let result = Self::test_commits(this.clone(), source_kind, template, cx).await;
+            this.update(cx, |this, cx| {
+                this.test_run = None;
+                cx.notify();
+            })
+            .ok();
+            if let Err(error) = result {
+                workspace
+                    .update(cx, |workspace, cx| workspace.show_error(&error, cx))
+                    .ok();
+            }
+        }));
+        cx.notify();
+    }
+
+    /// Runs the task on each commit to test, marking the commit from the exit code of the task
+    /// like `git bisect run` does, until the first bad commit is found.
+    async fn test_commits(
+        this: WeakEntity<Self>,
+        source_kind: TaskSourceKind,
+        template: TaskTemplate,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<()> {
+        let id_base = source_kind.to_id_base();
+        loop {
+            let (repository, workspace, task_context) = this.update(cx, |this, cx| {
+                (
+                    this.repository.clone(),
+                    this.workspace.clone(),
+                    this.task_context(cx),
+                )
+            })?;
+            let state = repository
+                .update(cx, |repository, _| repository.bisect_state())
+                .await??;
+            if state.is_none_or(|state| state.first_bad.is_some()) {
+                return Ok(());
+            }
+
+            let resolved = template
+                .resolve_task(&id_base, &task_context)
+                .with_context(|| format!("failed to resolve the task \"{}\"", template.label))?;
+            let status = workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.spawn_in_terminal(resolved.resolved, window, cx)
+                })?
+                .await
+                .context("the test task was cancelled")??;
+            let code = status
+                .code()
+                .context("the test task was terminated by a signal")?;
+            let mark = BisectMark::from_exit_code(code)
+                .with_context(|| format!("the test task failed with exit code {code}"))?;
+
+            repository
+                .update(cx, |repository, cx| repository.bisect_mark(mark, None, cx))
+                .await?;
+            let state = repository.update(cx, |repository, _| repository.bisect_state());
+            let state = state.await??;
+            this.update(cx, |this, cx| {
+                this.state = state;
+                cx.notify();
+            })?;
+        }
+    }
+
+    fn open_commit(&self, sha: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        CommitView::open(
+            sha.to_string(),
+            self.repository.downgrade(),
+            self.workspace.clone(),
+            None,
+            None,
+            window,
+            cx,
+        );
+    }
+
+    fn render_header(
+        &self,
+        title: SharedString,
+        buttons: impl IntoElement,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .h(rems_from_px(41.))
+            .pl_3()
+            .pr_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).color(Color::Muted).truncate())
+            .child(buttons)
+    }
+
+    fn render_start(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let buttons = Button::new("start-bisect", "Start Bisecting")
+            .style(ButtonStyle::Filled)
+            .label_size(LabelSize::Small)
+            .disabled(self.running)
+            .on_click(cx.listener(|this, _, window, cx| this.start(&Start, window, cx)));
+        let field = |label: &'static str, editor: &Entity<Editor>, cx: &mut Context<Self>| {
+            v_flex()
+                .gap_1()
+                .child(Label::new(label).size(LabelSize::Small).color(Color::Muted))
+                .child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .border_1()
+                        .border_color(cx.theme().colors().border)
+                        .child(editor.clone()),
+                )
+        };
+
+        v_flex()
+            .size_full()
+            .child(self.render_header("Git Bisect".into(), buttons, cx))
+            .child(
+                v_flex()
+                    .p_3()
+                    .gap_3()
+                    .max_w(rems(40.))
+                    .child(
+                        Label::new(
+                            "Bisecting checks out the commits between a bad commit and good ones, \
+                             halving them with each commit you test, until the first bad commit \
+                             is found.",
+                        )
+                        .size(LabelSize::Small),
+                    )
+                    .child(field("Bad Commit", &self.bad_editor, cx))
+                    .child(field("Good Commits", &self.good_editor, cx)),
+            )
+    }
+
+    fn render_session(
+        &self,
+        state: &BisectState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let title = if state.first_bad.is_some() {
+            "Found the first bad commit".to_string()
+        } else if state.candidates.is_empty() {
+            "Waiting for a good and a bad commit".to_string()
+        } else {
+            format!(
+                "{} left to test, roughly {} {}",
+                pluralize_commits(state.candidates.len()),
+                state.steps_left(),
+                if state.steps_left() == 1 {
+                    "step"
+                } else {
+                    "steps"
+                }
+            )
+        };
+        let can_mark = self.can_mark();
+        let buttons = h_flex()
+            .gap_1()
+            .child(
+                Button::new("reset-bisect", "Reset")
+                    .label_size(LabelSize::Small)
+                    .disabled(self.running)
+                    .tooltip(Tooltip::text(format!(
+                        "Stop bisecting and check out {}",
+                        state.start
+                    )))
+                    .on_click(cx.listener(|this, _, window, cx| this.reset(&Reset, window, cx))),
+            )
+            .child(
+                Button::new("skip-commit", "Skip")
+                    .label_size(LabelSize::Small)
+                    .disabled(!can_mark)
+                    .tooltip(Tooltip::text("The commit can't be tested"))
+                    .on_click(cx.listener(|this, _, window, cx| this.skip(&Skip, window, cx))),
+            )
+            .child(
+                Button::new("mark-bad", "Bad")
+                    .label_size(LabelSize::Small)
+                    .disabled(!can_mark)
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.mark_bad(&MarkBad, window, cx)),
+                    ),
+            )
+            .child(
+                Button::new("mark-good", "Good")
+                    .label_size(LabelSize::Small)
+                    .disabled(!can_mark)
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.mark_good(&MarkGood, window, cx)),
+                    ),
+            );
+
+        let (commit_label, commit) = match (&state.first_bad, &state.current) {
+            (Some(first_bad), _) => ("First bad commit", Some(first_bad)),
+            (None, current) => ("Testing", current.as_ref()),
+        };
+
+        v_flex()
+            .size_full()
+            .child(self.render_header(title.into(), buttons, cx))
+            .when_some(commit, |this, commit| {
+                this.child(
+                    h_flex()
+                        .px_3()
+                        .py_2()
+                        .gap_2()
+                        .border_b_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .child(
+                            Label::new(commit_label)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(self.render_commit(commit, None, cx))
+                        .child(div().flex_1())
+                        .child(
+                            Button::new("open-commit", "Open Commit")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener({
+                                    let sha = commit.sha.clone();
+                                    move |this, _, window, cx| {
+                                        this.open_commit(sha.clone(), window, cx)
+                                    }
+                                })),
+                        ),
+                )
+            })
+            .when(state.first_bad.is_none(), |this| {
+                this.child(self.render_test_task(window, cx))
+            })
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .child(self.render_section_title("Candidates", cx))
+                    .child(
+                        uniform_list(
+                            "bisect-candidates",
+                            state.candidates.len(),
+                            cx.processor(Self::render_candidates),
+                        )
+                        .flex_1(),
+                    ),
+            )
+            .when(!state.log.is_empty(), |this| {
+                this.child(self.render_section_title("Log", cx)).child(
+                    v_flex()
+                        .id("bisect-log")
+                        .max_h(rems(12.))
+                        .overflow_y_scroll()
+                        .children(state.log.iter().enumerate().map(|(ix, entry)| {
+                            ListItem::new(("bisect-log-entry", ix))
+                                .child(
+                                    h_flex()
+                                        .gap_2()
+                                        .child(
+                                            div().w(rems(3.)).child(
+                                                Label::new(entry.mark.label())
+                                                    .size(LabelSize::Small)
+                                                    .color(mark_color(entry.mark)),
+                                            ),
+                                        )
+                                        .child(self.render_commit(&entry.commit, None, cx)),
+                                )
+                                .on_click(cx.listener({
+                                    let sha = entry.commit.sha.clone();
+                                    move |this, _, window, cx| {
+                                        this.open_commit(sha.clone(), window, cx)
+                                    }
+                                }))
+                        })),
+                )
+            })
+    }
+
+    fn render_test_task(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let selected = self
+            .test_task
+            .and_then(|ix| self.tasks.get(ix))
+            .map(|(_, template)| SharedString::from(template.label.clone()));
+        let menu = ContextMenu::build(window, cx, {
+            let labels = self
+                .tasks
+                .iter()
+                .map(|(_, template)| template.label.clone())
+                .collect::<Vec<_>>();
+            let test_task = self.test_task;
+            move |mut menu, window, _| {
+                if labels.is_empty() {
+                    return menu.header("No tasks defined");
+                }
+                for (ix, label) in labels.into_iter().enumerate() {
+                    menu = menu.toggleable_entry(
+                        label,
+                        test_task == Some(ix),
+                        IconPosition::Start,
+                        None,
+                        window.handler_for(&view, move |this, _, cx| {
+                            this.test_task = Some(ix);
+                            cx.notify();
+                        }),
+                    );
+                }
+                menu
+            }
+        });
+        let testing = self.test_run.is_some();
+
+        h_flex()
+            .px_3()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new("Test Task")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                DropdownMenu::new(
+                    "bisect-test-task",
+                    selected.unwrap_or_else(|| "None".into()),
+                    menu,
+                )
+                .trigger_size(ButtonSize::Compact)
+                .disabled(testing)
+                .trigger_tooltip(Tooltip::text(
+                    "The task testing each commit: exiting with 0 marks it good, 125 skips it, \
+                     and other codes up to 127 mark it bad",
+                )),
+            )
+            .child(
+                Button::new(
+                    "toggle-test-run",
+                    if testing { "Stop" } else { "Run Automatically" },
+                )
+                .label_size(LabelSize::Small)
+                .icon(if testing {
+                    IconName::Stop
+                } else {
+                    IconName::PlayFilled
+                })
+                .icon_size(IconSize::Small)
+                .icon_position(IconPosition::Start)
+                .disabled(!testing && (self.test_task.is_none() || !self.can_mark()))
+                .on_click(cx.listener(|this, _, window, cx| {
+                    this.toggle_test_run(&ToggleTestRun, window, cx)
+                })),
+            )
+    }
+
+    fn render_section_title(
+        &self,
+        title: &'static str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .px_3()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+    }
+
+    fn render_candidates(
+        &mut self,
+        range: Range<usize>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Vec<AnyElement> {
+        let Some(state) = self.state.as_ref() else {
+            return Vec::new();
+        };
+        let current = state.current.as_ref().map(|commit| commit.sha.clone());
+        state.candidates
+            [range.start.min(state.candidates.len())..range.end.min(state.candidates.len())]
+            .iter()
+            .enumerate()
+            .map(|(offset, commit)| {
+                let ix = range.start + offset;
+                let is_current = current.as_ref() == Some(&commit.sha);
+                ListItem::new(("bisect-candidate", ix))
+                    .toggle_state(is_current)
+                    .child(self.render_commit(commit, state.mark_of(&commit.sha), cx))
+                    .on_click(cx.listener({
+                        let sha = commit.sha.clone();
+                        move |this, _, window, cx| this.open_commit(sha.clone(), window, cx)
+                    }))
+                    .into_any_element()
+            })
+            .collect()
+    }
+
+    fn render_commit(
+        &self,
+        commit: &BisectCommit,
+        mark: Option<BisectMark>,
+        cx: &App,
+    ) -> impl IntoElement {
+        h_flex()
+            .min_w_0()
+            .gap_2()
+            .child(
+                Label::new(short_sha(&commit.sha))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .child(
+                Label::new(commit.subject.clone())
+                    .size(LabelSize::Small)
+                    .truncate(),
+            )
+            .when_some(mark, |this, mark| {
+                this.child(
+                    Label::new(mark.label())
+                        .size(LabelSize::Small)
+                        .color(mark_color(mark)),
+                )
+            })
+    }
+}
+
+fn mark_color(mark: BisectMark) -> Color {
+    match mark {
+        BisectMark::Good => Color::Success,
+        BisectMark::Bad => Color::Error,
+        BisectMark::Skip => Color::Muted,
+    }
+}
+
+fn short_sha(sha: &str) -> SharedString {
+    sha.get(..SHORT_SHA_LENGTH)
+        .unwrap_or(sha)
+        .to_string()
+        .into()
+}
+
+fn pluralize_commits(count: usize) -> String {
+    if count == 1 {
+        "1 commit".to_string()
+    } else {
+        format!("{count} commits")
+    }
+}
+
+impl EventEmitter<ItemEvent> for BisectView {}
+
+impl Focusable for BisectView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for BisectView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if let Some(state) = self.state.clone() {
+            self.render_session(&state, window, cx).into_any_element()
+        } else {
+            self.render_start(cx).into_any_element()
+        };
+
+        v_flex()
+            .id("git_bisect_view")
+            .key_context("GitBisect")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::start))
+            .on_action(cx.listener(Self::mark_good))
+            .on_action(cx.listener(Self::mark_bad))
+            .on_action(cx.listener(Self::skip))
+            .on_action(cx.listener(Self::reset))
+            .on_action(cx.listener(Self::toggle_test_run))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(content)
+    }
+}
+
+impl Item for BisectView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Git Bisect".into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<SharedString> {
+        let work_directory = &self.repository.read(cx).work_directory_abs_path;
+        Some(format!("Bisect {}", work_directory.display()).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::MagnifyingGlass))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("git bisect")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(
+        &mut self,
+        _: Arc<dyn Any + Send>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> bool {
+        false
+    }
+
+    fn deactivated(&mut self, _window: &mut Window, _: &mut Context<Self>) {}
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn has_conflict(&self, _: &App) -> bool {
+        false
+    }
+
+    fn breadcrumbs(&self, _cx: &App) -> Option<Vec<workspace::item::BreadcrumbText>> {
+        None
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn pixel_position_of_cursor(&self, _: &App) -> Option<gpui::Point<gpui::Pixels>> {
+        None
+    }
+
+    fn set_nav_history(
+        &mut self,
+        _: workspace::ItemNavHistory,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
@@ -28,14 +28,14 @@ use std::{
     sync::Arc,
 };
 use theme::ActiveTheme;
-use ui::{DiffStat, Divider, Tooltip, prelude::*};
+use ui::{ContextMenu, DiffStat, Divider, Tooltip, prelude::*, right_click_menu};
 use util::{ResultExt, paths::PathStyle, rel_path::RelPath, truncate_and_trailoff};
 use workspace::item::TabTooltipContent;
 use workspace::{
     Item, ItemHandle, ItemNavHistory, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView,
     Workspace,
     item::{ItemEvent, TabContentParams},
-    notifications::NotifyTaskExt,
+    notifications::{DetachAndPromptErr, NotifyTaskExt},
     pane::SaveIntent,
     searchable::SearchableItemHandle,
 };
@@ -43,7 +43,16 @@ use workspace::{
 use crate::commit_tooltip::CommitAvatar;
 use crate::git_panel::GitPanel;
 
-actions!(git, [ApplyCurrentStash, PopCurrentStash, DropCurrentStash,]);
+actions!(
+    git,
+    [
+        ApplyCurrentStash,
+        PopCurrentStash,
+        DropCurrentStash,
+        CherryPickCurrentCommit,
+        RevertCurrentCommit,
+    ]
+);
 
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
@@ -56,10 +65,52 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &PopCurrentStash, window, cx| {
             CommitView::pop_stash(workspace, window, cx);
         });
+        workspace.register_action(|workspace, _: &CherryPickCurrentCommit, window, cx| {
+            CommitView::apply_commit(workspace, CommitOperation::CherryPick, window, cx);
+        });
+        workspace.register_action(|workspace, _: &RevertCurrentCommit, window, cx| {
+            CommitView::apply_commit(workspace, CommitOperation::Revert, window, cx);
+        });
     })
     .detach();
 }
 
+/// An operation applying the changes of commits to the working tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitOperation {
+    CherryPick,
+    Revert,
+}
+
+impl CommitOperation {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CherryPick => "Cherry-Pick Commit",
+            Self::Revert => "Revert Commit",
+        }
+    }
+
+    /// Cherry-picks or reverts the given commits or commit ranges. A conflict stops the
+    /// operation, leaving the conflicts in the working tree to be resolved and committed.
+    pub fn run(
+        self,
+        commits: Vec<String>,
+        repository: Entity<Repository>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let task = repository.update(cx, |repository, cx| match self {
+            Self::CherryPick => repository.cherry_pick(commits, cx),
+            Self::Revert => repository.revert(commits, cx),
+        });
+        let message = match self {
+            Self::CherryPick => "Failed to cherry-pick",
+            Self::Revert => "Failed to revert",
+        };
+        task.detach_and_prompt_err(message, window, cx, |_, _, _| None);
+    }
+}
+
 pub struct CommitView {
     commit: CommitDetails,
     editor: Entity<Editor>,
@@ -544,6 +595,24 @@ impl CommitView {
             })
     }
 
+    fn apply_commit(
+        workspace: &mut Workspace,
+        operation: CommitOperation,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let Some(commit_view) = workspace.active_item_as::<CommitView>(cx) else {
+            return;
+        };
+        let commit_view = commit_view.read(cx);
+        if commit_view.stash.is_some() {
+            return;
+        }
+        let sha = commit_view.commit.sha.to_string();
+        let repository = commit_view.repository.clone();
+        operation.run(vec![sha], repository, window, cx);
+    }
+
     fn apply_stash(workspace: &mut Workspace, window: &mut Window, cx: &mut App) {
         Self::stash_action(
             workspace,
@@ -987,12 +1056,34 @@ impl Item for CommitView {
 impl Render for CommitView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let is_stash = self.stash.is_some();
+        let header = self.render_header(window, cx).into_any_element();
 
         v_flex()
             .key_context(if is_stash { "StashDiff" } else { "CommitDiff" })
             .size_full()
             .bg(cx.theme().colors().editor_background)
-            .child(self.render_header(window, cx))
+            .map(|this| {
+                if is_stash {
+                    this.child(header)
+                } else {
+                    this.child(
+                        right_click_menu("commit-view-header-menu")
+                            .trigger(move |_, _, _| header)
+                            .menu(|window, cx| {
+                                ContextMenu::build(window, cx, |menu, _, _| {
+                                    menu.action(
+                                        CommitOperation::CherryPick.label(),
+                                        Box::new(CherryPickCurrentCommit),
+                                    )
+                                    .action(
+                                        CommitOperation::Revert.label(),
+                                        Box::new(RevertCurrentCommit),
+                                    )
+                                })
+                            }),
+                    )
+                }
+            })
             .when(!self.editor.read(cx).is_empty(cx), |this| {
                 this.child(div().flex_grow().child(self.editor.clone()))
             })
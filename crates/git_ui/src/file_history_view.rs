@@ -14,15 +14,17 @@ use std::any::{Any, TypeId};
 use std::sync::Arc;
 
 use time::OffsetDateTime;
-use ui::{Chip, Divider, ListItem, WithScrollbar, prelude::*};
+use ui::{Chip, ContextMenu, Divider, ListItem, WithScrollbar, prelude::*, right_click_menu};
 use util::ResultExt;
 use workspace::{
     Item, Workspace,
     item::{ItemEvent, SaveOptions},
+    notifications::DetachAndPromptErr,
 };
 
 use crate::commit_tooltip::CommitAvatar;
-use crate::commit_view::CommitView;
+use crate::commit_view::{CommitOperation, CommitView};
+use crate::notebook_diff_view::{self, NotebookDiffView};
 
 const PAGE_SIZE: usize = 50;
 
@@ -315,7 +317,7 @@ impl FileHistoryView {
             time_format::TimestampFormat::Relative,
         );
 
-        ListItem::new(("commit", ix))
+        let item = ListItem::new(("commit", ix))
             .toggle_state(Some(ix) == self.selected_entry)
             .child(
                 h_flex()
@@ -373,7 +375,58 @@ impl FileHistoryView {
                 cx.notify();
 
                 this.open_commit_view(window, cx);
-            }))
+            }));
+
+        let sha = entry.sha.to_string();
+        let repository = self.repository.clone();
+        let workspace = self.workspace.clone();
+        let notebook_path =
+            notebook_diff_view::is_notebook(&self.history.path).then(|| self.history.path.clone());
+        right_click_menu(("commit-menu", ix))
+            .trigger(move |_, _, _| item)
+            .menu(move |window, cx| {
+                let sha = sha.clone();
+                let repository = repository.clone();
+                let workspace = workspace.clone();
+                let notebook_path = notebook_path.clone();
+                ContextMenu::build(window, cx, move |mut menu, _, _| {
+                    if let Some(path) = notebook_path {
+                        let sha = sha.clone();
+                        let repository = repository.clone();
+                        menu = menu
+                            .entry("Open Notebook Diff", None, move |window, cx| {
+                                let Some(repository) = repository.upgrade() else {
+                                    return;
+                                };
+                                NotebookDiffView::open_commit(
+                                    sha.clone().into(),
+                                    path.clone(),
+                                    repository,
+                                    workspace.clone(),
+                                    window,
+                                    cx,
+                                )
+                                .detach_and_prompt_err(
+                                    "Failed to diff the notebook",
+                                    window,
+                                    cx,
+                                    |_, _, _| None,
+                                );
+                            })
+                            .separator();
+                    }
+                    for operation in [CommitOperation::CherryPick, CommitOperation::Revert] {
+                        let sha = sha.clone();
+                        let repository = repository.clone();
+                        menu = menu.entry(operation.label(), None, move |window, cx| {
+                            if let Some(repository) = repository.upgrade() {
+                                operation.run(vec![sha.clone()], repository, window, cx);
+                            }
+                        });
+                    }
+                    menu
+                })
+            })
             .into_any_element()
     }
 }
@@ -2783,6 +2783,7 @@ impl GitPanel {
                     tool_choice: None,
                     stop: Vec::new(),
                     temperature,
+                    max_tokens: None,
                     thinking_allowed: false,
                     thinking_effort: None,
                     speed: None,
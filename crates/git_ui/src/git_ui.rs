@@ -16,7 +16,8 @@ use git::{
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
 };
 use gpui::{
-    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString, Window,
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, SharedString, Task,
+    Window,
 };
 use menu::{Cancel, Confirm};
 use project::git_store::Repository;
@@ -28,6 +29,7 @@ use zed_actions;
 use crate::{git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+pub mod bisect_view;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
@@ -39,8 +41,10 @@ pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
 pub mod multi_diff_view;
+pub mod notebook_diff_view;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_view;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod stash_picker;
@@ -205,6 +209,27 @@ pub fn init(cx: &mut App) {
         workspace.register_action(|workspace, _: &git::RenameBranch, window, cx| {
             rename_current_branch(workspace, window, cx);
         });
+        workspace.register_action(|workspace, action: &git::InteractiveRebase, window, cx| {
+            let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+                return;
+            };
+            rebase_view::InteractiveRebaseView::open(
+                action.onto.clone(),
+                repository,
+                workspace.weak_handle(),
+                window,
+                cx,
+            );
+        });
+        workspace.register_action(|workspace, _: &git::Bisect, window, cx| {
+            let Some(repository) = workspace.project().read(cx).active_repository(cx) else {
+                return;
+            };
+            bisect_view::BisectView::open(repository, workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &git::DiffNotebook, window, cx| {
+            diff_active_notebook(workspace, window, cx);
+        });
         workspace.register_action(
             |workspace, action: &DiffClipboardWithSelectionData, window, cx| {
                 if let Some(task) = TextDiffView::open(action, workspace, window, cx) {
@@ -279,6 +304,57 @@ fn open_modified_files(
     }
 }
 
+/// Opens the cell-level diff of the notebook in the active editor, or at the cursor of the
+/// project diff.
+fn diff_active_notebook(
+    workspace: &mut Workspace,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(active_item) = workspace.active_item(cx) else {
+        return;
+    };
+    let buffer = if let Some(project_diff) = active_item.act_as::<ProjectDiff>(cx) {
+        let Some(project_path) = project_diff
+            .read(cx)
+            .active_path(cx)
+            .filter(|project_path| notebook_diff_view::is_notebook(&project_path.path))
+        else {
+            return;
+        };
+        workspace
+            .project()
+            .update(cx, |project, cx| project.open_buffer(project_path, cx))
+    } else if let Some(buffer) = active_item
+        .act_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        .filter(|buffer| {
+            buffer
+                .read(cx)
+                .file()
+                .is_some_and(|file| notebook_diff_view::is_notebook(file.path()))
+        })
+    {
+        Task::ready(Ok(buffer))
+    } else {
+        return;
+    };
+
+    let workspace = workspace.weak_handle();
+    window
+        .spawn(cx, async move |cx| {
+            let buffer = buffer.await?;
+            cx.update(|window, cx| {
+                notebook_diff_view::NotebookDiffView::open_uncommitted(
+                    buffer, workspace, window, cx,
+                )
+            })?
+            .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to diff the notebook", window, cx, |_, _, _| None);
+}
+
 /// Resolves the repository for git operations, respecting the workspace's
 /// active worktree override from the project dropdown.
 pub fn resolve_active_repository(workspace: &Workspace, cx: &App) -> Option<Entity<Repository>> {
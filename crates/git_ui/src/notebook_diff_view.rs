@@ -0,0 +1,953 @@
+//! NotebookDiffView shows the changes to a Jupyter notebook cell by cell, instead of as changes
+//! to its JSON.
+
+use anyhow::{Context as _, Result};
+use buffer_diff::{BufferDiff, BufferDiffEvent};
+use editor::{Editor, EditorEvent, MultiBuffer};
+use futures::{FutureExt, select_biased};
+use git::repository::RepoPath;
+use gpui::{
+    AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    IntoElement, Render, Task, WeakEntity, Window, actions,
+};
+use language::{Buffer, Capability};
+use project::{Project, git_store::Repository};
+use serde_json::Value;
+use std::{
+    any::{Any, TypeId},
+    collections::VecDeque,
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+use ui::{Checkbox, ToggleState, prelude::*};
+use util::rel_path::RelPath;
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+actions!(
+    notebook_diff,
+    [
+        /// Toggles showing the outputs of the cells, instead of only whether they changed.
+        ToggleOutputs,
+        /// Toggles showing the metadata of the cells, instead of only whether it changed.
+        ToggleMetadata,
+    ]
+);
+
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Whether the path is that of a Jupyter notebook.
+pub fn is_notebook(path: &RelPath) -> bool {
+    path.extension() == Some("ipynb")
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CellKind {
+    Code,
+    Markdown,
+    Raw,
+}
+
+impl CellKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Code => "code",
+            Self::Markdown => "markdown",
+            Self::Raw => "raw",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct NotebookCell {
+    /// The id of the cell, which notebooks have since nbformat 4.5.
+    id: Option<String>,
+    kind: CellKind,
+    source: String,
+    execution_count: Option<i64>,
+    /// The text of each output.
+    outputs: Vec<String>,
+    metadata: Value,
+}
+
+/// Parses the cells of a notebook. A missing notebook, like that of an added file at HEAD, has
+/// no cells.
+fn parse_notebook(text: Option<&str>) -> Result<Vec<NotebookCell>> {
+    let Some(text) = text.filter(|text| !text.trim().is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let notebook: Value = serde_json::from_str(text).context("parsing the notebook")?;
+    let cells = notebook
+        .get("cells")
+        .and_then(Value::as_array)
+        .context("the notebook has no cells")?;
+    cells
+        .iter()
+        .map(|cell| {
+            let kind = match cell.get("cell_type").and_then(Value::as_str) {
+                Some("code") => CellKind::Code,
+                Some("markdown") => CellKind::Markdown,
+                Some("raw") => CellKind::Raw,
+                kind => anyhow::bail!("unknown cell type {kind:?}"),
+            };
+            Ok(NotebookCell {
+                id: cell
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string),
+                kind,
+                source: multiline_text(cell.get("source")),
+                execution_count: cell.get("execution_count").and_then(Value::as_i64),
+                outputs: cell
+                    .get("outputs")
+                    .and_then(Value::as_array)
+                    .map(|outputs| outputs.iter().map(output_text).collect())
+                    .unwrap_or_default(),
+                metadata: cell.get("metadata").cloned().unwrap_or(Value::Null),
+            })
+        })
+        .collect()
+}
+
+/// Notebooks store text either as a string or as a list of lines.
+fn multiline_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Shows an output as text, naming the kinds of data that can't be shown as text, like images.
+fn output_text(output: &Value) -> String {
+    match output.get("output_type").and_then(Value::as_str) {
+        Some("stream") => multiline_text(output.get("text")),
+        Some("error") => {
+            let field = |name: &str| output.get(name).and_then(Value::as_str).unwrap_or_default();
+            format!("{}: {}", field("ename"), field("evalue"))
+        }
+        _ => {
+            let Some(data) = output.get("data").and_then(Value::as_object) else {
+                return String::new();
+            };
+            if let Some(text) = data.get("text/plain") {
+                multiline_text(Some(text))
+            } else {
+                data.keys()
+                    .map(|mime_type| format!("<{mime_type}>"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+        }
+    }
+}
+
+/// A cell of the old notebook and of the new one that are the same cell, or a cell of only one
+/// of them, which was removed or added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct AlignedCell {
+    old: Option<usize>,
+    new: Option<usize>,
+}
+
+/// Aligns the cells of two versions of a notebook. Cells are the same if they have the same
+/// id, or for notebooks without ids, the same source. Between those, removed and added cells of
+/// the same kind are paired up as modified cells.
+fn align_cells(old: &[NotebookCell], new: &[NotebookCell]) -> Vec<AlignedCell> {
+    let same_cell = |old: &NotebookCell, new: &NotebookCell| match (&old.id, &new.id) {
+        (Some(old_id), Some(new_id)) => old_id == new_id,
+        _ => old.kind == new.kind && old.source == new.source,
+    };
+
+    // The longest common subsequence of cells, from the end of both notebooks.
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for old_ix in (0..old.len()).rev() {
+        for new_ix in (0..new.len()).rev() {
+            lengths[old_ix][new_ix] = if same_cell(&old[old_ix], &new[new_ix]) {
+                lengths[old_ix + 1][new_ix + 1] + 1
+            } else {
+                lengths[old_ix + 1][new_ix].max(lengths[old_ix][new_ix + 1])
+            };
+        }
+    }
+
+    let mut aligned = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let (mut old_ix, mut new_ix) = (0, 0);
+    while old_ix < old.len() || new_ix < new.len() {
+        if old_ix < old.len() && new_ix < new.len() && same_cell(&old[old_ix], &new[new_ix]) {
+            pair_changed_cells(&mut removed, &mut added, old, new, &mut aligned);
+            aligned.push(AlignedCell {
+                old: Some(old_ix),
+                new: Some(new_ix),
+            });
+            old_ix += 1;
+            new_ix += 1;
+        } else if new_ix == new.len()
+            || (old_ix < old.len() && lengths[old_ix + 1][new_ix] >= lengths[old_ix][new_ix + 1])
+        {
+            removed.push(old_ix);
+            old_ix += 1;
+        } else {
+            added.push(new_ix);
+            new_ix += 1;
+        }
+    }
+    pair_changed_cells(&mut removed, &mut added, old, new, &mut aligned);
+    aligned
+}
+
+/// Pairs up the cells removed and added between two unchanged cells, in order. Each removed
+/// cell is paired with the next added cell of the same kind, and cells that can't be paired
+/// are removed or added.
+fn pair_changed_cells(
+    removed: &mut Vec<usize>,
+    added: &mut Vec<usize>,
+    old: &[NotebookCell],
+    new: &[NotebookCell],
+    aligned: &mut Vec<AlignedCell>,
+) {
+    let mut added = added.drain(..).collect::<VecDeque<_>>();
+    for old_ix in removed.drain(..) {
+        let Some(pair_ix) = added
+            .iter()
+            .position(|&new_ix| new[new_ix].kind == old[old_ix].kind)
+        else {
+            aligned.push(AlignedCell {
+                old: Some(old_ix),
+                new: None,
+            });
+            continue;
+        };
+        for new_ix in added.drain(..pair_ix) {
+            aligned.push(AlignedCell {
+                old: None,
+                new: Some(new_ix),
+            });
+        }
+        aligned.push(AlignedCell {
+            old: Some(old_ix),
+            new: added.pop_front(),
+        });
+    }
+    aligned.extend(added.into_iter().map(|new_ix| AlignedCell {
+        old: None,
+        new: Some(new_ix),
+    }));
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct NotebookDiffOptions {
+    show_outputs: bool,
+    show_metadata: bool,
+}
+
+/// Lays out both versions of a notebook as text, so that aligned cells are diffed against each
+/// other. Outputs and metadata that aren't shown only get a line on the new side when they
+/// changed.
+fn notebook_diff_texts(
+    old: &[NotebookCell],
+    new: &[NotebookCell],
+    aligned: &[AlignedCell],
+    options: NotebookDiffOptions,
+) -> (String, String) {
+    let mut old_text = String::new();
+    let mut new_text = String::new();
+    for (ix, cell) in aligned.iter().enumerate() {
+        let old_cell = cell.old.map(|ix| &old[ix]);
+        let new_cell = cell.new.map(|ix| &new[ix]);
+        if let Some(old_cell) = old_cell {
+            write_cell(&mut old_text, ix, old_cell, options);
+        }
+        if let Some(new_cell) = new_cell {
+            write_cell(&mut new_text, ix, new_cell, options);
+            if let Some(old_cell) = old_cell {
+                if !options.show_outputs && old_cell.outputs != new_cell.outputs {
+                    new_text.push_str(&format!(
+                        "── Outputs changed ({} → {}) ──\n",
+                        old_cell.outputs.len(),
+                        new_cell.outputs.len()
+                    ));
+                }
+                if !options.show_metadata
+                    && (old_cell.metadata != new_cell.metadata
+                        || old_cell.execution_count != new_cell.execution_count)
+                {
+                    new_text.push_str("── Metadata changed ──\n");
+                }
+            }
+        }
+        if old_cell.is_some() {
+            old_text.push('\n');
+        }
+        if new_cell.is_some() {
+            new_text.push('\n');
+        }
+    }
+    (old_text, new_text)
+}
+
+fn write_cell(text: &mut String, ix: usize, cell: &NotebookCell, options: NotebookDiffOptions) {
+    text.push_str(&format!("━━ Cell {} · {} ━━\n", ix + 1, cell.kind.label()));
+    push_lines(text, &cell.source);
+    if options.show_outputs && !cell.outputs.is_empty() {
+        text.push_str("── Outputs ──\n");
+        for output in &cell.outputs {
+            push_lines(text, output);
+        }
+    }
+    if options.show_metadata {
+        text.push_str("── Metadata ──\n");
+        if let Some(execution_count) = cell.execution_count {
+            text.push_str(&format!("execution_count: {execution_count}\n"));
+        }
+        if cell
+            .metadata
+            .as_object()
+            .is_none_or(|metadata| !metadata.is_empty())
+        {
+            push_lines(
+                text,
+                &serde_json::to_string_pretty(&cell.metadata).unwrap_or_default(),
+            );
+        }
+    }
+}
+
+fn push_lines(text: &mut String, lines: &str) {
+    if lines.is_empty() {
+        return;
+    }
+    text.push_str(lines);
+    if !lines.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+/// The two versions of the notebook being diffed.
+enum NotebookDiffSource {
+    /// The working copy, against HEAD.
+    Uncommitted {
+        buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+    },
+    /// The changes made by a commit.
+    Commit {
+        sha: SharedString,
+        old_text: Option<String>,
+        new_text: Option<String>,
+    },
+}
+
+pub struct NotebookDiffView {
+    editor: Entity<Editor>,
+    /// The new notebook laid out as text, which the old one is diffed against.
+    new_buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    source: NotebookDiffSource,
+    path: RepoPath,
+    options: NotebookDiffOptions,
+    /// Why the notebooks couldn't be diffed, if they couldn't.
+    error: Option<SharedString>,
+    source_changes_tx: watch::Sender<()>,
+    _recalculate_diff_task: Task<Result<()>>,
+}
+
+impl NotebookDiffView {
+    /// Opens the diff of the notebook in the buffer against HEAD.
+    pub fn open_uncommitted(
+        buffer: Entity<Buffer>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let (_, path) = project
+                .update(cx, |project, cx| {
+                    project
+                        .git_store()
+                        .read(cx)
+                        .repository_and_path_for_buffer_id(buffer.read(cx).remote_id(), cx)
+                })
+                .context("the notebook isn't in a git repository")?;
+            let diff = project
+                .update(cx, |project, cx| {
+                    project.open_uncommitted_diff(buffer.clone(), cx)
+                })
+                .await?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let source = NotebookDiffSource::Uncommitted { buffer, diff };
+                Self::add_to_workspace(source, path, project, workspace, window, cx)
+            })
+        })
+    }
+
+    /// Opens the changes a commit made to a notebook.
+    pub fn open_commit(
+        sha: SharedString,
+        path: RepoPath,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let commit_diff = repository.update(cx, |repository, _| {
+            repository.load_commit_diff(sha.to_string())
+        });
+        window.spawn(cx, async move |cx| {
+            let commit_diff = commit_diff.await??;
+            let file = commit_diff
+                .files
+                .into_iter()
+                .find(|file| file.path == path)
+                .with_context(|| format!("the commit didn't change {}", path.as_unix_str()))?;
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let source = NotebookDiffSource::Commit {
+                    sha,
+                    old_text: file.old_text,
+                    new_text: file.new_text,
+                };
+                Self::add_to_workspace(source, path, project, workspace, window, cx)
+            })
+        })
+    }
+
+    fn add_to_workspace(
+        source: NotebookDiffSource,
+        path: RepoPath,
+        project: Entity<Project>,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let diff_view = cx.new(|cx| Self::new(source, path, project, window, cx));
+        let pane = workspace.active_pane();
+        pane.update(cx, |pane, cx| {
+            pane.add_item(Box::new(diff_view.clone()), true, true, None, window, cx);
+        });
+        diff_view
+    }
+
+    fn new(
+        source: NotebookDiffSource,
+        path: RepoPath,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let new_buffer = cx.new(|cx| {
+            let mut buffer = Buffer::local("", cx);
+            buffer.set_capability(Capability::ReadOnly, cx);
+            buffer
+        });
+        let diff = cx.new(|cx| BufferDiff::new(&new_buffer.read(cx).text_snapshot(), cx));
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(new_buffer.clone(), cx);
+            multibuffer.add_diff(diff.clone(), cx);
+            multibuffer
+        });
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        });
+
+        let (source_changes_tx, mut source_changes_rx) = watch::channel(());
+        if let NotebookDiffSource::Uncommitted {
+            buffer,
+            diff: source_diff,
+        } = &source
+        {
+            cx.subscribe(buffer, |this, _, event, _| {
+                if let language::BufferEvent::Edited = event {
+                    this.source_changes_tx.send(()).ok();
+                }
+            })
+            .detach();
+            // The diff changes when HEAD does.
+            cx.subscribe(source_diff, |this, _, event, _| {
+                if let BufferDiffEvent::DiffChanged(_) = event {
+                    this.source_changes_tx.send(()).ok();
+                }
+            })
+            .detach();
+        }
+
+        let mut this = Self {
+            editor,
+            new_buffer,
+            diff,
+            source,
+            path,
+            options: NotebookDiffOptions::default(),
+            error: None,
+            source_changes_tx,
+            _recalculate_diff_task: cx.spawn(async move |this, cx| {
+                while source_changes_rx.recv().await.is_ok() {
+                    loop {
+                        let mut timer = cx
+                            .background_executor()
+                            .timer(RECALCULATE_DIFF_DEBOUNCE)
+                            .fuse();
+                        let mut recv = pin!(source_changes_rx.recv().fuse());
+                        select_biased! {
+                            _ = timer => break,
+                            _ = recv => continue,
+                        }
+                    }
+                    this.update(cx, |this, cx| this.recalculate(cx))?;
+                }
+                Ok(())
+            }),
+        };
+        this.recalculate(cx);
+        this
+    }
+
+    fn texts(&self, cx: &App) -> (Option<String>, Option<String>) {
+        match &self.source {
+            NotebookDiffSource::Uncommitted { buffer, diff } => (
+                diff.read(cx).base_text_string(cx),
+                Some(buffer.read(cx).text()),
+            ),
+            NotebookDiffSource::Commit {
+                old_text, new_text, ..
+            } => (old_text.clone(), new_text.clone()),
+        }
+    }
+
+    /// Lays out both notebooks as text again, and diffs them.
+    fn recalculate(&mut self, cx: &mut Context<Self>) {
+        let (old_text, new_text) = self.texts(cx);
+        let cells = parse_notebook(old_text.as_deref())
+            .context("reading the old notebook")
+            .and_then(|old| {
+                let new =
+                    parse_notebook(new_text.as_deref()).context("reading the new notebook")?;
+                Ok((old, new))
+            });
+        let (old_text, new_text) = match cells {
+            Ok((old, new)) => {
+                self.error = None;
+                let aligned = align_cells(&old, &new);
+                notebook_diff_texts(&old, &new, &aligned, self.options)
+            }
+            Err(error) => {
+                self.error = Some(format!("{error:#}").into());
+                (String::new(), String::new())
+            }
+        };
+
+        self.new_buffer.update(cx, |buffer, cx| {
+            buffer.set_text(new_text, cx);
+        });
+        let new_snapshot = self.new_buffer.read(cx).text_snapshot();
+        let diff_updated = self.diff.update(cx, |diff, cx| {
+            diff.set_base_text(Some(old_text.into()), None, new_snapshot, cx)
+        });
+        cx.spawn(async move |_, _| diff_updated.await.ok()).detach();
+        cx.notify();
+    }
+
+    fn toggle_outputs(&mut self, _: &ToggleOutputs, _: &mut Window, cx: &mut Context<Self>) {
+        self.options.show_outputs = !self.options.show_outputs;
+        self.recalculate(cx);
+    }
+
+    fn toggle_metadata(&mut self, _: &ToggleMetadata, _: &mut Window, cx: &mut Context<Self>) {
+        self.options.show_metadata = !self.options.show_metadata;
+        self.recalculate(cx);
+    }
+
+    fn file_name(&self) -> SharedString {
+        self.path
+            .file_name()
+            .unwrap_or("untitled")
+            .to_string()
+            .into()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        h_flex()
+            .px_3()
+            .py_1()
+            .gap_3()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Checkbox::new("show-outputs", self.options.show_outputs.into())
+                    .label("Show Outputs")
+                    .label_size(LabelSize::Small)
+                    .on_click({
+                        let view = view.clone();
+                        move |_: &ToggleState, window, cx| {
+                            view.update(cx, |view, cx| {
+                                view.toggle_outputs(&ToggleOutputs, window, cx)
+                            })
+                        }
+                    }),
+            )
+            .child(
+                Checkbox::new("show-metadata", self.options.show_metadata.into())
+                    .label("Show Metadata")
+                    .label_size(LabelSize::Small)
+                    .on_click(move |_: &ToggleState, window, cx| {
+                        view.update(cx, |view, cx| {
+                            view.toggle_metadata(&ToggleMetadata, window, cx)
+                        })
+                    }),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    Label::new(error)
+                        .size(LabelSize::Small)
+                        .color(Color::Error)
+                        .truncate(),
+                )
+            })
+    }
+}
+
+impl EventEmitter<EditorEvent> for NotebookDiffView {}
+
+impl Focusable for NotebookDiffView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for NotebookDiffView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Diff).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        match &self.source {
+            NotebookDiffSource::Uncommitted { .. } => {
+                format!("{} (Uncommitted)", self.file_name()).into()
+            }
+            NotebookDiffSource::Commit { sha, .. } => format!(
+                "{} @ {}",
+                self.file_name(),
+                sha.get(..git::SHORT_SHA_LENGTH).unwrap_or(sha)
+            )
+            .into(),
+        }
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        let path = self.path.as_unix_str();
+        Some(match &self.source {
+            NotebookDiffSource::Uncommitted { .. } => format!("{path}: HEAD ↔ Working Tree").into(),
+            NotebookDiffSource::Commit { sha, .. } => format!("{path} @ {sha}").into(),
+        })
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Notebook Diff View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<gpui::AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+}
+
+impl Render for NotebookDiffView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("NotebookDiffView")
+            .on_action(cx.listener(Self::toggle_outputs))
+            .on_action(cx.listener(Self::toggle_metadata))
+            .size_full()
+            .child(self.render_header(cx))
+            .child(div().flex_1().min_h_0().child(self.editor.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn notebook(cells: Value) -> String {
+        json!({
+            "cells": cells,
+            "metadata": {},
+            "nbformat": 4,
+            "nbformat_minor": 5,
+        })
+        .to_string()
+    }
+
+    fn code(id: &str, source: &[&str], outputs: Value) -> Value {
+        json!({
+            "id": id,
+            "cell_type": "code",
+            "execution_count": 1,
+            "metadata": {},
+            "source": source,
+            "outputs": outputs,
+        })
+    }
+
+    fn markdown(id: &str, source: &str) -> Value {
+        json!({
+            "id": id,
+            "cell_type": "markdown",
+            "metadata": {},
+            "source": source,
+        })
+    }
+
+    #[test]
+    fn test_parse_notebook() {
+        let text = notebook(json!([
+            markdown("intro", "# Title"),
+            code(
+                "load",
+                &["import pandas as pd\n", "df = pd.read_csv('a.csv')"],
+                json!([
+                    {"output_type": "stream", "name": "stdout", "text": ["loaded\n"]},
+                    {"output_type": "display_data", "data": {"image/png": "iVBOR"}},
+                    {"output_type": "execute_result", "data": {"text/plain": "42"}},
+                    {"output_type": "error", "ename": "KeyError", "evalue": "'x'"},
+                ]),
+            ),
+        ]));
+        let cells = parse_notebook(Some(&text)).unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].kind, CellKind::Markdown);
+        assert_eq!(cells[0].source, "# Title");
+        assert_eq!(cells[1].id.as_deref(), Some("load"));
+        assert_eq!(
+            cells[1].source,
+            "import pandas as pd\ndf = pd.read_csv('a.csv')"
+        );
+        assert_eq!(
+            cells[1].outputs,
+            ["loaded\n", "<image/png>", "42", "KeyError: 'x'"]
+        );
+
+        assert_eq!(parse_notebook(None).unwrap(), Vec::new());
+        assert!(parse_notebook(Some("{")).is_err());
+    }
+
+    #[test]
+    fn test_align_cells() {
+        let cells = |cells: Value| parse_notebook(Some(&notebook(cells))).unwrap();
+        let aligned = |old: &[NotebookCell], new: &[NotebookCell]| {
+            align_cells(old, new)
+                .into_iter()
+                .map(|cell| (cell.old, cell.new))
+                .collect::<Vec<_>>()
+        };
+
+        // Cells with ids are aligned by id, even when their source changed.
+        let old = cells(json!([
+            markdown("a", "# Title"),
+            code("b", &["x = 1"], json!([])),
+            code("c", &["print(x)"], json!([])),
+        ]));
+        let new = cells(json!([
+            markdown("a", "# Title"),
+            code("d", &["y = 2"], json!([])),
+            code("c", &["print(x + 1)"], json!([])),
+        ]));
+        assert_eq!(
+            aligned(&old, &new),
+            [(Some(0), Some(0)), (Some(1), Some(1)), (Some(2), Some(2)),]
+        );
+
+        // Without ids, cells are aligned by source, and changed cells of the same kind are
+        // paired up.
+        let without_ids = |cells: Vec<NotebookCell>| {
+            cells
+                .into_iter()
+                .map(|cell| NotebookCell { id: None, ..cell })
+                .collect::<Vec<_>>()
+        };
+        let old = without_ids(cells(json!([
+            markdown("", "# Title"),
+            code("", &["x = 1"], json!([])),
+            code("", &["print(x)"], json!([])),
+        ])));
+        let new = without_ids(cells(json!([
+            markdown("", "# Title"),
+            markdown("", "Some notes"),
+            code("", &["x = 2"], json!([])),
+            code("", &["print(x)"], json!([])),
+        ])));
+        assert_eq!(
+            aligned(&old, &new),
+            [
+                (Some(0), Some(0)),
+                (None, Some(1)),
+                (Some(1), Some(2)),
+                (Some(2), Some(3)),
+            ]
+        );
+
+        assert_eq!(
+            aligned(&[], &new),
+            [
+                (None, Some(0)),
+                (None, Some(1)),
+                (None, Some(2)),
+                (None, Some(3))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notebook_diff_texts() {
+        let old = parse_notebook(Some(&notebook(json!([
+            markdown("a", "# Title"),
+            code(
+                "b",
+                &["x = 1\n", "x"],
+                json!([{"output_type": "execute_result", "data": {"text/plain": "1"}}])
+            ),
+        ]))))
+        .unwrap();
+        let new = parse_notebook(Some(&notebook(json!([
+            markdown("a", "# Title"),
+            code(
+                "b",
+                &["x = 2\n", "x"],
+                json!([{"output_type": "execute_result", "data": {"text/plain": "2"}}])
+            ),
+        ]))))
+        .unwrap();
+        let aligned = align_cells(&old, &new);
+
+        let (old_text, new_text) =
+            notebook_diff_texts(&old, &new, &aligned, NotebookDiffOptions::default());
+        assert_eq!(
+            old_text,
+            "\
+━━ Cell 1 · markdown ━━
+# Title
+
+━━ Cell 2 · code ━━
+x = 1
+x
+
+"
+        );
+        assert_eq!(
+            new_text,
+            "\
+━━ Cell 1 · markdown ━━
+# Title
+
+━━ Cell 2 · code ━━
+x = 2
+x
+── Outputs changed (1 → 1) ──
+
+"
+        );
+
+        let options = NotebookDiffOptions {
+            show_outputs: true,
+            show_metadata: false,
+        };
+        let (old_text, new_text) = notebook_diff_texts(&old, &new, &aligned, options);
+        assert!(old_text.contains("── Outputs ──\n1\n"));
+        assert!(new_text.contains("── Outputs ──\n2\n"));
+        assert!(!new_text.contains("Outputs changed"));
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     conflict_view::ConflictAddon,
     git_panel::{GitPanel, GitPanelAddon, GitStatusEntry},
     git_panel_settings::GitPanelSettings,
+    notebook_diff_view,
     remote_button::{render_publish_button, render_push_button},
     resolve_active_repository,
 };
@@ -18,7 +19,7 @@ use editor::{
 use git::repository::DiffType;
 
 use git::{
-    Commit, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
+    Commit, DiffNotebook, StageAll, StageAndNext, ToggleStaged, UnstageAll, UnstageAndNext,
     repository::{Branch, RepoPath, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::FileStatus,
 };
@@ -1431,6 +1432,10 @@ impl Render for ProjectDiffToolbar {
         let focus_handle = project_diff.focus_handle(cx);
         let button_states = project_diff.read(cx).button_states(cx);
         let review_count = project_diff.read(cx).total_review_comment_count();
+        let notebook_at_cursor = project_diff
+            .read(cx)
+            .active_path(cx)
+            .is_some_and(|project_path| notebook_diff_view::is_notebook(&project_path.path));
 
         h_group_xl()
             .my_neg_1()
@@ -1572,6 +1577,19 @@ impl Render for ProjectDiffToolbar {
                             })),
                     ),
             )
+            .when(notebook_at_cursor, |el| {
+                el.child(vertical_divider()).child(
+                    Button::new("diff-notebook", "Notebook Diff")
+                        .tooltip(Tooltip::for_action_title_in(
+                            "Show the changes to the notebook cell by cell",
+                            &DiffNotebook,
+                            &focus_handle,
+                        ))
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.dispatch_action(&DiffNotebook, window, cx)
+                        })),
+                )
+            })
             // "Send Review to Agent" button (only shown when there are review comments)
             .when(review_count > 0, |el| {
                 el.child(vertical_divider()).child(
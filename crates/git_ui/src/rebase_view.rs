@@ -0,0 +1,855 @@
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use git::SHORT_SHA_LENGTH;
+use git::rebase::{RebaseAction, RebaseCommand, RebaseState, RebaseTodoEntry};
+use gpui::{
+    AnyEntity, App, Context, Entity, EventEmitter, FocusHandle, Focusable, IntoElement, Render,
+    Subscription, Task, WeakEntity, Window, actions,
+};
+use project::{
+    Project, ProjectPath,
+    git_store::{Repository, RepositoryEvent},
+};
+use std::any::{Any, TypeId};
+use std::sync::Arc;
+use ui::{ContextMenu, DropdownMenu, Tooltip, prelude::*};
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, SaveOptions},
+    notifications::DetachAndPromptErr,
+};
+
+actions!(
+    interactive_rebase,
+    [
+        /// Moves the selected commit up in the todo list.
+        MoveUp,
+        /// Moves the selected commit down in the todo list.
+        MoveDown,
+        /// Starts the rebase following the todo list.
+        Start,
+        /// Continues the rebase in progress.
+        Continue,
+        /// Skips the commit the rebase in progress stopped at.
+        Skip,
+        /// Aborts the rebase in progress.
+        Abort,
+    ]
+);
+
+/// A commit of the todo list being edited.
+struct TodoItem {
+    entry: RebaseTodoEntry,
+    /// Edits the new message of the commit, once it was reworded.
+    message_editor: Option<Entity<Editor>>,
+}
+
+/// Edits the todo list of an interactive rebase, then follows the rebase until it finishes.
+pub struct InteractiveRebaseView {
+    repository: Entity<Repository>,
+    onto: SharedString,
+    todo: Vec<TodoItem>,
+    selected_ix: Option<usize>,
+    /// Whether a rebase command started from the view is running.
+    running: bool,
+    /// Whether the last command completed the rebase.
+    finished: bool,
+    focus_handle: FocusHandle,
+    _repository_subscription: Subscription,
+}
+
+#[derive(Clone)]
+struct DraggedTodoItem {
+    ix: usize,
+    action: RebaseAction,
+    subject: SharedString,
+}
+
+impl InteractiveRebaseView {
+    /// Opens the todo list of a rebase of HEAD onto `onto`, which defaults to the upstream of
+    /// the current branch, or the default branch. Follows the rebase in progress instead, if
+    /// there is one.
+    pub fn open(
+        onto: Option<String>,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let rebase = repository.read(cx).rebase.clone();
+        let upstream = repository
+            .read(cx)
+            .branch
+            .as_ref()
+            .and_then(|branch| branch.upstream.as_ref())
+            .filter(|upstream| !upstream.tracking.is_gone())
+            .map(|upstream| upstream.ref_name.to_string());
+        let onto = onto.or(upstream);
+        let default_branch = (rebase.is_none() && onto.is_none())
+            .then(|| repository.update(cx, |repository, _| repository.default_branch(true)));
+
+        window
+            .spawn(cx, async move |cx| {
+                let (onto, todo) = if let Some(rebase) = rebase {
+                    (rebase.onto, Vec::new())
+                } else {
+                    let onto = match onto {
+                        Some(onto) => onto,
+                        None => default_branch
+                            .context("no branch to rebase onto")?
+                            .await??
+                            .context("the current branch has no upstream to rebase onto")?
+                            .to_string(),
+                    };
+                    let todo = repository
+                        .update(cx, |repository, _| repository.rebase_todo(onto.clone()))
+                        .await??;
+                    (onto.into(), todo)
+                };
+
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let pane = workspace.active_pane().clone();
+                    let existing = pane.read(cx).items().find_map(|item| {
+                        item.downcast::<Self>()
+                            .filter(|view| view.read(cx).repository == repository)
+                    });
+                    if let Some(view) = existing {
+                        view.update(cx, |view, cx| view.set_todo(onto, todo, cx));
+                        pane.update(cx, |pane, cx| {
+                            if let Some(ix) = pane.index_for_item(&view) {
+                                pane.activate_item(ix, true, true, window, cx);
+                            }
+                        });
+                    } else {
+                        let view = cx.new(|cx| Self::new(onto, todo, repository, cx));
+                        pane.update(cx, |pane, cx| {
+                            pane.add_item(Box::new(view), true, true, None, window, cx);
+                        });
+                    }
+                })
+            })
+            .detach_and_prompt_err(
+                "Failed to start an interactive rebase",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+    }
+
+    fn new(
+        onto: SharedString,
+        todo: Vec<RebaseTodoEntry>,
+        repository: Entity<Repository>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let repository_subscription =
+            cx.subscribe(&repository, |_, _, event: &RepositoryEvent, cx| {
+                if matches!(
+                    event,
+                    RepositoryEvent::RebaseChanged | RepositoryEvent::StatusesChanged
+                ) {
+                    cx.notify();
+                }
+            });
+        let mut this = Self {
+            repository,
+            onto: SharedString::default(),
+            todo: Vec::new(),
+            selected_ix: None,
+            running: false,
+            finished: false,
+            focus_handle: cx.focus_handle(),
+            _repository_subscription: repository_subscription,
+        };
+        this.set_todo(onto, todo, cx);
+        this
+    }
+
+    fn set_todo(&mut self, onto: SharedString, todo: Vec<RebaseTodoEntry>, cx: &mut Context<Self>) {
+        self.onto = onto;
+        self.selected_ix = (!todo.is_empty()).then_some(0);
+        self.todo = todo
+            .into_iter()
+            .map(|entry| TodoItem {
+                entry,
+                message_editor: None,
+            })
+            .collect();
+        self.finished = false;
+        cx.notify();
+    }
+
+    fn set_action(
+        &mut self,
+        ix: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(item) = self.todo.get(ix) else {
+            return;
+        };
+        let message_editor = match &item.message_editor {
+            None if action == RebaseAction::Reword => {
+                Some(self.create_message_editor(&item.entry, window, cx))
+            }
+            message_editor => message_editor.clone(),
+        };
+        let item = &mut self.todo[ix];
+        item.entry.action = action;
+        item.message_editor = message_editor;
+        self.selected_ix = Some(ix);
+        cx.notify();
+    }
+
+    /// Creates an editor for the new message of a commit, filled with its current message.
+    fn create_message_editor(
+        &self,
+        entry: &RebaseTodoEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        let subject = entry.subject.clone();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(1, 8, window, cx);
+            editor.set_placeholder_text("Commit message", window, cx);
+            editor.set_text(subject.to_string(), window, cx);
+            editor
+        });
+
+        // Only the subject of the commit is known, so the rest of the message is loaded.
+        let details = self
+            .repository
+            .update(cx, |repository, _| repository.show(entry.sha.to_string()));
+        let weak_editor = editor.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let details = details.await??;
+            weak_editor.update_in(cx, |editor, window, cx| {
+                if editor.text(cx) == *subject {
+                    editor.set_text(details.message.trim_end(), window, cx);
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+        editor
+    }
+
+    /// Moves the commit at `from` so that it becomes the commit at `to`.
+    fn move_item(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to || from >= self.todo.len() || to >= self.todo.len() {
+            return;
+        }
+        let item = self.todo.remove(from);
+        self.todo.insert(to, item);
+        self.selected_ix = Some(to);
+        cx.notify();
+    }
+
+    fn move_up(&mut self, _: &MoveUp, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_ix.filter(|ix| *ix > 0) {
+            self.move_item(ix, ix - 1, cx);
+        }
+    }
+
+    fn move_down(&mut self, _: &MoveDown, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ix) = self.selected_ix {
+            self.move_item(ix, ix + 1, cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.todo.is_empty() {
+            return;
+        }
+        self.selected_ix = Some(match self.selected_ix {
+            Some(ix) if ix + 1 < self.todo.len() => ix + 1,
+            Some(ix) => ix,
+            None => 0,
+        });
+        cx.notify();
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.todo.is_empty() {
+            return;
+        }
+        self.selected_ix = Some(self.selected_ix.map_or(0, |ix| ix.saturating_sub(1)));
+        cx.notify();
+    }
+
+    /// Explains why the todo list can't be applied, if it can't.
+    fn todo_error(&self) -> Option<&'static str> {
+        let first = self
+            .todo
+            .iter()
+            .find(|item| item.entry.action != RebaseAction::Drop)?;
+        first
+            .entry
+            .action
+            .melds_into_previous()
+            .then_some("The first commit can't be squashed or fixed up into a previous one")
+    }
+
+    fn start(&mut self, _: &Start, window: &mut Window, cx: &mut Context<Self>) {
+        if self.running || self.todo.is_empty() || self.todo_error().is_some() {
+            return;
+        }
+        let todo = self
+            .todo
+            .iter()
+            .map(|item| {
+                let mut entry = item.entry.clone();
+                if entry.action == RebaseAction::Reword {
+                    entry.message = item
+                        .message_editor
+                        .as_ref()
+                        .map(|editor| editor.read(cx).text(cx))
+                        .filter(|message| !message.trim().is_empty());
+                }
+                entry
+            })
+            .collect();
+        let onto = self.onto.to_string();
+        let task = self.repository.update(cx, |repository, cx| {
+            repository.rebase_interactive(onto, todo, cx)
+        });
+        self.run(task, true, window, cx);
+    }
+
+    fn continue_rebase(&mut self, _: &Continue, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_command(RebaseCommand::Continue, window, cx);
+    }
+
+    fn skip(&mut self, _: &Skip, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_command(RebaseCommand::Skip, window, cx);
+    }
+
+    fn abort(&mut self, _: &Abort, window: &mut Window, cx: &mut Context<Self>) {
+        self.run_command(RebaseCommand::Abort, window, cx);
+    }
+
+    fn run_command(&mut self, command: RebaseCommand, window: &mut Window, cx: &mut Context<Self>) {
+        if self.running || self.repository.read(cx).rebase.is_none() {
+            return;
+        }
+        let task = self
+            .repository
+            .update(cx, |repository, cx| repository.rebase_command(command, cx));
+        self.run(task, command != RebaseCommand::Abort, window, cx);
+    }
+
+    /// Runs a rebase command. Unless the rebase is left in progress, it finished once a command
+    /// that goes on with it succeeds.
+    fn run(
+        &mut self,
+        task: Task<Result<()>>,
+        goes_on: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.running = true;
+        cx.notify();
+        cx.spawn_in(window, async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                this.running = false;
+                this.finished = goes_on && result.is_ok();
+                cx.notify();
+            })?;
+            result
+        })
+        .detach_and_prompt_err("Failed to rebase", window, cx, |_, _, _| None);
+    }
+
+    fn render_header(
+        &self,
+        title: SharedString,
+        buttons: impl IntoElement,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        h_flex()
+            .h(rems_from_px(41.))
+            .pl_3()
+            .pr_2()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new(title).color(Color::Muted).truncate())
+            .child(buttons)
+    }
+
+    fn render_todo(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = format!(
+            "Rebase {} onto {}",
+            pluralize_commits(self.todo.len()),
+            self.onto
+        );
+        let error = self.todo_error();
+        let buttons = Button::new("start-rebase", "Start Rebase")
+            .style(ButtonStyle::Filled)
+            .label_size(LabelSize::Small)
+            .disabled(self.running || self.todo.is_empty() || error.is_some())
+            .on_click(cx.listener(|this, _, window, cx| this.start(&Start, window, cx)));
+
+        let view = cx.entity();
+        v_flex()
+            .size_full()
+            .child(self.render_header(title.into(), buttons, cx))
+            .when_some(error, |this, error| {
+                this.child(
+                    h_flex()
+                        .px_3()
+                        .py_1()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Warning)
+                                .size(IconSize::Small)
+                                .color(Color::Warning),
+                        )
+                        .child(Label::new(error).size(LabelSize::Small)),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("rebase-todo-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .when(self.todo.is_empty(), |this| {
+                        this.child(
+                            div().p_3().child(
+                                Label::new(format!(
+                                    "There are no commits to rebase onto {}",
+                                    self.onto
+                                ))
+                                .color(Color::Muted),
+                            ),
+                        )
+                    })
+                    .children(
+                        self.todo
+                            .iter()
+                            .enumerate()
+                            .map(|(ix, item)| self.render_todo_item(ix, item, &view, window, cx)),
+                    ),
+            )
+    }
+
+    fn render_todo_item(
+        &self,
+        ix: usize,
+        item: &TodoItem,
+        view: &Entity<Self>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let entry = &item.entry;
+        let action = entry.action;
+        let menu = ContextMenu::build(window, cx, {
+            let view = view.clone();
+            move |mut menu, window, _| {
+                for candidate in RebaseAction::ALL {
+                    menu = menu.toggleable_entry(
+                        candidate.label(),
+                        candidate == action,
+                        IconPosition::Start,
+                        None,
+                        window.handler_for(&view, move |this, window, cx| {
+                            this.set_action(ix, candidate, window, cx)
+                        }),
+                    );
+                }
+                menu
+            }
+        });
+        let dragged = DraggedTodoItem {
+            ix,
+            action,
+            subject: entry.subject.clone(),
+        };
+
+        v_flex()
+            .id(("rebase-todo-item", ix))
+            .w_full()
+            .px_3()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .cursor_move()
+            .when(self.selected_ix == Some(ix), |this| {
+                this.bg(cx.theme().colors().element_selected)
+            })
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.selected_ix = Some(ix);
+                cx.notify();
+            }))
+            .on_drag(dragged, |dragged, _, _, cx| cx.new(|_| dragged.clone()))
+            .drag_over::<DraggedTodoItem>(|style, _, _, cx| {
+                style.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(cx.listener(move |this, dragged: &DraggedTodoItem, _, cx| {
+                this.move_item(dragged.ix, ix, cx);
+            }))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        DropdownMenu::new(("rebase-action", ix), action.label(), menu)
+                            .trigger_size(ButtonSize::Compact)
+                            .trigger_tooltip(Tooltip::text("What to do with the commit")),
+                    )
+                    .child(
+                        Label::new(short_sha(&entry.sha))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        Label::new(entry.subject.clone())
+                            .size(LabelSize::Small)
+                            .truncate()
+                            .when(action == RebaseAction::Drop, |label| {
+                                label.strikethrough().color(Color::Muted)
+                            }),
+                    ),
+            )
+            .when_some(
+                item.message_editor
+                    .clone()
+                    .filter(|_| action == RebaseAction::Reword),
+                |this, editor| {
+                    this.child(
+                        div()
+                            .p_1()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(cx.theme().colors().editor_background)
+                            .child(editor),
+                    )
+                },
+            )
+    }
+
+    fn render_progress(&self, rebase: &RebaseState, cx: &mut Context<Self>) -> impl IntoElement {
+        let title = format!(
+            "Rebasing {} onto {}: {} of {} applied",
+            rebase.head_name.as_deref().unwrap_or("detached HEAD"),
+            short_sha(&rebase.onto),
+            rebase.done.len(),
+            rebase.done.len() + rebase.todo.len(),
+        );
+        let has_conflicts = !self
+            .repository
+            .read(cx)
+            .merge
+            .merge_heads_by_conflicted_path
+            .is_empty();
+        let hint = if has_conflicts {
+            Some("Resolve the conflicts and stage the files, then continue")
+        } else if rebase.stopped_sha.is_some() {
+            Some("Amend the commit if needed, then continue")
+        } else {
+            None
+        };
+        let buttons = h_flex()
+            .gap_1()
+            .child(
+                Button::new("abort-rebase", "Abort")
+                    .label_size(LabelSize::Small)
+                    .disabled(self.running)
+                    .on_click(cx.listener(|this, _, window, cx| this.abort(&Abort, window, cx))),
+            )
+            .child(
+                Button::new("skip-rebase", "Skip")
+                    .label_size(LabelSize::Small)
+                    .disabled(self.running)
+                    .tooltip(Tooltip::text("Leave out the commit the rebase stopped at"))
+                    .on_click(cx.listener(|this, _, window, cx| this.skip(&Skip, window, cx))),
+            )
+            .child(
+                Button::new("continue-rebase", "Continue")
+                    .style(ButtonStyle::Filled)
+                    .label_size(LabelSize::Small)
+                    .disabled(self.running || has_conflicts)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.continue_rebase(&Continue, window, cx)
+                    })),
+            );
+
+        // The todo list abbreviates the commits that the rebase stops at.
+        let stopped_ix = rebase.stopped_sha.as_ref().and_then(|stopped_sha| {
+            rebase.done.iter().rposition(|entry| {
+                stopped_sha.starts_with(&*entry.sha) || entry.sha.starts_with(&**stopped_sha)
+            })
+        });
+        let entries = rebase
+            .done
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| {
+                let icon = if Some(ix) == stopped_ix {
+                    Icon::new(IconName::Warning).color(Color::Warning)
+                } else {
+                    Icon::new(IconName::Check).color(Color::Success)
+                };
+                (Some(icon), entry)
+            })
+            .chain(rebase.todo.iter().map(|entry| (None, entry)));
+
+        v_flex()
+            .size_full()
+            .child(self.render_header(title.into(), buttons, cx))
+            .when_some(hint, |this, hint| {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_1()
+                        .child(Label::new(hint).size(LabelSize::Small)),
+                )
+            })
+            .child(
+                v_flex()
+                    .id("rebase-progress-list")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .children(entries.map(|(icon, entry)| {
+                        let applied = icon.is_some();
+                        h_flex()
+                            .px_3()
+                            .py_1()
+                            .gap_2()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(div().w_4().when_some(icon, |this, icon| {
+                                this.child(icon.size(IconSize::Small))
+                            }))
+                            .child(
+                                Label::new(entry.action.label())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Label::new(short_sha(&entry.sha))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .buffer_font(cx),
+                            )
+                            .child(
+                                Label::new(entry.subject.clone())
+                                    .size(LabelSize::Small)
+                                    .truncate()
+                                    .when(!applied, |label| label.color(Color::Muted)),
+                            )
+                    })),
+            )
+    }
+}
+
+fn short_sha(sha: &str) -> SharedString {
+    sha.get(..SHORT_SHA_LENGTH)
+        .unwrap_or(sha)
+        .to_string()
+        .into()
+}
+
+fn pluralize_commits(count: usize) -> String {
+    if count == 1 {
+        "1 commit".to_string()
+    } else {
+        format!("{count} commits")
+    }
+}
+
+impl Render for DraggedTodoItem {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .font_ui(cx)
+            .px_2()
+            .py_1()
+            .gap_2()
+            .rounded_sm()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().elevated_surface_background)
+            .child(
+                Label::new(self.action.label())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(self.subject.clone()).size(LabelSize::Small))
+    }
+}
+
+impl EventEmitter<ItemEvent> for InteractiveRebaseView {}
+
+impl Focusable for InteractiveRebaseView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for InteractiveRebaseView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rebase = self.repository.read(cx).rebase.clone();
+        let content = if let Some(rebase) = rebase {
+            self.render_progress(&rebase, cx).into_any_element()
+        } else if self.finished {
+            div()
+                .p_3()
+                .child(Label::new(format!("Rebased onto {}", self.onto)).color(Color::Muted))
+                .into_any_element()
+        } else {
+            self.render_todo(window, cx).into_any_element()
+        };
+
+        v_flex()
+            .id("interactive_rebase_view")
+            .key_context("InteractiveRebase")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::move_up))
+            .on_action(cx.listener(Self::move_down))
+            .on_action(cx.listener(Self::start))
+            .on_action(cx.listener(Self::continue_rebase))
+            .on_action(cx.listener(Self::skip))
+            .on_action(cx.listener(Self::abort))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(content)
+    }
+}
+
+impl Item for InteractiveRebaseView {
+    type Event = ItemEvent;
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Interactive Rebase".into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(format!("Rebase onto {}", self.onto).into())
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("interactive rebase")
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<workspace::WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Task<Option<Entity<Self>>> {
+        Task::ready(None)
+    }
+
+    fn navigate(
+        &mut self,
+        _: Arc<dyn Any + Send>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> bool {
+        false
+    }
+
+    fn deactivated(&mut self, _window: &mut Window, _: &mut Context<Self>) {}
+
+    fn can_save(&self, _: &App) -> bool {
+        false
+    }
+
+    fn save(
+        &mut self,
+        _options: SaveOptions,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn save_as(
+        &mut self,
+        _project: Entity<Project>,
+        _path: ProjectPath,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn reload(
+        &mut self,
+        _project: Entity<Project>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn has_conflict(&self, _: &App) -> bool {
+        false
+    }
+
+    fn breadcrumbs(&self, _cx: &App) -> Option<Vec<workspace::item::BreadcrumbText>> {
+        None
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        _workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn pixel_position_of_cursor(&self, _: &App) -> Option<gpui::Point<gpui::Pixels>> {
+        None
+    }
+
+    fn set_nav_history(
+        &mut self,
+        _: workspace::ItemNavHistory,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else {
+            None
+        }
+    }
+}
@@ -4365,6 +4365,21 @@ impl Window {
         node_id: DispatchNodeId,
         action: &dyn Action,
         cx: &mut App,
+    ) {
+        self.dispatch_action_to_listeners(node_id, action, cx);
+        if !cx.propagate_event {
+            cx.action_observers
+                .clone()
+                .retain(&(), |callback| (callback)(action, self, cx));
+            cx.propagate_event = false;
+        }
+    }
+
+    fn dispatch_action_to_listeners(
+        &mut self,
+        node_id: DispatchNodeId,
+        action: &dyn Action,
+        cx: &mut App,
     ) {
         let dispatch_path = self.rendered_frame.dispatch_tree.dispatch_path(node_id);
 
@@ -0,0 +1,989 @@
+mod hierarchy_panel_settings;
+
+use std::{ops::Range, sync::Arc};
+
+use anyhow::Context as _;
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, HighlightKey, SelectionEffects, scroll::Autoscroll};
+use gpui::{
+    Action, App, AsyncWindowContext, ClickEvent, Context, Div, Entity, EventEmitter, FocusHandle,
+    Focusable, KeyContext, Pixels, Render, ScrollStrategy, SharedString, Stateful, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, px, uniform_list,
+};
+use hierarchy_panel_settings::{DockSide, HierarchyPanelSettings};
+use language::{Anchor, Buffer, ToPoint as _};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{CallHierarchyItem, Fs, Project, TypeHierarchyItem};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, Tab, prelude::*};
+use util::ResultExt;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+actions!(
+    hierarchy_panel,
+    [
+        /// Collapses the selected entry, or selects its parent.
+        CollapseSelectedEntry,
+        /// Expands the selected entry, or selects its first child.
+        ExpandSelectedEntry,
+        /// Opens the symbol of the selected entry in the editor.
+        OpenSelectedEntry,
+        /// Shows the callers of the function or method under the cursor.
+        ShowIncomingCalls,
+        /// Shows the functions and methods called by the one under the cursor.
+        ShowOutgoingCalls,
+        /// Shows the types that the type under the cursor derives from or implements.
+        ShowSupertypes,
+        /// Shows the types that derive from or implement the type under the cursor.
+        ShowSubtypes,
+        /// Switches between showing incoming and outgoing calls, or supertypes and subtypes.
+        ToggleDirection,
+        /// Toggles focus on the hierarchy panel.
+        ToggleFocus,
+    ]
+);
+
+const HIERARCHY_PANEL_KEY: &str = "HierarchyPanel";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyDirection {
+    IncomingCalls,
+    OutgoingCalls,
+    Supertypes,
+    Subtypes,
+}
+
+impl HierarchyDirection {
+    fn label(self) -> &'static str {
+        match self {
+            Self::IncomingCalls => "Incoming",
+            Self::OutgoingCalls => "Outgoing",
+            Self::Supertypes => "Supertypes",
+            Self::Subtypes => "Subtypes",
+        }
+    }
+
+    fn shows_calls(self) -> bool {
+        matches!(self, Self::IncomingCalls | Self::OutgoingCalls)
+    }
+
+    fn opposite(self) -> Self {
+        match self {
+            Self::IncomingCalls => Self::OutgoingCalls,
+            Self::OutgoingCalls => Self::IncomingCalls,
+            Self::Supertypes => Self::Subtypes,
+            Self::Subtypes => Self::Supertypes,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum HierarchyItem {
+    Call(CallHierarchyItem),
+    Type(TypeHierarchyItem),
+}
+
+impl HierarchyItem {
+    fn name(&self) -> &str {
+        match self {
+            Self::Call(item) => &item.name,
+            Self::Type(item) => &item.name,
+        }
+    }
+
+    fn buffer(&self) -> &Entity<Buffer> {
+        match self {
+            Self::Call(item) => &item.buffer,
+            Self::Type(item) => &item.buffer,
+        }
+    }
+
+    fn selection_range(&self) -> &Range<Anchor> {
+        match self {
+            Self::Call(item) => &item.selection_range,
+            Self::Type(item) => &item.selection_range,
+        }
+    }
+}
+
+/// An item of the tree, which is either one the hierarchy was prepared with, or a call, supertype
+/// or subtype of its parent.
+struct HierarchyEntry {
+    item: HierarchyItem,
+    parent: Option<usize>,
+    depth: usize,
+    /// The buffer of the call sites: the caller's for incoming calls, and the parent's for
+    /// outgoing calls.
+    call_sites_buffer: Entity<Buffer>,
+    /// Where the calls between the item and its parent are made. Empty for the roots and for
+    /// types.
+    call_sites: Vec<Range<Anchor>>,
+    expanded: bool,
+    children: Children,
+}
+
+enum Children {
+    Unloaded,
+    Loading,
+    Loaded(Vec<usize>),
+}
+
+pub struct HierarchyPanel {
+    fs: Arc<dyn Fs>,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    width: Option<Pixels>,
+    active: bool,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    direction: HierarchyDirection,
+    entries: Vec<HierarchyEntry>,
+    roots: Vec<usize>,
+    visible_entries: Vec<usize>,
+    selected_entry: Option<usize>,
+    /// Why there are no entries, once the hierarchy was requested.
+    status: Option<SharedString>,
+    highlighted_editor: Option<WeakEntity<Editor>>,
+    prepare_task: Task<()>,
+    children_tasks: HashMap<usize, Task<()>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedHierarchyPanel {
+    width: Option<Pixels>,
+    active: Option<bool>,
+}
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<HierarchyPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowIncomingCalls, window, cx| {
+            show_hierarchy(workspace, HierarchyDirection::IncomingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowOutgoingCalls, window, cx| {
+            show_hierarchy(workspace, HierarchyDirection::OutgoingCalls, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSupertypes, window, cx| {
+            show_hierarchy(workspace, HierarchyDirection::Supertypes, window, cx);
+        });
+        workspace.register_action(|workspace, _: &ShowSubtypes, window, cx| {
+            show_hierarchy(workspace, HierarchyDirection::Subtypes, window, cx);
+        });
+    })
+    .detach();
+}
+
+fn show_hierarchy(
+    workspace: &mut Workspace,
+    direction: HierarchyDirection,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some(panel) = workspace.focus_panel::<HierarchyPanel>(window, cx) else {
+        return;
+    };
+    panel.update(cx, |panel, cx| {
+        panel.show_hierarchy(&editor, direction, cx);
+    });
+}
+
+fn types_to_children(
+    types: anyhow::Result<Vec<TypeHierarchyItem>>,
+) -> anyhow::Result<Vec<(HierarchyItem, Vec<Range<Anchor>>)>> {
+    Ok(types?
+        .into_iter()
+        .map(|item| (HierarchyItem::Type(item), Vec::new()))
+        .collect())
+}
+
+impl HierarchyPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| {
+                HierarchyPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(&serialization_key) })
+                .await
+                .context("loading hierarchy panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedHierarchyPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten(),
+            None => None,
+        };
+
+        workspace.update_in(&mut cx, |workspace, _, cx| {
+            let panel = Self::new(workspace, cx);
+            if let Some(serialized_panel) = serialized_panel {
+                panel.update(cx, |panel, cx| {
+                    panel.width = serialized_panel.width.map(|px| px.round());
+                    panel.active = serialized_panel.active.unwrap_or(false);
+                    cx.notify();
+                });
+            }
+            panel
+        })
+    }
+
+    fn new(workspace: &mut Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let project = workspace.project().clone();
+        let workspace = cx.entity().downgrade();
+        cx.new(|cx| Self {
+            fs,
+            project,
+            workspace,
+            width: None,
+            active: false,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            pending_serialization: Task::ready(None),
+            direction: HierarchyDirection::IncomingCalls,
+            entries: Vec::new(),
+            roots: Vec::new(),
+            visible_entries: Vec::new(),
+            selected_entry: None,
+            status: None,
+            highlighted_editor: None,
+            prepare_task: Task::ready(()),
+            children_tasks: HashMap::default(),
+        })
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", HIERARCHY_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| {
+                HierarchyPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let width = self.width;
+        let active = Some(self.active);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        serialization_key,
+                        serde_json::to_string(&SerializedHierarchyPanel { width, active })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn dispatch_context(&self) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("HierarchyPanel");
+        dispatch_context.add("menu");
+        dispatch_context
+    }
+
+    /// Prepares the call or type hierarchy, depending on the direction, of the symbol under the
+    /// cursor of the editor.
+    pub fn show_hierarchy(
+        &mut self,
+        editor: &Entity<Editor>,
+        direction: HierarchyDirection,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = editor.read(cx);
+        let Some((buffer, position)) = editor
+            .buffer()
+            .read(cx)
+            .text_anchor_for_position(editor.selections.newest_anchor().head(), cx)
+        else {
+            return;
+        };
+
+        self.direction = direction;
+        self.status = Some("Loading…".into());
+        self.set_roots(Vec::new(), cx);
+        let items: Task<anyhow::Result<Vec<HierarchyItem>>> =
+            self.project.update(cx, |project, cx| {
+                if direction.shows_calls() {
+                    let items = project.prepare_call_hierarchy(&buffer, position, cx);
+                    cx.spawn(async move |_, _| {
+                        Ok(items.await?.into_iter().map(HierarchyItem::Call).collect())
+                    })
+                } else {
+                    let items = project.prepare_type_hierarchy(&buffer, position, cx);
+                    cx.spawn(async move |_, _| {
+                        Ok(items.await?.into_iter().map(HierarchyItem::Type).collect())
+                    })
+                }
+            });
+        self.prepare_task = cx.spawn(async move |panel, cx| {
+            let items = items.await;
+            panel
+                .update(cx, |panel, cx| {
+                    match items {
+                        Ok(items) if items.is_empty() => {
+                            panel.status = Some(if direction.shows_calls() {
+                                "No call hierarchy at the cursor".into()
+                            } else {
+                                "No type hierarchy at the cursor".into()
+                            });
+                        }
+                        Ok(items) => {
+                            panel.status = None;
+                            panel.set_roots(items, cx);
+                            panel.selected_entry = panel.roots.first().copied();
+                        }
+                        Err(error) => {
+                            panel.status = Some(format!("{error:#}").into());
+                        }
+                    }
+                    cx.notify();
+                })
+                .ok();
+        });
+    }
+
+    /// Switches the direction the shown hierarchy is followed in, which can't change whether it's
+    /// of calls or types.
+    fn set_direction(&mut self, direction: HierarchyDirection, cx: &mut Context<Self>) {
+        if self.direction == direction || self.direction.shows_calls() != direction.shows_calls() {
+            return;
+        }
+        self.direction = direction;
+        let roots = self
+            .roots
+            .iter()
+            .map(|&ix| self.entries[ix].item.clone())
+            .collect();
+        let selected_root = self
+            .selected_entry
+            .and_then(|selected| self.roots.iter().position(|&ix| ix == selected))
+            .unwrap_or(0);
+        self.set_roots(roots, cx);
+        self.selected_entry = self.roots.get(selected_root).copied();
+    }
+
+    fn toggle_direction(&mut self, _: &ToggleDirection, _: &mut Window, cx: &mut Context<Self>) {
+        self.set_direction(self.direction.opposite(), cx);
+    }
+
+    /// Replaces the tree with the given items, and starts fetching their children.
+    fn set_roots(&mut self, items: Vec<HierarchyItem>, cx: &mut Context<Self>) {
+        self.clear_highlights(cx);
+        self.children_tasks.clear();
+        self.entries.clear();
+        self.selected_entry = None;
+        self.roots = items
+            .into_iter()
+            .map(|item| {
+                self.push_entry(HierarchyEntry {
+                    call_sites_buffer: item.buffer().clone(),
+                    item,
+                    parent: None,
+                    depth: 0,
+                    call_sites: Vec::new(),
+                    expanded: false,
+                    children: Children::Unloaded,
+                })
+            })
+            .collect();
+        for ix in self.roots.clone() {
+            self.expand_entry(ix, cx);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    fn push_entry(&mut self, entry: HierarchyEntry) -> usize {
+        self.entries.push(entry);
+        self.entries.len() - 1
+    }
+
+    fn expand_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get_mut(ix) else {
+            return;
+        };
+        entry.expanded = true;
+        if matches!(entry.children, Children::Unloaded) {
+            entry.children = Children::Loading;
+            let item = entry.item.clone();
+            let direction = self.direction;
+            let children: Task<anyhow::Result<Vec<_>>> = self.project.update(cx, |project, cx| {
+                let calls = match (&item, direction) {
+                    (HierarchyItem::Call(item), HierarchyDirection::IncomingCalls) => {
+                        project.incoming_calls(item, cx)
+                    }
+                    (HierarchyItem::Call(item), HierarchyDirection::OutgoingCalls) => {
+                        project.outgoing_calls(item, cx)
+                    }
+                    (HierarchyItem::Type(item), HierarchyDirection::Supertypes) => {
+                        let types = project.supertypes(item, cx);
+                        return cx.spawn(async move |_, _| types_to_children(types.await));
+                    }
+                    (HierarchyItem::Type(item), HierarchyDirection::Subtypes) => {
+                        let types = project.subtypes(item, cx);
+                        return cx.spawn(async move |_, _| types_to_children(types.await));
+                    }
+                    _ => return Task::ready(Ok(Vec::new())),
+                };
+                cx.spawn(async move |_, _| {
+                    Ok(calls
+                        .await?
+                        .into_iter()
+                        .map(|call| (HierarchyItem::Call(call.item), call.call_sites))
+                        .collect())
+                })
+            });
+            let task = cx.spawn(async move |panel, cx| {
+                let children = children.await;
+                panel
+                    .update(cx, |panel, cx| panel.insert_children(ix, children, cx))
+                    .ok();
+            });
+            self.children_tasks.insert(ix, task);
+        }
+        self.update_visible_entries(cx);
+    }
+
+    /// Adds the items, and the sites of their calls if they're calls, under the parent.
+    fn insert_children(
+        &mut self,
+        parent_ix: usize,
+        children: anyhow::Result<Vec<(HierarchyItem, Vec<Range<Anchor>>)>>,
+        cx: &mut Context<Self>,
+    ) {
+        self.children_tasks.remove(&parent_ix);
+        let children = children
+            .context(if self.direction.shows_calls() {
+                "fetching calls"
+            } else {
+                "fetching types"
+            })
+            .log_err()
+            .unwrap_or_default();
+        let parent = &self.entries[parent_ix];
+        let depth = parent.depth + 1;
+        let parent_buffer = parent.item.buffer().clone();
+        let children = children
+            .into_iter()
+            .map(|(item, call_sites)| {
+                let call_sites_buffer = match self.direction {
+                    HierarchyDirection::OutgoingCalls => parent_buffer.clone(),
+                    _ => item.buffer().clone(),
+                };
+                self.push_entry(HierarchyEntry {
+                    item,
+                    parent: Some(parent_ix),
+                    depth,
+                    call_sites_buffer,
+                    call_sites,
+                    expanded: false,
+                    children: Children::Unloaded,
+                })
+            })
+            .collect();
+        self.entries[parent_ix].children = Children::Loaded(children);
+        self.update_visible_entries(cx);
+    }
+
+    fn collapse_entry(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get_mut(ix) else {
+            return;
+        };
+        entry.expanded = false;
+        self.update_visible_entries(cx);
+        if self
+            .selected_entry
+            .is_some_and(|selected| !self.visible_entries.contains(&selected))
+        {
+            self.selected_entry = Some(ix);
+        }
+    }
+
+    fn toggle_expanded(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if self.entries.get(ix).is_some_and(|entry| entry.expanded) {
+            self.collapse_entry(ix, cx);
+        } else {
+            self.expand_entry(ix, cx);
+        }
+    }
+
+    fn update_visible_entries(&mut self, cx: &mut Context<Self>) {
+        let mut visible_entries = Vec::new();
+        let mut stack = self.roots.iter().rev().copied().collect::<Vec<_>>();
+        while let Some(ix) = stack.pop() {
+            visible_entries.push(ix);
+            let entry = &self.entries[ix];
+            if entry.expanded
+                && let Children::Loaded(children) = &entry.children
+            {
+                stack.extend(children.iter().rev().copied());
+            }
+        }
+        self.visible_entries = visible_entries;
+        cx.notify();
+    }
+
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selected) = self.selected_entry else {
+            return;
+        };
+        let entry = &self.entries[selected];
+        match &entry.children {
+            Children::Loaded(children) if entry.expanded => {
+                if let Some(&first_child) = children.first() {
+                    self.select_entry(first_child, window, cx);
+                }
+            }
+            _ => self.expand_entry(selected, cx),
+        }
+    }
+
+    fn collapse_selected_entry(
+        &mut self,
+        _: &CollapseSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selected) = self.selected_entry else {
+            return;
+        };
+        let entry = &self.entries[selected];
+        if entry.expanded {
+            self.collapse_entry(selected, cx);
+        } else if let Some(parent) = entry.parent {
+            self.select_entry(parent, window, cx);
+        }
+    }
+
+    fn select_next(&mut self, _: &SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        let next = match self.selected_visible_index() {
+            Some(visible_ix) => visible_ix + 1,
+            None => 0,
+        };
+        if let Some(&ix) = self.visible_entries.get(next) {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        let previous = match self.selected_visible_index() {
+            Some(visible_ix) => visible_ix.saturating_sub(1),
+            None => self.visible_entries.len().saturating_sub(1),
+        };
+        if let Some(&ix) = self.visible_entries.get(previous) {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(&ix) = self.visible_entries.first() {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(&ix) = self.visible_entries.last() {
+            self.select_entry(ix, window, cx);
+        }
+    }
+
+    fn selected_visible_index(&self) -> Option<usize> {
+        let selected = self.selected_entry?;
+        self.visible_entries.iter().position(|&ix| ix == selected)
+    }
+
+    fn select_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_entry = Some(ix);
+        if let Some(visible_ix) = self.selected_visible_index() {
+            self.scroll_handle
+                .scroll_to_item(visible_ix, ScrollStrategy::Center);
+        }
+        self.reveal_call_sites(ix, window, cx);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_selected_entry(&OpenSelectedEntry, window, cx);
+    }
+
+    fn open_selected_entry(
+        &mut self,
+        _: &OpenSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(selected) = self.selected_entry {
+            self.open_entry(selected, window, cx);
+        }
+    }
+
+    /// Opens the symbol of the entry, focusing the editor.
+    fn open_entry(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let buffer = entry.item.buffer().clone();
+        let ranges = vec![entry.item.selection_range().clone()];
+        self.open_ranges(buffer, ranges, true, window, cx);
+    }
+
+    /// Highlights where the entry is called, or calls its parent, without moving the focus. Types
+    /// have no call sites, so their names are highlighted instead.
+    fn reveal_call_sites(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+        let (buffer, ranges) = if entry.call_sites.is_empty() {
+            (
+                entry.item.buffer().clone(),
+                vec![entry.item.selection_range().clone()],
+            )
+        } else {
+            (entry.call_sites_buffer.clone(), entry.call_sites.clone())
+        };
+        self.open_ranges(buffer, ranges, false, window, cx);
+    }
+
+    fn open_ranges(
+        &mut self,
+        buffer: Entity<Buffer>,
+        ranges: Vec<Range<Anchor>>,
+        focus: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let editor = workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace
+                .open_project_item::<Editor>(pane, buffer, focus, focus, true, true, window, cx)
+        });
+        if let Some(previous_editor) = self
+            .highlighted_editor
+            .take()
+            .and_then(|editor| editor.upgrade())
+            && previous_editor != editor
+        {
+            previous_editor.update(cx, |editor, cx| {
+                editor.clear_background_highlights(HighlightKey::CallHierarchy, cx);
+            });
+        }
+        self.highlighted_editor = Some(editor.downgrade());
+
+        editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let ranges = ranges
+                .into_iter()
+                .filter_map(|range| {
+                    Some(
+                        snapshot.as_singleton_anchor(range.start)?
+                            ..snapshot.as_singleton_anchor(range.end)?,
+                    )
+                })
+                .collect::<Vec<_>>();
+            editor.highlight_background(
+                HighlightKey::CallHierarchy,
+                &ranges,
+                |_, theme| theme.colors().editor_document_highlight_read_background,
+                cx,
+            );
+            if let Some(first_range) = ranges.first() {
+                let position = first_range.start;
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::center()),
+                    window,
+                    cx,
+                    |selections| selections.select_anchor_ranges([position..position]),
+                );
+            }
+        });
+    }
+
+    fn clear_highlights(&mut self, cx: &mut Context<Self>) {
+        if let Some(editor) = self
+            .highlighted_editor
+            .take()
+            .and_then(|editor| editor.upgrade())
+        {
+            editor.update(cx, |editor, cx| {
+                editor.clear_background_highlights(HighlightKey::CallHierarchy, cx);
+            });
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> Div {
+        let direction_button = |direction: HierarchyDirection| {
+            Button::new(direction.label(), direction.label())
+                .label_size(LabelSize::Small)
+                .toggle_state(self.direction == direction)
+                .on_click(cx.listener(move |panel, _, _, cx| {
+                    panel.set_direction(direction, cx);
+                }))
+        };
+        h_flex()
+            .h(Tab::container_height(cx))
+            .px_2()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .map(|header| {
+                if self.direction.shows_calls() {
+                    header
+                        .child(Label::new("Calls").color(Color::Muted))
+                        .child(direction_button(HierarchyDirection::IncomingCalls))
+                        .child(direction_button(HierarchyDirection::OutgoingCalls))
+                } else {
+                    header
+                        .child(Label::new("Types").color(Color::Muted))
+                        .child(direction_button(HierarchyDirection::Supertypes))
+                        .child(direction_button(HierarchyDirection::Subtypes))
+                }
+            })
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> ListItem {
+        let entry = &self.entries[ix];
+        let indent_size = HierarchyPanelSettings::get_global(cx).indent_size;
+        let toggle = match &entry.children {
+            Children::Loaded(children) if children.is_empty() => None,
+            _ => Some(entry.expanded),
+        };
+        let buffer = entry.item.buffer().read(cx);
+        let location = buffer.file().map(|file| {
+            let row = entry.item.selection_range().start.to_point(buffer).row + 1;
+            format!("{}:{row}", file.file_name(cx))
+        });
+        let call_count = entry.call_sites.len();
+
+        ListItem::new(("call-hierarchy-entry", ix))
+            .indent_level(entry.depth)
+            .indent_step_size(px(indent_size))
+            .toggle_state(self.selected_entry == Some(ix))
+            .toggle(toggle)
+            .on_toggle(cx.listener(move |panel, _, _, cx| {
+                panel.toggle_expanded(ix, cx);
+            }))
+            .on_click(cx.listener(move |panel, event: &ClickEvent, window, cx| {
+                if event.click_count() > 1 {
+                    panel.selected_entry = Some(ix);
+                    panel.open_entry(ix, window, cx);
+                } else {
+                    panel.select_entry(ix, window, cx);
+                }
+            }))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(Label::new(entry.item.name().to_string()))
+                    .when(call_count > 1, |row| {
+                        row.child(
+                            Label::new(format!("×{call_count}"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .when_some(location, |row, location| {
+                        row.child(
+                            Label::new(location)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                    }),
+            )
+    }
+
+    fn render_empty_state(&self, window: &mut Window, cx: &mut Context<Self>) -> Stateful<Div> {
+        let message = self
+            .status
+            .clone()
+            .unwrap_or_else(|| "No hierarchy shown".into());
+        let show_incoming_calls = window.keystroke_text_for(&ShowIncomingCalls);
+        v_flex()
+            .id("empty-call-hierarchy-state")
+            .gap_0p5()
+            .flex_1()
+            .justify_center()
+            .size_full()
+            .child(h_flex().justify_center().child(Label::new(message)))
+            .when(self.status.is_none(), |state| {
+                state.child(
+                    h_flex().justify_center().child(
+                        Label::new(format!("Show Incoming Calls With {show_incoming_calls}"))
+                            .color(Color::Muted),
+                    ),
+                )
+            })
+            .child(h_flex().justify_center().child({
+                let keystroke = match self.position(window, cx) {
+                    DockPosition::Left => window.keystroke_text_for(&workspace::ToggleLeftDock),
+                    DockPosition::Bottom => window.keystroke_text_for(&workspace::ToggleBottomDock),
+                    DockPosition::Right => window.keystroke_text_for(&workspace::ToggleRightDock),
+                };
+                Label::new(format!("Toggle Panel With {keystroke}")).color(Color::Muted)
+            }))
+    }
+}
+
+impl Panel for HierarchyPanel {
+    fn persistent_name() -> &'static str {
+        "Hierarchy Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        HIERARCHY_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match HierarchyPanelSettings::get_global(cx).dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.hierarchy_panel.get_or_insert_default().dock = Some(dock);
+        });
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| HierarchyPanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        cx.notify();
+        cx.defer_in(window, |this, _, cx| {
+            this.serialize(cx);
+        });
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        HierarchyPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ArrowRightLeft)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
+        Some("Hierarchy Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _window: &Window, _: &App) -> bool {
+        self.active
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active != active {
+            self.active = active;
+            if !active {
+                self.clear_highlights(cx);
+            }
+            self.serialize(cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        7
+    }
+}
+
+impl Focusable for HierarchyPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for HierarchyPanel {}
+
+impl Render for HierarchyPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("call-hierarchy-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context())
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::expand_selected_entry))
+            .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::open_selected_entry))
+            .on_action(cx.listener(Self::toggle_direction))
+            .track_focus(&self.focus_handle)
+            .child(self.render_header(cx))
+            .map(|panel| {
+                if self.visible_entries.is_empty() {
+                    panel.child(self.render_empty_state(window, cx))
+                } else {
+                    panel.child(
+                        uniform_list(
+                            "call-hierarchy-entries",
+                            self.visible_entries.len(),
+                            cx.processor(|panel, range: Range<usize>, _, cx| {
+                                range
+                                    .map(|visible_ix| {
+                                        panel.render_entry(panel.visible_entries[visible_ix], cx)
+                                    })
+                                    .collect()
+                            }),
+                        )
+                        .size_full()
+                        .track_scroll(&self.scroll_handle),
+                    )
+                }
+            })
+    }
+}
@@ -51,6 +51,7 @@ pub enum IconName {
     BoltOutlined,
     Book,
     BookCopy,
+    Bookmark,
     Box,
     CaseSensitive,
     Chat,
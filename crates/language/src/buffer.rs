@@ -3716,6 +3716,41 @@ impl BufferSnapshot {
         self.syntax.captures(range, &self.text, query)
     }
 
+    /// Returns the ranges that intersect `range` and are highlighted with the capture `name`,
+    /// or a more specific capture such as `name.doc`. The ranges are sorted, and overlapping
+    /// ranges are merged.
+    pub fn highlight_capture_ranges(&self, range: Range<usize>, name: &str) -> Vec<Range<usize>> {
+        let mut captures = self.captures(range, |grammar| {
+            grammar
+                .highlights_config
+                .as_ref()
+                .map(|config| &config.query)
+        });
+        let mut ranges = Vec::new();
+        while let Some(capture) = captures.next() {
+            let Some(config) = &captures.grammars()[capture.grammar_index].highlights_config else {
+                continue;
+            };
+            let capture_name = config.query.capture_names()[capture.index as usize];
+            if capture_name
+                .strip_prefix(name)
+                .is_some_and(|suffix| suffix.is_empty() || suffix.starts_with('.'))
+            {
+                ranges.push(capture.node.byte_range());
+            }
+        }
+
+        ranges.sort_unstable_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
     #[ztracing::instrument(skip_all)]
     fn get_highlights(&self, range: Range<usize>) -> (SyntaxMapCaptures<'_>, Vec<HighlightMap>) {
         let captures = self.syntax.captures(range, &self.text, |grammar| {
@@ -2890,6 +2890,41 @@ fn test_language_at_for_markdown_code_block(cx: &mut App) {
     });
 }
 
+#[gpui::test]
+fn test_highlight_capture_ranges(cx: &mut App) {
+    cx.new(|cx| {
+        let text = indoc! {r#"
+            /// Doc comment
+            fn a() {
+                // Line comment
+                b("string \n", /* block */ 'c');
+            }
+        "#};
+        let buffer = Buffer::local(text, cx).with_language(rust_lang(), cx);
+        let snapshot = buffer.snapshot();
+        let ranges_text = |name| {
+            snapshot
+                .highlight_capture_ranges(0..text.len(), name)
+                .into_iter()
+                .map(|range| text[range].trim_end())
+                .collect::<Vec<_>>()
+        };
+
+        // Doc comments are captured as `comment.doc`, and line comments may include their newline.
+        assert_eq!(
+            ranges_text("comment"),
+            ["/// Doc comment", "// Line comment", "/* block */"]
+        );
+        assert_eq!(ranges_text("string"), ["\"string \\n\"", "'c'"]);
+        assert_eq!(
+            snapshot.highlight_capture_ranges(text.find("b(").unwrap()..text.len(), "comment"),
+            [text.find("/*").unwrap()..text.find("*/").unwrap() + 2]
+        );
+
+        buffer
+    });
+}
+
 #[gpui::test]
 fn test_syntax_layer_at_for_combined_injections(cx: &mut App) {
     init_settings(cx, |_| {});
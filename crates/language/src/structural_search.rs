@@ -0,0 +1,549 @@
+//! Searching buffers for syntax rather than text.
+//!
+//! A [`StructuralQuery`] is either a tree-sitter query, or a code pattern in the style of
+//! [ast-grep](https://ast-grep.github.io/guide/pattern-syntax.html) that is parsed with the
+//! grammar of each syntax layer it's matched against. In a pattern:
+//!
+//! - `$NAME` matches any single named node. Every occurrence of the same name must match the same text.
+//! - `$$$NAME` matches any number of nodes, including none.
+//! - `$_` and `$$$` match the same way without capturing anything.
+//!
+//! Metavariable names consist of uppercase letters, digits and underscores, so `$foo` matches
+//! itself literally.
+
+use crate::{BufferSnapshot, Grammar, GrammarId, syntax_map::TextProvider, with_parser};
+use collections::HashMap;
+use parking_lot::Mutex;
+use std::{cmp::Reverse, fmt, ops::Range, sync::Arc};
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Node, Query, Tree};
+
+/// Metavariables are replaced with identifiers starting with these prefixes before a pattern
+/// is parsed, so that they end up as leaves of the pattern's syntax tree.
+const METAVARIABLE_PREFIX: &str = "zed_metavar_";
+const MULTI_METAVARIABLE_PREFIX: &str = "zed_metavars_";
+
+/// The capture that determines the range of a tree-sitter query match. Without it, matches
+/// span all of their captures.
+const MATCH_CAPTURE: &str = "match";
+
+pub struct StructuralQuery {
+    source: String,
+    kind: StructuralQueryKind,
+    compiled: Mutex<HashMap<GrammarId, Option<Arc<CompiledQuery>>>>,
+}
+
+enum StructuralQueryKind {
+    TreeSitter,
+    Pattern {
+        /// The pattern with its metavariables replaced by placeholder identifiers.
+        text: String,
+        required_word: Option<String>,
+    },
+}
+
+enum CompiledQuery {
+    TreeSitter(Query),
+    Pattern { tree: Tree, root: Range<usize> },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuralMatch {
+    pub range: Range<usize>,
+    /// The ranges matched by named metavariables, or by the captures of a tree-sitter query.
+    pub captures: HashMap<String, Range<usize>>,
+}
+
+enum Metavariable<'a> {
+    Single(&'a str),
+    Multiple(&'a str),
+}
+
+impl StructuralQuery {
+    /// Parses a structural query. Queries that start with `(` and capture something are
+    /// tree-sitter queries, anything else is a pattern.
+    pub fn new(source: impl Into<String>) -> anyhow::Result<Self> {
+        let source = source.into();
+        let trimmed = source.trim();
+        anyhow::ensure!(!trimmed.is_empty(), "structural query is empty");
+
+        let kind = if trimmed.starts_with('(') && trimmed.contains('@') {
+            StructuralQueryKind::TreeSitter
+        } else {
+            let text = replace_metavariables(trimmed);
+            anyhow::ensure!(
+                text.split(|c: char| !is_word_char(c))
+                    .any(|word| !word.is_empty() && !is_placeholder(word))
+                    || text.chars().any(|c| !is_word_char(c) && !c.is_whitespace()),
+                "structural pattern must contain something besides metavariables"
+            );
+            let required_word = text
+                .split(|c: char| !is_word_char(c))
+                .filter(|word| !is_placeholder(word))
+                .max_by_key(|word| word.len())
+                .filter(|word| !word.is_empty())
+                .map(ToString::to_string);
+            StructuralQueryKind::Pattern {
+                text,
+                required_word,
+            }
+        };
+
+        Ok(Self {
+            source,
+            kind,
+            compiled: Mutex::default(),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    pub fn is_tree_sitter_query(&self) -> bool {
+        matches!(self.kind, StructuralQueryKind::TreeSitter)
+    }
+
+    /// A word that occurs in any text this query matches, which can be used to skip files
+    /// without parsing them.
+    pub fn required_word(&self) -> Option<&str> {
+        match &self.kind {
+            StructuralQueryKind::TreeSitter => None,
+            StructuralQueryKind::Pattern { required_word, .. } => required_word.as_deref(),
+        }
+    }
+
+    /// Finds the matches of this query within `range` of the buffer, in every syntax layer
+    /// whose grammar the query is valid for. Matches don't overlap, and are sorted by position.
+    pub fn matches(&self, buffer: &BufferSnapshot, range: Range<usize>) -> Vec<StructuralMatch> {
+        let mut matches = Vec::new();
+        for layer in buffer.syntax_layers_for_range(range.clone(), true) {
+            let Some(grammar) = layer.language.grammar() else {
+                continue;
+            };
+            let Some(compiled) = self.compile(grammar) else {
+                continue;
+            };
+            match compiled.as_ref() {
+                CompiledQuery::TreeSitter(query) => {
+                    query_matches(query, layer.node(), buffer, &range, &mut matches)
+                }
+                CompiledQuery::Pattern { tree, root } => {
+                    let StructuralQueryKind::Pattern { text, .. } = &self.kind else {
+                        continue;
+                    };
+                    let Some(pattern) = tree
+                        .root_node()
+                        .descendant_for_byte_range(root.start, root.end)
+                    else {
+                        continue;
+                    };
+                    let matcher = PatternMatcher {
+                        pattern_text: text,
+                        buffer,
+                    };
+                    matcher.find(pattern, layer.node(), &range, &mut matches);
+                }
+            }
+        }
+
+        matches.sort_by_key(|mat| (mat.range.start, Reverse(mat.range.end)));
+        let mut end = 0;
+        matches.retain(|mat| {
+            let keep = mat.range.start >= end;
+            if keep {
+                end = mat.range.end;
+            }
+            keep
+        });
+        matches
+    }
+
+    fn compile(&self, grammar: &Grammar) -> Option<Arc<CompiledQuery>> {
+        self.compiled
+            .lock()
+            .entry(grammar.id())
+            .or_insert_with(|| {
+                let compiled = match &self.kind {
+                    StructuralQueryKind::TreeSitter => {
+                        Query::new(&grammar.ts_language, &self.source)
+                            .ok()
+                            .map(CompiledQuery::TreeSitter)
+                    }
+                    StructuralQueryKind::Pattern { text, .. } => parse_pattern(text, grammar),
+                };
+                compiled.map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+impl fmt::Debug for StructuralQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StructuralQuery")
+            .field(&self.source)
+            .finish()
+    }
+}
+
+impl StructuralMatch {
+    /// Expands `$NAME` and `$$$NAME` in `template` to the text captured under that name.
+    /// References to names that weren't captured are left as they are.
+    pub fn expand_replacement(&self, template: &str, buffer: &BufferSnapshot) -> String {
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(ix) = rest.find('$') {
+            result.push_str(&rest[..ix]);
+            let after_sigils = rest[ix..].trim_start_matches('$');
+            let name_len = after_sigils
+                .find(|c: char| !is_word_char(c))
+                .unwrap_or(after_sigils.len());
+            match self.captures.get(&after_sigils[..name_len]) {
+                Some(range) => {
+                    result.extend(buffer.text_for_range(range.clone()));
+                    rest = &after_sigils[name_len..];
+                }
+                None => {
+                    result.push('$');
+                    rest = &rest[ix + 1..];
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn query_matches(
+    query: &Query,
+    root: Node,
+    buffer: &BufferSnapshot,
+    range: &Range<usize>,
+    matches: &mut Vec<StructuralMatch>,
+) {
+    let match_capture = query.capture_index_for_name(MATCH_CAPTURE);
+    crate::with_query_cursor(|cursor| {
+        cursor.set_byte_range(range.clone());
+        let mut query_matches = cursor.matches(query, root, TextProvider(buffer.as_rope()));
+        while let Some(query_match) = query_matches.next() {
+            let mut captures = HashMap::default();
+            let mut match_range: Option<Range<usize>> = None;
+            for capture in query_match.captures {
+                let capture_range = capture.node.byte_range();
+                if match_capture.is_none_or(|ix| ix == capture.index) {
+                    match_range = Some(match match_range {
+                        Some(match_range) => {
+                            match_range.start.min(capture_range.start)
+                                ..match_range.end.max(capture_range.end)
+                        }
+                        None => capture_range.clone(),
+                    });
+                }
+                captures.insert(
+                    query.capture_names()[capture.index as usize].to_string(),
+                    capture_range,
+                );
+            }
+
+            if let Some(match_range) = match_range
+                && range.start <= match_range.start
+                && match_range.end <= range.end
+            {
+                matches.push(StructuralMatch {
+                    range: match_range,
+                    captures,
+                });
+            }
+        }
+    })
+}
+
+/// Parses a pattern with the given grammar. The pattern has to parse without errors into a
+/// single node, although the nodes around it may contain errors, as many grammars don't
+/// accept a bare expression as a whole file. Expressions are also tried as statements for
+/// the grammars whose error recovery doesn't cope with that.
+fn parse_pattern(text: &str, grammar: &Grammar) -> Option<CompiledQuery> {
+    ["", ";"].into_iter().find_map(|suffix| {
+        let tree = with_parser(|parser| {
+            parser.set_language(&grammar.ts_language).ok()?;
+            parser.parse(format!("{text}{suffix}"), None)
+        })?;
+        let root = tree.root_node().descendant_for_byte_range(0, text.len())?;
+        if root.byte_range() != (0..text.len()) || root.is_error() || root.has_error() {
+            return None;
+        }
+        let root = root.byte_range();
+        Some(CompiledQuery::Pattern { tree, root })
+    })
+}
+
+struct PatternMatcher<'a> {
+    pattern_text: &'a str,
+    buffer: &'a BufferSnapshot,
+}
+
+impl PatternMatcher<'_> {
+    fn find(
+        &self,
+        pattern: Node,
+        root: Node,
+        range: &Range<usize>,
+        matches: &mut Vec<StructuralMatch>,
+    ) {
+        let mut cursor = root.walk();
+        loop {
+            let node = cursor.node();
+            let mut descend = node.start_byte() < range.end && node.end_byte() > range.start;
+            if descend
+                && node.kind_id() == pattern.kind_id()
+                && range.start <= node.start_byte()
+                && node.end_byte() <= range.end
+            {
+                let mut captures = HashMap::default();
+                if self.match_node(pattern, node, &mut captures) {
+                    matches.push(StructuralMatch {
+                        range: node.byte_range(),
+                        captures,
+                    });
+                    descend = false;
+                }
+            }
+
+            if descend && cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn match_node(
+        &self,
+        pattern: Node,
+        target: Node,
+        captures: &mut HashMap<String, Range<usize>>,
+    ) -> bool {
+        if let Some(Metavariable::Single(name) | Metavariable::Multiple(name)) =
+            self.metavariable(pattern)
+        {
+            return target.is_named() && self.bind(name, target.byte_range(), captures);
+        }
+        if pattern.kind_id() != target.kind_id() {
+            return false;
+        }
+        if pattern.child_count() == 0 || target.child_count() == 0 {
+            return pattern.child_count() == target.child_count()
+                && self.target_text(target.byte_range())
+                    == self.pattern_text[pattern.byte_range()];
+        }
+
+        let patterns = significant_children(pattern);
+        let targets = significant_children(target);
+        self.match_sequence(&patterns, &targets, target.end_byte(), captures)
+    }
+
+    /// Matches a sequence of sibling nodes, backtracking over the number of nodes matched by
+    /// each multiple metavariable.
+    fn match_sequence(
+        &self,
+        patterns: &[Node],
+        targets: &[Node],
+        end: usize,
+        captures: &mut HashMap<String, Range<usize>>,
+    ) -> bool {
+        let Some((pattern, remaining_patterns)) = patterns.split_first() else {
+            return targets.is_empty();
+        };
+
+        if let Some(Metavariable::Multiple(name)) = self.metavariable(*pattern) {
+            for count in 0..=targets.len() {
+                let range = match &targets[..count] {
+                    [] => {
+                        let position = targets.first().map_or(end, |target| target.start_byte());
+                        position..position
+                    }
+                    [first, .., last] => first.start_byte()..last.end_byte(),
+                    [only] => only.byte_range(),
+                };
+                let snapshot = captures.clone();
+                if self.bind(name, range, captures)
+                    && self.match_sequence(remaining_patterns, &targets[count..], end, captures)
+                {
+                    return true;
+                }
+                *captures = snapshot;
+            }
+            return false;
+        }
+
+        let Some((target, remaining_targets)) = targets.split_first() else {
+            return false;
+        };
+        let snapshot = captures.clone();
+        if self.match_node(*pattern, *target, captures)
+            && self.match_sequence(remaining_patterns, remaining_targets, end, captures)
+        {
+            return true;
+        }
+        *captures = snapshot;
+        false
+    }
+
+    fn metavariable(&self, node: Node) -> Option<Metavariable<'_>> {
+        if node.child_count() != 0 {
+            return None;
+        }
+        let text = &self.pattern_text[node.byte_range()];
+        if let Some(name) = text.strip_prefix(MULTI_METAVARIABLE_PREFIX) {
+            Some(Metavariable::Multiple(name))
+        } else {
+            text.strip_prefix(METAVARIABLE_PREFIX)
+                .map(Metavariable::Single)
+        }
+    }
+
+    fn bind(
+        &self,
+        name: &str,
+        range: Range<usize>,
+        captures: &mut HashMap<String, Range<usize>>,
+    ) -> bool {
+        if name.is_empty() || name.starts_with('_') {
+            return true;
+        }
+        if let Some(bound) = captures.get(name) {
+            return self.target_text(bound.clone()) == self.target_text(range);
+        }
+        captures.insert(name.to_string(), range);
+        true
+    }
+
+    fn target_text(&self, range: Range<usize>) -> String {
+        self.buffer.text_for_range(range).collect()
+    }
+}
+
+/// The children of a node, without the extra nodes such as comments that can occur anywhere.
+fn significant_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| !child.is_extra())
+        .collect()
+}
+
+fn replace_metavariables(pattern: &str) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+    while let Some(ix) = rest.find('$') {
+        result.push_str(&rest[..ix]);
+        let (prefix, after_sigils) = match rest[ix..].strip_prefix("$$$") {
+            Some(after_sigils) => (MULTI_METAVARIABLE_PREFIX, after_sigils),
+            None => (METAVARIABLE_PREFIX, &rest[ix + 1..]),
+        };
+        let name_len = after_sigils
+            .find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+            .unwrap_or(after_sigils.len());
+        if name_len == 0 && prefix == METAVARIABLE_PREFIX {
+            result.push('$');
+            rest = &rest[ix + 1..];
+            continue;
+        }
+        result.push_str(prefix);
+        result.push_str(&after_sigils[..name_len]);
+        rest = &after_sigils[name_len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn is_placeholder(word: &str) -> bool {
+    word.starts_with(METAVARIABLE_PREFIX) || word.starts_with(MULTI_METAVARIABLE_PREFIX)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Buffer, rust_lang};
+    use gpui::App;
+    use text::Rope;
+
+    fn find(query: &str, text: &str, cx: &mut App) -> Vec<String> {
+        let buffer = Buffer::build_snapshot_sync(Rope::from(text), Some(rust_lang()), None, cx);
+        StructuralQuery::new(query)
+            .unwrap()
+            .matches(&buffer, 0..buffer.len())
+            .into_iter()
+            .map(|mat| buffer.text_for_range(mat.range).collect())
+            .collect()
+    }
+
+    #[gpui::test]
+    fn test_pattern_metavariables(cx: &mut App) {
+        let text = "
+            fn main() {
+                foo(a, 1);
+                foo(b, \"two\");
+                foo(c);
+                bar(d, 3);
+                foo(x, x);
+            }
+        ";
+
+        assert_eq!(
+            find("foo($A, $B)", text, cx),
+            ["foo(a, 1)", "foo(b, \"two\")", "foo(x, x)"]
+        );
+        assert_eq!(find("foo($A, $A)", text, cx), ["foo(x, x)"]);
+        assert_eq!(
+            find("foo($$$ARGS)", text, cx),
+            ["foo(a, 1)", "foo(b, \"two\")", "foo(c)", "foo(x, x)"]
+        );
+        // Whitespace in the pattern doesn't matter.
+        assert_eq!(find("bar( $_ ,3 )", text, cx), ["bar(d, 3)"]);
+    }
+
+    #[gpui::test]
+    fn test_tree_sitter_query(cx: &mut App) {
+        let text = "fn one() {}\nfn two() { one() }\n";
+        assert_eq!(
+            find("(function_item name: (identifier) @name)", text, cx),
+            ["one", "two"]
+        );
+        assert_eq!(
+            find(
+                "(function_item name: (identifier) @name (#eq? @name \"two\")) @match",
+                text,
+                cx
+            ),
+            ["fn two() { one() }"]
+        );
+    }
+
+    #[gpui::test]
+    fn test_expand_replacement(cx: &mut App) {
+        let text = "fn main() { assert_eq!(left, right); check(first, second); }";
+        let buffer = Buffer::build_snapshot_sync(Rope::from(text), Some(rust_lang()), None, cx);
+        let query = StructuralQuery::new("check($A, $B)").unwrap();
+        let matches = query.matches(&buffer, 0..buffer.len());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            matches[0].expand_replacement("check($B, $A, $UNKNOWN)", &buffer),
+            "check(second, first, $UNKNOWN)"
+        );
+
+        assert!(StructuralQuery::new("$A").is_err());
+        assert_eq!(
+            StructuralQuery::new("check($A, $B)")
+                .unwrap()
+                .required_word(),
+            Some("check")
+        );
+    }
+}
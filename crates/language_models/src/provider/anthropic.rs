@@ -694,7 +694,9 @@ pub fn into_anthropic(
     anthropic::Request {
         model,
         messages: new_messages,
-        max_tokens: max_output_tokens,
+        max_tokens: request.max_tokens.map_or(max_output_tokens, |max_tokens| {
+            max_tokens.min(max_output_tokens)
+        }),
         system: if system_message.is_empty() {
             None
         } else {
@@ -1102,6 +1104,7 @@ mod tests {
             intent: None,
             stop: vec![],
             temperature: None,
+            max_tokens: None,
             tools: vec![],
             tool_choice: None,
             thinking_allowed: true,
@@ -1166,6 +1169,7 @@ mod tests {
             intent: None,
             stop: vec![],
             temperature: None,
+            max_tokens: None,
             tools: vec![],
             tool_choice: None,
             thinking_allowed: true,
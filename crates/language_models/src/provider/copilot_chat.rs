@@ -928,6 +928,7 @@ fn into_copilot_responses(
         tool_choice,
         stop: _,
         temperature,
+        max_tokens: _,
         thinking_allowed: _,
         thinking_effort: _,
         speed: _,
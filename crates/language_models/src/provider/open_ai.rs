@@ -502,7 +502,11 @@ pub fn into_open_ai(
         stream,
         stop: request.stop,
         temperature: request.temperature.or(Some(1.0)),
-        max_completion_tokens: max_output_tokens,
+        max_completion_tokens: request
+            .max_tokens
+            .into_iter()
+            .chain(max_output_tokens)
+            .min(),
         parallel_tool_calls: if supports_parallel_tool_calls && !request.tools.is_empty() {
             // Disable parallel tool calls, as the Agent currently expects a maximum of one per turn.
             Some(false)
@@ -553,10 +557,12 @@ pub fn into_open_ai_response(
         tool_choice,
         stop: _,
         temperature,
+        max_tokens,
         thinking_allowed: _,
         thinking_effort: _,
         speed: _,
     } = request;
+    let max_output_tokens = max_tokens.into_iter().chain(max_output_tokens).min();
 
     let mut input_items = Vec::new();
     for (index, message) in messages.into_iter().enumerate() {
@@ -1434,6 +1440,7 @@ mod tests {
             tool_choice: None,
             stop: vec![],
             temperature: None,
+            max_tokens: None,
             thinking_allowed: true,
             thinking_effort: None,
             speed: None,
@@ -1573,6 +1580,7 @@ mod tests {
             tool_choice: Some(LanguageModelToolChoice::Any),
             stop: vec!["<STOP>".into()],
             temperature: None,
+            max_tokens: None,
             thinking_allowed: false,
             thinking_effort: None,
             speed: None,
@@ -877,6 +877,7 @@ impl SearchableItem for LspLogView {
             word: true,
             regex: true,
             find_in_results: false,
+            scope: false,
             // LSP log is read-only.
             replacement: false,
             selection: false,
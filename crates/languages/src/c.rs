@@ -1,7 +1,7 @@
 use anyhow::{Context as _, Result, bail};
 use async_trait::async_trait;
 use futures::StreamExt;
-use gpui::{App, AsyncApp};
+use gpui::{App, AsyncApp, Task};
 use http_client::github::{AssetKind, GitHubLspBinaryVersion, latest_github_release};
 use http_client::github_download::{GithubBinaryMetadata, download_server_binary};
 pub use language::*;
@@ -10,10 +10,99 @@ use project::lsp_store::clangd_ext;
 use serde_json::json;
 use smol::fs;
 use std::{env::consts, path::PathBuf, sync::Arc};
-use util::{ResultExt, fs::remove_matching, maybe, merge_json_value_into};
+use task::{TaskTemplate, TaskTemplates, VariableName};
+use util::{ResultExt, fs::remove_matching, maybe, merge_json_value_into, rel_path::RelPath};
 
 pub struct CLspAdapter;
 
+/// Builds CMake and Make targets; the debugger locates the resulting executables
+/// when these tasks are used to start a debug session.
+///
+/// The tasks of each build system are only offered when the worktree root has its build file.
+pub(super) struct CContextProvider;
+
+impl ContextProvider for CContextProvider {
+    fn associated_tasks(
+        &self,
+        file: Option<Arc<dyn File>>,
+        cx: &App,
+    ) -> Task<Option<TaskTemplates>> {
+        let Some(file) = project::File::from_dyn(file.as_ref()) else {
+            return Task::ready(None);
+        };
+        let worktree = file.worktree.read(cx);
+        let has_file = |name: &str| {
+            RelPath::unix(name).is_ok_and(|path| worktree.entry_for_path(path).is_some())
+        };
+
+        let mut templates = Vec::new();
+        if has_file("CMakeLists.txt") {
+            templates.extend(cmake_tasks());
+        }
+        if ["GNUmakefile", "makefile", "Makefile"]
+            .into_iter()
+            .any(has_file)
+        {
+            templates.extend(make_tasks());
+        }
+        Task::ready(Some(TaskTemplates(templates)))
+    }
+}
+
+const BUILD_DIR: &str = "build";
+
+fn cmake_tasks() -> [TaskTemplate; 3] {
+    let stem = VariableName::Stem.template_value();
+    [
+        TaskTemplate {
+            label: format!("cmake configure '{BUILD_DIR}' (Debug)"),
+            command: "cmake".into(),
+            args: vec![
+                "-S".into(),
+                ".".into(),
+                "-B".into(),
+                BUILD_DIR.into(),
+                "-DCMAKE_BUILD_TYPE=Debug".into(),
+            ],
+            cwd: Some(VariableName::WorktreeRoot.template_value()),
+            ..TaskTemplate::default()
+        },
+        TaskTemplate {
+            label: format!("cmake --build {BUILD_DIR}"),
+            command: "cmake".into(),
+            args: vec!["--build".into(), BUILD_DIR.into()],
+            cwd: Some(VariableName::WorktreeRoot.template_value()),
+            ..TaskTemplate::default()
+        },
+        TaskTemplate {
+            label: format!("cmake --build {BUILD_DIR} --target '{stem}'"),
+            command: "cmake".into(),
+            args: vec!["--build".into(), BUILD_DIR.into(), "--target".into(), stem],
+            cwd: Some(VariableName::WorktreeRoot.template_value()),
+            ..TaskTemplate::default()
+        },
+    ]
+}
+
+fn make_tasks() -> [TaskTemplate; 2] {
+    let stem = VariableName::Stem.template_value();
+    [
+        TaskTemplate {
+            label: "make".into(),
+            command: "make".into(),
+            cwd: Some(VariableName::WorktreeRoot.template_value()),
+            ..TaskTemplate::default()
+        },
+        TaskTemplate {
+            label: format!("make '{stem}'"),
+            command: "make".into(),
+            args: vec![stem],
+            cwd: Some(VariableName::Dirname.template_value()),
+            ..TaskTemplate::default()
+        },
+    ]
+}
+
 impl CLspAdapter {
     const SERVER_NAME: LanguageServerName = LanguageServerName::new_static("clangd");
 }
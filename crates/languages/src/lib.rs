@@ -111,6 +111,8 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
     let vtsls_adapter = Arc::new(vtsls::VtslsLspAdapter::new(node.clone(), fs.clone()));
     let yaml_lsp_adapter = Arc::new(yaml::YamlLspAdapter::new(node));
 
+    let c_context_provider = Arc::new(c::CContextProvider);
+
     let built_in_languages = [
         LanguageInfo {
             name: "bash",
@@ -120,11 +122,13 @@ pub fn init(languages: Arc<LanguageRegistry>, fs: Arc<dyn Fs>, node: NodeRuntime
         LanguageInfo {
             name: "c",
             adapters: vec![c_lsp_adapter.clone()],
+            context: Some(c_context_provider.clone()),
             ..Default::default()
         },
         LanguageInfo {
             name: "cpp",
             adapters: vec![c_lsp_adapter],
+            context: Some(c_context_provider),
             ..Default::default()
         },
         LanguageInfo {
@@ -0,0 +1,613 @@
+use anyhow::Result;
+use buffer_diff::{BufferDiff, DiffHunkStatus};
+use editor::{Anchor, Editor, EditorEvent, MultiBuffer, ToPoint as _};
+use futures::{FutureExt as _, select_biased};
+use gpui::{
+    AnyElement, AnyEntity, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Pixels, Render, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, Window, actions, uniform_list,
+};
+use language::{Buffer, BufferEvent};
+use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    Fs, Project,
+    local_history_store::{LocalHistoryEntry, LocalHistoryReason, LocalHistoryStore},
+};
+use std::{
+    any::{Any, TypeId},
+    ops::Range,
+    path::Path,
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+use time::OffsetDateTime;
+use ui::{ListItem, Tooltip, WithScrollbar, prelude::*};
+use util::{ResultExt as _, size::format_file_size};
+use workspace::{
+    Item, ItemHandle as _, ItemNavHistory, Workspace,
+    item::{ItemEvent, SaveOptions, TabContentParams},
+    notifications::DetachAndPromptErr,
+    searchable::SearchableItemHandle,
+};
+
+actions!(
+    local_history,
+    [
+        /// Shows the local history of the active file.
+        ShowHistory,
+        /// Restores the whole file to the selected snapshot of its local history.
+        RestoreFile,
+    ]
+);
+
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
+    LocalHistoryStore::init_global(fs, cx);
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ShowHistory, window, cx| {
+            LocalHistoryView::deploy(workspace, window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Shows the snapshots of a file in its local history, and the changes between the selected
+/// snapshot and the file's buffer.
+pub struct LocalHistoryView {
+    local_history: Entity<LocalHistoryStore>,
+    buffer: Entity<Buffer>,
+    abs_path: Arc<Path>,
+    /// Ordered from newest to oldest.
+    entries: Vec<LocalHistoryEntry>,
+    selected_entry_id: Option<u64>,
+    /// The contents of the selected snapshot, which the buffer is diffed against.
+    base_text: Option<Arc<str>>,
+    diff_editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    buffer_changes_tx: watch::Sender<()>,
+    _load_snapshot: Task<Option<()>>,
+    _recalculate_diff: Task<Result<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl LocalHistoryView {
+    /// Opens the local history of the active editor's file, reusing an existing view for it.
+    pub fn deploy(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(local_history) = LocalHistoryStore::try_global(cx) else {
+            return;
+        };
+        let Some(buffer) = workspace
+            .active_item_as::<Editor>(cx)
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        else {
+            return;
+        };
+        let Some(abs_path) = buffer
+            .read(cx)
+            .file()
+            .and_then(|file| file.as_local())
+            .map(|file| Arc::<Path>::from(file.abs_path(cx)))
+        else {
+            return;
+        };
+
+        if let Some(existing) = workspace
+            .items_of_type::<Self>(cx)
+            .find(|view| view.read(cx).buffer == buffer)
+        {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let view = cx.new(|cx| Self::new(local_history, buffer, abs_path, project, window, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+    }
+
+    fn new(
+        local_history: Entity<LocalHistoryStore>,
+        buffer: Entity<Buffer>,
+        abs_path: Arc<Path>,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        let diff = cx.new(|cx| BufferDiff::new_unchanged(&buffer_snapshot.text, cx));
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(buffer.clone(), cx);
+            multibuffer.add_diff(diff.clone(), cx);
+            multibuffer
+        });
+        let diff_editor = cx.new(|cx| {
+            let mut editor = Editor::for_multibuffer(multibuffer, Some(project), window, cx);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_expand_all_diff_hunks(cx);
+            editor.set_render_diff_hunk_controls(Arc::new(render_restore_hunk_control), cx);
+            editor
+        });
+
+        let (buffer_changes_tx, mut buffer_changes_rx) = watch::channel(());
+        let subscriptions = vec![
+            cx.subscribe(&buffer, |this, _, event, _| match event {
+                BufferEvent::Edited | BufferEvent::LanguageChanged(_) | BufferEvent::Reparsed => {
+                    this.buffer_changes_tx.send(()).ok();
+                }
+                _ => {}
+            }),
+            cx.subscribe(&diff_editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone());
+            }),
+            cx.observe(&local_history, |this, _, cx| this.update_entries(cx)),
+        ];
+
+        let recalculate_diff = cx.spawn(async move |this, cx| {
+            while buffer_changes_rx.recv().await.is_ok() {
+                loop {
+                    let mut timer = cx
+                        .background_executor()
+                        .timer(RECALCULATE_DIFF_DEBOUNCE)
+                        .fuse();
+                    let mut recv = pin!(buffer_changes_rx.recv().fuse());
+                    select_biased! {
+                        _ = timer => break,
+                        _ = recv => continue,
+                    }
+                }
+
+                let (buffer_snapshot, base_text) = this.update(cx, |this, cx| {
+                    let buffer_snapshot = this.buffer.read(cx).snapshot();
+                    let base_text = this
+                        .base_text
+                        .clone()
+                        .unwrap_or_else(|| buffer_snapshot.text().into());
+                    (buffer_snapshot, base_text)
+                })?;
+                diff.update(cx, |diff, cx| {
+                    diff.set_base_text(
+                        Some(base_text),
+                        buffer_snapshot.language().cloned(),
+                        buffer_snapshot.text.clone(),
+                        cx,
+                    )
+                })
+                .await
+                .ok();
+            }
+            Ok(())
+        });
+
+        let mut this = Self {
+            local_history,
+            buffer,
+            abs_path,
+            entries: Vec::new(),
+            selected_entry_id: None,
+            base_text: None,
+            diff_editor,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: UniformListScrollHandle::new(),
+            buffer_changes_tx,
+            _load_snapshot: Task::ready(None),
+            _recalculate_diff: recalculate_diff,
+            _subscriptions: subscriptions,
+        };
+        this.update_entries(cx);
+        this
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        self.entries = self
+            .local_history
+            .read(cx)
+            .entries_for_path(&self.abs_path)
+            .cloned()
+            .collect();
+        let selection_removed = self
+            .selected_entry_id
+            .is_some_and(|id| !self.entries.iter().any(|entry| entry.id == id));
+        if self.selected_entry_id.is_none() || selection_removed {
+            self.select_entry(self.entries.first().map(|entry| entry.id), cx);
+        }
+        cx.notify();
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let id = self.selected_entry_id?;
+        self.entries.iter().position(|entry| entry.id == id)
+    }
+
+    fn select_entry(&mut self, id: Option<u64>, cx: &mut Context<Self>) {
+        if self.selected_entry_id == id {
+            return;
+        }
+        self.selected_entry_id = id;
+        if let Some(ix) = self.selected_index() {
+            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+        cx.notify();
+
+        let Some(id) = id else {
+            self.base_text = None;
+            self._load_snapshot = Task::ready(None);
+            self.buffer_changes_tx.send(()).ok();
+            return;
+        };
+        let load = self.local_history.read(cx).load_text(id, cx);
+        self._load_snapshot = cx.spawn(async move |this, cx| {
+            let text = load.await.log_err()?;
+            this.update(cx, |this, cx| {
+                this.base_text = Some(text.into());
+                this.buffer_changes_tx.send(()).ok();
+                cx.notify();
+            })
+            .ok()
+        });
+    }
+
+    fn select_ix(&mut self, ix: usize, cx: &mut Context<Self>) {
+        let id = self.entries.get(ix).map(|entry| entry.id);
+        self.select_entry(id, cx);
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = match self.selected_index() {
+            _ if self.entries.is_empty() => return,
+            Some(ix) if ix + 1 < self.entries.len() => ix + 1,
+            _ => 0,
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        let ix = match self.selected_index() {
+            _ if self.entries.is_empty() => return,
+            Some(ix) if ix > 0 => ix - 1,
+            _ => self.entries.len() - 1,
+        };
+        self.select_ix(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_ix(0, cx);
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        self.select_ix(self.entries.len().saturating_sub(1), cx);
+    }
+
+    /// Replaces the contents of the buffer with the selected snapshot, after snapshotting the
+    /// buffer so that the restore can be undone from the history as well.
+    fn restore_file(&mut self, _: &RestoreFile, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(id) = self.selected_entry_id else {
+            return;
+        };
+        let load = self.local_history.read(cx).load_text(id, cx);
+        let snapshot_before_restore = self.local_history.update(cx, |local_history, cx| {
+            local_history.snapshot_buffer(&self.buffer, LocalHistoryReason::BeforeRestore, cx)
+        });
+        let buffer = self.buffer.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let text = load.await?;
+            snapshot_before_restore.await?;
+            let diff = buffer
+                .read_with(cx, |buffer, cx| buffer.diff(text, cx))
+                .await;
+            buffer.update(cx, |buffer, cx| {
+                buffer.finalize_last_transaction();
+                buffer.apply_diff(diff, cx);
+                buffer.finalize_last_transaction();
+            });
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to restore the file", window, cx, |_, _, _| None);
+    }
+
+    fn render_entry(&self, ix: usize, entry: &LocalHistoryEntry, cx: &Context<Self>) -> AnyElement {
+        let timestamp = OffsetDateTime::from_unix_timestamp(entry.timestamp)
+            .unwrap_or(OffsetDateTime::UNIX_EPOCH);
+        let relative_timestamp = time_format::format_localized_timestamp(
+            timestamp,
+            OffsetDateTime::now_utc(),
+            time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC),
+            time_format::TimestampFormat::Relative,
+        );
+        let id = entry.id;
+
+        ListItem::new(("local-history-entry", ix))
+            .toggle_state(Some(id) == self.selected_entry_id)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .justify_between()
+                    .child(
+                        v_flex()
+                            .min_w_0()
+                            .child(Label::new(relative_timestamp).size(LabelSize::Small))
+                            .child(
+                                Label::new(entry.reason.label())
+                                    .size(LabelSize::XSmall)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            ),
+                    )
+                    .child(
+                        Label::new(format_file_size(entry.len, false))
+                            .size(LabelSize::XSmall)
+                            .color(Color::Muted),
+                    ),
+            )
+            .on_click(cx.listener(move |this, _, _, cx| this.select_entry(Some(id), cx)))
+            .into_any_element()
+    }
+}
+
+fn render_restore_hunk_control(
+    row: u32,
+    _status: &DiffHunkStatus,
+    hunk_range: Range<Anchor>,
+    _is_created_file: bool,
+    line_height: Pixels,
+    editor: &Entity<Editor>,
+    _window: &mut Window,
+    cx: &mut App,
+) -> AnyElement {
+    h_flex()
+        .h(line_height)
+        .mr_1()
+        .px_0p5()
+        .pb_1()
+        .border_x_1()
+        .border_b_1()
+        .border_color(cx.theme().colors().border_variant)
+        .rounded_b_lg()
+        .bg(cx.theme().colors().editor_background)
+        .block_mouse_except_scroll()
+        .shadow_md()
+        .child(
+            Button::new(("restore", row as u64), "Restore")
+                .tooltip(Tooltip::text("Restore This Hunk From the Snapshot"))
+                .on_click({
+                    let editor = editor.clone();
+                    move |_event, window, cx| {
+                        editor.update(cx, |editor, cx| {
+                            let snapshot = editor.snapshot(window, cx);
+                            let point = hunk_range.start.to_point(&snapshot.buffer_snapshot());
+                            editor.restore_hunks_in_ranges(vec![point..point], window, cx);
+                        });
+                    }
+                }),
+        )
+        .into_any_element()
+}
+
+impl EventEmitter<EditorEvent> for LocalHistoryView {}
+
+impl Focusable for LocalHistoryView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for LocalHistoryView {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entry_count = self.entries.len();
+
+        h_flex()
+            .id("local_history_view")
+            .key_context("LocalHistoryView")
+            .on_action(cx.listener(Self::restore_file))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .track_focus(&self.focus_handle)
+                    .on_action(cx.listener(Self::select_next))
+                    .on_action(cx.listener(Self::select_previous))
+                    .on_action(cx.listener(Self::select_first))
+                    .on_action(cx.listener(Self::select_last))
+                    .w(rems(20.))
+                    .h_full()
+                    .flex_none()
+                    .border_r_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(
+                        h_flex()
+                            .h(rems_from_px(41.))
+                            .pl_3()
+                            .pr_2()
+                            .justify_between()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(
+                                Label::new(format!("{entry_count} snapshots"))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(
+                                Button::new("restore-file", "Restore File")
+                                    .label_size(LabelSize::Small)
+                                    .disabled(self.selected_entry_id.is_none())
+                                    .tooltip(Tooltip::for_action_title(
+                                        "Restore the File to the Selected Snapshot",
+                                        &RestoreFile,
+                                    ))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.restore_file(&RestoreFile, window, cx);
+                                    })),
+                            ),
+                    )
+                    .map(|this| {
+                        if entry_count == 0 {
+                            this.child(v_flex().flex_1().justify_center().items_center().child(
+                                Label::new("No local history for this file").color(Color::Muted),
+                            ))
+                        } else {
+                            this.child(
+                                v_flex()
+                                    .flex_1()
+                                    .size_full()
+                                    .child({
+                                        let view = cx.weak_entity();
+                                        uniform_list(
+                                            "local-history-entries",
+                                            entry_count,
+                                            move |range, _, cx| {
+                                                let Some(view) = view.upgrade() else {
+                                                    return Vec::new();
+                                                };
+                                                view.update(cx, |this, cx| {
+                                                    this.entries[range.clone()]
+                                                        .iter()
+                                                        .zip(range)
+                                                        .map(|(entry, ix)| {
+                                                            this.render_entry(ix, entry, cx)
+                                                        })
+                                                        .collect()
+                                                })
+                                            },
+                                        )
+                                        .flex_1()
+                                        .size_full()
+                                        .track_scroll(&self.scroll_handle)
+                                    })
+                                    .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+                            )
+                        }
+                    }),
+            )
+            .child(div().flex_1().h_full().child(self.diff_editor.clone()))
+    }
+}
+
+impl Item for LocalHistoryView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::HistoryRerun).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _: &App) -> SharedString {
+        let file_name = self
+            .abs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "File".to_string());
+        format!("Local History: {file_name}").into()
+    }
+
+    fn tab_tooltip_text(&self, _: &App) -> Option<SharedString> {
+        Some(format!("Local history for {}", self.abs_path.display()).into())
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Local History Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.diff_editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyEntity> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.clone().into())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.diff_editor.clone().into())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>, _: &App) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.diff_editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.diff_editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.diff_editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Arc<dyn Any + Send>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.diff_editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.diff_editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.diff_editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.diff_editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx))
+    }
+}
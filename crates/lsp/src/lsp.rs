@@ -106,6 +106,9 @@ pub struct LanguageServer {
     process_name: Arc<str>,
     binary: LanguageServerBinary,
     capabilities: RwLock<ServerCapabilities>,
+    /// The capabilities from the server's `initialize` response, including the ones that
+    /// [`ServerCapabilities`] has no field for.
+    raw_capabilities: Value,
     /// Configuration sent to the server, stored for display in the language server logs
     /// buffer. This is represented as the message sent to the LSP in order to avoid cloning it (can
     /// be large in cases like sending schemas to the json server).
@@ -339,6 +342,18 @@ pub struct AdapterServerCapabilities {
     pub server_capabilities: ServerCapabilities,
     // List of code actions supported by the LspAdapter matching the server
     pub code_action_kinds: Option<Vec<CodeActionKind>>,
+    // Capabilities reported by the server, as JSON, including those missing from `server_capabilities`
+    pub raw_capabilities: Value,
+}
+
+/// The `initialize` request, with its result kept as JSON so that the server capabilities
+/// which lsp-types has no field for aren't lost.
+pub enum InitializeRaw {}
+
+impl request::Request for InitializeRaw {
+    type Params = InitializeParams;
+    type Result = Value;
+    const METHOD: &'static str = request::Initialize::METHOD;
 }
 
 // See the VSCode docs [1] and the LSP Spec [2]
@@ -593,6 +608,7 @@ impl LanguageServer {
                 .unwrap_or_default(),
             binary,
             capabilities: Default::default(),
+            raw_capabilities: Value::Null,
             configuration,
             code_action_kinds,
             next_id: Default::default(),
@@ -987,6 +1003,16 @@ impl LanguageServer {
                     code_lens: Some(CodeLensClientCapabilities {
                         dynamic_registration: Some(true),
                     }),
+                    call_hierarchy: Some(CallHierarchyClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                        dynamic_registration: Some(false),
+                    }),
+                    document_link: Some(DocumentLinkClientCapabilities {
+                        dynamic_registration: Some(false),
+                        tooltip_support: Some(true),
+                    }),
                     document_symbol: Some(DocumentSymbolClientCapabilities {
                         hierarchical_document_symbol_support: Some(true),
                         dynamic_registration: Some(true),
@@ -1057,7 +1083,7 @@ impl LanguageServer {
     ) -> Task<Result<Arc<Self>>> {
         cx.background_spawn(async move {
             let response = self
-                .request::<request::Initialize>(params, timeout)
+                .request::<InitializeRaw>(params, timeout)
                 .await
                 .into_response()
                 .with_context(|| {
@@ -1067,11 +1093,15 @@ impl LanguageServer {
                         self.server_id()
                     )
                 })?;
+            let raw_capabilities = response.get("capabilities").cloned().unwrap_or_default();
+            let response = serde_json::from_value::<InitializeResult>(response)
+                .context("parsing the initialize response")?;
             if let Some(info) = response.server_info {
                 self.version = info.version.map(SharedString::from);
                 self.process_name = info.name.into();
             }
             self.capabilities = RwLock::new(response.capabilities);
+            self.raw_capabilities = raw_capabilities;
             self.configuration = configuration;
 
             self.notify::<notification::Initialized>(InitializedParams {})?;
@@ -1316,12 +1346,44 @@ impl LanguageServer {
         self.capabilities.read().clone()
     }
 
+    /// Get the capabilities the language server reported when it was initialized, as JSON.
+    /// Unlike [`Self::capabilities`], this includes the ones lsp-types has no field for, but
+    /// not the ones registered dynamically.
+    pub fn raw_capabilities(&self) -> &Value {
+        &self.raw_capabilities
+    }
+
+    /// Get the reported capabilities of the running language server as JSON. Besides
+    /// [`Self::capabilities`], this includes the ones from the `initialize` response that
+    /// lsp-types has no field for.
+    pub fn capabilities_json(&self) -> Value {
+        let mut capabilities = serde_json::to_value(self.capabilities()).unwrap_or_default();
+        if let (Value::Object(capabilities), Value::Object(raw_capabilities)) =
+            (&mut capabilities, &self.raw_capabilities)
+        {
+            let known_capabilities =
+                serde_json::from_value::<ServerCapabilities>(self.raw_capabilities.clone())
+                    .ok()
+                    .and_then(|known_capabilities| serde_json::to_value(known_capabilities).ok())
+                    .unwrap_or_default();
+            for (name, value) in raw_capabilities {
+                if known_capabilities.get(name).is_none() {
+                    capabilities
+                        .entry(name.clone())
+                        .or_insert_with(|| value.clone());
+                }
+            }
+        }
+        capabilities
+    }
+
     /// Get the reported capabilities of the running language server and
     /// what we know on the client/adapter-side of its capabilities.
     pub fn adapter_server_capabilities(&self) -> AdapterServerCapabilities {
         AdapterServerCapabilities {
             server_capabilities: self.capabilities(),
             code_action_kinds: self.code_action_kinds(),
+            raw_capabilities: self.raw_capabilities.clone(),
         }
     }
 
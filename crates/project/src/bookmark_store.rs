@@ -0,0 +1,397 @@
+//! Module for managing bookmarks in a project.
+//!
+//! Bookmarks are anchored in buffers, so they follow the lines they were set on as the buffers
+//! are edited. They're kept by the client and persisted with its workspace, which makes them work
+//! the same way in local, remote and shared projects.
+use anyhow::Result;
+use collections::BTreeMap;
+use gpui::{App, AppContext as _, Context, Entity, EventEmitter, SharedString, Subscription, Task};
+use language::{Buffer, BufferEvent, BufferSnapshot};
+use std::{ops::Range, path::Path, sync::Arc};
+use text::Point;
+
+use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub position: text::Anchor,
+    pub label: Option<SharedString>,
+}
+
+/// A bookmark as it's persisted, by the row it's on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceBookmark {
+    pub row: u32,
+    pub label: Option<Arc<str>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BookmarkStoreEvent {
+    BookmarksUpdated,
+}
+
+struct BookmarksInFile {
+    buffer: Entity<Buffer>,
+    /// Ordered by position. Anchors keep their order as the buffer is edited.
+    bookmarks: Vec<Bookmark>,
+    _subscription: Subscription,
+}
+
+pub struct BookmarkStore {
+    buffer_store: Entity<BufferStore>,
+    worktree_store: Entity<WorktreeStore>,
+    bookmarks: BTreeMap<Arc<Path>, BookmarksInFile>,
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+impl BookmarkStore {
+    pub fn new(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
+        Self {
+            buffer_store,
+            worktree_store,
+            bookmarks: BTreeMap::default(),
+        }
+    }
+
+    fn abs_path_from_buffer(buffer: &Entity<Buffer>, cx: &App) -> Option<Arc<Path>> {
+        worktree::File::from_dyn(buffer.read(cx).file())
+            .map(|file| file.worktree.read(cx).absolutize(&file.path))
+            .map(Arc::<Path>::from)
+    }
+
+    fn bookmarks_in_file(buffer: Entity<Buffer>, cx: &mut Context<Self>) -> BookmarksInFile {
+        let subscription = cx.subscribe(&buffer, |this, buffer, event, cx| {
+            if let BufferEvent::FileHandleChanged = event {
+                this.buffer_file_changed(buffer, cx);
+            }
+        });
+        BookmarksInFile {
+            buffer,
+            bookmarks: Vec::new(),
+            _subscription: subscription,
+        }
+    }
+
+    /// Moves the bookmarks of a renamed file to its new path, and drops those of a deleted one.
+    fn buffer_file_changed(&mut self, buffer: Entity<Buffer>, cx: &mut Context<Self>) {
+        let Some(old_path) = self
+            .bookmarks
+            .iter()
+            .find(|(_, in_file)| in_file.buffer == buffer)
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        let new_path = if buffer
+            .read(cx)
+            .file()
+            .is_none_or(|file| file.disk_state().is_deleted())
+        {
+            None
+        } else {
+            Self::abs_path_from_buffer(&buffer, cx)
+        };
+        if new_path.as_ref() == Some(&old_path) {
+            return;
+        }
+
+        if let Some(in_file) = self.bookmarks.remove(&old_path)
+            && let Some(new_path) = new_path
+        {
+            self.bookmarks.insert(new_path, in_file);
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    fn bookmarks_changed(&mut self, cx: &mut Context<Self>) {
+        cx.emit(BookmarkStoreEvent::BookmarksUpdated);
+        cx.notify();
+    }
+
+    /// Removes the bookmark on the row of `position`, or sets one there if there is none.
+    pub fn toggle_bookmark(
+        &mut self,
+        buffer: Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+        let snapshot = buffer.read(cx).text_snapshot();
+        let row = snapshot.summary_for_anchor::<Point>(&position).row;
+
+        let in_file = self
+            .bookmarks
+            .entry(abs_path.clone())
+            .or_insert_with(|| Self::bookmarks_in_file(buffer, cx));
+        let len_before = in_file.bookmarks.len();
+        in_file
+            .bookmarks
+            .retain(|bookmark| snapshot.summary_for_anchor::<Point>(&bookmark.position).row != row);
+        if in_file.bookmarks.len() == len_before {
+            let position = snapshot.anchor_after(Point::new(row, 0));
+            let ix = in_file
+                .bookmarks
+                .partition_point(|bookmark| bookmark.position.cmp(&position, &snapshot).is_lt());
+            in_file.bookmarks.insert(
+                ix,
+                Bookmark {
+                    position,
+                    label: None,
+                },
+            );
+        }
+
+        if in_file.bookmarks.is_empty() {
+            self.bookmarks.remove(&abs_path);
+        }
+        self.bookmarks_changed(cx);
+    }
+
+    /// Sets the label of the bookmark at `position`. Blank labels remove it.
+    pub fn set_label(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        label: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(bookmark) = self
+            .bookmarks
+            .values_mut()
+            .find(|in_file| in_file.buffer == *buffer)
+            .and_then(|in_file| {
+                in_file
+                    .bookmarks
+                    .iter_mut()
+                    .find(|bookmark| bookmark.position == position)
+            })
+        else {
+            return;
+        };
+        bookmark.label = label.filter(|label| !label.trim().is_empty());
+        self.bookmarks_changed(cx);
+    }
+
+    pub fn remove_bookmark(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &mut Context<Self>,
+    ) {
+        let mut removed = false;
+        self.bookmarks.retain(|_, in_file| {
+            if in_file.buffer == *buffer {
+                let len_before = in_file.bookmarks.len();
+                in_file
+                    .bookmarks
+                    .retain(|bookmark| bookmark.position != position);
+                removed = in_file.bookmarks.len() != len_before;
+            }
+            !in_file.bookmarks.is_empty()
+        });
+        if removed {
+            self.bookmarks_changed(cx);
+        }
+    }
+
+    pub fn clear_bookmarks(&mut self, cx: &mut Context<Self>) {
+        if !self.bookmarks.is_empty() {
+            self.bookmarks.clear();
+            self.bookmarks_changed(cx);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+    }
+
+    /// Returns the bookmarks of the buffer in order, optionally only those within `range`.
+    pub fn bookmarks_in_buffer<'a>(
+        &'a self,
+        buffer: &'a Entity<Buffer>,
+        range: Option<Range<text::Anchor>>,
+        buffer_snapshot: &'a BufferSnapshot,
+    ) -> impl Iterator<Item = &'a Bookmark> + 'a {
+        self.bookmarks
+            .values()
+            .find(|in_file| in_file.buffer == *buffer)
+            .into_iter()
+            .flat_map(|in_file| &in_file.bookmarks)
+            .filter(move |bookmark| {
+                buffer_snapshot.can_resolve(&bookmark.position)
+                    && range.as_ref().is_none_or(|range| {
+                        bookmark.position.cmp(&range.start, buffer_snapshot).is_ge()
+                            && bookmark.position.cmp(&range.end, buffer_snapshot).is_le()
+                    })
+            })
+    }
+
+    /// Returns every bookmark with the path and buffer it's in, ordered by path and position.
+    pub fn all_bookmarks(&self) -> impl Iterator<Item = (&Arc<Path>, &Entity<Buffer>, &Bookmark)> {
+        self.bookmarks.iter().flat_map(|(path, in_file)| {
+            in_file
+                .bookmarks
+                .iter()
+                .map(move |bookmark| (path, &in_file.buffer, bookmark))
+        })
+    }
+
+    /// Returns the first bookmark after the row of `position`, continuing with the files that
+    /// come after the buffer's and wrapping around.
+    pub fn next_bookmark(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &App,
+    ) -> Option<(Entity<Buffer>, Bookmark)> {
+        self.adjacent_bookmark(buffer, position, false, cx)
+    }
+
+    /// Returns the last bookmark before the row of `position`, continuing with the files that
+    /// come before the buffer's and wrapping around.
+    pub fn previous_bookmark(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        cx: &App,
+    ) -> Option<(Entity<Buffer>, Bookmark)> {
+        self.adjacent_bookmark(buffer, position, true, cx)
+    }
+
+    fn adjacent_bookmark(
+        &self,
+        buffer: &Entity<Buffer>,
+        position: text::Anchor,
+        reverse: bool,
+        cx: &App,
+    ) -> Option<(Entity<Buffer>, Bookmark)> {
+        let current = Self::abs_path_from_buffer(buffer, cx).map(|path| {
+            let row = buffer.read(cx).summary_for_anchor::<Point>(&position).row;
+            (path, row)
+        });
+        let is_adjacent = |path: &Arc<Path>, row: u32| match &current {
+            Some((current_path, current_row)) => {
+                let ordering = path.cmp(current_path).then(row.cmp(current_row));
+                if reverse {
+                    ordering.is_lt()
+                } else {
+                    ordering.is_gt()
+                }
+            }
+            None => true,
+        };
+
+        let bookmarks = self
+            .all_bookmarks()
+            .map(|(path, buffer, bookmark)| {
+                let row = buffer
+                    .read(cx)
+                    .summary_for_anchor::<Point>(&bookmark.position)
+                    .row;
+                (path, row, buffer, bookmark)
+            })
+            .collect::<Vec<_>>();
+        let adjacent = if reverse {
+            bookmarks
+                .iter()
+                .rev()
+                .find(|(path, row, _, _)| is_adjacent(path, *row))
+                .or(bookmarks.last())
+        } else {
+            bookmarks
+                .iter()
+                .find(|(path, row, _, _)| is_adjacent(path, *row))
+                .or(bookmarks.first())
+        };
+        adjacent.map(|(_, _, buffer, bookmark)| ((*buffer).clone(), (*bookmark).clone()))
+    }
+
+    pub fn all_source_bookmarks(&self, cx: &App) -> BTreeMap<Arc<Path>, Vec<SourceBookmark>> {
+        self.bookmarks
+            .iter()
+            .map(|(path, in_file)| {
+                let buffer = in_file.buffer.read(cx);
+                let bookmarks = in_file
+                    .bookmarks
+                    .iter()
+                    .map(|bookmark| SourceBookmark {
+                        row: buffer.summary_for_anchor::<Point>(&bookmark.position).row,
+                        label: bookmark
+                            .label
+                            .as_ref()
+                            .map(|label| Arc::from(label.as_ref())),
+                    })
+                    .collect();
+                (path.clone(), bookmarks)
+            })
+            .collect()
+    }
+
+    /// Replaces the bookmarks with persisted ones, opening the buffers they're in.
+    pub fn with_serialized_bookmarks(
+        &self,
+        bookmarks: BTreeMap<Arc<Path>, Vec<SourceBookmark>>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let worktree_store = self.worktree_store.downgrade();
+        let buffer_store = self.buffer_store.downgrade();
+        cx.spawn(async move |this, cx| {
+            let mut new_bookmarks = BTreeMap::default();
+            for (path, source_bookmarks) in bookmarks {
+                if source_bookmarks.is_empty() {
+                    continue;
+                }
+                let (worktree, relative_path) = worktree_store
+                    .update(cx, |worktree_store, cx| {
+                        worktree_store.find_or_create_worktree(&path, false, cx)
+                    })?
+                    .await?;
+                let buffer = buffer_store
+                    .update(cx, |buffer_store, cx| {
+                        let path = ProjectPath {
+                            worktree_id: worktree.read(cx).id(),
+                            path: relative_path,
+                        };
+                        buffer_store.open_buffer(path, cx)
+                    })?
+                    .await;
+                let buffer = match buffer {
+                    Ok(buffer) => buffer,
+                    Err(error) => {
+                        log::warn!("Skipping bookmarks in {path:?}: {error:#}");
+                        continue;
+                    }
+                };
+                let snapshot = buffer.read_with(cx, |buffer, _| buffer.text_snapshot());
+
+                let mut in_file = this.update(cx, |_, cx| Self::bookmarks_in_file(buffer, cx))?;
+                for source_bookmark in source_bookmarks {
+                    if source_bookmark.row > snapshot.max_point().row {
+                        log::warn!("Skipping a bookmark past the end of {path:?}");
+                        continue;
+                    }
+                    let position = snapshot.anchor_after(Point::new(source_bookmark.row, 0));
+                    let ix = in_file.bookmarks.partition_point(|bookmark| {
+                        bookmark.position.cmp(&position, &snapshot).is_lt()
+                    });
+                    in_file.bookmarks.insert(
+                        ix,
+                        Bookmark {
+                            position,
+                            label: source_bookmark.label.map(SharedString::from),
+                        },
+                    );
+                }
+                new_bookmarks.insert(path, in_file);
+            }
+
+            this.update(cx, |this, cx| {
+                this.bookmarks = new_bookmarks;
+                cx.notify();
+            })
+        })
+    }
+}
@@ -7,7 +7,8 @@ use breakpoints_in_file::{BreakpointsInFile, StatefulBreakpoint};
 use collections::{BTreeMap, HashMap};
 use dap::{StackFrameId, client::SessionId};
 use gpui::{
-    App, AppContext, AsyncApp, Context, Entity, EntityId, EventEmitter, Subscription, Task,
+    App, AppContext, AsyncApp, Context, Entity, EntityId, EventEmitter, SharedString, Subscription,
+    Task,
 };
 use itertools::Itertools;
 use language::{Buffer, BufferSnapshot, proto::serialize_anchor as serialize_text_anchor};
@@ -15,9 +16,11 @@ use rpc::{
     AnyProtoClient, TypedEnvelope,
     proto::{self},
 };
+use serde::{Deserialize, Serialize};
 use std::{hash::Hash, ops::Range, path::Path, sync::Arc, u32};
 use text::{Point, PointUtf16};
-use util::maybe;
+use util::{ResultExt as _, maybe, rel_path::RelPath};
+use worktree::WorktreeId;
 
 use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
 
@@ -54,11 +57,14 @@ mod breakpoints_in_file {
         }
     }
 
-    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
     pub struct BreakpointSessionState {
         /// Session-specific identifier for the breakpoint, as assigned by Debug Adapter.
-        pub id: u64,
+        pub id: Option<u64>,
         pub verified: bool,
+        /// Explanation from the Debug Adapter of why the breakpoint could not be verified,
+        /// e.g. a condition that failed to parse.
+        pub message: Option<SharedString>,
     }
     #[derive(Clone)]
     pub(super) struct BreakpointsInFile {
@@ -251,6 +257,7 @@ impl BreakpointStore {
                             let state = BreakpointSessionState {
                                 id: state.id,
                                 verified: state.verified,
+                                message: state.message.clone().map(Into::into),
                             };
                             (SessionId::from_proto(*session_id), state)
                         })
@@ -357,7 +364,7 @@ impl BreakpointStore {
                         .find_map(|state| {
                             let state = state.session_state.get_mut(&session_id)?;
 
-                            if state.id == event_id {
+                            if state.id == Some(event_id) {
                                 Some(state)
                             } else {
                                 None
@@ -366,6 +373,7 @@ impl BreakpointStore {
                 })?;
 
             state.verified = breakpoint.verified;
+            state.message = breakpoint.message.map(Into::into);
             Some(())
         });
     }
@@ -645,7 +653,7 @@ impl BreakpointStore {
                         }
                         let session_state = active_session_id
                             .and_then(|id| bp.session_state.get(&id))
-                            .copied();
+                            .cloned();
                         Some((&bp.bp, session_state))
                     }
                 })
@@ -881,6 +889,158 @@ impl BreakpointStore {
         }
     }
 
+    /// Returns all breakpoints with paths relative to the worktree they belong to, so that they
+    /// can be shared between machines. Breakpoints in files outside of the project are skipped.
+    ///
+    /// `worktree_id` is the worktree the breakpoints are exported to; breakpoints in other
+    /// worktrees record the name of their worktree.
+    pub fn export_breakpoints(&self, worktree_id: WorktreeId, cx: &App) -> Vec<ExportedBreakpoint> {
+        let worktree_store = self.worktree_store.read(cx);
+        self.all_source_breakpoints(cx)
+            .into_iter()
+            .filter_map(|(abs_path, breakpoints)| {
+                let Some((worktree, path)) = worktree_store.find_worktree(&abs_path, cx) else {
+                    log::warn!("Not exporting breakpoints outside of the project in {abs_path:?}");
+                    return None;
+                };
+                let worktree = worktree.read(cx);
+                let worktree_name =
+                    (worktree.id() != worktree_id).then(|| worktree.root_name_str().to_owned());
+                Some(breakpoints.into_iter().map(move |breakpoint| {
+                    ExportedBreakpoint {
+                        worktree: worktree_name.clone(),
+                        path: path.as_unix_str().to_owned(),
+                        line: breakpoint.row + 1,
+                        enabled: breakpoint.state.is_enabled(),
+                        condition: breakpoint.condition.map(|condition| condition.to_string()),
+                        hit_condition: breakpoint
+                            .hit_condition
+                            .map(|hit_condition| hit_condition.to_string()),
+                        log_message: breakpoint.message.map(|message| message.to_string()),
+                    }
+                }))
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Adds previously exported breakpoints, replacing any breakpoint already set on the same
+    /// line. Paths are resolved against the worktree with `worktree_id`, unless a breakpoint
+    /// names another worktree.
+    pub fn import_breakpoints(
+        &mut self,
+        worktree_id: WorktreeId,
+        breakpoints: Vec<ExportedBreakpoint>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let mut breakpoints_by_path = BTreeMap::<ProjectPath, Vec<ExportedBreakpoint>>::default();
+        {
+            let worktree_store = self.worktree_store.read(cx);
+            for breakpoint in breakpoints {
+                let worktree = match &breakpoint.worktree {
+                    Some(name) => worktree_store
+                        .worktrees()
+                        .find(|worktree| worktree.read(cx).root_name_str() == name),
+                    None => worktree_store.worktree_for_id(worktree_id, cx),
+                };
+                let Some(worktree) = worktree else {
+                    log::warn!(
+                        "Skipping breakpoint in {:?}: no such worktree",
+                        breakpoint.path
+                    );
+                    continue;
+                };
+                let Some(path) = RelPath::unix(&breakpoint.path).log_err() else {
+                    continue;
+                };
+                let project_path = ProjectPath {
+                    worktree_id: worktree.read(cx).id(),
+                    path: path.into_arc(),
+                };
+                breakpoints_by_path
+                    .entry(project_path)
+                    .or_default()
+                    .push(breakpoint);
+            }
+        }
+
+        let buffer_store = self.buffer_store.downgrade();
+        cx.spawn(async move |this, cx| {
+            for (project_path, breakpoints) in breakpoints_by_path {
+                let buffer = buffer_store
+                    .update(cx, |buffer_store, cx| {
+                        buffer_store.open_buffer(project_path.clone(), cx)
+                    })?
+                    .await;
+                let buffer = match buffer {
+                    Ok(buffer) => buffer,
+                    Err(error) => {
+                        log::warn!("Skipping breakpoints in {:?}: {error:#}", project_path.path);
+                        continue;
+                    }
+                };
+
+                this.update(cx, |this, cx| {
+                    let snapshot = buffer.read(cx).snapshot();
+                    let abs_path = Self::abs_path_from_buffer(&buffer, cx);
+                    for breakpoint in breakpoints {
+                        let row = breakpoint.line.saturating_sub(1);
+                        if row > snapshot.max_point_utf16().row {
+                            log::warn!(
+                                "Skipping breakpoint past the end of {:?} on line {}",
+                                project_path.path,
+                                breakpoint.line
+                            );
+                            continue;
+                        }
+
+                        let existing = abs_path
+                            .as_ref()
+                            .and_then(|abs_path| this.breakpoints.get(abs_path))
+                            .and_then(|breakpoints| {
+                                breakpoints.breakpoints.iter().find(|existing| {
+                                    snapshot
+                                        .summary_for_anchor::<PointUtf16>(existing.position())
+                                        .row
+                                        == row
+                                })
+                            })
+                            .map(|existing| existing.bp.clone());
+                        if let Some(existing) = existing {
+                            this.toggle_breakpoint(
+                                buffer.clone(),
+                                existing,
+                                BreakpointEditAction::Toggle,
+                                cx,
+                            );
+                        }
+
+                        let breakpoint = BreakpointWithPosition {
+                            position: snapshot.anchor_after(PointUtf16::new(row, 0)),
+                            bp: Breakpoint {
+                                message: breakpoint.log_message.map(Into::into),
+                                state: if breakpoint.enabled {
+                                    BreakpointState::Enabled
+                                } else {
+                                    BreakpointState::Disabled
+                                },
+                                condition: breakpoint.condition.map(Into::into),
+                                hit_condition: breakpoint.hit_condition.map(Into::into),
+                            },
+                        };
+                        this.toggle_breakpoint(
+                            buffer.clone(),
+                            breakpoint,
+                            BreakpointEditAction::Toggle,
+                            cx,
+                        );
+                    }
+                })?;
+            }
+            Ok(())
+        })
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn breakpoint_paths(&self) -> Vec<Arc<Path>> {
         self.breakpoints.keys().cloned().collect()
@@ -1002,6 +1162,7 @@ impl Breakpoint {
                         proto::BreakpointSessionState {
                             id: state.id,
                             verified: state.verified,
+                            message: state.message.as_ref().map(|s| String::from(s.as_ref())),
                         },
                     )
                 })
@@ -1032,6 +1193,31 @@ impl Breakpoint {
     }
 }
 
+/// A breakpoint as written to a shareable file such as `.zed/breakpoints.json`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedBreakpoint {
+    /// The root name of the worktree containing the file, if it is not the worktree the
+    /// breakpoints were exported to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+    /// The path of the file, relative to its worktree.
+    pub path: String,
+    /// The 1-based line of the breakpoint.
+    pub line: u32,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hit_condition: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_message: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 /// Breakpoint for location within source code.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct SourceBreakpoint {
@@ -1,4 +1,6 @@
 pub(crate) mod cargo;
+pub mod cmake;
 pub mod go;
+pub mod make;
 pub(crate) mod node;
 pub mod python;
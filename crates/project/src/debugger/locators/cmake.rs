@@ -0,0 +1,275 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use dap::{DapLocator, DebugRequest, adapters::DebugAdapterName};
+use futures::StreamExt as _;
+use gpui::{BackgroundExecutor, SharedString};
+use serde::Deserialize;
+use serde_json::Value;
+use smol::process::Stdio as SmolStdio;
+use task::{BuildTaskDefinition, DebugScenario, ShellBuilder, SpawnInTerminal, TaskTemplate};
+
+/// Locates executables built by `cmake --build` through the CMake file API.
+///
+/// See <https://cmake.org/cmake/help/latest/manual/cmake-file-api.7.html>.
+pub struct CMakeLocator;
+
+/// Debug adapters that can launch native executables.
+pub(crate) const NATIVE_ADAPTERS: &[&str] = &["CodeLLDB", "GDB"];
+
+const CODEMODEL_QUERY: &str = ".cmake/api/v1/query/codemodel-v2";
+const REPLY_DIR: &str = ".cmake/api/v1/reply";
+
+#[derive(Debug, Deserialize)]
+struct ReplyIndex {
+    objects: Vec<ReplyObject>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReplyObject {
+    kind: String,
+    json_file: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Codemodel {
+    configurations: Vec<CodemodelConfiguration>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodemodelConfiguration {
+    name: String,
+    targets: Vec<CodemodelTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodemodelTarget {
+    name: String,
+    json_file: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    path: PathBuf,
+}
+
+/// The parts of a `cmake --build` invocation that determine which executable gets built.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CMakeBuildArgs {
+    pub build_dir: PathBuf,
+    pub target: Option<String>,
+    pub config: Option<String>,
+}
+
+impl CMakeBuildArgs {
+    pub fn parse(args: &[String]) -> Option<Self> {
+        let mut parsed = Self::default();
+        let mut has_build_dir = false;
+        let mut args = args.iter().take_while(|arg| *arg != "--");
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--build" => {
+                    parsed.build_dir = args.next()?.into();
+                    has_build_dir = true;
+                }
+                "--target" | "-t" => parsed.target = args.next().cloned(),
+                "--config" => parsed.config = args.next().cloned(),
+                _ => {
+                    if let Some(target) = arg.strip_prefix("--target=") {
+                        parsed.target = Some(target.to_owned());
+                    } else if let Some(config) = arg.strip_prefix("--config=") {
+                        parsed.config = Some(config.to_owned());
+                    }
+                }
+            }
+        }
+        has_build_dir.then_some(parsed)
+    }
+}
+
+#[async_trait]
+impl DapLocator for CMakeLocator {
+    fn name(&self) -> SharedString {
+        SharedString::new_static("cmake-debug-locator")
+    }
+
+    async fn create_scenario(
+        &self,
+        build_config: &TaskTemplate,
+        resolved_label: &str,
+        adapter: &DebugAdapterName,
+    ) -> Option<DebugScenario> {
+        if build_config.command != "cmake" || !NATIVE_ADAPTERS.contains(&adapter.as_ref()) {
+            return None;
+        }
+        let build_args = CMakeBuildArgs::parse(&build_config.args)?;
+        // Utility targets don't produce anything that could be debugged.
+        if build_args
+            .target
+            .as_deref()
+            .is_some_and(|target| matches!(target, "all" | "clean" | "install" | "test"))
+        {
+            return None;
+        }
+
+        Some(DebugScenario {
+            adapter: adapter.0.clone(),
+            label: resolved_label.to_string().into(),
+            build: Some(BuildTaskDefinition::Template {
+                task_template: build_config.clone(),
+                locator_name: Some(self.name()),
+            }),
+            config: Value::Null,
+            tcp_connection: None,
+        })
+    }
+
+    async fn run(
+        &self,
+        build_config: SpawnInTerminal,
+        _executor: BackgroundExecutor,
+    ) -> Result<DebugRequest> {
+        let cwd = build_config
+            .cwd
+            .clone()
+            .context("Couldn't get cwd from debug config which is needed for locators")?;
+        let build_args =
+            CMakeBuildArgs::parse(&build_config.args).context("Not a `cmake --build` command")?;
+        let build_dir = cwd.join(&build_args.build_dir);
+
+        let query_path = build_dir.join(CODEMODEL_QUERY);
+        if !smol::fs::metadata(&query_path)
+            .await
+            .is_ok_and(|metadata| metadata.is_file())
+        {
+            // The file API only answers queries that existed when the project was configured,
+            // so register ours and configure again.
+            smol::fs::create_dir_all(query_path.parent().context("invalid query path")?).await?;
+            smol::fs::write(&query_path, "").await?;
+
+            let builder = ShellBuilder::new(&build_config.shell, cfg!(windows)).non_interactive();
+            let status = builder
+                .build_smol_command(
+                    Some("cmake".into()),
+                    &[build_dir.to_string_lossy().into_owned()],
+                )
+                .envs(build_config.env.iter().map(|(k, v)| (k.clone(), v.clone())))
+                .current_dir(&cwd)
+                .stdout(SmolStdio::null())
+                .status()
+                .await?;
+            anyhow::ensure!(status.success(), "CMake failed to configure {build_dir:?}");
+        }
+
+        let program = find_executable(
+            &build_dir,
+            build_args.target.as_deref(),
+            build_args.config.as_deref(),
+        )
+        .await?;
+
+        Ok(DebugRequest::Launch(task::LaunchRequest {
+            program: program.to_string_lossy().into_owned(),
+            cwd: Some(cwd),
+            args: Vec::new(),
+            env: build_config.env.into_iter().collect(),
+        }))
+    }
+}
+
+/// Reads the latest CMake file API reply in `build_dir` and returns the executable
+/// produced by `target`, or the only executable target when none is given.
+pub async fn find_executable(
+    build_dir: &Path,
+    target: Option<&str>,
+    config: Option<&str>,
+) -> Result<PathBuf> {
+    let reply_dir = build_dir.join(REPLY_DIR);
+    let mut entries = smol::fs::read_dir(&reply_dir)
+        .await
+        .with_context(|| format!("No CMake file API reply in {reply_dir:?}"))?;
+    let mut latest_index = None;
+    while let Some(entry) = entries.next().await {
+        let file_name = entry?.file_name().to_string_lossy().into_owned();
+        // Index files are named after their creation time, so the greatest name is the latest one.
+        if file_name.starts_with("index-")
+            && file_name.ends_with(".json")
+            && latest_index
+                .as_ref()
+                .is_none_or(|latest: &String| &file_name > latest)
+        {
+            latest_index = Some(file_name);
+        }
+    }
+    let latest_index = latest_index.context("No CMake file API index file found")?;
+
+    let index: ReplyIndex = read_reply(&reply_dir, &latest_index).await?;
+    let codemodel_file = index
+        .objects
+        .iter()
+        .find(|object| object.kind == "codemodel")
+        .context("CMake file API reply has no codemodel")?;
+    let codemodel: Codemodel = read_reply(&reply_dir, &codemodel_file.json_file).await?;
+
+    let configuration = match config {
+        Some(config) => codemodel
+            .configurations
+            .iter()
+            .find(|configuration| configuration.name.eq_ignore_ascii_case(config))
+            .with_context(|| format!("CMake configuration {config:?} not found"))?,
+        None => codemodel
+            .configurations
+            .first()
+            .context("CMake codemodel has no configurations")?,
+    };
+
+    let mut executables = Vec::new();
+    for codemodel_target in &configuration.targets {
+        if target.is_some_and(|target| target != codemodel_target.name) {
+            continue;
+        }
+        let target: Target = read_reply(&reply_dir, &codemodel_target.json_file).await?;
+        if target.kind != "EXECUTABLE" {
+            continue;
+        }
+        if let Some(artifact) = target.artifacts.into_iter().next() {
+            executables.push((codemodel_target.name.clone(), build_dir.join(artifact.path)));
+        }
+    }
+
+    match executables.as_slice() {
+        [] => match target {
+            Some(target) => anyhow::bail!("CMake target {target:?} is not an executable"),
+            None => anyhow::bail!("CMake project has no executable targets"),
+        },
+        [(_, executable)] => Ok(executable.clone()),
+        _ => anyhow::bail!(
+            "CMake project has several executable targets ({}), pass one with `--target`",
+            executables
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+async fn read_reply<T: for<'de> Deserialize<'de>>(reply_dir: &Path, file_name: &str) -> Result<T> {
+    let path = reply_dir.join(file_name);
+    let contents = smol::fs::read(&path)
+        .await
+        .with_context(|| format!("reading {path:?}"))?;
+    serde_json::from_slice(&contents).with_context(|| format!("parsing {path:?}"))
+}
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use dap::{DapLocator, DebugRequest, adapters::DebugAdapterName};
+use gpui::{BackgroundExecutor, SharedString};
+use serde_json::Value;
+use task::{BuildTaskDefinition, DebugScenario, SpawnInTerminal, TaskTemplate};
+
+use super::cmake::NATIVE_ADAPTERS;
+
+/// Debugs the file produced by a `make <target>` invocation.
+///
+/// Make has no notion of executables, so this relies on the common convention
+/// of naming a program's target after the file it produces.
+pub struct MakeLocator;
+
+/// Conventional targets that don't produce a program.
+const PHONY_TARGETS: &[&str] = &[
+    "all",
+    "check",
+    "clean",
+    "distclean",
+    "install",
+    "test",
+    "uninstall",
+];
+
+/// The parts of a `make` invocation that determine which file gets built.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MakeArgs {
+    pub directory: Option<PathBuf>,
+    pub target: String,
+}
+
+impl MakeArgs {
+    pub fn parse(args: &[String]) -> Option<Self> {
+        let mut directory = None;
+        let mut target = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-C" | "--directory" => directory = Some(args.next()?.into()),
+                // Flags that take a separate value.
+                "-f" | "--file" | "--makefile" | "-I" | "--include-dir" | "-o" | "-W" => {
+                    args.next();
+                }
+                _ if arg.starts_with('-') => {
+                    if let Some(dir) = arg
+                        .strip_prefix("--directory=")
+                        .or_else(|| arg.strip_prefix("-C"))
+                    {
+                        directory = Some(dir.into());
+                    }
+                }
+                // Variable assignments such as `CFLAGS=-g`, and job counts passed as `-j 8`.
+                _ if arg.contains('=') || arg.bytes().all(|byte| byte.is_ascii_digit()) => {}
+                _ => {
+                    // Only a single target can be debugged.
+                    if target.replace(arg.clone()).is_some() {
+                        return None;
+                    }
+                }
+            }
+        }
+        let target = target?;
+        (!PHONY_TARGETS.contains(&target.as_str())).then_some(Self { directory, target })
+    }
+}
+
+#[async_trait]
+impl DapLocator for MakeLocator {
+    fn name(&self) -> SharedString {
+        SharedString::new_static("make-debug-locator")
+    }
+
+    async fn create_scenario(
+        &self,
+        build_config: &TaskTemplate,
+        resolved_label: &str,
+        adapter: &DebugAdapterName,
+    ) -> Option<DebugScenario> {
+        if build_config.command != "make" || !NATIVE_ADAPTERS.contains(&adapter.as_ref()) {
+            return None;
+        }
+        MakeArgs::parse(&build_config.args)?;
+
+        Some(DebugScenario {
+            adapter: adapter.0.clone(),
+            label: resolved_label.to_string().into(),
+            build: Some(BuildTaskDefinition::Template {
+                task_template: build_config.clone(),
+                locator_name: Some(self.name()),
+            }),
+            config: Value::Null,
+            tcp_connection: None,
+        })
+    }
+
+    async fn run(
+        &self,
+        build_config: SpawnInTerminal,
+        _executor: BackgroundExecutor,
+    ) -> Result<DebugRequest> {
+        let cwd = build_config
+            .cwd
+            .clone()
+            .context("Couldn't get cwd from debug config which is needed for locators")?;
+        let make_args = MakeArgs::parse(&build_config.args)
+            .context("Couldn't find the make target to debug")?;
+        let directory = match make_args.directory {
+            Some(directory) => cwd.join(directory),
+            None => cwd,
+        };
+        let program = directory.join(&make_args.target);
+        anyhow::ensure!(
+            smol::fs::metadata(&program)
+                .await
+                .is_ok_and(|metadata| metadata.is_file()),
+            "make target {:?} did not produce {program:?}",
+            make_args.target
+        );
+
+        Ok(DebugRequest::Launch(task::LaunchRequest {
+            program: program.to_string_lossy().into_owned(),
+            cwd: Some(directory),
+            args: Vec::new(),
+            env: build_config.env.into_iter().collect(),
+        }))
+    }
+}
@@ -2,17 +2,21 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
-    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
-    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, GotoCommand, GotoTargetsCommand,
+    Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand,
+    NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand, ScopesCommand,
+    SetDataBreakpointsCommand, SetExceptionBreakpoints, SetInstructionBreakpointsCommand,
+    SetVariableValueCommand, StackTraceCommand, StepBackCommand, StepCommand, StepInCommand,
+    StepInTargetsCommand, StepOutCommand, TerminateCommand, TerminateThreadsCommand,
+    ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
 use crate::debugger::dap_command::{DataBreakpointContext, ReadMemory};
 use crate::debugger::memory::{self, Memory, MemoryIterator, MemoryPageBuilder, PageAddress};
+use crate::lsp_store::CompletionDocumentation;
+use crate::{Completion, CompletionSource};
 use anyhow::{Context as _, Result, anyhow, bail};
 use base64::Engine;
 use collections::{HashMap, HashSet, IndexMap};
@@ -49,7 +53,7 @@ use smol::net::{TcpListener, TcpStream};
 use std::any::TypeId;
 use std::collections::{BTreeMap, VecDeque};
 use std::net::Ipv4Addr;
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 use std::path::PathBuf;
 use std::time::Duration;
 use std::u64;
@@ -61,7 +65,7 @@ use std::{
     sync::Arc,
 };
 use task::SharedTaskContext;
-use text::{PointUtf16, ToPointUtf16};
+use text::{PointUtf16, ToOffset, ToPointUtf16};
 use url::Url;
 use util::command::Stdio;
 use util::command::new_command;
@@ -176,6 +180,27 @@ pub struct RunningMode {
 pub struct SessionQuirks {
     pub compact: bool,
     pub prefer_thread_name: bool,
+    /// The session inspects a core dump, so there is no live process to step or modify.
+    pub post_mortem: bool,
+}
+
+/// Picks the goto target whose range contains the given one-based position, falling back to
+/// the first target when none of them does (e.g. when the adapter doesn't report columns).
+fn goto_target_at(
+    targets: Vec<dap::GotoTarget>,
+    line: u64,
+    column: u64,
+) -> Option<dap::GotoTarget> {
+    let contains_position = |target: &dap::GotoTarget| {
+        let start = (target.line, target.column.unwrap_or(1));
+        let end = (
+            target.end_line.unwrap_or(target.line),
+            target.end_column.unwrap_or(u64::MAX),
+        );
+        start <= (line, column) && (line, column) <= end
+    };
+    let ix = targets.iter().position(contains_position).unwrap_or(0);
+    targets.into_iter().nth(ix)
 }
 
 fn client_source(abs_path: &Path) -> dap::Source {
@@ -299,14 +324,15 @@ impl RunningMode {
                     breakpoints
                         .into_iter()
                         .zip(raw_breakpoints)
-                        .filter_map(|(dap_bp, zed_bp)| {
-                            Some((
+                        .map(|(dap_bp, zed_bp)| {
+                            (
                                 zed_bp,
                                 BreakpointSessionState {
-                                    id: dap_bp.id?,
+                                    id: dap_bp.id,
                                     verified: dap_bp.verified,
+                                    message: dap_bp.message.map(Into::into),
                                 },
-                            ))
+                            )
                         });
                 breakpoint_store
                     .update(cx, |this, _| {
@@ -383,17 +409,20 @@ impl RunningMode {
                 async move |cx| {
                     let breakpoints = cx.background_spawn(send_request).await?;
 
-                    let breakpoints = breakpoints.into_iter().zip(raw_breakpoints).filter_map(
-                        |(dap_bp, zed_bp)| {
-                            Some((
-                                zed_bp,
-                                BreakpointSessionState {
-                                    id: dap_bp.id?,
-                                    verified: dap_bp.verified,
-                                },
-                            ))
-                        },
-                    );
+                    let breakpoints =
+                        breakpoints
+                            .into_iter()
+                            .zip(raw_breakpoints)
+                            .map(|(dap_bp, zed_bp)| {
+                                (
+                                    zed_bp,
+                                    BreakpointSessionState {
+                                        id: dap_bp.id,
+                                        verified: dap_bp.verified,
+                                        message: dap_bp.message.map(Into::into),
+                                    },
+                                )
+                            });
                     breakpoint_store
                         .update(cx, |this, _| {
                             this.mark_breakpoints_verified(session_id, &path, breakpoints);
@@ -447,6 +476,8 @@ impl RunningMode {
         let supports_exception_filters = capabilities
             .supports_exception_filter_options
             .unwrap_or_default();
+        let instruction_breakpoints_supported =
+            SetInstructionBreakpointsCommand::is_supported(capabilities);
         let this = self.clone();
         let worktree = self.worktree().clone();
         let mut filters = capabilities
@@ -519,6 +550,20 @@ impl RunningMode {
                         .ok();
                 }
 
+                if instruction_breakpoints_supported {
+                    let breakpoints = session.read_with(cx, |this, _| {
+                        this.instruction_breakpoints
+                            .values()
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    })?;
+                    if !breakpoints.is_empty() {
+                        this.request(SetInstructionBreakpointsCommand { breakpoints })
+                            .await
+                            .log_err();
+                    }
+                }
+
                 if configuration_done_supported {
                     this.request(ConfigurationDone {})
                 } else {
@@ -709,6 +754,7 @@ pub struct Session {
     ignore_breakpoints: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
     data_breakpoints: BTreeMap<String, DataBreakpointState>,
+    instruction_breakpoints: BTreeMap<String, dap::InstructionBreakpoint>,
     background_tasks: Vec<Task<()>>,
     restart_task: Option<Task<()>>,
     task_context: SharedTaskContext,
@@ -790,6 +836,95 @@ impl CompletionsQuery {
     }
 }
 
+/// Converts the items of a DAP `completions` response into editor completions that
+/// replace the text before `buffer_position`.
+pub fn completions_from_dap(
+    items: Vec<dap::CompletionItem>,
+    buffer_position: text::Anchor,
+    snapshot: &text::BufferSnapshot,
+) -> Vec<Completion> {
+    let buffer_text = snapshot.text();
+    items
+        .into_iter()
+        .map(|item| {
+            let sort_text = completion_item_sort_text(&item);
+            let new_text = item.text.as_ref().unwrap_or(&item.label).to_owned();
+
+            Completion {
+                replace_range: completion_replace_range(
+                    &buffer_text,
+                    buffer_position,
+                    new_text.as_bytes(),
+                    snapshot,
+                ),
+                new_text,
+                label: language::CodeLabel::plain(item.label, None),
+                icon_path: None,
+                documentation: item
+                    .detail
+                    .map(|detail| CompletionDocumentation::MultiLineMarkdown(detail.into())),
+                match_start: None,
+                snippet_deduplication_key: None,
+                confirm: None,
+                source: CompletionSource::Dap { sort_text },
+                insert_text_mode: None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the range before `buffer_position` that inserting `new_bytes` should replace, i.e.
+/// the longest prefix of `new_bytes` that has already been typed.
+pub fn completion_replace_range(
+    buffer_text: &str,
+    buffer_position: text::Anchor,
+    new_bytes: &[u8],
+    snapshot: &text::BufferSnapshot,
+) -> Range<text::Anchor> {
+    let buffer_offset = buffer_position.to_offset(snapshot);
+    let buffer_bytes = &buffer_text.as_bytes()[0..buffer_offset];
+
+    let mut prefix_len = 0;
+    for i in (0..new_bytes.len()).rev() {
+        if buffer_bytes.ends_with(&new_bytes[0..i]) {
+            prefix_len = i;
+            break;
+        }
+    }
+
+    let start = snapshot.clip_offset(buffer_offset - prefix_len, text::Bias::Left);
+
+    snapshot.anchor_before(start)..buffer_position
+}
+
+const fn completion_type_score(completion_type: dap::CompletionItemType) -> usize {
+    match completion_type {
+        dap::CompletionItemType::Field | dap::CompletionItemType::Property => 0,
+        dap::CompletionItemType::Variable | dap::CompletionItemType::Value => 1,
+        dap::CompletionItemType::Method
+        | dap::CompletionItemType::Function
+        | dap::CompletionItemType::Constructor => 2,
+        dap::CompletionItemType::Class
+        | dap::CompletionItemType::Interface
+        | dap::CompletionItemType::Module => 3,
+        _ => 4,
+    }
+}
+
+fn completion_item_sort_text(completion_item: &dap::CompletionItem) -> String {
+    completion_item.sort_text.clone().unwrap_or_else(|| {
+        format!(
+            "{:03}_{}",
+            completion_type_score(
+                completion_item
+                    .type_
+                    .unwrap_or(dap::CompletionItemType::Text)
+            ),
+            completion_item.label.to_ascii_lowercase()
+        )
+    })
+}
+
 #[derive(Debug)]
 pub enum SessionEvent {
     Modules,
@@ -883,6 +1018,7 @@ impl Session {
                 ignore_breakpoints: false,
                 breakpoint_store,
                 data_breakpoints: Default::default(),
+                instruction_breakpoints: Default::default(),
                 exception_breakpoints: Default::default(),
                 label,
                 adapter,
@@ -2086,6 +2222,60 @@ impl Session {
         self.send_data_breakpoints(cx);
     }
 
+    pub fn instruction_breakpoints(&self) -> impl Iterator<Item = &dap::InstructionBreakpoint> {
+        self.instruction_breakpoints.values()
+    }
+
+    pub fn has_instruction_breakpoint(&self, instruction_reference: &str) -> bool {
+        self.instruction_breakpoints
+            .contains_key(instruction_reference)
+    }
+
+    pub fn toggle_instruction_breakpoint(
+        &mut self,
+        instruction_reference: String,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .instruction_breakpoints
+            .remove(&instruction_reference)
+            .is_none()
+        {
+            self.instruction_breakpoints.insert(
+                instruction_reference.clone(),
+                dap::InstructionBreakpoint {
+                    instruction_reference,
+                    offset: None,
+                    condition: None,
+                    hit_condition: None,
+                    mode: None,
+                },
+            );
+        }
+        self.send_instruction_breakpoints(cx);
+    }
+
+    /// Seeds the instruction breakpoints of a session that has not been booted yet,
+    /// e.g. when restarting a session. They are sent during the configuration sequence.
+    pub fn restore_instruction_breakpoints(
+        &mut self,
+        breakpoints: impl IntoIterator<Item = dap::InstructionBreakpoint>,
+    ) {
+        self.instruction_breakpoints.extend(
+            breakpoints
+                .into_iter()
+                .map(|breakpoint| (breakpoint.instruction_reference.clone(), breakpoint)),
+        );
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if let Some(mode) = self.as_running() {
+            let breakpoints = self.instruction_breakpoints.values().cloned().collect();
+            let command = SetInstructionBreakpointsCommand { breakpoints };
+            mode.request(command).detach_and_log_err(cx);
+        }
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2343,6 +2533,27 @@ impl Session {
         thread_id: ThreadId,
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
+    ) {
+        self.step_in_inner(thread_id, granularity, None, cx);
+    }
+
+    /// Steps into a specific call on the current line, as returned by [`Self::step_in_targets`].
+    pub fn step_in_target(
+        &mut self,
+        thread_id: ThreadId,
+        target_id: u64,
+        granularity: SteppingGranularity,
+        cx: &mut Context<Self>,
+    ) {
+        self.step_in_inner(thread_id, granularity, Some(target_id), cx);
+    }
+
+    fn step_in_inner(
+        &mut self,
+        thread_id: ThreadId,
+        granularity: SteppingGranularity,
+        target_id: Option<u64>,
+        cx: &mut Context<Self>,
     ) {
         self.select_historic_snapshot(None, cx);
 
@@ -2359,6 +2570,7 @@ impl Session {
                 granularity: supports_stepping_granularity.then(|| granularity),
                 single_thread: supports_single_thread_execution_requests,
             },
+            target_id,
         };
 
         self.active_snapshot.thread_states.process_step(thread_id);
@@ -2370,6 +2582,76 @@ impl Session {
         .detach();
     }
 
+    pub fn step_in_targets(
+        &mut self,
+        stack_frame_id: StackFrameId,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::StepInTarget>>> {
+        self.request(
+            StepInTargetsCommand { stack_frame_id },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    pub fn goto_targets(
+        &mut self,
+        source: Source,
+        line: u64,
+        column: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::GotoTarget>>> {
+        self.request(
+            GotoTargetsCommand {
+                source,
+                line,
+                column,
+            },
+            |_, response, _| response.log_err(),
+            cx,
+        )
+    }
+
+    /// Moves the execution of a stopped thread to the given goto target without running the code in between.
+    pub fn goto(&mut self, thread_id: ThreadId, target_id: u64, cx: &mut Context<Self>) {
+        self.select_historic_snapshot(None, cx);
+
+        let command = GotoCommand {
+            thread_id: thread_id.0,
+            target_id,
+        };
+
+        self.active_snapshot.thread_states.process_step(thread_id);
+        self.request(
+            command,
+            Self::on_step_response::<GotoCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
+    /// Moves the execution of a stopped thread to the goto target the adapter reports
+    /// for the given zero-based row and column.
+    pub fn jump_to_position(
+        &mut self,
+        abs_path: &Path,
+        row: u32,
+        column: u32,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let line = row as u64 + 1;
+        let column = column as u64 + 1;
+        let targets = self.goto_targets(client_source(abs_path), line, Some(column), cx);
+        cx.spawn(async move |this, cx| {
+            let target = targets
+                .await
+                .and_then(|targets| goto_target_at(targets, line, column))
+                .context("Debug adapter returned no goto targets for this line")?;
+            this.update(cx, |this, cx| this.goto(thread_id, target.id, cx))
+        })
+    }
+
     pub fn step_out(
         &mut self,
         thread_id: ThreadId,
@@ -2617,6 +2899,20 @@ impl Session {
         })
     }
 
+    /// Adds a watch expression without evaluating it; it is evaluated along with the other
+    /// watches the next time the session stops.
+    pub fn add_pending_watcher(&mut self, expression: SharedString, cx: &mut Context<Self>) {
+        self.watchers
+            .entry(expression.clone())
+            .or_insert_with(|| Watcher {
+                expression,
+                value: SharedString::default(),
+                variables_reference: 0,
+                presentation_hint: None,
+            });
+        cx.emit(SessionEvent::Watchers);
+    }
+
     pub fn refresh_watchers(&mut self, frame_id: u64, cx: &mut Context<Self>) {
         let watches = self.watchers.clone();
         for (_, watch) in watches.into_iter() {
@@ -2625,8 +2921,10 @@ impl Session {
         }
     }
 
-    pub fn remove_watcher(&mut self, expression: SharedString) {
-        self.watchers.remove(&expression);
+    pub fn remove_watcher(&mut self, expression: SharedString, cx: &mut Context<Self>) {
+        if self.watchers.remove(&expression).is_some() {
+            cx.emit(SessionEvent::Watchers);
+        }
     }
 
     pub fn variables(
@@ -2677,6 +2975,24 @@ impl Session {
         self.request(command, |_, response, _| response.ok(), cx)
     }
 
+    /// Disassembles `instruction_count` instructions around the given memory reference,
+    /// starting `instruction_offset` instructions away from it (negative values go backwards).
+    pub fn disassemble(
+        &mut self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<Vec<dap::DisassembledInstruction>>> {
+        let command = DisassembleCommand {
+            memory_reference,
+            instruction_offset,
+            instruction_count,
+        };
+
+        self.request(command, |_, response, _| response.log_err(), cx)
+    }
+
     pub fn set_variable_value(
         &mut self,
         stack_frame_id: u64,
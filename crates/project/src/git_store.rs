@@ -26,8 +26,10 @@ use futures::{
 };
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid, RunHook,
+    bisect::{BisectCommit, BisectLogEntry, BisectMark, BisectState},
     blame::Blame,
     parse_git_remote_url,
+    rebase::{RebaseAction, RebaseCommand, RebaseState, RebaseTodoEntry},
     repository::{
         Branch, CommitDetails, CommitDiff, CommitFile, CommitOptions, DiffType, FetchOptions,
         GitRepository, GitRepositoryCheckpoint, GraphCommitData, InitialGraphCommitData, LogOrder,
@@ -279,6 +281,7 @@ pub struct RepositorySnapshot {
     pub remote_origin_url: Option<String>,
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
+    pub rebase: Option<RebaseState>,
 }
 
 type JobId = u64;
@@ -413,6 +416,7 @@ pub enum RepositoryEvent {
     StatusesChanged,
     BranchChanged,
     StashEntriesChanged,
+    RebaseChanged,
     PendingOpsChanged { pending_ops: SumTree<PendingOps> },
     GraphEvent((LogSource, LogOrder), GitGraphEvent),
 }
@@ -534,6 +538,15 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_stash_pop);
         client.add_entity_request_handler(Self::handle_stash_apply);
         client.add_entity_request_handler(Self::handle_stash_drop);
+        client.add_entity_request_handler(Self::handle_git_rebase_todo);
+        client.add_entity_request_handler(Self::handle_git_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_git_rebase_command);
+        client.add_entity_request_handler(Self::handle_git_cherry_pick);
+        client.add_entity_request_handler(Self::handle_git_revert);
+        client.add_entity_request_handler(Self::handle_git_bisect_start);
+        client.add_entity_request_handler(Self::handle_git_bisect_mark);
+        client.add_entity_request_handler(Self::handle_git_bisect_reset);
+        client.add_entity_request_handler(Self::handle_git_bisect_get_state);
         client.add_entity_request_handler(Self::handle_commit);
         client.add_entity_request_handler(Self::handle_run_hook);
         client.add_entity_request_handler(Self::handle_reset);
@@ -2142,6 +2155,173 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_git_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.onto)
+            })
+            .await??;
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_git_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let todo = envelope
+            .payload
+            .todo
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect();
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_interactive(envelope.payload.onto, todo, cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_rebase_command(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseCommand>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let command = match envelope.payload.command() {
+            proto::git_rebase_command::Command::Continue => RebaseCommand::Continue,
+            proto::git_rebase_command::Command::Skip => RebaseCommand::Skip,
+            proto::git_rebase_command::Command::Abort => RebaseCommand::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.rebase_command(command, cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.cherry_pick(envelope.payload.commits, cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.revert(envelope.payload.commits, cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_start(envelope.payload.bad, envelope.payload.good, cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let mark = proto_to_bisect_mark(envelope.payload.mark());
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_mark(mark, envelope.payload.commit, cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.bisect_reset(cx)
+            })
+            .await?;
+
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_git_bisect_get_state(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectGetState>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitBisectGetStateResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let state = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_state()
+            })
+            .await??;
+        Ok(proto::GitBisectGetStateResponse {
+            state: state.as_ref().map(bisect_state_to_proto),
+        })
+    }
+
     async fn handle_stash_apply(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::StashApply>,
@@ -3514,6 +3694,7 @@ impl RepositorySnapshot {
             remote_origin_url: None,
             remote_upstream_url: None,
             stash_entries: Default::default(),
+            rebase: None,
             path_style,
         }
     }
@@ -3552,6 +3733,7 @@ impl RepositorySnapshot {
             original_repo_abs_path: Some(
                 self.original_repo_abs_path.to_string_lossy().into_owned(),
             ),
+            rebase_state: self.rebase.as_ref().map(rebase_state_to_proto),
         }
     }
 
@@ -3626,6 +3808,7 @@ impl RepositorySnapshot {
             original_repo_abs_path: Some(
                 self.original_repo_abs_path.to_string_lossy().into_owned(),
             ),
+            rebase_state: self.rebase.as_ref().map(rebase_state_to_proto),
         }
     }
 
@@ -3712,6 +3895,136 @@ pub fn proto_to_stash(entry: &proto::StashEntry) -> Result<StashEntry> {
     })
 }
 
+fn rebase_state_to_proto(state: &RebaseState) -> proto::GitRebaseState {
+    proto::GitRebaseState {
+        head_name: state.head_name.as_ref().map(ToString::to_string),
+        onto: state.onto.to_string(),
+        done: state.done.iter().map(rebase_todo_entry_to_proto).collect(),
+        todo: state.todo.iter().map(rebase_todo_entry_to_proto).collect(),
+        stopped_sha: state.stopped_sha.as_ref().map(ToString::to_string),
+    }
+}
+
+fn proto_to_rebase_state(state: &proto::GitRebaseState) -> RebaseState {
+    RebaseState {
+        head_name: state.head_name.clone().map(SharedString::from),
+        onto: state.onto.clone().into(),
+        done: state.done.iter().map(proto_to_rebase_todo_entry).collect(),
+        todo: state.todo.iter().map(proto_to_rebase_todo_entry).collect(),
+        stopped_sha: state.stopped_sha.clone().map(SharedString::from),
+    }
+}
+
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::GitRebaseTodoEntry {
+    use proto::git_rebase_todo_entry::Action;
+    let action = match entry.action {
+        RebaseAction::Pick => Action::Pick,
+        RebaseAction::Reword => Action::Reword,
+        RebaseAction::Edit => Action::Edit,
+        RebaseAction::Squash => Action::Squash,
+        RebaseAction::Fixup => Action::Fixup,
+        RebaseAction::Drop => Action::Drop,
+    };
+    proto::GitRebaseTodoEntry {
+        action: action as i32,
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.clone(),
+    }
+}
+
+fn proto_to_rebase_todo_entry(entry: &proto::GitRebaseTodoEntry) -> RebaseTodoEntry {
+    use proto::git_rebase_todo_entry::Action;
+    let action = match entry.action() {
+        Action::Pick => RebaseAction::Pick,
+        Action::Reword => RebaseAction::Reword,
+        Action::Edit => RebaseAction::Edit,
+        Action::Squash => RebaseAction::Squash,
+        Action::Fixup => RebaseAction::Fixup,
+        Action::Drop => RebaseAction::Drop,
+    };
+    RebaseTodoEntry {
+        action,
+        sha: entry.sha.clone().into(),
+        subject: entry.subject.clone().into(),
+        message: entry.message.clone(),
+    }
+}
+
+fn bisect_mark_to_proto(mark: BisectMark) -> proto::git_bisect_mark::Mark {
+    match mark {
+        BisectMark::Good => proto::git_bisect_mark::Mark::Good,
+        BisectMark::Bad => proto::git_bisect_mark::Mark::Bad,
+        BisectMark::Skip => proto::git_bisect_mark::Mark::Skip,
+    }
+}
+
+fn proto_to_bisect_mark(mark: proto::git_bisect_mark::Mark) -> BisectMark {
+    match mark {
+        proto::git_bisect_mark::Mark::Good => BisectMark::Good,
+        proto::git_bisect_mark::Mark::Bad => BisectMark::Bad,
+        proto::git_bisect_mark::Mark::Skip => BisectMark::Skip,
+    }
+}
+
+fn bisect_commit_to_proto(commit: &BisectCommit) -> proto::GitBisectCommit {
+    proto::GitBisectCommit {
+        sha: commit.sha.to_string(),
+        subject: commit.subject.to_string(),
+    }
+}
+
+fn proto_to_bisect_commit(commit: &proto::GitBisectCommit) -> BisectCommit {
+    BisectCommit {
+        sha: commit.sha.clone().into(),
+        subject: commit.subject.clone().into(),
+    }
+}
+
+fn bisect_state_to_proto(state: &BisectState) -> proto::GitBisectState {
+    proto::GitBisectState {
+        start: state.start.to_string(),
+        log: state
+            .log
+            .iter()
+            .map(|entry| proto::GitBisectLogEntry {
+                mark: bisect_mark_to_proto(entry.mark) as i32,
+                commit: Some(bisect_commit_to_proto(&entry.commit)),
+            })
+            .collect(),
+        candidates: state
+            .candidates
+            .iter()
+            .map(bisect_commit_to_proto)
+            .collect(),
+        current: state.current.as_ref().map(bisect_commit_to_proto),
+        first_bad: state.first_bad.as_ref().map(bisect_commit_to_proto),
+    }
+}
+
+fn proto_to_bisect_state(state: &proto::GitBisectState) -> BisectState {
+    BisectState {
+        start: state.start.clone().into(),
+        log: state
+            .log
+            .iter()
+            .filter_map(|entry| {
+                Some(BisectLogEntry {
+                    mark: proto_to_bisect_mark(entry.mark()),
+                    commit: proto_to_bisect_commit(entry.commit.as_ref()?),
+                })
+            })
+            .collect(),
+        candidates: state
+            .candidates
+            .iter()
+            .map(proto_to_bisect_commit)
+            .collect(),
+        current: state.current.as_ref().map(proto_to_bisect_commit),
+        first_bad: state.first_bad.as_ref().map(proto_to_bisect_commit),
+    }
+}
+
 impl MergeDetails {
     async fn update(
         &mut self,
@@ -5039,6 +5352,345 @@ impl Repository {
         })
     }
 
+    /// Returns the todo list of an interactive rebase of HEAD onto `onto`.
+    pub fn rebase_todo(&mut self, onto: String) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_todo(onto).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            onto,
+                        })
+                        .await?;
+
+                    Ok(response
+                        .entries
+                        .iter()
+                        .map(proto_to_rebase_todo_entry)
+                        .collect())
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        onto: String,
+        todo: Vec<RebaseTodoEntry>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(
+                    Some("git rebase --interactive".into()),
+                    move |git_repo, _cx| async move {
+                        match git_repo {
+                            RepositoryState::Local(LocalRepositoryState {
+                                backend,
+                                environment,
+                                ..
+                            }) => backend.rebase_interactive(onto, todo, environment).await,
+                            RepositoryState::Remote(RemoteRepositoryState {
+                                project_id,
+                                client,
+                            }) => {
+                                client
+                                    .request(proto::GitRebaseInteractive {
+                                        project_id: project_id.0,
+                                        repository_id: id.to_proto(),
+                                        onto,
+                                        todo: todo.iter().map(rebase_todo_entry_to_proto).collect(),
+                                    })
+                                    .await
+                                    .context("sending rebase request")?;
+                                Ok(())
+                            }
+                        }
+                    },
+                )
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Continues, skips the current commit of, or aborts the rebase in progress.
+    pub fn rebase_command(
+        &mut self,
+        command: RebaseCommand,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(
+                    Some(format!("git rebase {}", command.flag()).into()),
+                    move |git_repo, _cx| async move {
+                        match git_repo {
+                            RepositoryState::Local(LocalRepositoryState {
+                                backend,
+                                environment,
+                                ..
+                            }) => backend.rebase_command(command, environment).await,
+                            RepositoryState::Remote(RemoteRepositoryState {
+                                project_id,
+                                client,
+                            }) => {
+                                let command = match command {
+                                    RebaseCommand::Continue => {
+                                        proto::git_rebase_command::Command::Continue
+                                    }
+                                    RebaseCommand::Skip => proto::git_rebase_command::Command::Skip,
+                                    RebaseCommand::Abort => {
+                                        proto::git_rebase_command::Command::Abort
+                                    }
+                                };
+                                client
+                                    .request(proto::GitRebaseCommand {
+                                        project_id: project_id.0,
+                                        repository_id: id.to_proto(),
+                                        command: command as i32,
+                                    })
+                                    .await
+                                    .context("sending rebase request")?;
+                                Ok(())
+                            }
+                        }
+                    },
+                )
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Applies the changes of the given commits or commit ranges onto HEAD.
+    pub fn cherry_pick(
+        &mut self,
+        commits: Vec<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(
+                    Some("git cherry-pick".into()),
+                    move |git_repo, _cx| async move {
+                        match git_repo {
+                            RepositoryState::Local(LocalRepositoryState {
+                                backend,
+                                environment,
+                                ..
+                            }) => backend.cherry_pick(commits, environment).await,
+                            RepositoryState::Remote(RemoteRepositoryState {
+                                project_id,
+                                client,
+                            }) => {
+                                client
+                                    .request(proto::GitCherryPick {
+                                        project_id: project_id.0,
+                                        repository_id: id.to_proto(),
+                                        commits,
+                                    })
+                                    .await
+                                    .context("sending cherry-pick request")?;
+                                Ok(())
+                            }
+                        }
+                    },
+                )
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Reverts the changes of the given commits or commit ranges.
+    pub fn revert(
+        &mut self,
+        commits: Vec<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(Some("git revert".into()), move |git_repo, _cx| async move {
+                    match git_repo {
+                        RepositoryState::Local(LocalRepositoryState {
+                            backend,
+                            environment,
+                            ..
+                        }) => backend.revert(commits, environment).await,
+                        RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                            client
+                                .request(proto::GitRevert {
+                                    project_id: project_id.0,
+                                    repository_id: id.to_proto(),
+                                    commits,
+                                })
+                                .await
+                                .context("sending revert request")?;
+                            Ok(())
+                        }
+                    }
+                })
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Starts bisecting between a bad commit and the good ones.
+    pub fn bisect_start(
+        &mut self,
+        bad: String,
+        good: Vec<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(
+                    Some("git bisect start".into()),
+                    move |git_repo, _cx| async move {
+                        match git_repo {
+                            RepositoryState::Local(LocalRepositoryState {
+                                backend,
+                                environment,
+                                ..
+                            }) => backend.bisect_start(bad, good, environment).await,
+                            RepositoryState::Remote(RemoteRepositoryState {
+                                project_id,
+                                client,
+                            }) => {
+                                client
+                                    .request(proto::GitBisectStart {
+                                        project_id: project_id.0,
+                                        repository_id: id.to_proto(),
+                                        bad,
+                                        good,
+                                    })
+                                    .await
+                                    .context("sending bisect request")?;
+                                Ok(())
+                            }
+                        }
+                    },
+                )
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Marks a commit of the bisect session, HEAD by default.
+    pub fn bisect_mark(
+        &mut self,
+        mark: BisectMark,
+        commit: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(
+                    Some(format!("git bisect {}", mark.command()).into()),
+                    move |git_repo, _cx| async move {
+                        match git_repo {
+                            RepositoryState::Local(LocalRepositoryState {
+                                backend,
+                                environment,
+                                ..
+                            }) => backend.bisect_mark(mark, commit, environment).await,
+                            RepositoryState::Remote(RemoteRepositoryState {
+                                project_id,
+                                client,
+                            }) => {
+                                client
+                                    .request(proto::GitBisectMark {
+                                        project_id: project_id.0,
+                                        repository_id: id.to_proto(),
+                                        mark: bisect_mark_to_proto(mark) as i32,
+                                        commit,
+                                    })
+                                    .await
+                                    .context("sending bisect request")?;
+                                Ok(())
+                            }
+                        }
+                    },
+                )
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Ends the bisect session in progress.
+    pub fn bisect_reset(&mut self, cx: &mut Context<Self>) -> Task<anyhow::Result<()>> {
+        let id = self.id;
+        cx.spawn(async move |this, cx| {
+            this.update(cx, |this, _| {
+                this.send_job(
+                    Some("git bisect reset".into()),
+                    move |git_repo, _cx| async move {
+                        match git_repo {
+                            RepositoryState::Local(LocalRepositoryState {
+                                backend,
+                                environment,
+                                ..
+                            }) => backend.bisect_reset(environment).await,
+                            RepositoryState::Remote(RemoteRepositoryState {
+                                project_id,
+                                client,
+                            }) => {
+                                client
+                                    .request(proto::GitBisectReset {
+                                        project_id: project_id.0,
+                                        repository_id: id.to_proto(),
+                                    })
+                                    .await
+                                    .context("sending bisect request")?;
+                                Ok(())
+                            }
+                        }
+                    },
+                )
+            })?
+            .await??;
+            Ok(())
+        })
+    }
+
+    /// Returns the state of the bisect session in progress, if any.
+    pub fn bisect_state(&mut self) -> oneshot::Receiver<Result<Option<BisectState>>> {
+        let id = self.id;
+        self.send_job(None, move |repo, _cx| async move {
+            match repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.bisect_state().await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitBisectGetState {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                        })
+                        .await?;
+
+                    Ok(response.state.as_ref().map(proto_to_bisect_state))
+                }
+            }
+        })
+    }
+
     pub fn stash_apply(
         &mut self,
         index: Option<usize>,
@@ -6082,6 +6734,11 @@ impl Repository {
             cx.emit(RepositoryEvent::StashEntriesChanged)
         }
         self.snapshot.stash_entries = new_stash_entries;
+        let new_rebase = update.rebase_state.as_ref().map(proto_to_rebase_state);
+        if self.snapshot.rebase != new_rebase {
+            cx.emit(RepositoryEvent::RebaseChanged)
+        }
+        self.snapshot.rebase = new_rebase;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -6837,6 +7494,11 @@ async fn compute_snapshot(
         events.push(RepositoryEvent::BranchChanged);
     }
 
+    let rebase = backend.rebase_state().await.log_err().flatten();
+    if rebase != prev_snapshot.rebase {
+        events.push(RepositoryEvent::RebaseChanged);
+    }
+
     let remote_origin_url = backend.remote_url("origin").await;
     let remote_upstream_url = backend.remote_url("upstream").await;
 
@@ -6853,6 +7515,7 @@ async fn compute_snapshot(
         remote_origin_url,
         remote_upstream_url,
         stash_entries,
+        rebase,
     };
 
     Ok((snapshot, events))
@@ -0,0 +1,360 @@
+//! Module for keeping a local history of file contents.
+//!
+//! Snapshots of local files are taken when they're saved, before they're reloaded because they
+//! changed on disk, and before the agent edits them. They're kept in a single store for the whole
+//! app under [`paths::local_history_dir`]: an index of all entries, and one file per snapshot.
+//! The oldest snapshots are removed once a file has too many of them, or once the store grows
+//! past its size limit.
+use anyhow::{Context as _, Result, anyhow};
+use fs::{Fs, RemoveOptions};
+use futures::{FutureExt as _, future::Shared};
+use gpui::{App, AppContext as _, Context, Entity, Global, Task};
+use language::Buffer;
+use serde::{Deserialize, Serialize};
+use settings::{RegisterSetting, Settings};
+use sha2::{Digest, Sha256};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use text::Rope;
+use util::ResultExt as _;
+
+/// Files larger than this aren't snapshotted.
+const MAX_SNAPSHOT_LEN: usize = 4 * 1024 * 1024;
+const INDEX_FILE_NAME: &str = "index.json";
+const SNAPSHOTS_DIR_NAME: &str = "snapshots";
+
+#[derive(Clone, Copy, Debug, RegisterSetting)]
+pub struct LocalHistorySettings {
+    pub enabled: bool,
+    pub max_entries_per_file: usize,
+    /// The maximum total size of all snapshots, in bytes.
+    pub max_total_size: u64,
+}
+
+impl Settings for LocalHistorySettings {
+    fn from_settings(content: &settings::SettingsContent) -> Self {
+        let local_history = content.local_history.as_ref().unwrap();
+        Self {
+            enabled: local_history.enabled.unwrap(),
+            max_entries_per_file: local_history.max_entries_per_file.unwrap().max(1),
+            max_total_size: local_history.max_total_size_mb.unwrap() * 1024 * 1024,
+        }
+    }
+}
+
+/// Why a snapshot was taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalHistoryReason {
+    /// The file was saved with these contents.
+    Saved,
+    /// The file changed on disk, and had these contents before it was reloaded.
+    BeforeReload,
+    /// The file had these contents before the agent edited it.
+    BeforeAgentEdit,
+    /// The file had these contents before it was restored from another snapshot.
+    BeforeRestore,
+}
+
+impl LocalHistoryReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LocalHistoryReason::Saved => "Saved",
+            LocalHistoryReason::BeforeReload => "Before reload",
+            LocalHistoryReason::BeforeAgentEdit => "Before agent edit",
+            LocalHistoryReason::BeforeRestore => "Before restore",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalHistoryEntry {
+    pub id: u64,
+    pub abs_path: Arc<Path>,
+    /// When the snapshot was taken, in seconds since the Unix epoch.
+    pub timestamp: i64,
+    pub reason: LocalHistoryReason,
+    /// The length of the snapshot, in bytes.
+    pub len: u64,
+    hash: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct LocalHistoryIndex {
+    next_id: u64,
+    /// Ordered from oldest to newest.
+    entries: Vec<LocalHistoryEntry>,
+}
+
+struct GlobalLocalHistoryStore(Entity<LocalHistoryStore>);
+
+impl Global for GlobalLocalHistoryStore {}
+
+pub struct LocalHistoryStore {
+    fs: Arc<dyn Fs>,
+    directory: PathBuf,
+    index: LocalHistoryIndex,
+    loaded: Shared<Task<()>>,
+    pending_write: Shared<Task<Result<(), Arc<anyhow::Error>>>>,
+}
+
+impl LocalHistoryStore {
+    /// Initializes the global local history store, kept in [`paths::local_history_dir`].
+    pub fn init_global(fs: Arc<dyn Fs>, cx: &mut App) -> Entity<Self> {
+        if let Some(store) = Self::try_global(cx) {
+            return store;
+        }
+
+        let store = cx.new(|cx| Self::new(fs, paths::local_history_dir().clone(), cx));
+        cx.set_global(GlobalLocalHistoryStore(store.clone()));
+        store
+    }
+
+    pub fn try_global(cx: &App) -> Option<Entity<Self>> {
+        cx.try_global::<GlobalLocalHistoryStore>()
+            .map(|store| store.0.clone())
+    }
+
+    pub fn new(fs: Arc<dyn Fs>, directory: PathBuf, cx: &mut Context<Self>) -> Self {
+        let loaded = cx
+            .spawn({
+                let fs = fs.clone();
+                let index_path = directory.join(INDEX_FILE_NAME);
+                async move |this, cx| {
+                    let index = load_index(fs.as_ref(), &index_path)
+                        .await
+                        .log_err()
+                        .flatten();
+                    if let Some(index) = index {
+                        this.update(cx, |this, cx| {
+                            this.index = index;
+                            cx.notify();
+                        })
+                        .ok();
+                    }
+                }
+            })
+            .shared();
+
+        Self {
+            fs,
+            directory,
+            index: LocalHistoryIndex::default(),
+            loaded,
+            pending_write: Task::ready(Ok(())).shared(),
+        }
+    }
+
+    /// Returns the snapshots of the file at the given path, from newest to oldest.
+    pub fn entries_for_path<'a>(
+        &'a self,
+        abs_path: &'a Path,
+    ) -> impl Iterator<Item = &'a LocalHistoryEntry> + 'a {
+        self.index
+            .entries
+            .iter()
+            .rev()
+            .filter(move |entry| entry.abs_path.as_ref() == abs_path)
+    }
+
+    /// Resolves once the snapshots that were kept before the store was created are available.
+    pub fn wait_until_loaded(&self) -> Shared<Task<()>> {
+        self.loaded.clone()
+    }
+
+    /// Loads the contents of the snapshot with the given id.
+    pub fn load_text(&self, entry_id: u64, cx: &App) -> Task<Result<String>> {
+        let fs = self.fs.clone();
+        let path = self.snapshot_path(entry_id);
+        let pending_write = self.pending_write.clone();
+        cx.background_spawn(async move {
+            pending_write.await.ok();
+            fs.load(&path)
+                .await
+                .with_context(|| format!("loading local history snapshot {path:?}"))
+        })
+    }
+
+    /// Snapshots the current contents of a local buffer, unless it's a private file.
+    pub fn snapshot_buffer(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        reason: LocalHistoryReason,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let buffer = buffer.read(cx);
+        let Some(abs_path) = buffer
+            .file()
+            .filter(|file| !file.is_private())
+            .and_then(|file| file.as_local())
+            .map(|file| file.abs_path(cx))
+        else {
+            return Task::ready(Ok(()));
+        };
+        let text = buffer.as_rope().clone();
+        self.snapshot_text(abs_path.into(), text, reason, cx)
+    }
+
+    /// Snapshots the given contents of the file at the given path, unless they're the same as
+    /// its latest snapshot.
+    pub fn snapshot_text(
+        &mut self,
+        abs_path: Arc<Path>,
+        text: Rope,
+        reason: LocalHistoryReason,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !LocalHistorySettings::get_global(cx).enabled || text.len() > MAX_SNAPSHOT_LEN {
+            return Task::ready(Ok(()));
+        }
+
+        let loaded = self.loaded.clone();
+        cx.spawn(async move |this, cx| {
+            let (text, hash) = cx
+                .background_spawn(async move {
+                    let mut hasher = Sha256::new();
+                    for chunk in text.chunks() {
+                        hasher.update(chunk.as_bytes());
+                    }
+                    (text.to_string(), format!("{:x}", hasher.finalize()))
+                })
+                .await;
+            loaded.await;
+            this.update(cx, |this, cx| {
+                this.insert_entry(abs_path, text, hash, reason, cx)
+            })?
+            .await
+        })
+    }
+
+    fn insert_entry(
+        &mut self,
+        abs_path: Arc<Path>,
+        text: String,
+        hash: String,
+        reason: LocalHistoryReason,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if self
+            .entries_for_path(&abs_path)
+            .next()
+            .is_some_and(|latest| latest.hash == hash)
+        {
+            return Task::ready(Ok(()));
+        }
+
+        let id = self.index.next_id;
+        self.index.next_id += 1;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs() as i64);
+        self.index.entries.push(LocalHistoryEntry {
+            id,
+            abs_path,
+            timestamp,
+            reason,
+            len: text.len() as u64,
+            hash,
+        });
+        let removed_ids = self.prune(LocalHistorySettings::get_global(cx));
+        cx.notify();
+
+        let index = serde_json::to_string(&self.index);
+        let fs = self.fs.clone();
+        let snapshot_path = self.snapshot_path(id);
+        let removed_paths = removed_ids
+            .into_iter()
+            .map(|id| self.snapshot_path(id))
+            .collect::<Vec<_>>();
+        let index_path = self.directory.join(INDEX_FILE_NAME);
+        let previous_write = self.pending_write.clone();
+        let write = cx
+            .background_spawn(async move {
+                previous_write.await.ok();
+                let result = async {
+                    let index = index?;
+                    let snapshots_dir = snapshot_path.parent().context("invalid snapshot path")?;
+                    fs.create_dir(snapshots_dir).await?;
+                    fs.atomic_write(snapshot_path, text).await?;
+                    fs.atomic_write(index_path, index).await?;
+                    for path in removed_paths {
+                        fs.remove_file(
+                            &path,
+                            RemoveOptions {
+                                recursive: false,
+                                ignore_if_not_exists: true,
+                            },
+                        )
+                        .await
+                        .log_err();
+                    }
+                    anyhow::Ok(())
+                };
+                result.await.map_err(Arc::new)
+            })
+            .shared();
+        self.pending_write = write.clone();
+        cx.background_spawn(async move { write.await.map_err(|error| anyhow!("{error:#}")) })
+    }
+
+    /// Removes the oldest snapshots until the limits in the settings are respected, returning the
+    /// ids of the removed snapshots. The newest snapshot is always kept.
+    fn prune(&mut self, settings: &LocalHistorySettings) -> Vec<u64> {
+        let Some(newest) = self.index.entries.last().cloned() else {
+            return Vec::new();
+        };
+
+        let mut removed_ids = Vec::new();
+        let mut entries_for_newest_path = self
+            .index
+            .entries
+            .iter()
+            .filter(|entry| entry.abs_path == newest.abs_path)
+            .count();
+        let mut total_size = self
+            .index
+            .entries
+            .iter()
+            .map(|entry| entry.len)
+            .sum::<u64>();
+        self.index.entries.retain(|entry| {
+            if entry.id == newest.id {
+                return true;
+            }
+            if entry.abs_path == newest.abs_path
+                && entries_for_newest_path > settings.max_entries_per_file
+            {
+                entries_for_newest_path -= 1;
+            } else if total_size > settings.max_total_size {
+                if entry.abs_path == newest.abs_path {
+                    entries_for_newest_path -= 1;
+                }
+            } else {
+                return true;
+            }
+            total_size -= entry.len;
+            removed_ids.push(entry.id);
+            false
+        });
+        removed_ids
+    }
+
+    fn snapshot_path(&self, entry_id: u64) -> PathBuf {
+        self.directory
+            .join(SNAPSHOTS_DIR_NAME)
+            .join(entry_id.to_string())
+    }
+}
+
+async fn load_index(fs: &dyn Fs, index_path: &Path) -> Result<Option<LocalHistoryIndex>> {
+    if !fs.is_file(index_path).await {
+        return Ok(None);
+    }
+    let content = fs.load(index_path).await?;
+    let index = serde_json::from_str(&content)
+        .with_context(|| format!("parsing local history index {index_path:?}"))?;
+    Ok(Some(index))
+}
@@ -12,6 +12,7 @@
 pub mod clangd_ext;
 mod code_lens;
 mod document_colors;
+mod document_links;
 mod document_symbols;
 mod folding_ranges;
 mod inlay_hints;
@@ -24,6 +25,7 @@ pub mod vue_language_server_ext;
 
 use self::code_lens::CodeLensData;
 use self::document_colors::DocumentColorData;
+use self::document_links::DocumentLinksData;
 use self::document_symbols::DocumentSymbolsData;
 use self::inlay_hints::BufferInlayHints;
 use crate::{
@@ -3836,7 +3838,7 @@ impl LocalLspStore {
 }
 
 fn notify_server_capabilities_updated(server: &LanguageServer, cx: &mut Context<LspStore>) {
-    if let Some(capabilities) = serde_json::to_string(&server.capabilities()).ok() {
+    if let Some(capabilities) = serde_json::to_string(&server.capabilities_json()).ok() {
         cx.emit(LspStoreEvent::LanguageServerUpdate {
             language_server_id: server.server_id(),
             name: Some(server.name()),
@@ -3903,6 +3905,8 @@ pub struct LspStore {
     diagnostic_summaries:
         HashMap<WorktreeId, HashMap<Arc<RelPath>, HashMap<LanguageServerId, DiagnosticSummary>>>,
     pub lsp_server_capabilities: HashMap<LanguageServerId, lsp::ServerCapabilities>,
+    /// The capabilities of each language server as JSON, including the ones lsp-types has no field for.
+    pub lsp_server_raw_capabilities: HashMap<LanguageServerId, serde_json::Value>,
     semantic_token_config: SemanticTokenConfig,
     lsp_data: HashMap<BufferId, BufferLspData>,
     next_hint_id: Arc<AtomicUsize>,
@@ -3912,6 +3916,7 @@ pub struct LspStore {
 pub struct BufferLspData {
     buffer_version: Global,
     document_colors: Option<DocumentColorData>,
+    document_links: Option<DocumentLinksData>,
     code_lens: Option<CodeLensData>,
     semantic_tokens: Option<SemanticTokensData>,
     folding_ranges: Option<FoldingRangeData>,
@@ -3932,6 +3937,7 @@ impl BufferLspData {
         Self {
             buffer_version: buffer.read(cx).version(),
             document_colors: None,
+            document_links: None,
             code_lens: None,
             semantic_tokens: None,
             folding_ranges: None,
@@ -3947,6 +3953,10 @@ impl BufferLspData {
             document_colors.remove_server_data(for_server);
         }
 
+        if let Some(document_links) = &mut self.document_links {
+            document_links.remove_server_data(for_server);
+        }
+
         if let Some(code_lens) = &mut self.code_lens {
             code_lens.remove_server_data(for_server);
         }
@@ -4080,6 +4090,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_get_project_symbols);
         client.add_entity_request_handler(Self::handle_resolve_inlay_hint);
         client.add_entity_request_handler(Self::handle_get_color_presentation);
+        client.add_entity_request_handler(Self::handle_resolve_document_link);
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_semantic_tokens);
@@ -4095,6 +4106,12 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareCallHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetIncomingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetOutgoingCalls>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<PrepareTypeHierarchy>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSupertypes>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetSubtypes>);
 
         client.add_entity_request_handler(Self::handle_lsp_ext_cancel_flycheck);
         client.add_entity_request_handler(Self::handle_lsp_ext_run_flycheck);
@@ -4232,6 +4249,7 @@ impl LspStore {
             nonce: StdRng::from_os_rng().random(),
             diagnostic_summaries: HashMap::default(),
             lsp_server_capabilities: HashMap::default(),
+            lsp_server_raw_capabilities: HashMap::default(),
             semantic_token_config: SemanticTokenConfig::new(cx),
             lsp_data: HashMap::default(),
             next_hint_id: Arc::default(),
@@ -4293,6 +4311,7 @@ impl LspStore {
             nonce: StdRng::from_os_rng().random(),
             diagnostic_summaries: HashMap::default(),
             lsp_server_capabilities: HashMap::default(),
+            lsp_server_raw_capabilities: HashMap::default(),
             semantic_token_config: SemanticTokenConfig::new(cx),
             next_hint_id: Arc::default(),
             lsp_data: HashMap::default(),
@@ -4845,12 +4864,17 @@ impl LspStore {
     where
         R: LspCommand,
     {
-        self.check_if_capable_for_proto_request(
+        self.check_if_server_capable_for_proto_request(
             buffer,
-            |capabilities| {
+            |server_id, capabilities| {
                 request.check_capabilities(AdapterServerCapabilities {
                     server_capabilities: capabilities.clone(),
                     code_action_kinds: None,
+                    raw_capabilities: self
+                        .lsp_server_raw_capabilities
+                        .get(&server_id)
+                        .cloned()
+                        .unwrap_or_default(),
                 })
             },
             cx,
@@ -4860,11 +4884,27 @@ impl LspStore {
     fn check_if_capable_for_proto_request<F>(
         &self,
         buffer: &Entity<Buffer>,
-        check: F,
+        mut check: F,
         cx: &App,
     ) -> bool
     where
         F: FnMut(&lsp::ServerCapabilities) -> bool,
+    {
+        self.check_if_server_capable_for_proto_request(
+            buffer,
+            |_, capabilities| check(capabilities),
+            cx,
+        )
+    }
+
+    fn check_if_server_capable_for_proto_request<F>(
+        &self,
+        buffer: &Entity<Buffer>,
+        mut check: F,
+        cx: &App,
+    ) -> bool
+    where
+        F: FnMut(LanguageServerId, &lsp::ServerCapabilities) -> bool,
     {
         let Some(language) = buffer.read(cx).language().cloned() else {
             return false;
@@ -4886,8 +4926,12 @@ impl LspStore {
                     || self.languages.is_lsp_adapter_available(&server_status.name);
                 is_relevant.then_some(server_id)
             })
-            .filter_map(|server_id| self.lsp_server_capabilities.get(server_id))
-            .any(check)
+            .filter_map(|server_id| {
+                self.lsp_server_capabilities
+                    .get(server_id)
+                    .map(|capabilities| (*server_id, capabilities))
+            })
+            .any(|(server_id, capabilities)| check(server_id, capabilities))
     }
 
     fn all_capable_for_proto_request<F>(
@@ -8123,7 +8167,7 @@ impl LspStore {
                             name: status.name.to_string(),
                             worktree_id: status.worktree.map(|id| id.to_proto()),
                         }),
-                        capabilities: serde_json::to_string(&server.capabilities())
+                        capabilities: serde_json::to_string(&server.capabilities_json())
                             .expect("serializing server LSP capabilities"),
                     })
                     .log_err();
@@ -8160,9 +8204,15 @@ impl LspStore {
             .zip(server_capabilities)
             .map(|(server, server_capabilities)| {
                 let server_id = LanguageServerId(server.id as usize);
-                if let Ok(server_capabilities) = serde_json::from_str(&server_capabilities) {
+                if let Ok(raw_capabilities) =
+                    serde_json::from_str::<serde_json::Value>(&server_capabilities)
+                    && let Ok(server_capabilities) =
+                        serde_json::from_value(raw_capabilities.clone())
+                {
                     self.lsp_server_capabilities
                         .insert(server_id, server_capabilities);
+                    self.lsp_server_raw_capabilities
+                        .insert(server_id, raw_capabilities);
                 }
 
                 let name = LanguageServerName::from_proto(server.name);
@@ -8818,6 +8868,18 @@ impl LspStore {
                 )
                 .await?;
             }
+            Request::GetDocumentLinks(get_document_links) => {
+                Self::query_lsp_locally::<GetDocumentLinks>(
+                    lsp_store,
+                    server_id,
+                    sender_id,
+                    lsp_request_id,
+                    get_document_links,
+                    None,
+                    &mut cx,
+                )
+                .await?;
+            }
             Request::GetFoldingRanges(get_folding_ranges) => {
                 Self::query_lsp_locally::<GetFoldingRanges>(
                     lsp_store,
@@ -9360,8 +9422,16 @@ impl LspStore {
         mut cx: AsyncApp,
     ) -> Result<()> {
         let server = envelope.payload.server.context("invalid server")?;
+        let raw_capabilities =
+            serde_json::from_str::<serde_json::Value>(&envelope.payload.capabilities)
+                .with_context(|| {
+                    format!(
+                        "incorrect server capabilities {}",
+                        envelope.payload.capabilities
+                    )
+                })?;
         let server_capabilities =
-            serde_json::from_str::<lsp::ServerCapabilities>(&envelope.payload.capabilities)
+            serde_json::from_value::<lsp::ServerCapabilities>(raw_capabilities.clone())
                 .with_context(|| {
                     format!(
                         "incorrect server capabilities {}",
@@ -9374,6 +9444,9 @@ impl LspStore {
             lsp_store
                 .lsp_server_capabilities
                 .insert(server_id, server_capabilities);
+            lsp_store
+                .lsp_server_raw_capabilities
+                .insert(server_id, raw_capabilities);
             lsp_store.language_server_statuses.insert(
                 server_id,
                 LanguageServerStatus {
@@ -11362,13 +11435,15 @@ impl LspStore {
                         name: language_server.name().to_string(),
                         worktree_id: Some(key.worktree_id.to_proto()),
                     }),
-                    capabilities: serde_json::to_string(&server_capabilities)
+                    capabilities: serde_json::to_string(&language_server.capabilities_json())
                         .expect("serializing server LSP capabilities"),
                 })
                 .log_err();
         }
         self.lsp_server_capabilities
             .insert(server_id, server_capabilities);
+        self.lsp_server_raw_capabilities
+            .insert(server_id, language_server.raw_capabilities().clone());
 
         // Tell the language server about every open buffer in the worktree that matches the language.
         // Also check for buffers in worktrees that reused this server
@@ -11978,6 +12053,7 @@ impl LspStore {
 
     fn cleanup_lsp_data(&mut self, for_server: LanguageServerId) {
         self.lsp_server_capabilities.remove(&for_server);
+        self.lsp_server_raw_capabilities.remove(&for_server);
         self.semantic_token_config.remove_server_data(for_server);
         for lsp_data in self.lsp_data.values_mut() {
             lsp_data.remove_server_data(for_server);
@@ -0,0 +1,321 @@
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use clock::Global;
+use collections::HashMap;
+use futures::{
+    FutureExt as _,
+    future::{Shared, join_all},
+};
+use gpui::{AppContext as _, AsyncApp, Context, Entity, Task};
+use language::Buffer;
+use lsp::LanguageServerId;
+use rpc::{TypedEnvelope, proto};
+use settings::Settings as _;
+use text::BufferId;
+
+use crate::{
+    DocumentLink, LspStore,
+    lsp_command::{GetDocumentLinks, LspCommand as _},
+    project_settings::ProjectSettings,
+};
+
+pub(super) type DocumentLinksTask =
+    Shared<Task<std::result::Result<Option<Vec<DocumentLink>>, Arc<anyhow::Error>>>>;
+
+#[derive(Debug, Default)]
+pub(super) struct DocumentLinksData {
+    pub(super) links: HashMap<LanguageServerId, Vec<DocumentLink>>,
+    pub(super) update: Option<(Global, DocumentLinksTask)>,
+}
+
+impl DocumentLinksData {
+    pub(super) fn remove_server_data(&mut self, server_id: LanguageServerId) {
+        self.links.remove(&server_id);
+    }
+}
+
+impl LspStore {
+    pub fn document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> DocumentLinksTask {
+        let version_queried_for = buffer.read(cx).version();
+        let buffer_id = buffer.read(cx).remote_id();
+        let existing_servers = self.as_local().map(|local| {
+            local
+                .buffers_opened_in_servers
+                .get(&buffer_id)
+                .cloned()
+                .unwrap_or_default()
+        });
+
+        if let Some(lsp_data) = self.current_lsp_data(buffer_id) {
+            if let Some(cached_links) = &lsp_data.document_links {
+                if !version_queried_for.changed_since(&lsp_data.buffer_version) {
+                    let has_different_servers = existing_servers.is_some_and(|existing_servers| {
+                        existing_servers != cached_links.links.keys().copied().collect()
+                    });
+                    if !has_different_servers {
+                        return Task::ready(Ok(Some(
+                            cached_links.links.values().flatten().cloned().collect(),
+                        )))
+                        .shared();
+                    }
+                } else if let Some((updating_for, running_update)) = cached_links.update.as_ref() {
+                    if !version_queried_for.changed_since(updating_for) {
+                        return running_update.clone();
+                    }
+                }
+            }
+        }
+
+        let links_lsp_data = self
+            .latest_lsp_data(buffer, cx)
+            .document_links
+            .get_or_insert_default();
+        let buffer = buffer.clone();
+        let query_version_queried_for = version_queried_for.clone();
+        let new_task = cx
+            .spawn(async move |lsp_store, cx| {
+                let fetched_links = lsp_store
+                    .update(cx, |lsp_store, cx| {
+                        lsp_store.fetch_document_links(&buffer, cx)
+                    })
+                    .map_err(Arc::new)?
+                    .await
+                    .context("fetching document links")
+                    .map_err(Arc::new);
+                let fetched_links = match fetched_links {
+                    Ok(fetched_links) => fetched_links,
+                    Err(e) => {
+                        lsp_store
+                            .update(cx, |lsp_store, _| {
+                                if let Some(links_lsp_data) = lsp_store
+                                    .lsp_data
+                                    .get_mut(&buffer_id)
+                                    .and_then(|lsp_data| lsp_data.document_links.as_mut())
+                                {
+                                    links_lsp_data.update = None;
+                                }
+                            })
+                            .ok();
+                        return Err(e);
+                    }
+                };
+
+                lsp_store
+                    .update(cx, |lsp_store, _| {
+                        let lsp_data = lsp_store.current_lsp_data(buffer_id)?;
+                        let document_links = lsp_data.document_links.as_mut()?;
+                        if let Some(fetched_links) = fetched_links {
+                            if lsp_data.buffer_version == query_version_queried_for {
+                                document_links.links.extend(fetched_links);
+                            } else if !lsp_data
+                                .buffer_version
+                                .changed_since(&query_version_queried_for)
+                            {
+                                lsp_data.buffer_version = query_version_queried_for;
+                                document_links.links = fetched_links;
+                            }
+                        }
+                        document_links.update = None;
+                        Some(document_links.links.values().flatten().cloned().collect())
+                    })
+                    .map_err(Arc::new)
+            })
+            .shared();
+        links_lsp_data.update = Some((version_queried_for, new_task.clone()));
+        new_task
+    }
+
+    pub(super) fn fetch_document_links(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Option<HashMap<LanguageServerId, Vec<DocumentLink>>>>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request = GetDocumentLinks;
+            if !self.is_capable_for_proto_request(buffer, &request, cx) {
+                return Task::ready(Ok(None));
+            }
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            let request_task = upstream_client.request_lsp(
+                project_id,
+                None,
+                request_timeout,
+                cx.background_executor().clone(),
+                request.to_proto(project_id, buffer.read(cx)),
+            );
+            let buffer = buffer.clone();
+            cx.spawn(async move |weak_lsp_store, cx| {
+                let Some(lsp_store) = weak_lsp_store.upgrade() else {
+                    return Ok(None);
+                };
+                let Some(responses) = request_task.await? else {
+                    return Ok(None);
+                };
+
+                let document_links = join_all(responses.payload.into_iter().map(|response| {
+                    let lsp_store = lsp_store.clone();
+                    let buffer = buffer.clone();
+                    let cx = cx.clone();
+                    async move {
+                        (
+                            LanguageServerId::from_proto(response.server_id),
+                            GetDocumentLinks
+                                .response_from_proto(response.response, lsp_store, buffer, cx)
+                                .await,
+                        )
+                    }
+                }))
+                .await;
+
+                let mut has_errors = false;
+                let document_links = document_links
+                    .into_iter()
+                    .filter_map(|(server_id, links)| match links {
+                        Ok(links) => Some((server_id, links)),
+                        Err(e) => {
+                            has_errors = true;
+                            log::error!("{e:#}");
+                            None
+                        }
+                    })
+                    .collect::<HashMap<_, _>>();
+                anyhow::ensure!(
+                    !has_errors || !document_links.is_empty(),
+                    "Failed to fetch document links"
+                );
+                Ok(Some(document_links))
+            })
+        } else {
+            let document_links_task =
+                self.request_multiple_lsp_locally(buffer, None::<usize>, GetDocumentLinks, cx);
+            cx.background_spawn(
+                async move { Ok(Some(document_links_task.await.into_iter().collect())) },
+            )
+        }
+    }
+
+    /// Asks the language server where the link goes, if it didn't tell when computing the links.
+    pub fn resolve_document_link(
+        &mut self,
+        link: DocumentLink,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        if link.target.is_some() {
+            return Task::ready(Ok(link));
+        }
+
+        let buffer_id = buffer.read(cx).remote_id();
+        let resolve_task =
+            self.resolve_lsp_document_link(link.lsp_link.clone(), link.server_id, buffer, cx);
+        cx.spawn(async move |lsp_store, cx| {
+            let lsp_link = resolve_task.await?;
+            let link = DocumentLink {
+                target: lsp_link.target.clone(),
+                tooltip: lsp_link.tooltip.clone(),
+                lsp_link,
+                ..link
+            };
+            lsp_store
+                .update(cx, |lsp_store, _| {
+                    let cached_link = lsp_store
+                        .current_lsp_data(buffer_id)?
+                        .document_links
+                        .as_mut()?
+                        .links
+                        .get_mut(&link.server_id)?
+                        .iter_mut()
+                        .find(|cached_link| cached_link.range == link.range)?;
+                    *cached_link = link.clone();
+                    Some(())
+                })
+                .ok();
+            Ok(link)
+        })
+    }
+
+    fn resolve_lsp_document_link(
+        &mut self,
+        lsp_link: lsp::DocumentLink,
+        server_id: LanguageServerId,
+        buffer: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<lsp::DocumentLink>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request = proto::ResolveDocumentLink {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                server_id: server_id.to_proto(),
+                lsp_link: serde_json::to_vec(&lsp_link).unwrap_or_default(),
+            };
+            cx.background_spawn(async move {
+                let response = upstream_client
+                    .request(request)
+                    .await
+                    .context("document link resolve proto request")?;
+                serde_json::from_slice(&response.lsp_link).context("invalid document link")
+            })
+        } else {
+            let Some(lang_server) = buffer.update(cx, |buffer, cx| {
+                self.language_server_for_local_buffer(buffer, server_id, cx)
+                    .map(|(_, server)| server.clone())
+            }) else {
+                return Task::ready(Ok(lsp_link));
+            };
+            let can_resolve = lang_server
+                .capabilities()
+                .document_link_provider
+                .as_ref()
+                .and_then(|options| options.resolve_provider)
+                .unwrap_or(false);
+            if !can_resolve {
+                return Task::ready(Ok(lsp_link));
+            }
+
+            let request_timeout = ProjectSettings::get_global(cx)
+                .global_lsp_settings
+                .get_request_timeout();
+            cx.background_spawn(async move {
+                lang_server
+                    .request::<lsp::request::DocumentLinkResolve>(lsp_link, request_timeout)
+                    .await
+                    .into_response()
+                    .context("document link resolve LSP request")
+            })
+        }
+    }
+
+    pub(super) async fn handle_resolve_document_link(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::ResolveDocumentLink>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ResolveDocumentLinkResponse> {
+        let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })?;
+        let lsp_link = serde_json::from_slice(&envelope.payload.lsp_link)
+            .context("invalid document link resolve request")?;
+        let resolved_link = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.resolve_lsp_document_link(
+                    lsp_link,
+                    LanguageServerId::from_proto(envelope.payload.server_id),
+                    &buffer,
+                    cx,
+                )
+            })
+            .await
+            .context("resolving document link")?;
+        Ok(proto::ResolveDocumentLinkResponse {
+            lsp_link: serde_json::to_vec(&resolved_link)?,
+        })
+    }
+}
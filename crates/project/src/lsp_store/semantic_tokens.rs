@@ -280,6 +280,11 @@ impl LspStore {
                     let capabilities = AdapterServerCapabilities {
                         server_capabilities: self.lsp_server_capabilities.get(&server_id)?.clone(),
                         code_action_kinds: None,
+                        raw_capabilities: self
+                            .lsp_server_raw_capabilities
+                            .get(&server_id)
+                            .cloned()
+                            .unwrap_or_default(),
                     };
                     let request_task = match self.semantic_tokens_result_id(server_id, buffer, cx) {
                         Some(result_id) => {
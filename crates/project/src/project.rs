@@ -1,5 +1,6 @@
 pub mod agent_registry_store;
 pub mod agent_server_store;
+pub mod bookmark_store;
 pub mod buffer_store;
 pub mod color_extractor;
 pub mod connection_manager;
@@ -8,6 +9,7 @@ pub mod debounced_delay;
 pub mod debugger;
 pub mod git_store;
 pub mod image_store;
+pub mod local_history_store;
 pub mod lsp_command;
 pub mod lsp_store;
 pub mod manifest_tree;
@@ -54,6 +56,7 @@ pub use manifest_tree::ManifestTree;
 pub use project_search::{Search, SearchResults};
 
 use anyhow::{Context as _, Result, anyhow};
+use bookmark_store::BookmarkStore;
 use buffer_store::{BufferStore, BufferStoreEvent};
 use client::{
     Client, Collaborator, PendingEntitySubscription, ProjectId, TypedEnvelope, UserStore, proto,
@@ -80,6 +83,7 @@ use futures::{
 };
 pub use image_store::{ImageItem, ImageStore};
 use image_store::{ImageItemEvent, ImageStoreEvent};
+pub use local_history_store::{LocalHistoryReason, LocalHistoryStore};
 
 use ::git::{blame::Blame, status::FileStatus};
 use gpui::{
@@ -211,6 +215,7 @@ pub struct Project {
     agent_server_store: Entity<AgentServerStore>,
 
     breakpoint_store: Entity<BreakpointStore>,
+    bookmark_store: Entity<BookmarkStore>,
     collab_client: Arc<client::Client>,
     join_project_response_message_id: u32,
     task_store: Entity<TaskStore>,
@@ -845,6 +850,44 @@ pub struct DocumentSymbol {
     pub children: Vec<DocumentSymbol>,
 }
 
+/// A function or method in a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    pub buffer: Entity<Buffer>,
+    pub range: Range<language::Anchor>,
+    /// The range of the name of the item.
+    pub selection_range: Range<language::Anchor>,
+    /// The item as the language server returned it, which its calls are requested with.
+    pub lsp_item: lsp::CallHierarchyItem,
+}
+
+/// A type in a type hierarchy.
+#[derive(Clone, Debug)]
+pub struct TypeHierarchyItem {
+    pub name: String,
+    pub kind: lsp::SymbolKind,
+    pub detail: Option<String>,
+    pub buffer: Entity<Buffer>,
+    pub range: Range<language::Anchor>,
+    /// The range of the name of the item.
+    pub selection_range: Range<language::Anchor>,
+    /// The item as the language server returned it, which its supertypes and subtypes are
+    /// requested with.
+    pub lsp_item: lsp::TypeHierarchyItem,
+}
+
+/// Calls between two items of a call hierarchy.
+#[derive(Clone, Debug)]
+pub struct CallHierarchyCall {
+    /// The caller of incoming calls, or the callee of outgoing calls.
+    pub item: CallHierarchyItem,
+    /// Where the calls are made, in the buffer of the caller.
+    pub call_sites: Vec<Range<language::Anchor>>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HoverBlock {
     pub text: String,
@@ -932,6 +975,17 @@ impl std::hash::Hash for ColorPresentation {
     }
 }
 
+/// A range of a document that links somewhere, as computed by a language server.
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<language::Anchor>,
+    /// Where the link goes, which some servers only tell once the link is resolved.
+    pub target: Option<lsp::Uri>,
+    pub tooltip: Option<String>,
+    pub server_id: LanguageServerId,
+    pub lsp_link: lsp::DocumentLink,
+}
+
 #[derive(Clone)]
 pub enum DirectoryLister {
     Project(Entity<Project>),
@@ -1196,6 +1250,8 @@ impl Project {
 
             let breakpoint_store =
                 cx.new(|_| BreakpointStore::local(worktree_store.clone(), buffer_store.clone()));
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_local(
@@ -1312,6 +1368,7 @@ impl Project {
                 fs,
                 remote_client: None,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 agent_server_store,
 
@@ -1475,6 +1532,8 @@ impl Project {
                     worktree_store.clone(),
                 )
             });
+            let bookmark_store =
+                cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
 
             let dap_store = cx.new(|cx| {
                 DapStore::new_remote(
@@ -1513,6 +1572,7 @@ impl Project {
                 lsp_store,
                 context_server_store,
                 breakpoint_store,
+                bookmark_store,
                 dap_store,
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -1697,6 +1757,8 @@ impl Project {
                 worktree_store.clone(),
             )
         });
+        let bookmark_store =
+            cx.new(|_| BookmarkStore::new(worktree_store.clone(), buffer_store.clone()));
         let dap_store = cx.new(|cx| {
             DapStore::new_collab(
                 remote_id,
@@ -1822,6 +1884,7 @@ impl Project {
                     replica_id,
                 },
                 breakpoint_store: breakpoint_store.clone(),
+                bookmark_store,
                 dap_store: dap_store.clone(),
                 git_store: git_store.clone(),
                 agent_server_store,
@@ -2059,6 +2122,11 @@ impl Project {
         self.breakpoint_store.clone()
     }
 
+    #[inline]
+    pub fn bookmark_store(&self) -> Entity<BookmarkStore> {
+        self.bookmark_store.clone()
+    }
+
     pub fn active_debug_session(&self, cx: &App) -> Option<(Entity<Session>, ActiveStackFrame)> {
         let active_position = self.breakpoint_store.read(cx).active_position()?;
         let session = self
@@ -3428,14 +3496,19 @@ impl Project {
                 match message {
                     proto::update_language_server::Variant::MetadataUpdated(update) => {
                         self.lsp_store.update(cx, |lsp_store, _| {
-                            if let Some(capabilities) = update
-                                .capabilities
-                                .as_ref()
-                                .and_then(|capabilities| serde_json::from_str(capabilities).ok())
+                            if let Some(raw_capabilities) =
+                                update.capabilities.as_ref().and_then(|capabilities| {
+                                    serde_json::from_str::<serde_json::Value>(capabilities).ok()
+                                })
+                                && let Ok(capabilities) =
+                                    serde_json::from_value(raw_capabilities.clone())
                             {
                                 lsp_store
                                     .lsp_server_capabilities
                                     .insert(*language_server_id, capabilities);
+                                lsp_store
+                                    .lsp_server_raw_capabilities
+                                    .insert(*language_server_id, raw_capabilities);
                             }
 
                             if let Some(language_server_status) = lsp_store
@@ -3646,7 +3719,27 @@ impl Project {
 
         let buffer_id = buffer.read(cx).remote_id();
         match event {
+            BufferEvent::Saved => {
+                if let Some(local_history) = LocalHistoryStore::try_global(cx) {
+                    local_history
+                        .update(cx, |local_history, cx| {
+                            local_history.snapshot_buffer(&buffer, LocalHistoryReason::Saved, cx)
+                        })
+                        .detach_and_log_err(cx);
+                }
+            }
             BufferEvent::ReloadNeeded => {
+                if let Some(local_history) = LocalHistoryStore::try_global(cx) {
+                    local_history
+                        .update(cx, |local_history, cx| {
+                            local_history.snapshot_buffer(
+                                &buffer,
+                                LocalHistoryReason::BeforeReload,
+                                cx,
+                            )
+                        })
+                        .detach_and_log_err(cx);
+                }
                 if !self.is_via_collab() {
                     self.reload_buffers([buffer.clone()].into_iter().collect(), true, cx)
                         .detach_and_log_err(cx);
@@ -4091,6 +4184,95 @@ impl Project {
         )
     }
 
+    /// Returns the items of the call hierarchy at the position, to request their calls with.
+    pub fn prepare_call_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareCallHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the callers of the item, with the call sites in each caller.
+    pub fn incoming_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetIncomingCalls { item: item.clone() },
+            cx,
+        )
+    }
+
+    /// Returns the callees of the item, with the call sites in the item.
+    pub fn outgoing_calls(
+        &mut self,
+        item: &CallHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<CallHierarchyCall>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetOutgoingCalls { item: item.clone() },
+            cx,
+        )
+    }
+
+    /// Returns the items of the type hierarchy at the position, to request their supertypes
+    /// and subtypes with.
+    pub fn prepare_type_hierarchy<T: ToPointUtf16>(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        position: T,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        let position = position.to_point_utf16(buffer.read(cx));
+        self.request_lsp(
+            buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            PrepareTypeHierarchy { position },
+            cx,
+        )
+    }
+
+    /// Returns the types the item directly extends or implements.
+    pub fn supertypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSupertypes { item: item.clone() },
+            cx,
+        )
+    }
+
+    /// Returns the types directly extending or implementing the item.
+    pub fn subtypes(
+        &mut self,
+        item: &TypeHierarchyItem,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<TypeHierarchyItem>>> {
+        self.request_lsp(
+            item.buffer.clone(),
+            LanguageServerToQuery::FirstCapable,
+            GetSubtypes { item: item.clone() },
+            cx,
+        )
+    }
+
     pub fn document_symbols(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -5783,6 +5965,32 @@ impl Project {
             .any(|capabilities| capabilities.semantic_tokens_provider.is_some())
     }
 
+    pub fn any_language_server_supports_document_links(
+        &self,
+        buffer: &Buffer,
+        cx: &mut App,
+    ) -> bool {
+        let Some(language) = buffer.language().cloned() else {
+            return false;
+        };
+        let lsp_store = self.lsp_store.read(cx);
+        let relevant_language_servers = lsp_store
+            .languages
+            .lsp_adapters(&language.name())
+            .into_iter()
+            .map(|lsp_adapter| lsp_adapter.name())
+            .collect::<HashSet<_>>();
+        lsp_store
+            .language_server_statuses()
+            .filter_map(|(server_id, server_status)| {
+                relevant_language_servers
+                    .contains(&server_status.name)
+                    .then_some(server_id)
+            })
+            .filter_map(|server_id| lsp_store.lsp_server_capabilities.get(&server_id))
+            .any(|capabilities| capabilities.document_link_provider.is_some())
+    }
+
     pub fn language_server_id_for_name(
         &self,
         buffer: &Buffer,
@@ -15,7 +15,7 @@ use fs::Fs;
 use futures::FutureExt as _;
 use futures::{SinkExt, StreamExt, select_biased, stream::FuturesOrdered};
 use gpui::{App, AppContext, AsyncApp, BackgroundExecutor, Entity, Priority, Task};
-use language::{Buffer, BufferSnapshot};
+use language::{Buffer, BufferEvent, BufferSnapshot};
 use parking_lot::Mutex;
 use postage::oneshot;
 use rpc::{AnyProtoClient, proto};
@@ -27,7 +27,7 @@ use worktree::{Entry, ProjectEntryId, Snapshot, Worktree, WorktreeSettings};
 use crate::{
     Project, ProjectItem, ProjectPath, RemotelyCreatedModels,
     buffer_store::BufferStore,
-    search::{SearchQuery, SearchResult},
+    search::{SearchQuery, SearchResult, SearchScope},
     worktree_store::WorktreeStore,
 };
 
@@ -327,6 +327,7 @@ impl Search {
                 };
 
                 let should_find_all_matches = !tx.is_closed();
+                let wait_for_syntax = query.is_structural() || query.scope() != SearchScope::All;
 
                 let _executor = executor.clone();
                 let worker_pool = executor.spawn(async move {
@@ -365,6 +366,7 @@ impl Search {
                             grab_buffer_snapshot_rx,
                             find_all_matches_tx,
                             sorted_matches_tx,
+                            wait_for_syntax,
                             cx.clone(),
                         )
                         .boxed_local(),
@@ -533,10 +535,14 @@ impl Search {
             oneshot::Sender<(Entity<Buffer>, Vec<Range<language::Anchor>>)>,
         )>,
         results: Sender<oneshot::Receiver<(Entity<Buffer>, Vec<Range<language::Anchor>>)>>,
+        wait_for_syntax: bool,
         mut cx: AsyncApp,
     ) {
         _ = maybe!(async move {
             while let Ok(buffer) = rx.recv().await {
+                if wait_for_syntax {
+                    Self::wait_for_syntax(&buffer, &mut cx).await;
+                }
                 let snapshot = buffer.read_with(&mut cx, |this, _| this.snapshot());
                 let (tx, rx) = oneshot::channel();
                 find_all_matches_tx.send((buffer, snapshot, tx)).await?;
@@ -548,6 +554,37 @@ impl Search {
         .await;
     }
 
+    /// Buffers opened for the search get their language assigned and parsed in the background,
+    /// so structural and scoped queries have to wait for both before snapshotting the buffer.
+    async fn wait_for_syntax(buffer: &Entity<Buffer>, cx: &mut AsyncApp) {
+        let language_load = buffer.read_with(cx, |buffer, cx| {
+            if buffer.language().is_some() {
+                return None;
+            }
+            let languages = buffer.language_registry()?;
+            let language =
+                languages.language_for_file(buffer.file()?, Some(buffer.as_rope()), cx)?;
+            Some(languages.load_language(&language))
+        });
+        if let Some(language_load) = language_load
+            && let Ok(Ok(_)) = language_load.await
+        {
+            // The language is assigned by the LSP store once the registry reports it as loaded.
+            let (language_changed_tx, language_changed_rx) = unbounded();
+            let _subscription = cx.subscribe(buffer, move |_, event: &BufferEvent, _| {
+                if let BufferEvent::LanguageChanged(_) = event {
+                    language_changed_tx.try_send(()).ok();
+                }
+            });
+            if buffer.read_with(cx, |buffer, _| buffer.language().is_none()) {
+                language_changed_rx.recv().await.ok();
+            }
+        }
+        buffer
+            .read_with(cx, |buffer, _| buffer.parsing_idle())
+            .await;
+    }
+
     async fn ensure_matched_ranges_are_reported_in_order(
         rx: Receiver<oneshot::Receiver<(Entity<Buffer>, Vec<Range<language::Anchor>>)>>,
         tx: Sender<SearchResult>,
@@ -4,7 +4,7 @@ use client::proto;
 use fancy_regex::{Captures, Regex, RegexBuilder};
 use gpui::Entity;
 use itertools::Itertools as _;
-use language::{Buffer, BufferSnapshot, CharKind};
+use language::{Buffer, BufferSnapshot, CharKind, StructuralQuery};
 use smol::future::yield_now;
 use std::{
     borrow::Cow,
@@ -34,6 +34,39 @@ pub enum SearchInputKind {
     Exclude,
 }
 
+/// The kind of text that a search's matches are restricted to, according to the syntax
+/// highlighting of each buffer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchScope {
+    #[default]
+    All,
+    /// Text outside of comments, including string literals.
+    Code,
+    Comments,
+    Strings,
+}
+
+impl SearchScope {
+    pub fn from_proto(scope: i32) -> Self {
+        match proto::search_query::Scope::from_i32(scope) {
+            Some(proto::search_query::Scope::Code) => Self::Code,
+            Some(proto::search_query::Scope::Comments) => Self::Comments,
+            Some(proto::search_query::Scope::Strings) => Self::Strings,
+            Some(proto::search_query::Scope::All) | None => Self::All,
+        }
+    }
+
+    pub fn to_proto(self) -> i32 {
+        let scope = match self {
+            Self::All => proto::search_query::Scope::All,
+            Self::Code => proto::search_query::Scope::Code,
+            Self::Comments => proto::search_query::Scope::Comments,
+            Self::Strings => proto::search_query::Scope::Strings,
+        };
+        scope as i32
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SearchInputs {
     query: Arc<str>,
@@ -41,6 +74,7 @@ pub struct SearchInputs {
     files_to_exclude: PathMatcher,
     match_full_paths: bool,
     buffers: Option<Vec<Entity<Buffer>>>,
+    scope: SearchScope,
 }
 
 impl SearchInputs {
@@ -56,6 +90,9 @@ impl SearchInputs {
     pub fn buffers(&self) -> &Option<Vec<Entity<Buffer>>> {
         &self.buffers
     }
+    pub fn scope(&self) -> SearchScope {
+        self.scope
+    }
 }
 #[derive(Clone, Debug)]
 pub enum SearchQuery {
@@ -77,6 +114,12 @@ pub enum SearchQuery {
         one_match_per_line: bool,
         inner: SearchInputs,
     },
+    Structural {
+        query: Arc<StructuralQuery>,
+        replacement: Option<String>,
+        include_ignored: bool,
+        inner: SearchInputs,
+    },
 }
 
 static WORD_MATCH_TEST: LazyLock<Regex> = LazyLock::new(|| {
@@ -126,6 +169,7 @@ impl SearchQuery {
             files_to_include,
             match_full_paths,
             buffers,
+            scope: SearchScope::All,
         };
         Ok(Self::Text {
             search,
@@ -193,6 +237,7 @@ impl SearchQuery {
             files_to_include,
             match_full_paths,
             buffers,
+            scope: SearchScope::All,
         };
         Ok(Self::Regex {
             regex,
@@ -206,6 +251,36 @@ impl SearchQuery {
         })
     }
 
+    /// Create a structural query, matching either a tree-sitter query or a code pattern with
+    /// metavariables against the syntax trees of buffers. See [`StructuralQuery`] for the syntax.
+    ///
+    /// `match_full_paths` has the same meaning as for [`Self::regex`].
+    pub fn structural(
+        query: impl ToString,
+        include_ignored: bool,
+        files_to_include: PathMatcher,
+        files_to_exclude: PathMatcher,
+        match_full_paths: bool,
+        buffers: Option<Vec<Entity<Buffer>>>,
+    ) -> Result<Self> {
+        let query = query.to_string();
+        let structural_query = StructuralQuery::new(query.as_str())?;
+        let inner = SearchInputs {
+            query: query.into(),
+            files_to_exclude,
+            files_to_include,
+            match_full_paths,
+            buffers,
+            scope: SearchScope::All,
+        };
+        Ok(Self::Structural {
+            query: Arc::new(structural_query),
+            replacement: None,
+            include_ignored,
+            inner,
+        })
+    }
+
     /// Extracts case sensitivity settings from pattern items in the provided
     /// query and returns the same query, with the pattern items removed.
     ///
@@ -270,7 +345,17 @@ impl SearchQuery {
             message.files_to_exclude
         };
 
-        if message.regex {
+        let scope = SearchScope::from_proto(message.scope);
+        let query = if message.structural {
+            Self::structural(
+                message.query,
+                message.include_ignored,
+                PathMatcher::new(files_to_include, path_style)?,
+                PathMatcher::new(files_to_exclude, path_style)?,
+                message.match_full_paths,
+                None, // search opened only don't need search remote
+            )
+        } else if message.regex {
             Self::regex(
                 message.query,
                 message.whole_word,
@@ -293,7 +378,8 @@ impl SearchQuery {
                 message.match_full_paths,
                 None, // search opened only don't need search remote
             )
-        }
+        }?;
+        Ok(query.with_scope(scope))
     }
 
     pub fn with_replacement(mut self, new_replacement: String) -> Self {
@@ -305,6 +391,10 @@ impl SearchQuery {
             | Self::Regex {
                 ref mut replacement,
                 ..
+            }
+            | Self::Structural {
+                ref mut replacement,
+                ..
             } => {
                 *replacement = Some(new_replacement);
                 self
@@ -312,12 +402,26 @@ impl SearchQuery {
         }
     }
 
+    /// Restricts matches to comments, strings or code.
+    pub fn with_scope(mut self, scope: SearchScope) -> Self {
+        match &mut self {
+            Self::Text { inner, .. }
+            | Self::Regex { inner, .. }
+            | Self::Structural { inner, .. } => {
+                inner.scope = scope;
+            }
+        }
+        self
+    }
+
     pub fn to_proto(&self) -> proto::SearchQuery {
         let mut files_to_include = self.files_to_include().sources();
         let mut files_to_exclude = self.files_to_exclude().sources();
         proto::SearchQuery {
             query: self.as_str().to_string(),
             regex: self.is_regex(),
+            structural: self.is_structural(),
+            scope: self.scope().to_proto(),
             whole_word: self.whole_word(),
             case_sensitive: self.case_sensitive(),
             include_ignored: self.include_ignored(),
@@ -387,14 +491,35 @@ impl SearchQuery {
                     Ok(false)
                 }
             }
+            Self::Structural { query, .. } => {
+                // Files are only parsed once they're opened, so this just rules out the ones
+                // that can't contain a match.
+                let Some(required_word) = query.required_word() else {
+                    return Ok(true);
+                };
+                let mut text = String::new();
+                let mut bytes_read = 0;
+                while reader.read_line(&mut text)? > 0 {
+                    if text.contains(required_word) {
+                        return Ok(true);
+                    }
+                    bytes_read += text.len();
+                    if bytes_read >= YIELD_THRESHOLD {
+                        bytes_read = 0;
+                        smol::future::yield_now().await;
+                    }
+                    text.clear();
+                }
+                Ok(false)
+            }
         }
     }
     /// Returns the replacement text for this `SearchQuery`.
     pub fn replacement(&self) -> Option<&str> {
         match self {
-            SearchQuery::Text { replacement, .. } | SearchQuery::Regex { replacement, .. } => {
-                replacement.as_deref()
-            }
+            SearchQuery::Text { replacement, .. }
+            | SearchQuery::Regex { replacement, .. }
+            | SearchQuery::Structural { replacement, .. } => replacement.as_deref(),
         }
     }
     /// Replaces search hits if replacement is set. `text` is assumed to be a string that matches this `SearchQuery` exactly, without any leftovers on either side.
@@ -421,9 +546,33 @@ impl SearchQuery {
                     None
                 }
             }
+            // Structural replacements depend on the syntax around the match.
+            SearchQuery::Structural { .. } => None,
         }
     }
 
+    /// Replaces a match of a structural query found at `range` in `buffer`, substituting the
+    /// text captured by metavariables into the replacement.
+    pub fn structural_replacement_for(
+        &self,
+        buffer: &BufferSnapshot,
+        range: Range<usize>,
+    ) -> Option<String> {
+        let SearchQuery::Structural {
+            query,
+            replacement: Some(replacement),
+            ..
+        } = self
+        else {
+            return None;
+        };
+        query
+            .matches(buffer, range.clone())
+            .into_iter()
+            .find(|mat| mat.range == range)
+            .map(|mat| mat.expand_replacement(replacement, buffer))
+    }
+
     pub async fn search(
         &self,
         buffer: &BufferSnapshot,
@@ -436,7 +585,7 @@ impl SearchQuery {
         }
 
         let range_offset = subrange.as_ref().map(|r| r.start).unwrap_or(0);
-        let rope = if let Some(range) = subrange {
+        let rope = if let Some(range) = subrange.clone() {
             buffer.as_rope().slice(range)
         } else {
             buffer.as_rope().clone()
@@ -519,6 +668,37 @@ impl SearchQuery {
                     }
                 }
             }
+
+            Self::Structural { query, .. } => {
+                let range = subrange.clone().unwrap_or(0..buffer.len());
+                matches.extend(
+                    query
+                        .matches(buffer, range)
+                        .into_iter()
+                        .map(|mat| mat.range.start - range_offset..mat.range.end - range_offset),
+                );
+            }
+        }
+
+        let scope = self.scope();
+        if scope != SearchScope::All && !matches.is_empty() {
+            let capture_name = match scope {
+                SearchScope::Strings => "string",
+                _ => "comment",
+            };
+            let scope_ranges = buffer
+                .highlight_capture_ranges(range_offset..range_offset + rope.len(), capture_name);
+            matches.retain(|mat| {
+                let start = range_offset + mat.start;
+                let end = range_offset + mat.end;
+                let ix = scope_ranges.partition_point(|range| range.end <= start);
+                let scope_range = scope_ranges.get(ix);
+                if scope == SearchScope::Code {
+                    scope_range.is_none_or(|range| range.start >= end)
+                } else {
+                    scope_range.is_some_and(|range| range.start <= start && end <= range.end)
+                }
+            });
         }
 
         matches
@@ -536,6 +716,7 @@ impl SearchQuery {
         match self {
             Self::Text { whole_word, .. } => *whole_word,
             Self::Regex { whole_word, .. } => *whole_word,
+            Self::Structural { .. } => false,
         }
     }
 
@@ -543,6 +724,7 @@ impl SearchQuery {
         match self {
             Self::Text { case_sensitive, .. } => *case_sensitive,
             Self::Regex { case_sensitive, .. } => *case_sensitive,
+            Self::Structural { .. } => true,
         }
     }
 
@@ -554,6 +736,9 @@ impl SearchQuery {
             Self::Regex {
                 include_ignored, ..
             } => *include_ignored,
+            Self::Structural {
+                include_ignored, ..
+            } => *include_ignored,
         }
     }
 
@@ -561,6 +746,14 @@ impl SearchQuery {
         matches!(self, Self::Regex { .. })
     }
 
+    pub fn is_structural(&self) -> bool {
+        matches!(self, Self::Structural { .. })
+    }
+
+    pub fn scope(&self) -> SearchScope {
+        self.as_inner().scope
+    }
+
     pub fn files_to_include(&self) -> &PathMatcher {
         self.as_inner().files_to_include()
     }
@@ -604,20 +797,22 @@ impl SearchQuery {
     }
     pub fn as_inner(&self) -> &SearchInputs {
         match self {
-            Self::Regex { inner, .. } | Self::Text { inner, .. } => inner,
+            Self::Regex { inner, .. }
+            | Self::Text { inner, .. }
+            | Self::Structural { inner, .. } => inner,
         }
     }
 
     /// Whether this search should replace only one match per line, instead of
     /// all matches.
-    /// Returns `None` for text searches, as only regex searches support this
-    /// option.
+    /// Returns `None` for text and structural searches, as only regex searches
+    /// support this option.
     pub fn one_match_per_line(&self) -> Option<bool> {
         match self {
             Self::Regex {
                 one_match_per_line, ..
             } => Some(*one_match_per_line),
-            Self::Text { .. } => None,
+            Self::Text { .. } | Self::Structural { .. } => None,
         }
     }
 }
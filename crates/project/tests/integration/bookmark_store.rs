@@ -0,0 +1,152 @@
+use fs::FakeFs;
+use gpui::{Entity, TestAppContext};
+use language::{Buffer, Point};
+use project::{Project, bookmark_store::BookmarkStore};
+use serde_json::json;
+use util::path;
+
+fn bookmark_rows(
+    bookmark_store: &Entity<BookmarkStore>,
+    cx: &TestAppContext,
+) -> Vec<(String, u32, Option<String>)> {
+    bookmark_store.read_with(cx, |bookmark_store, cx| {
+        bookmark_store
+            .all_bookmarks()
+            .map(|(path, buffer, bookmark)| {
+                (
+                    path.file_name().unwrap().to_string_lossy().into_owned(),
+                    buffer
+                        .read(cx)
+                        .summary_for_anchor::<Point>(&bookmark.position)
+                        .row,
+                    bookmark.label.as_ref().map(|label| label.to_string()),
+                )
+            })
+            .collect()
+    })
+}
+
+fn adjacent_row(
+    bookmark_store: &Entity<BookmarkStore>,
+    buffer: &Entity<Buffer>,
+    row: u32,
+    reverse: bool,
+    cx: &TestAppContext,
+) -> Option<(Entity<Buffer>, u32)> {
+    bookmark_store.read_with(cx, |bookmark_store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(row, 0));
+        let (buffer, bookmark) = if reverse {
+            bookmark_store.previous_bookmark(buffer, position, cx)?
+        } else {
+            bookmark_store.next_bookmark(buffer, position, cx)?
+        };
+        let row = buffer
+            .read(cx)
+            .summary_for_anchor::<Point>(&bookmark.position)
+            .row;
+        Some((buffer, row))
+    })
+}
+
+#[gpui::test]
+async fn test_toggling_and_navigating_bookmarks(cx: &mut TestAppContext) {
+    crate::init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({
+            "a.rs": "one\ntwo\nthree\nfour\n",
+            "b.rs": "five\nsix\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+    let buffer_a = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let buffer_b = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+
+    let toggle = |buffer: &Entity<Buffer>, point: Point, cx: &mut TestAppContext| {
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            let position = buffer.read(cx).anchor_before(point);
+            bookmark_store.toggle_bookmark(buffer.clone(), position, cx);
+        });
+    };
+    toggle(&buffer_a, Point::new(2, 3), cx);
+    toggle(&buffer_a, Point::new(0, 0), cx);
+    toggle(&buffer_b, Point::new(1, 1), cx);
+    assert_eq!(
+        bookmark_rows(&bookmark_store, cx),
+        vec![
+            ("a.rs".into(), 0, None),
+            ("a.rs".into(), 2, None),
+            ("b.rs".into(), 1, None),
+        ]
+    );
+
+    // Bookmarks follow their lines when the text above them changes.
+    buffer_a.update(cx, |buffer, cx| {
+        buffer.edit([(Point::new(1, 0)..Point::new(1, 0), "new\n")], None, cx);
+    });
+    assert_eq!(
+        bookmark_rows(&bookmark_store, cx),
+        vec![
+            ("a.rs".into(), 0, None),
+            ("a.rs".into(), 3, None),
+            ("b.rs".into(), 1, None),
+        ]
+    );
+
+    // Navigation continues in the next and previous files, and wraps around.
+    let next = adjacent_row(&bookmark_store, &buffer_a, 0, false, cx).unwrap();
+    assert_eq!((next.0 == buffer_a, next.1), (true, 3));
+    let next = adjacent_row(&bookmark_store, &buffer_a, 3, false, cx).unwrap();
+    assert_eq!((next.0 == buffer_b, next.1), (true, 1));
+    let next = adjacent_row(&bookmark_store, &buffer_b, 1, false, cx).unwrap();
+    assert_eq!((next.0 == buffer_a, next.1), (true, 0));
+    let previous = adjacent_row(&bookmark_store, &buffer_a, 0, true, cx).unwrap();
+    assert_eq!((previous.0 == buffer_b, previous.1), (true, 1));
+    let previous = adjacent_row(&bookmark_store, &buffer_a, 2, true, cx).unwrap();
+    assert_eq!((previous.0 == buffer_a, previous.1), (true, 0));
+
+    // Toggling anywhere on a bookmarked line removes its bookmark, and blank labels are dropped.
+    toggle(&buffer_a, Point::new(3, 4), cx);
+    let set_label = |label: &str, cx: &mut TestAppContext| {
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            let snapshot = buffer_b.read(cx).snapshot();
+            let position = bookmark_store
+                .bookmarks_in_buffer(&buffer_b, None, &snapshot)
+                .next()
+                .unwrap()
+                .position;
+            bookmark_store.set_label(&buffer_b, position, Some(label.to_string().into()), cx);
+        });
+    };
+    set_label("six", cx);
+    assert_eq!(
+        bookmark_rows(&bookmark_store, cx),
+        vec![
+            ("a.rs".into(), 0, None),
+            ("b.rs".into(), 1, Some("six".into()))
+        ]
+    );
+    set_label("  ", cx);
+    assert_eq!(
+        bookmark_rows(&bookmark_store, cx),
+        vec![("a.rs".into(), 0, None), ("b.rs".into(), 1, None)]
+    );
+
+    bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.clear_bookmarks(cx));
+    assert_eq!(bookmark_rows(&bookmark_store, cx), Vec::new());
+    assert!(adjacent_row(&bookmark_store, &buffer_a, 0, false, cx).is_none());
+}
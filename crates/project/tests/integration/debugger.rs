@@ -247,6 +247,219 @@ mod python_locator {
     }
 }
 
+mod cmake_locator {
+    use std::path::{Path, PathBuf};
+
+    use dap::{DapLocator, adapters::DebugAdapterName};
+    use project::debugger::locators::cmake::{CMakeBuildArgs, CMakeLocator, find_executable};
+    use serde_json::json;
+    use task::{BuildTaskDefinition, TaskTemplate};
+
+    fn cmake_task(args: &[&str]) -> TaskTemplate {
+        TaskTemplate {
+            label: "cmake build".into(),
+            command: "cmake".into(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            cwd: Some("$ZED_WORKTREE_ROOT".into()),
+            ..Default::default()
+        }
+    }
+
+    #[gpui::test]
+    async fn test_create_scenario_for_cmake_build() {
+        let locator = CMakeLocator;
+        let task = cmake_task(&["--build", "build", "--target", "server"]);
+
+        let scenario = locator
+            .create_scenario(&task, "cmake build", &DebugAdapterName("CodeLLDB".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            scenario.build,
+            Some(BuildTaskDefinition::Template {
+                task_template: task.clone(),
+                locator_name: Some(locator.name()),
+            })
+        );
+
+        let scenario = locator
+            .create_scenario(&task, "cmake build", &DebugAdapterName("Delve".into()))
+            .await;
+        assert!(scenario.is_none());
+
+        let configure = cmake_task(&["-S", ".", "-B", "build"]);
+        let scenario = locator
+            .create_scenario(
+                &configure,
+                "cmake configure",
+                &DebugAdapterName("GDB".into()),
+            )
+            .await;
+        assert!(scenario.is_none());
+    }
+
+    #[test]
+    fn test_parse_cmake_build_args() {
+        let args = [
+            "--build",
+            "out/debug",
+            "--config=Debug",
+            "-t",
+            "server",
+            "--",
+            "-j8",
+        ]
+        .map(String::from);
+        assert_eq!(
+            CMakeBuildArgs::parse(&args),
+            Some(CMakeBuildArgs {
+                build_dir: PathBuf::from("out/debug"),
+                target: Some("server".into()),
+                config: Some("Debug".into()),
+            })
+        );
+        assert_eq!(
+            CMakeBuildArgs::parse(&["--install".into(), "build".into()]),
+            None
+        );
+    }
+
+    fn write_reply(reply_dir: &Path, file_name: &str, contents: serde_json::Value) {
+        std::fs::write(reply_dir.join(file_name), contents.to_string()).unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_find_executable_in_cmake_reply() {
+        let build_dir = tempfile::tempdir().unwrap();
+        let reply_dir = build_dir.path().join(".cmake/api/v1/reply");
+        std::fs::create_dir_all(&reply_dir).unwrap();
+
+        write_reply(
+            &reply_dir,
+            "index-2024-01-01T00-00-00-0000.json",
+            json!({ "objects": [] }),
+        );
+        write_reply(
+            &reply_dir,
+            "index-2024-06-01T00-00-00-0000.json",
+            json!({
+                "objects": [
+                    { "kind": "cache", "version": { "major": 2, "minor": 0 }, "jsonFile": "cache-v2.json" },
+                    { "kind": "codemodel", "version": { "major": 2, "minor": 6 }, "jsonFile": "codemodel-v2.json" }
+                ]
+            }),
+        );
+        write_reply(
+            &reply_dir,
+            "codemodel-v2.json",
+            json!({
+                "configurations": [{
+                    "name": "Debug",
+                    "targets": [
+                        { "name": "server", "id": "server::@6890", "jsonFile": "target-server.json" },
+                        { "name": "client", "id": "client::@6890", "jsonFile": "target-client.json" },
+                        { "name": "common", "id": "common::@6890", "jsonFile": "target-common.json" }
+                    ]
+                }]
+            }),
+        );
+        write_reply(
+            &reply_dir,
+            "target-server.json",
+            json!({ "name": "server", "type": "EXECUTABLE", "artifacts": [{ "path": "services/server" }] }),
+        );
+        write_reply(
+            &reply_dir,
+            "target-client.json",
+            json!({ "name": "client", "type": "EXECUTABLE", "artifacts": [{ "path": "client" }] }),
+        );
+        write_reply(
+            &reply_dir,
+            "target-common.json",
+            json!({ "name": "common", "type": "STATIC_LIBRARY", "artifacts": [{ "path": "libcommon.a" }] }),
+        );
+
+        assert_eq!(
+            find_executable(build_dir.path(), Some("server"), None)
+                .await
+                .unwrap(),
+            build_dir.path().join("services/server")
+        );
+        assert_eq!(
+            find_executable(build_dir.path(), Some("client"), Some("debug"))
+                .await
+                .unwrap(),
+            build_dir.path().join("client")
+        );
+        assert!(
+            find_executable(build_dir.path(), Some("common"), None)
+                .await
+                .is_err()
+        );
+        // Without a target, the choice between several executables is ambiguous.
+        assert!(find_executable(build_dir.path(), None, None).await.is_err());
+        assert!(
+            find_executable(build_dir.path(), Some("server"), Some("Release"))
+                .await
+                .is_err()
+        );
+    }
+}
+
+mod make_locator {
+    use std::path::PathBuf;
+
+    use dap::{DapLocator, adapters::DebugAdapterName};
+    use project::debugger::locators::make::{MakeArgs, MakeLocator};
+    use task::TaskTemplate;
+
+    #[test]
+    fn test_parse_make_args() {
+        let parse = |args: &[&str]| {
+            MakeArgs::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            parse(&["-j", "8", "-C", "tools", "CFLAGS=-g", "server"]),
+            Some(MakeArgs {
+                directory: Some(PathBuf::from("tools")),
+                target: "server".into(),
+            })
+        );
+        assert_eq!(
+            parse(&["--directory=tools", "server"]),
+            Some(MakeArgs {
+                directory: Some(PathBuf::from("tools")),
+                target: "server".into(),
+            })
+        );
+        assert_eq!(parse(&[]), None);
+        assert_eq!(parse(&["clean"]), None);
+        assert_eq!(parse(&["server", "client"]), None);
+    }
+
+    #[gpui::test]
+    async fn test_create_scenario_for_make_target() {
+        let locator = MakeLocator;
+        let task = TaskTemplate {
+            label: "make server".into(),
+            command: "make".into(),
+            args: vec!["server".into()],
+            ..Default::default()
+        };
+
+        let scenario = locator
+            .create_scenario(&task, "make server", &DebugAdapterName("GDB".into()))
+            .await;
+        assert!(scenario.is_some_and(|scenario| scenario.build.is_some()));
+
+        let scenario = locator
+            .create_scenario(&task, "make server", &DebugAdapterName("Debugpy".into()))
+            .await;
+        assert!(scenario.is_none());
+    }
+}
+
 mod memory {
     use project::debugger::{
         MemoryCell,
@@ -291,3 +504,131 @@ mod memory {
         assert_eq!(actual, expected);
     }
 }
+
+mod breakpoint_export {
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use language::Point;
+    use project::{
+        Project,
+        debugger::breakpoint_store::{
+            Breakpoint, BreakpointEditAction, BreakpointState, BreakpointWithPosition,
+            ExportedBreakpoint,
+        },
+    };
+    use serde_json::json;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_export_and_import_breakpoints(cx: &mut TestAppContext) {
+        crate::init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "src": {
+                    "main.rs": "fn main() {\n    let x = 1;\n    println!(\"{x}\");\n}\n",
+                },
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/root/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let worktree_id = project.read_with(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        });
+        let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+
+        breakpoint_store.update(cx, |breakpoint_store, cx| {
+            let position = buffer.read(cx).anchor_before(Point::new(1, 0));
+            breakpoint_store.toggle_breakpoint(
+                buffer.clone(),
+                BreakpointWithPosition {
+                    position,
+                    bp: Breakpoint::new_standard(),
+                },
+                BreakpointEditAction::EditCondition("x > 0".into()),
+                cx,
+            );
+        });
+
+        let exported = breakpoint_store.read_with(cx, |breakpoint_store, cx| {
+            breakpoint_store.export_breakpoints(worktree_id, cx)
+        });
+        assert_eq!(
+            exported,
+            vec![ExportedBreakpoint {
+                worktree: None,
+                path: "src/main.rs".into(),
+                line: 2,
+                enabled: true,
+                condition: Some("x > 0".into()),
+                hit_condition: None,
+                log_message: None,
+            }]
+        );
+
+        // Importing replaces breakpoints on the same line and keeps the others.
+        breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.clear_breakpoints(cx);
+            let position = buffer.read(cx).anchor_before(Point::new(2, 0));
+            breakpoint_store.toggle_breakpoint(
+                buffer.clone(),
+                BreakpointWithPosition {
+                    position,
+                    bp: Breakpoint::new_standard(),
+                },
+                BreakpointEditAction::Toggle,
+                cx,
+            );
+        });
+        let imported = vec![
+            exported[0].clone(),
+            ExportedBreakpoint {
+                worktree: None,
+                path: "src/main.rs".into(),
+                line: 3,
+                enabled: false,
+                condition: None,
+                hit_condition: None,
+                log_message: Some("x is {x}".into()),
+            },
+        ];
+        breakpoint_store
+            .update(cx, |breakpoint_store, cx| {
+                breakpoint_store.import_breakpoints(worktree_id, imported, cx)
+            })
+            .await
+            .unwrap();
+
+        let breakpoints = breakpoint_store.read_with(cx, |breakpoint_store, cx| {
+            breakpoint_store.all_source_breakpoints(cx)
+        });
+        let mut breakpoints = breakpoints
+            .values()
+            .flatten()
+            .map(|breakpoint| {
+                (
+                    breakpoint.row,
+                    breakpoint.state,
+                    breakpoint.condition.as_deref().map(str::to_owned),
+                    breakpoint.message.as_deref().map(str::to_owned),
+                )
+            })
+            .collect::<Vec<_>>();
+        breakpoints.sort_by_key(|(row, ..)| *row);
+        assert_eq!(
+            breakpoints,
+            vec![
+                (1, BreakpointState::Enabled, Some("x > 0".into()), None),
+                (2, BreakpointState::Disabled, None, Some("x is {x}".into())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use fs::{FakeFs, Fs as _};
+use gpui::{Entity, TestAppContext};
+use language::LineEnding;
+use project::{
+    Project,
+    local_history_store::{LocalHistoryReason, LocalHistoryStore},
+};
+use serde_json::json;
+use settings::SettingsStore;
+use std::path::Path;
+use util::path;
+
+fn history_entries(
+    local_history: &Entity<LocalHistoryStore>,
+    abs_path: &str,
+    cx: &TestAppContext,
+) -> Vec<(u64, LocalHistoryReason)> {
+    local_history.read_with(cx, |local_history, _| {
+        local_history
+            .entries_for_path(Path::new(abs_path))
+            .map(|entry| (entry.id, entry.reason))
+            .collect()
+    })
+}
+
+async fn history_texts(
+    local_history: &Entity<LocalHistoryStore>,
+    abs_path: &str,
+    cx: &TestAppContext,
+) -> Vec<String> {
+    let mut texts = Vec::new();
+    for (id, _) in history_entries(local_history, abs_path, cx) {
+        let load = local_history.read_with(cx, |local_history, cx| local_history.load_text(id, cx));
+        texts.push(load.await.unwrap());
+    }
+    texts
+}
+
+#[gpui::test]
+async fn test_local_history_snapshots_on_save_and_reload(cx: &mut TestAppContext) {
+    crate::init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({ "a.txt": "one\n", "b.txt": "old\n" }),
+    )
+    .await;
+    let local_history = cx.update(|cx| LocalHistoryStore::init_global(fs.clone(), cx));
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/a.txt"), cx)
+        })
+        .await
+        .unwrap();
+
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "zero\n")], None, cx));
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        history_texts(&local_history, path!("/root/a.txt"), cx).await,
+        ["zero\none\n"]
+    );
+
+    // Saving unchanged contents doesn't add another snapshot.
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        history_entries(&local_history, path!("/root/a.txt"), cx).len(),
+        1
+    );
+
+    // The contents from before a reload were already saved, so they're not snapshotted again.
+    fs.save(
+        path!("/root/a.txt").as_ref(),
+        &"changed on disk\n".into(),
+        LineEnding::Unix,
+    )
+    .await
+    .unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "changed on disk\n"
+    );
+    assert_eq!(
+        history_entries(&local_history, path!("/root/a.txt"), cx)
+            .into_iter()
+            .map(|(_, reason)| reason)
+            .collect::<Vec<_>>(),
+        [LocalHistoryReason::Saved]
+    );
+
+    // Files that were never saved are snapshotted before they're reloaded.
+    let other_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/b.txt"), cx)
+        })
+        .await
+        .unwrap();
+    fs.save(
+        path!("/root/b.txt").as_ref(),
+        &"new\n".into(),
+        LineEnding::Unix,
+    )
+    .await
+    .unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        other_buffer.read_with(cx, |buffer, _| buffer.text()),
+        "new\n"
+    );
+    assert_eq!(
+        history_texts(&local_history, path!("/root/b.txt"), cx).await,
+        ["old\n"]
+    );
+    assert_eq!(
+        history_entries(&local_history, path!("/root/b.txt"), cx)[0].1,
+        LocalHistoryReason::BeforeReload
+    );
+
+    local_history
+        .update(cx, |local_history, cx| {
+            local_history.snapshot_buffer(&buffer, LocalHistoryReason::BeforeRestore, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        history_texts(&local_history, path!("/root/a.txt"), cx).await,
+        ["changed on disk\n", "zero\none\n"]
+    );
+
+    // Snapshots are reloaded from disk by a new store.
+    let reloaded_history =
+        cx.new(|cx| LocalHistoryStore::new(fs.clone(), paths::local_history_dir().clone(), cx));
+    cx.run_until_parked();
+    assert_eq!(
+        history_entries(&reloaded_history, path!("/root/a.txt"), cx),
+        history_entries(&local_history, path!("/root/a.txt"), cx)
+    );
+}
+
+#[gpui::test]
+async fn test_local_history_skips_private_files(cx: &mut TestAppContext) {
+    crate::init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/root"), json!({ ".env": "SECRET=1\n" }))
+        .await;
+    let local_history = cx.update(|cx| LocalHistoryStore::init_global(fs.clone(), cx));
+    let project = Project::test(fs.clone(), [path!("/root").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/.env"), cx)
+        })
+        .await
+        .unwrap();
+
+    buffer.update(cx, |buffer, cx| {
+        buffer.edit([(0..0, "TOKEN=2\n")], None, cx)
+    });
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    fs.save(
+        path!("/root/.env").as_ref(),
+        &"SECRET=3\n".into(),
+        LineEnding::Unix,
+    )
+    .await
+    .unwrap();
+    cx.run_until_parked();
+    assert_eq!(
+        buffer.read_with(cx, |buffer, _| buffer.text()),
+        "SECRET=3\n"
+    );
+
+    local_history
+        .update(cx, |local_history, cx| {
+            local_history.snapshot_buffer(&buffer, LocalHistoryReason::BeforeRestore, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(history_entries(&local_history, path!("/root/.env"), cx), []);
+}
+
+#[gpui::test]
+async fn test_local_history_pruning(cx: &mut TestAppContext) {
+    crate::init_test(cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |store, cx| {
+            store.update_user_settings(cx, |settings| {
+                let local_history = settings.local_history.get_or_insert_default();
+                local_history.max_entries_per_file = Some(2);
+                local_history.max_total_size_mb = Some(1);
+            });
+        });
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    let local_history =
+        cx.new(|cx| LocalHistoryStore::new(fs.clone(), path!("/history").into(), cx));
+    let snapshot = |abs_path: &'static str, text: String, cx: &mut TestAppContext| {
+        local_history.update(cx, |local_history, cx| {
+            local_history.snapshot_text(
+                Path::new(abs_path).into(),
+                text.as_str().into(),
+                LocalHistoryReason::Saved,
+                cx,
+            )
+        })
+    };
+
+    for text in ["one", "two", "three"] {
+        snapshot(path!("/root/a.txt"), text.to_string(), cx)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        history_texts(&local_history, path!("/root/a.txt"), cx).await,
+        ["three", "two"]
+    );
+    assert!(
+        !fs.is_file(Path::new(path!("/history/snapshots/0"))).await,
+        "pruned snapshots are removed from disk"
+    );
+
+    // Once the total size limit is exceeded, the oldest snapshots of any file are removed.
+    snapshot(path!("/root/b.txt"), "b".repeat(700 * 1024), cx)
+        .await
+        .unwrap();
+    snapshot(path!("/root/c.txt"), "c".repeat(500 * 1024), cx)
+        .await
+        .unwrap();
+    for (abs_path, expected_count) in [
+        (path!("/root/a.txt"), 0),
+        (path!("/root/b.txt"), 0),
+        (path!("/root/c.txt"), 1),
+    ] {
+        assert_eq!(
+            history_entries(&local_history, abs_path, cx).len(),
+            expected_count,
+            "unexpected snapshots for {abs_path}"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::format_collect)]
 
+mod bookmark_store;
 mod color_extractor;
 mod context_server_store;
 mod debugger;
@@ -7,6 +8,7 @@ mod ext_agent_tests;
 mod extension_agent_tests;
 mod git_store;
 mod image_store;
+mod local_history_store;
 mod lsp_command;
 mod lsp_store;
 mod manifest_tree;
@@ -59,7 +61,7 @@ use pretty_assertions::{assert_eq, assert_matches};
 use project::{
     Event, TaskContexts,
     git_store::{GitStoreEvent, Repository, RepositoryEvent, StatusEntry, pending_op},
-    search::{SearchQuery, SearchResult},
+    search::{SearchQuery, SearchResult, SearchScope},
     task_store::{TaskSettingsLocation, TaskStore},
     *,
 };
@@ -4158,6 +4160,241 @@ async fn test_definition(cx: &mut gpui::TestAppContext) {
     }
 }
 
+#[gpui::test]
+async fn test_call_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() {}",
+            "b.rs": "fn b() { a(); a() }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            capabilities: lsp::ServerCapabilities {
+                call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
+                ..lsp::ServerCapabilities::default()
+            },
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, path: &str, start: u32, end: u32| lsp::CallHierarchyItem {
+        name: name.to_string(),
+        kind: lsp::SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: lsp::Uri::from_file_path(path).unwrap(),
+        range: lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, end)),
+        selection_range: lsp::Range::new(lsp::Position::new(0, start), lsp::Position::new(0, 4)),
+        data: None,
+    };
+    let item_a = lsp_item("a", path!("/dir/a.rs"), 3, 9);
+    let item_b = lsp_item("b", path!("/dir/b.rs"), 3, 19);
+
+    fake_server.set_request_handler::<lsp::request::CallHierarchyPrepare, _, _>({
+        let item_a = item_a.clone();
+        move |params, _| {
+            let item_a = item_a.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 3)
+                );
+                Ok(Some(vec![item_a]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::CallHierarchyIncomingCalls, _, _>(
+        move |params, _| {
+            let item_b = item_b.clone();
+            async move {
+                assert_eq!(params.item.name, "a");
+                Ok(Some(vec![lsp::CallHierarchyIncomingCall {
+                    from: item_b,
+                    from_ranges: vec![
+                        lsp::Range::new(lsp::Position::new(0, 9), lsp::Position::new(0, 10)),
+                        lsp::Range::new(lsp::Position::new(0, 14), lsp::Position::new(0, 15)),
+                    ],
+                }]))
+            }
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_call_hierarchy(&buffer, 3, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "a");
+    assert_eq!(items[0].buffer, buffer);
+
+    let calls = project
+        .update(cx, |project, cx| project.incoming_calls(&items[0], cx))
+        .await
+        .unwrap();
+    assert_eq!(calls.len(), 1);
+    cx.update(|cx| {
+        let caller = &calls[0].item;
+        let caller_buffer = caller.buffer.read(cx);
+        assert_eq!(caller.name, "b");
+        assert_eq!(
+            caller_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/b.rs")),
+        );
+        assert_eq!(caller.selection_range.to_offset(caller_buffer), 3..4);
+        assert_eq!(
+            calls[0]
+                .call_sites
+                .iter()
+                .map(|range| range.to_offset(caller_buffer))
+                .collect::<Vec<_>>(),
+            [9..10, 14..15]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_type_hierarchy(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "trait A {}",
+            "b.rs": "struct B; impl A for B {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+    let mut fake_servers = language_registry.register_fake_lsp(
+        "Rust",
+        FakeLspAdapter {
+            // lsp-types has no field for the capability, so the initialize response is sent as JSON.
+            initializer: Some(Box::new(|fake_server| {
+                fake_server.set_request_handler::<lsp::InitializeRaw, _, _>(|_, _| async move {
+                    Ok(json!({ "capabilities": { "typeHierarchyProvider": true } }))
+                });
+            })),
+            ..FakeLspAdapter::default()
+        },
+    );
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer_with_lsp(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let fake_server = fake_servers.next().await.unwrap();
+    cx.executor().run_until_parked();
+
+    let lsp_item = |name: &str, kind: lsp::SymbolKind, path: &str, start: u32, end: u32| {
+        let range = lsp::Range::new(lsp::Position::new(0, 0), lsp::Position::new(0, end));
+        let selection_range = lsp::Range::new(
+            lsp::Position::new(0, start),
+            lsp::Position::new(0, start + 1),
+        );
+        serde_json::from_value::<lsp::TypeHierarchyItem>(json!({
+            "name": name,
+            "kind": kind,
+            "uri": lsp::Uri::from_file_path(path).unwrap(),
+            "range": range,
+            "selectionRange": selection_range,
+        }))
+        .unwrap()
+    };
+    let item_a = lsp_item("A", lsp::SymbolKind::INTERFACE, path!("/dir/a.rs"), 6, 10);
+    let item_b = lsp_item("B", lsp::SymbolKind::STRUCT, path!("/dir/b.rs"), 7, 9);
+
+    fake_server.set_request_handler::<lsp::request::TypeHierarchyPrepare, _, _>({
+        let item_a = item_a.clone();
+        move |params, _| {
+            let item_a = item_a.clone();
+            async move {
+                assert_eq!(
+                    params.text_document_position_params.position,
+                    lsp::Position::new(0, 6)
+                );
+                Ok(Some(vec![item_a]))
+            }
+        }
+    });
+    fake_server.set_request_handler::<lsp::request::TypeHierarchySubtypes, _, _>(
+        move |params, _| {
+            let item_b = item_b.clone();
+            async move {
+                assert_eq!(params.item.name, "A");
+                Ok(Some(vec![item_b]))
+            }
+        },
+    );
+
+    let items = project
+        .update(cx, |project, cx| {
+            project.prepare_type_hierarchy(&buffer, 6, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "A");
+    assert_eq!(items[0].buffer, buffer);
+
+    let subtypes = project
+        .update(cx, |project, cx| project.subtypes(&items[0], cx))
+        .await
+        .unwrap();
+    assert_eq!(subtypes.len(), 1);
+    cx.update(|cx| {
+        let subtype = &subtypes[0];
+        let subtype_buffer = subtype.buffer.read(cx);
+        assert_eq!(subtype.name, "B");
+        assert_eq!(
+            subtype_buffer
+                .file()
+                .unwrap()
+                .as_local()
+                .unwrap()
+                .abs_path(cx),
+            Path::new(path!("/dir/b.rs")),
+        );
+        assert_eq!(subtype.selection_range.to_offset(subtype_buffer), 7..8);
+    });
+}
+
 #[gpui::test]
 async fn test_completions_with_text_edit(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -7244,6 +7481,102 @@ async fn test_search_with_unicode(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_structural_search_in_unopened_files(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "fn one() {\n    call(1, 2);\n    // call(3, 4)\n}\n",
+            "two.rs": "fn two() {\n    call(5);\n}\n",
+            "three.rs": "fn three() {\n    call(two(), 6);\n}\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    project
+        .read_with(cx, |project, _| project.languages().clone())
+        .add(rust_lang());
+
+    assert_eq!(
+        search(
+            &project,
+            SearchQuery::structural(
+                "call($A, $B)",
+                false,
+                Default::default(),
+                Default::default(),
+                false,
+                None,
+            )
+            .unwrap(),
+            cx
+        )
+        .await
+        .unwrap(),
+        HashMap::from_iter([
+            (path!("dir/one.rs").to_string(), vec![15..25]),
+            (path!("dir/three.rs").to_string(), vec![17..31]),
+        ])
+    );
+}
+
+#[gpui::test]
+async fn test_scoped_search_in_unopened_files(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "// todo: one\nfn one() {}\n",
+            "two.rs": "fn todo() {}\n",
+            "three.rs": "fn three() {\n    todo(\"three\");\n} // todo\n",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    project
+        .read_with(cx, |project, _| project.languages().clone())
+        .add(rust_lang());
+
+    let query = SearchQuery::text(
+        "todo",
+        false,
+        true,
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        search(
+            &project,
+            query.clone().with_scope(SearchScope::Comments),
+            cx
+        )
+        .await
+        .unwrap(),
+        HashMap::from_iter([
+            (path!("dir/one.rs").to_string(), vec![3..7]),
+            (path!("dir/three.rs").to_string(), vec![37..41]),
+        ])
+    );
+    assert_eq!(
+        search(&project, query.with_scope(SearchScope::Code), cx)
+            .await
+            .unwrap(),
+        HashMap::from_iter([
+            (path!("dir/two.rs").to_string(), vec![3..7]),
+            (path!("dir/three.rs").to_string(), vec![17..21]),
+        ])
+    );
+}
+
 #[gpui::test]
 async fn test_create_entry(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -1,4 +1,4 @@
-use project::search::SearchQuery;
+use project::search::{SearchQuery, SearchScope};
 use text::Rope;
 use util::{
     paths::{PathMatcher, PathStyle},
@@ -154,3 +154,82 @@ async fn test_multiline_regex(cx: &mut gpui::TestAppContext) {
     let results = search_query.search(&snapshot, None).await;
     assert_eq!(results, vec![0..6, 12..18]);
 }
+
+#[gpui::test]
+async fn test_structural_search(cx: &mut gpui::TestAppContext) {
+    let search_query = SearchQuery::structural(
+        "call($A, $B)",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .expect("Should be able to create a structural SearchQuery")
+    .with_replacement("call($B, $A)".to_string());
+
+    use language::{Buffer, rust_lang};
+    let text = Rope::from("fn f() {\n    call(1, two);\n    call(3);\n    // call(4, 5)\n}\n");
+    let snapshot = cx
+        .update(|app| Buffer::build_snapshot(text, Some(rust_lang()), None, app))
+        .await;
+
+    let results = search_query.search(&snapshot, None).await;
+    assert_eq!(results, vec![13..25]);
+    assert_eq!(
+        search_query
+            .structural_replacement_for(&snapshot, results[0].clone())
+            .as_deref(),
+        Some("call(two, 1)")
+    );
+
+    // Ranges are relative to the searched subrange.
+    let results = search_query.search(&snapshot, Some(9..40)).await;
+    assert_eq!(results, vec![4..16]);
+}
+
+#[gpui::test]
+async fn test_search_scope(cx: &mut gpui::TestAppContext) {
+    let search_query = SearchQuery::text(
+        "todo",
+        false,
+        false,
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .expect("Should be able to create a text SearchQuery");
+
+    use language::{Buffer, rust_lang};
+    let text = Rope::from("fn f() {\n    // todo: x\n    let s = \"todo\";\n    todo();\n}\n");
+    let snapshot = cx
+        .update(|app| Buffer::build_snapshot(text, Some(rust_lang()), None, app))
+        .await;
+
+    let results = search_query.search(&snapshot, None).await;
+    assert_eq!(results, vec![16..20, 37..41, 48..52]);
+
+    let results = search_query
+        .clone()
+        .with_scope(SearchScope::Comments)
+        .search(&snapshot, None)
+        .await;
+    assert_eq!(results, vec![16..20]);
+
+    let results = search_query
+        .clone()
+        .with_scope(SearchScope::Strings)
+        .search(&snapshot, None)
+        .await;
+    assert_eq!(results, vec![37..41]);
+
+    let code_query = search_query.with_scope(SearchScope::Code);
+    let results = code_query.search(&snapshot, None).await;
+    assert_eq!(results, vec![37..41, 48..52]);
+
+    // Ranges are relative to the searched subrange.
+    let results = code_query.search(&snapshot, Some(24..snapshot.len())).await;
+    assert_eq!(results, vec![13..17, 24..28]);
+}
@@ -157,6 +157,18 @@ messages!(
     (LeaveRoom, Foreground),
     (LinkedEditingRange, Background),
     (LinkedEditingRangeResponse, Background),
+    (PrepareCallHierarchy, Background),
+    (PrepareCallHierarchyResponse, Background),
+    (GetIncomingCalls, Background),
+    (GetIncomingCallsResponse, Background),
+    (GetOutgoingCalls, Background),
+    (GetOutgoingCallsResponse, Background),
+    (PrepareTypeHierarchy, Background),
+    (PrepareTypeHierarchyResponse, Background),
+    (GetSupertypes, Background),
+    (GetSupertypesResponse, Background),
+    (GetSubtypes, Background),
+    (GetSubtypesResponse, Background),
     (ListRemoteDirectory, Background),
     (ListRemoteDirectoryResponse, Background),
     (ListToolchains, Foreground),
@@ -229,6 +241,10 @@ messages!(
     (GetDocumentColorResponse, Background),
     (GetColorPresentation, Background),
     (GetColorPresentationResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
     (GetFoldingRanges, Background),
     (GetFoldingRangesResponse, Background),
     (RefreshCodeLens, Background),
@@ -324,6 +340,17 @@ messages!(
     (GitDiffResponse, Background),
     (GitDiffStat, Background),
     (GitDiffStatResponse, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitRebaseCommand, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitBisectGetState, Background),
+    (GitBisectGetStateResponse, Background),
     (GitInit, Background),
     (GetDebugAdapterBinary, Background),
     (DebugAdapterBinary, Background),
@@ -412,6 +439,12 @@ request_messages!(
     (OpenUncommittedDiff, OpenUncommittedDiffResponse),
     (GetTypeDefinition, GetTypeDefinitionResponse),
     (LinkedEditingRange, LinkedEditingRangeResponse),
+    (PrepareCallHierarchy, PrepareCallHierarchyResponse),
+    (GetIncomingCalls, GetIncomingCallsResponse),
+    (GetOutgoingCalls, GetOutgoingCallsResponse),
+    (PrepareTypeHierarchy, PrepareTypeHierarchyResponse),
+    (GetSupertypes, GetSupertypesResponse),
+    (GetSubtypes, GetSubtypesResponse),
     (ListRemoteDirectory, ListRemoteDirectoryResponse),
     (GetUsers, UsersResponse),
     (IncomingCall, Ack),
@@ -461,6 +494,8 @@ request_messages!(
     (GetDocumentColor, GetDocumentColorResponse),
     (GetFoldingRanges, GetFoldingRangesResponse),
     (GetColorPresentation, GetColorPresentationResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
     (SaveBuffer, BufferSaved),
@@ -542,6 +577,15 @@ request_messages!(
     (CheckForPushedCommits, CheckForPushedCommitsResponse),
     (GitDiff, GitDiffResponse),
     (GitDiffStat, GitDiffStatResponse),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebaseInteractive, Ack),
+    (GitRebaseCommand, Ack),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitBisectStart, Ack),
+    (GitBisectMark, Ack),
+    (GitBisectReset, Ack),
+    (GitBisectGetState, GitBisectGetStateResponse),
     (GitInit, Ack),
     (ToggleBreakpoint, Ack),
     (GetDebugAdapterBinary, DebugAdapterBinary),
@@ -571,6 +615,7 @@ request_messages!(
 lsp_messages!(
     (GetReferences, GetReferencesResponse, true),
     (GetDocumentColor, GetDocumentColorResponse, true),
+    (GetDocumentLinks, GetDocumentLinksResponse, true),
     (GetFoldingRanges, GetFoldingRangesResponse, true),
     (GetDocumentSymbols, GetDocumentSymbolsResponse, true),
     (GetHover, GetHoverResponse, true),
@@ -600,12 +645,14 @@ entity_messages!(
     Commit,
     RunGitHook,
     GetColorPresentation,
+    ResolveDocumentLink,
     CopyProjectEntry,
     CreateBufferForPeer,
     CreateFileForPeer,
     CreateImageForPeer,
     CreateProjectEntry,
     GetDocumentColor,
+    GetDocumentLinks,
     GetFoldingRanges,
     DeleteProjectEntry,
     ExpandProjectEntry,
@@ -635,6 +682,12 @@ entity_messages!(
     SpawnKernel,
     KillKernel,
     LinkedEditingRange,
+    PrepareCallHierarchy,
+    GetIncomingCalls,
+    GetOutgoingCalls,
+    PrepareTypeHierarchy,
+    GetSupertypes,
+    GetSubtypes,
     LoadCommitDiff,
     LspQuery,
     LspQueryResponse,
@@ -731,6 +784,15 @@ entity_messages!(
     CheckForPushedCommits,
     GitDiff,
     GitDiffStat,
+    GitRebaseTodo,
+    GitRebaseInteractive,
+    GitRebaseCommand,
+    GitCherryPick,
+    GitRevert,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitBisectGetState,
     GitInit,
     BreakpointsForFile,
     ToggleBreakpoint,
@@ -941,6 +1003,7 @@ impl LspQuery {
             Some(lsp_query::Request::GetImplementation(_)) => ("GetImplementation", false),
             Some(lsp_query::Request::GetReferences(_)) => ("GetReferences", false),
             Some(lsp_query::Request::GetDocumentColor(_)) => ("GetDocumentColor", false),
+            Some(lsp_query::Request::GetDocumentLinks(_)) => ("GetDocumentLinks", false),
             Some(lsp_query::Request::GetFoldingRanges(_)) => ("GetFoldingRanges", false),
             Some(lsp_query::Request::GetDocumentSymbols(_)) => ("GetDocumentSymbols", false),
             Some(lsp_query::Request::InlayHints(_)) => ("InlayHints", false),
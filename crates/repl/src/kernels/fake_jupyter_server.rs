@@ -0,0 +1,382 @@
+//! An in-memory stand-in for a Jupyter server. It answers the REST API through a
+//! [`FakeHttpClient`] and serves kernel channels through [`KernelSocketConnector`],
+//! buffering messages for disconnected clients the way the real server does.
+
+use std::sync::Arc;
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use futures::{FutureExt as _, StreamExt as _, channel::mpsc, future::LocalBoxFuture};
+use http_client::{AsyncBody, FakeHttpClient, HttpClient, Request, Response};
+use jupyter_protocol::{JupyterMessage, JupyterMessageContent};
+use jupyter_websocket_client::RemoteServer;
+use parking_lot::Mutex;
+use serde_json::{Value, json};
+use smol::io::AsyncReadExt as _;
+
+use super::{KernelSocketConnector, KernelSocketSink, KernelSocketStream};
+
+pub const FAKE_SERVER_URL: &str = "http://localhost:8888";
+
+#[derive(Clone)]
+pub struct FakeJupyterServer {
+    state: Arc<Mutex<FakeServerState>>,
+}
+
+struct FakeServerState {
+    token: String,
+    kernelspecs: Vec<String>,
+    kernels: Vec<FakeKernel>,
+    sessions: Vec<FakeSession>,
+    next_id: usize,
+    next_connection_id: usize,
+    reachable: bool,
+    api_requests: Vec<String>,
+}
+
+struct FakeKernel {
+    id: String,
+    name: String,
+    clients: HashMap<String, FakeClient>,
+    /// Messages published while a client was disconnected, replayed once it reconnects.
+    buffered: HashMap<String, Vec<JupyterMessage>>,
+    received: Vec<JupyterMessage>,
+}
+
+struct FakeClient {
+    connection_id: usize,
+    tx: mpsc::UnboundedSender<Result<JupyterMessage>>,
+}
+
+struct FakeSession {
+    id: String,
+    name: String,
+    kernel_id: String,
+}
+
+impl FakeJupyterServer {
+    pub fn new(token: &str, kernelspecs: &[&str]) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(FakeServerState {
+                token: token.to_string(),
+                kernelspecs: kernelspecs.iter().map(ToString::to_string).collect(),
+                kernels: Vec::new(),
+                sessions: Vec::new(),
+                next_id: 1,
+                next_connection_id: 0,
+                reachable: true,
+                api_requests: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn remote_server(&self) -> RemoteServer {
+        RemoteServer {
+            base_url: FAKE_SERVER_URL.to_string(),
+            token: self.state.lock().token.clone(),
+        }
+    }
+
+    pub fn http_client(&self) -> Arc<dyn HttpClient> {
+        let state = self.state.clone();
+        FakeHttpClient::create(move |request| {
+            let state = state.clone();
+            async move { handle_api_request(&state, request).await }
+        })
+    }
+
+    /// Starts a kernel that isn't part of any session, returning its id.
+    pub fn start_kernel(&self, name: &str) -> String {
+        self.state.lock().start_kernel(name)
+    }
+
+    /// Starts a kernel in a new session, returning the kernel's id.
+    pub fn start_session(&self, session_name: &str, kernel_name: &str) -> String {
+        let mut state = self.state.lock();
+        let kernel_id = state.start_kernel(kernel_name);
+        let id = state.next_id();
+        state.sessions.push(FakeSession {
+            id,
+            name: session_name.to_string(),
+            kernel_id: kernel_id.clone(),
+        });
+        kernel_id
+    }
+
+    pub fn kernel_ids(&self) -> Vec<String> {
+        let state = self.state.lock();
+        state
+            .kernels
+            .iter()
+            .map(|kernel| kernel.id.clone())
+            .collect()
+    }
+
+    pub fn connected_clients(&self, kernel_id: &str) -> usize {
+        let state = self.state.lock();
+        state
+            .kernel(kernel_id)
+            .map_or(0, |kernel| kernel.clients.len())
+    }
+
+    /// The messages clients sent to the kernel.
+    pub fn received_messages(&self, kernel_id: &str) -> Vec<JupyterMessage> {
+        let state = self.state.lock();
+        state
+            .kernel(kernel_id)
+            .map(|kernel| kernel.received.clone())
+            .unwrap_or_default()
+    }
+
+    /// The REST requests made so far, such as `POST /api/kernels/1/interrupt`.
+    pub fn api_requests(&self) -> Vec<String> {
+        self.state.lock().api_requests.clone()
+    }
+
+    /// Sends a message from the kernel to every client that has connected to it.
+    pub fn publish(&self, kernel_id: &str, content: impl Into<JupyterMessageContent>) {
+        let message = JupyterMessage::new(content.into(), None);
+        let mut state = self.state.lock();
+        let Some(kernel) = state.kernel_mut(kernel_id) else {
+            return;
+        };
+        for client in kernel.clients.values() {
+            client.tx.unbounded_send(Ok(message.clone())).ok();
+        }
+        for messages in kernel.buffered.values_mut() {
+            messages.push(message.clone());
+        }
+    }
+
+    /// Drops the connections of every client of the kernel, as if the network went away.
+    pub fn drop_connections(&self, kernel_id: &str) {
+        let mut state = self.state.lock();
+        if let Some(kernel) = state.kernel_mut(kernel_id) {
+            for (session_id, client) in kernel.clients.drain() {
+                client.tx.close_channel();
+                kernel.buffered.entry(session_id).or_default();
+            }
+        }
+    }
+
+    /// Makes new connections to kernel channels fail, or succeed again.
+    pub fn set_reachable(&self, reachable: bool) {
+        self.state.lock().reachable = reachable;
+    }
+}
+
+impl FakeServerState {
+    fn next_id(&mut self) -> String {
+        let id = self.next_id;
+        self.next_id += 1;
+        id.to_string()
+    }
+
+    fn start_kernel(&mut self, name: &str) -> String {
+        let id = self.next_id();
+        self.kernels.push(FakeKernel {
+            id: id.clone(),
+            name: name.to_string(),
+            clients: HashMap::default(),
+            buffered: HashMap::default(),
+            received: Vec::new(),
+        });
+        id
+    }
+
+    fn kernel(&self, kernel_id: &str) -> Option<&FakeKernel> {
+        self.kernels.iter().find(|kernel| kernel.id == kernel_id)
+    }
+
+    fn kernel_mut(&mut self, kernel_id: &str) -> Option<&mut FakeKernel> {
+        self.kernels
+            .iter_mut()
+            .find(|kernel| kernel.id == kernel_id)
+    }
+
+    fn kernel_json(kernel: &FakeKernel) -> Value {
+        json!({
+            "id": kernel.id,
+            "name": kernel.name,
+            "last_activity": "2024-01-01T00:00:00.000000Z",
+            "execution_state": "idle",
+            "connections": kernel.clients.len(),
+        })
+    }
+}
+
+impl FakeJupyterServer {
+    fn connect_client(
+        &self,
+        remote_server: &RemoteServer,
+        kernel_id: &str,
+        session_id: &str,
+    ) -> Result<(KernelSocketSink, KernelSocketStream)> {
+        let mut state = self.state.lock();
+        anyhow::ensure!(state.reachable, "connection refused");
+        anyhow::ensure!(remote_server.token == state.token, "403 Forbidden");
+        let connection_id = state.next_connection_id;
+        state.next_connection_id += 1;
+        let kernel = state.kernel_mut(kernel_id).context("404 Not Found")?;
+
+        let (tx, rx) = mpsc::unbounded();
+        for message in kernel.buffered.remove(session_id).unwrap_or_default() {
+            tx.unbounded_send(Ok(message)).ok();
+        }
+        kernel
+            .clients
+            .insert(session_id.to_string(), FakeClient { connection_id, tx });
+
+        let state = self.state.clone();
+        let kernel_id = kernel_id.to_string();
+        let session_id = session_id.to_string();
+        let sink = futures::sink::unfold((), move |(), message: JupyterMessage| {
+            let mut state = state.lock();
+            let result = match state.kernel_mut(&kernel_id) {
+                Some(kernel)
+                    if kernel
+                        .clients
+                        .get(&session_id)
+                        .is_some_and(|client| client.connection_id == connection_id) =>
+                {
+                    kernel.received.push(message);
+                    Ok(())
+                }
+                _ => Err(anyhow::anyhow!("connection closed")),
+            };
+            async move { result }
+        });
+
+        Ok((Box::pin(sink) as KernelSocketSink, rx.boxed_local()))
+    }
+}
+
+impl KernelSocketConnector for FakeJupyterServer {
+    fn connect(
+        &self,
+        remote_server: &RemoteServer,
+        kernel_id: &str,
+        session_id: &str,
+    ) -> LocalBoxFuture<'static, Result<(KernelSocketSink, KernelSocketStream)>> {
+        let result = self.connect_client(remote_server, kernel_id, session_id);
+        async move { result }.boxed_local()
+    }
+}
+
+async fn handle_api_request(
+    state: &Mutex<FakeServerState>,
+    request: Request<AsyncBody>,
+) -> Result<Response<AsyncBody>> {
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let authorized = request
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value == format!("token {}", state.lock().token));
+
+    let mut body = Vec::new();
+    request.into_body().read_to_end(&mut body).await?;
+
+    let mut state = state.lock();
+    state.api_requests.push(format!("{method} {path}"));
+    if !authorized {
+        return respond(403, json!({ "message": "Forbidden" }));
+    }
+
+    let segments = path
+        .trim_start_matches("/api/")
+        .split('/')
+        .collect::<Vec<_>>();
+    match (method.as_str(), segments.as_slice()) {
+        ("GET", ["kernelspecs"]) => {
+            let kernelspecs = state
+                .kernelspecs
+                .iter()
+                .map(|name| {
+                    let spec = json!({
+                        "name": name,
+                        "spec": {
+                            "argv": ["python", "-m", "ipykernel_launcher", "-f", "{connection_file}"],
+                            "display_name": name,
+                            "language": "python",
+                            "interrupt_mode": "message",
+                            "env": {},
+                            "metadata": {},
+                        },
+                        "resources": {},
+                    });
+                    (name.clone(), spec)
+                })
+                .collect::<serde_json::Map<_, _>>();
+            respond(
+                200,
+                json!({ "default": state.kernelspecs.first(), "kernelspecs": kernelspecs }),
+            )
+        }
+        ("GET", ["kernels"]) => respond(
+            200,
+            Value::Array(
+                state
+                    .kernels
+                    .iter()
+                    .map(FakeServerState::kernel_json)
+                    .collect(),
+            ),
+        ),
+        ("POST", ["kernels"]) => {
+            let request: Value = serde_json::from_slice(&body)?;
+            let name = request["name"].as_str().unwrap_or_default();
+            if !state
+                .kernelspecs
+                .iter()
+                .any(|kernelspec| kernelspec == name)
+            {
+                return respond(404, json!({ "message": "No such kernel" }));
+            }
+            let id = state.start_kernel(name);
+            let kernel = state.kernel(&id).map(FakeServerState::kernel_json);
+            respond(201, json!(kernel))
+        }
+        ("DELETE", ["kernels", kernel_id]) => {
+            state.kernels.retain(|kernel| kernel.id != *kernel_id);
+            state
+                .sessions
+                .retain(|session| session.kernel_id != *kernel_id);
+            respond(204, Value::Null)
+        }
+        ("POST", ["kernels", kernel_id, "interrupt" | "restart"]) => {
+            match state.kernel(kernel_id).map(FakeServerState::kernel_json) {
+                Some(kernel) => respond(200, kernel),
+                None => respond(404, json!({ "message": "No such kernel" })),
+            }
+        }
+        ("GET", ["sessions"]) => {
+            let sessions = state
+                .sessions
+                .iter()
+                .filter_map(|session| {
+                    let kernel = state.kernel(&session.kernel_id)?;
+                    Some(json!({
+                        "id": session.id,
+                        "name": session.name,
+                        "path": session.name,
+                        "type": "notebook",
+                        "kernel": FakeServerState::kernel_json(kernel),
+                    }))
+                })
+                .collect();
+            respond(200, Value::Array(sessions))
+        }
+        _ => respond(404, json!({ "message": "Not Found" })),
+    }
+}
+
+fn respond(status: u16, body: Value) -> Result<Response<AsyncBody>> {
+    let body = if body.is_null() {
+        AsyncBody::default()
+    } else {
+        AsyncBody::from(body.to_string())
+    };
+    Ok(Response::builder().status(status).body(body)?)
+}
@@ -7,6 +7,8 @@ use language::LanguageName;
 use log;
 pub use native_kernel::*;
 
+#[cfg(test)]
+mod fake_jupyter_server;
 mod remote_kernels;
 use project::{Project, ProjectPath, Toolchains, WorktreeId};
 pub use remote_kernels::*;
@@ -260,7 +262,10 @@ impl KernelSpecification {
         SharedString::from(match self {
             Self::Jupyter(spec) => spec.path.to_string_lossy().into_owned(),
             Self::PythonEnv(spec) => spec.path.to_string_lossy().into_owned(),
-            Self::JupyterServer(spec) => spec.url.to_string(),
+            Self::JupyterServer(spec) => match spec.attachment_label() {
+                Some(label) => format!("{} ({label})", spec.url),
+                None => spec.url.to_string(),
+            },
             Self::SshRemote(spec) => spec.path.to_string(),
             Self::WslRemote(_) => "WSL".to_string(),
         })
@@ -587,6 +592,16 @@ pub trait RunningKernel: Send + Debug {
     fn kernel_info(&self) -> Option<&KernelInfoReply>;
     fn set_kernel_info(&mut self, info: KernelInfoReply);
     fn force_shutdown(&mut self, window: &mut Window, cx: &mut App) -> Task<anyhow::Result<()>>;
+    /// Interrupts the kernel out of band. Returns `None` for kernels that are interrupted
+    /// with an `interrupt_request` on the control channel.
+    fn interrupt(&mut self, _cx: &mut App) -> Option<Task<anyhow::Result<()>>> {
+        None
+    }
+    /// Restarts the kernel in place, keeping the connection to it. Returns `None` for kernels
+    /// that have to be shut down and started again.
+    fn restart(&mut self, _cx: &mut App) -> Option<Task<anyhow::Result<()>>> {
+        None
+    }
     fn kill(&mut self);
 }
 
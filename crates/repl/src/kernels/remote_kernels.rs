@@ -1,12 +1,17 @@
-use futures::{SinkExt as _, channel::mpsc};
-use gpui::{App, AppContext as _, Entity, Task, Window};
+use futures::{
+    FutureExt as _, Sink, SinkExt as _,
+    channel::mpsc,
+    future::LocalBoxFuture,
+    stream::{LocalBoxStream, StreamExt as _},
+};
+use gpui::{App, AppContext as _, AsyncWindowContext, Entity, Task, Window};
 use http_client::{AsyncBody, HttpClient, Request};
 use jupyter_protocol::{ExecutionState, JupyterKernelspec, JupyterMessage, KernelInfoReply};
 
 use async_tungstenite::tokio::connect_async;
 use async_tungstenite::tungstenite::{client::IntoClientRequest, http::HeaderValue};
 
-use futures::StreamExt;
+use serde::Deserialize;
 use smol::io::AsyncReadExt as _;
 
 use super::{KernelSession, RunningKernel};
@@ -15,7 +20,18 @@ use jupyter_websocket_client::{
     JupyterWebSocket, JupyterWebSocketReader, JupyterWebSocketWriter, KernelLaunchRequest,
     KernelSpecsResponse, RemoteServer,
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, pin::Pin, rc::Rc, sync::Arc, time::Duration};
+use uuid::Uuid;
+
+/// How long to wait before each attempt to reconnect to a kernel whose WebSocket dropped.
+const RECONNECT_DELAYS: &[Duration] = &[
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+    Duration::from_secs(16),
+    Duration::from_secs(30),
+];
 
 #[derive(Debug, Clone)]
 pub struct RemoteKernelSpecification {
@@ -23,6 +39,68 @@ pub struct RemoteKernelSpecification {
     pub url: String,
     pub token: String,
     pub kernelspec: JupyterKernelspec,
+    /// The running kernel to attach to, instead of launching a new one.
+    pub attach_to: Option<RemoteKernelAttachment>,
+}
+
+/// A kernel that is already running on a Jupyter server, possibly shared with other clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteKernelAttachment {
+    pub kernel_id: String,
+    /// The name of the session the kernel belongs to, if any.
+    pub session_name: Option<String>,
+}
+
+/// A kernel running on a Jupyter server, as listed by `/api/kernels`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RemoteKernel {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub execution_state: Option<String>,
+    #[serde(default)]
+    pub connections: Option<u32>,
+}
+
+/// A session on a Jupyter server, as listed by `/api/sessions`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RemoteSession {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub path: String,
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    pub kernel: RemoteKernel,
+}
+
+/// Sends an authenticated request to the server's REST API and returns the response body.
+async fn send_api_request(
+    remote_server: &RemoteServer,
+    http_client: &Arc<dyn HttpClient>,
+    method: &str,
+    path: &str,
+    body: AsyncBody,
+) -> Result<Vec<u8>> {
+    let request = Request::builder()
+        .method(method)
+        .uri(&remote_server.api_url(path))
+        .header("Authorization", format!("token {}", remote_server.token))
+        .body(body)?;
+
+    let response = http_client.send(request).await?;
+    let status = response.status();
+
+    let mut body = Vec::new();
+    response.into_body().read_to_end(&mut body).await?;
+
+    anyhow::ensure!(
+        status.is_success(),
+        "{method} {path} failed ({status}): {}",
+        String::from_utf8_lossy(&body)
+    );
+    Ok(body)
 }
 
 pub async fn launch_remote_kernel(
@@ -64,7 +142,7 @@ pub async fn launch_remote_kernel(
 }
 
 pub async fn list_remote_kernelspecs(
-    remote_server: RemoteServer,
+    remote_server: &RemoteServer,
     http_client: Arc<dyn HttpClient>,
 ) -> Result<Vec<RemoteKernelSpecification>> {
     let url = remote_server.api_url("/kernelspecs");
@@ -97,6 +175,7 @@ pub async fn list_remote_kernelspecs(
             url: remote_server.base_url.clone(),
             token: remote_server.token.clone(),
             kernelspec: spec.spec,
+            attach_to: None,
         })
         .collect::<Vec<RemoteKernelSpecification>>();
 
@@ -104,18 +183,206 @@ pub async fn list_remote_kernelspecs(
     Ok(remote_kernelspecs)
 }
 
+pub async fn list_remote_kernels(
+    remote_server: &RemoteServer,
+    http_client: Arc<dyn HttpClient>,
+) -> Result<Vec<RemoteKernel>> {
+    let body = send_api_request(
+        remote_server,
+        &http_client,
+        "GET",
+        "/kernels",
+        AsyncBody::default(),
+    )
+    .await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+pub async fn list_remote_sessions(
+    remote_server: &RemoteServer,
+    http_client: Arc<dyn HttpClient>,
+) -> Result<Vec<RemoteSession>> {
+    let body = send_api_request(
+        remote_server,
+        &http_client,
+        "GET",
+        "/sessions",
+        AsyncBody::default(),
+    )
+    .await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Lists the kernels already running on the server as specifications that attach to them,
+/// named after the sessions they belong to.
+///
+/// Kernels whose kernelspec isn't in `kernelspecs` are skipped.
+pub async fn list_running_remote_kernels(
+    remote_server: &RemoteServer,
+    http_client: Arc<dyn HttpClient>,
+    kernelspecs: &[RemoteKernelSpecification],
+) -> Result<Vec<RemoteKernelSpecification>> {
+    let sessions = list_remote_sessions(remote_server, http_client.clone()).await?;
+    let kernels = list_remote_kernels(remote_server, http_client).await?;
+
+    let mut running_kernels = sessions
+        .into_iter()
+        .map(|session| {
+            let session_name = if session.name.is_empty() {
+                session.path
+            } else {
+                session.name
+            };
+            (session.kernel, Some(session_name))
+        })
+        .collect::<Vec<_>>();
+    let kernels_without_session = kernels
+        .into_iter()
+        .filter(|kernel| {
+            !running_kernels
+                .iter()
+                .any(|(session_kernel, _)| session_kernel.id == kernel.id)
+        })
+        .map(|kernel| (kernel, None))
+        .collect::<Vec<_>>();
+    running_kernels.extend(kernels_without_session);
+
+    Ok(running_kernels
+        .into_iter()
+        .filter_map(|(kernel, session_name)| {
+            let spec = kernelspecs.iter().find(|spec| spec.name == kernel.name)?;
+            Some(RemoteKernelSpecification {
+                attach_to: Some(RemoteKernelAttachment {
+                    kernel_id: kernel.id,
+                    session_name,
+                }),
+                ..spec.clone()
+            })
+        })
+        .collect())
+}
+
+pub async fn interrupt_remote_kernel(
+    remote_server: &RemoteServer,
+    http_client: Arc<dyn HttpClient>,
+    kernel_id: &str,
+) -> Result<()> {
+    send_api_request(
+        remote_server,
+        &http_client,
+        "POST",
+        &format!("/kernels/{kernel_id}/interrupt"),
+        AsyncBody::default(),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Restarts the kernel in place. Its id stays the same, so connections to it remain valid.
+pub async fn restart_remote_kernel(
+    remote_server: &RemoteServer,
+    http_client: Arc<dyn HttpClient>,
+    kernel_id: &str,
+) -> Result<()> {
+    send_api_request(
+        remote_server,
+        &http_client,
+        "POST",
+        &format!("/kernels/{kernel_id}/restart"),
+        AsyncBody::default(),
+    )
+    .await?;
+    Ok(())
+}
+
+impl RemoteKernelSpecification {
+    /// Describes the running kernel this specification attaches to, if any.
+    pub fn attachment_label(&self) -> Option<String> {
+        let attachment = self.attach_to.as_ref()?;
+        Some(match &attachment.session_name {
+            Some(session_name) => format!("session {session_name}"),
+            None => format!(
+                "kernel {}",
+                attachment
+                    .kernel_id
+                    .get(..8)
+                    .unwrap_or(&attachment.kernel_id)
+            ),
+        })
+    }
+}
+
 impl PartialEq for RemoteKernelSpecification {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.url == other.url
+        self.name == other.name && self.url == other.url && self.attach_to == other.attach_to
     }
 }
 
 impl Eq for RemoteKernelSpecification {}
 
+pub type KernelSocketSink = Pin<Box<dyn Sink<JupyterMessage, Error = anyhow::Error>>>;
+pub type KernelSocketStream = LocalBoxStream<'static, Result<JupyterMessage>>;
+
+/// Opens connections to the channels of kernels running on a Jupyter server.
+pub trait KernelSocketConnector {
+    /// Connects to the kernel's channels as the client identified by `session_id`.
+    fn connect(
+        &self,
+        remote_server: &RemoteServer,
+        kernel_id: &str,
+        session_id: &str,
+    ) -> LocalBoxFuture<'static, Result<(KernelSocketSink, KernelSocketStream)>>;
+}
+
+/// Connects over the server's `/api/kernels/{id}/channels` WebSocket.
+pub struct WebSocketConnector;
+
+impl KernelSocketConnector for WebSocketConnector {
+    fn connect(
+        &self,
+        remote_server: &RemoteServer,
+        kernel_id: &str,
+        session_id: &str,
+    ) -> LocalBoxFuture<'static, Result<(KernelSocketSink, KernelSocketStream)>> {
+        // The server buffers messages for a `session_id` while no client is connected with it,
+        // and replays them once one reconnects.
+        let ws_url = format!(
+            "{}/api/kernels/{}/channels?session_id={}&token={}",
+            remote_server.base_url.replace("http", "ws"),
+            kernel_id,
+            session_id,
+            remote_server.token
+        );
+
+        async move {
+            let mut req: Request<()> = ws_url.into_client_request()?;
+            let headers = req.headers_mut();
+
+            headers.insert(
+                "User-Agent",
+                HeaderValue::from_str(&format!(
+                    "Zed/{} ({}; {})",
+                    "repl",
+                    std::env::consts::OS,
+                    std::env::consts::ARCH
+                ))?,
+            );
+
+            let (ws_stream, _response) = connect_async(req).await?;
+
+            let kernel_socket = JupyterWebSocket { inner: ws_stream };
+
+            let (w, r): (JupyterWebSocketWriter, JupyterWebSocketReader) = kernel_socket.split();
+
+            anyhow::Ok((Box::pin(w) as KernelSocketSink, r.boxed_local()))
+        }
+        .boxed_local()
+    }
+}
+
 pub struct RemoteRunningKernel {
-    remote_server: RemoteServer,
-    _receiving_task: Task<Result<()>>,
-    _routing_task: Task<Result<()>>,
+    remote_server: Arc<RemoteServer>,
+    _connection_task: Task<()>,
     http_client: Arc<dyn HttpClient>,
     pub working_directory: std::path::PathBuf,
     pub request_tx: mpsc::Sender<JupyterMessage>,
@@ -123,6 +390,9 @@ pub struct RemoteRunningKernel {
     pub execution_state: ExecutionState,
     pub kernel_info: Option<KernelInfoReply>,
     pub kernel_id: String,
+    /// Whether the kernel was already running. Such kernels may be in use by other clients,
+    /// so they are only disconnected from when shutting down.
+    attached: bool,
 }
 
 impl RemoteRunningKernel {
@@ -133,90 +403,85 @@ impl RemoteRunningKernel {
         window: &mut Window,
         cx: &mut App,
     ) -> Task<Result<Box<dyn RunningKernel>>> {
-        let remote_server = RemoteServer {
+        Self::with_connector(
+            kernelspec,
+            working_directory,
+            Rc::new(WebSocketConnector),
+            session,
+            window,
+            cx,
+        )
+    }
+
+    pub fn with_connector<S: KernelSession + 'static>(
+        kernelspec: RemoteKernelSpecification,
+        working_directory: std::path::PathBuf,
+        connector: Rc<dyn KernelSocketConnector>,
+        session: Entity<S>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Box<dyn RunningKernel>>> {
+        let remote_server = Arc::new(RemoteServer {
             base_url: kernelspec.url,
             token: kernelspec.token,
-        };
+        });
 
         let http_client = cx.http_client();
 
         window.spawn(cx, async move |cx| {
-            let kernel_id = launch_remote_kernel(
-                &remote_server,
-                http_client.clone(),
-                &kernelspec.name,
-                working_directory.to_str().unwrap_or_default(),
-            )
-            .await?;
-
-            let ws_url = format!(
-                "{}/api/kernels/{}/channels?token={}",
-                remote_server.base_url.replace("http", "ws"),
-                kernel_id,
-                remote_server.token
-            );
-
-            let mut req: Request<()> = ws_url.into_client_request()?;
-            let headers = req.headers_mut();
-
-            headers.insert(
-                "User-Agent",
-                HeaderValue::from_str(&format!(
-                    "Zed/{} ({}; {})",
-                    "repl",
-                    std::env::consts::OS,
-                    std::env::consts::ARCH
-                ))?,
-            );
-
-            let response = connect_async(req).await;
-
-            let (ws_stream, _response) = response?;
-
-            let kernel_socket = JupyterWebSocket { inner: ws_stream };
-
-            let (mut w, mut r): (JupyterWebSocketWriter, JupyterWebSocketReader) =
-                kernel_socket.split();
+            let attached = kernelspec.attach_to.is_some();
+            let kernel_id = match kernelspec.attach_to {
+                Some(attachment) => attachment.kernel_id,
+                None => {
+                    launch_remote_kernel(
+                        &remote_server,
+                        http_client.clone(),
+                        &kernelspec.name,
+                        working_directory.to_str().unwrap_or_default(),
+                    )
+                    .await?
+                }
+            };
 
-            let (request_tx, mut request_rx) =
-                futures::channel::mpsc::channel::<JupyterMessage>(100);
+            // Reconnecting with the same client session id lets the server replay
+            // the messages that were sent while the connection was down.
+            let client_session_id = Uuid::new_v4().to_string();
+            let socket = connector
+                .connect(&remote_server, &kernel_id, &client_session_id)
+                .await?;
 
-            let routing_task = cx.background_spawn({
-                async move {
-                    while let Some(message) = request_rx.next().await {
-                        w.send(message).await.ok();
-                    }
-                    Ok(())
-                }
-            });
+            let (request_tx, request_rx) = futures::channel::mpsc::channel::<JupyterMessage>(100);
 
-            let receiving_task = cx.spawn({
-                let session = session.clone();
+            let connection_task = cx.spawn({
+                let remote_server = remote_server.clone();
+                let kernel_id = kernel_id.clone();
 
                 async move |cx| {
-                    while let Some(message) = r.next().await {
-                        match message {
-                            Ok(message) => {
-                                session
-                                    .update_in(cx, |session, window, cx| {
-                                        session.route(&message, window, cx);
-                                    })
-                                    .ok();
-                            }
-                            Err(e) => {
-                                log::error!("Error receiving message: {:?}", e);
-                            }
-                        }
+                    let result = run_kernel_connection(
+                        session.clone(),
+                        socket,
+                        request_rx,
+                        connector,
+                        &remote_server,
+                        &kernel_id,
+                        &client_session_id,
+                        cx,
+                    )
+                    .await;
+
+                    if let Err(err) = result {
+                        session.update(cx, |session, cx| {
+                            session.kernel_errored(format!("{err:#}"), cx);
+                            cx.notify();
+                        });
                     }
-                    Ok(())
                 }
             });
 
             let stdin_tx = request_tx.clone();
 
             anyhow::Ok(Box::new(Self {
-                _routing_task: routing_task,
-                _receiving_task: receiving_task,
+                _connection_task: connection_task,
                 remote_server,
                 working_directory,
                 request_tx,
@@ -226,11 +491,99 @@ impl RemoteRunningKernel {
                 kernel_info: None,
                 kernel_id,
                 http_client: http_client.clone(),
+                attached,
             }) as Box<dyn RunningKernel>)
         })
     }
 }
 
+/// Routes messages between the session and the kernel until the kernel is killed,
+/// reconnecting whenever the connection drops.
+///
+/// The session's outputs are left as they are while reconnecting, so executions that were in
+/// flight keep appending to them once the server replays what was missed.
+async fn run_kernel_connection<S: KernelSession + 'static>(
+    session: Entity<S>,
+    (mut sink, mut stream): (KernelSocketSink, KernelSocketStream),
+    mut request_rx: mpsc::Receiver<JupyterMessage>,
+    connector: Rc<dyn KernelSocketConnector>,
+    remote_server: &RemoteServer,
+    kernel_id: &str,
+    client_session_id: &str,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let mut unsent = None;
+    loop {
+        let mut outgoing = unsent.take();
+        loop {
+            if let Some(message) = outgoing.take()
+                && let Err(err) = sink.send(message.clone()).await
+            {
+                log::warn!("kernel {kernel_id}: failed to send message: {err:?}");
+                unsent = Some(message);
+                break;
+            }
+
+            futures::select_biased! {
+                message = stream.next().fuse() => match message {
+                    Some(Ok(message)) => {
+                        session
+                            .update_in(cx, |session, window, cx| {
+                                session.route(&message, window, cx);
+                            })
+                            .ok();
+                    }
+                    Some(Err(e)) => {
+                        log::error!("Error receiving message: {:?}", e);
+                    }
+                    None => break,
+                },
+                message = request_rx.next() => match message {
+                    Some(message) => outgoing = Some(message),
+                    None => return Ok(()),
+                },
+            }
+        }
+
+        log::warn!("kernel {kernel_id}: connection lost, reconnecting");
+        (sink, stream) = reconnect(
+            connector.as_ref(),
+            remote_server,
+            kernel_id,
+            client_session_id,
+            cx,
+        )
+        .await?;
+    }
+}
+
+async fn reconnect(
+    connector: &dyn KernelSocketConnector,
+    remote_server: &RemoteServer,
+    kernel_id: &str,
+    client_session_id: &str,
+    cx: &mut AsyncWindowContext,
+) -> Result<(KernelSocketSink, KernelSocketStream)> {
+    let mut last_error = anyhow::anyhow!("no reconnection attempts");
+    for delay in RECONNECT_DELAYS {
+        cx.background_executor().timer(*delay).await;
+        match connector
+            .connect(remote_server, kernel_id, client_session_id)
+            .await
+        {
+            Ok(socket) => {
+                log::info!("kernel {kernel_id}: reconnected");
+                return Ok(socket);
+            }
+            Err(err) => {
+                log::warn!("kernel {kernel_id}: failed to reconnect: {err:?}");
+                last_error = err;
+            }
+        }
+    }
+    Err(last_error.context("Lost connection to the Jupyter server"))
+}
+
 impl Debug for RemoteRunningKernel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RemoteRunningKernel")
@@ -274,6 +627,11 @@ impl RunningKernel for RemoteRunningKernel {
     }
 
     fn force_shutdown(&mut self, window: &mut Window, cx: &mut App) -> Task<anyhow::Result<()>> {
+        if self.attached {
+            self.kill();
+            return Task::ready(Ok(()));
+        }
+
         let url = self
             .remote_server
             .api_url(&format!("/kernels/{}", self.kernel_id));
@@ -298,8 +656,271 @@ impl RunningKernel for RemoteRunningKernel {
         })
     }
 
+    fn interrupt(&mut self, cx: &mut App) -> Option<Task<Result<()>>> {
+        let remote_server = self.remote_server.clone();
+        let http_client = self.http_client.clone();
+        let kernel_id = self.kernel_id.clone();
+        Some(cx.background_spawn(async move {
+            interrupt_remote_kernel(&remote_server, http_client, &kernel_id).await
+        }))
+    }
+
+    fn restart(&mut self, cx: &mut App) -> Option<Task<Result<()>>> {
+        let remote_server = self.remote_server.clone();
+        let http_client = self.http_client.clone();
+        let kernel_id = self.kernel_id.clone();
+        Some(cx.background_spawn(async move {
+            restart_remote_kernel(&remote_server, http_client, &kernel_id).await
+        }))
+    }
+
     fn kill(&mut self) {
         self.request_tx.close_channel();
         self.stdin_tx.close_channel();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kernels::fake_jupyter_server::FakeJupyterServer;
+    use gpui::{Context, TestAppContext, VisualTestContext};
+    use jupyter_protocol::{ExecuteRequest, JupyterMessageContent, Stdio, StreamContent};
+    use std::path::PathBuf;
+
+    #[derive(Default)]
+    struct RecordingSession {
+        messages: Vec<JupyterMessage>,
+        errors: Vec<String>,
+    }
+
+    impl RecordingSession {
+        fn stream_text(&self) -> Vec<&str> {
+            self.messages
+                .iter()
+                .filter_map(|message| match &message.content {
+                    JupyterMessageContent::StreamContent(stream) => Some(stream.text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    impl KernelSession for RecordingSession {
+        fn route(&mut self, message: &JupyterMessage, _: &mut Window, _: &mut Context<Self>) {
+            self.messages.push(message.clone());
+        }
+
+        fn kernel_errored(&mut self, error_message: String, _: &mut Context<Self>) {
+            self.errors.push(error_message);
+        }
+    }
+
+    fn stream(text: &str) -> StreamContent {
+        StreamContent {
+            name: Stdio::Stdout,
+            text: text.to_string(),
+        }
+    }
+
+    fn execute_request(code: &str) -> JupyterMessage {
+        ExecuteRequest {
+            code: code.to_string(),
+            ..ExecuteRequest::default()
+        }
+        .into()
+    }
+
+    async fn attach(
+        server: &FakeJupyterServer,
+        kernel_id: &str,
+        cx: &mut VisualTestContext,
+    ) -> (Box<dyn RunningKernel>, Entity<RecordingSession>) {
+        let remote_server = server.remote_server();
+        let kernelspecs = list_remote_kernelspecs(&remote_server, server.http_client())
+            .await
+            .unwrap();
+        let spec = list_running_remote_kernels(&remote_server, server.http_client(), &kernelspecs)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|spec| {
+                spec.attach_to
+                    .as_ref()
+                    .is_some_and(|attachment| attachment.kernel_id == kernel_id)
+            })
+            .unwrap();
+
+        let session = cx.new(|_| RecordingSession::default());
+        let kernel = cx
+            .update(|window, cx| {
+                RemoteRunningKernel::with_connector(
+                    spec,
+                    PathBuf::new(),
+                    Rc::new(server.clone()),
+                    session.clone(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        (kernel, session)
+    }
+
+    #[gpui::test]
+    async fn test_list_running_remote_kernels(_cx: &mut TestAppContext) {
+        let server = FakeJupyterServer::new("secret", &["python3", "ir"]);
+        let session_kernel_id = server.start_session("analysis.ipynb", "python3");
+        let kernel_id = server.start_kernel("ir");
+        // Kernels without a known kernelspec can't be offered.
+        server.start_kernel("julia-1.10");
+
+        let remote_server = server.remote_server();
+        let kernelspecs = list_remote_kernelspecs(&remote_server, server.http_client())
+            .await
+            .unwrap();
+        assert_eq!(kernelspecs.len(), 2);
+        assert!(kernelspecs.iter().all(|spec| spec.attach_to.is_none()));
+
+        let running_kernels =
+            list_running_remote_kernels(&remote_server, server.http_client(), &kernelspecs)
+                .await
+                .unwrap();
+        assert_eq!(
+            running_kernels
+                .iter()
+                .map(|spec| (spec.name.as_str(), spec.attach_to.clone().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "python3",
+                    RemoteKernelAttachment {
+                        kernel_id: session_kernel_id,
+                        session_name: Some("analysis.ipynb".into()),
+                    }
+                ),
+                (
+                    "ir",
+                    RemoteKernelAttachment {
+                        kernel_id,
+                        session_name: None,
+                    }
+                ),
+            ]
+        );
+
+        let unauthorized = RemoteServer {
+            token: "wrong".into(),
+            ..server.remote_server()
+        };
+        let error = list_remote_sessions(&unauthorized, server.http_client())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("403"), "{error}");
+    }
+
+    #[gpui::test]
+    async fn test_attach_to_running_session(cx: &mut TestAppContext) {
+        let server = FakeJupyterServer::new("secret", &["python3"]);
+        let kernel_id = server.start_session("analysis.ipynb", "python3");
+        cx.update(|cx| cx.set_http_client(server.http_client()));
+        let cx = cx.add_empty_window();
+
+        let (mut kernel, session) = attach(&server, &kernel_id, cx).await;
+        assert_eq!(server.kernel_ids(), vec![kernel_id.clone()]);
+        assert_eq!(server.connected_clients(&kernel_id), 1);
+
+        server.publish(&kernel_id, stream("hello\n"));
+        kernel
+            .request_tx()
+            .try_send(execute_request("1 + 1"))
+            .unwrap();
+        cx.run_until_parked();
+        session.read_with(cx, |session, _| {
+            assert_eq!(session.stream_text(), ["hello\n"]);
+        });
+        let received = server.received_messages(&kernel_id);
+        assert!(
+            matches!(
+                received.as_slice(),
+                [message] if matches!(message.content, JupyterMessageContent::ExecuteRequest(_))
+            ),
+            "{received:?}"
+        );
+
+        cx.update(|_, cx| kernel.interrupt(cx))
+            .unwrap()
+            .await
+            .unwrap();
+        cx.update(|_, cx| kernel.restart(cx))
+            .unwrap()
+            .await
+            .unwrap();
+        let api_requests = server.api_requests();
+        assert!(api_requests.contains(&format!("POST /api/kernels/{kernel_id}/interrupt")));
+        assert!(api_requests.contains(&format!("POST /api/kernels/{kernel_id}/restart")));
+
+        // Kernels that were attached to may be in use by others, so they keep running.
+        cx.update(|window, cx| kernel.force_shutdown(window, cx))
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(server.kernel_ids(), vec![kernel_id.clone()]);
+        assert!(
+            !server
+                .api_requests()
+                .iter()
+                .any(|request| request.starts_with("DELETE"))
+        );
+        session.read_with(cx, |session, _| assert!(session.errors.is_empty()));
+    }
+
+    #[gpui::test]
+    async fn test_reconnect_after_connection_drops(cx: &mut TestAppContext) {
+        let server = FakeJupyterServer::new("secret", &["python3"]);
+        let kernel_id = server.start_kernel("python3");
+        cx.update(|cx| cx.set_http_client(server.http_client()));
+        let cx = cx.add_empty_window();
+
+        let (kernel, session) = attach(&server, &kernel_id, cx).await;
+        server.publish(&kernel_id, stream("before\n"));
+        cx.run_until_parked();
+
+        server.drop_connections(&kernel_id);
+        cx.run_until_parked();
+        assert_eq!(server.connected_clients(&kernel_id), 0);
+
+        // Output produced and requests made while disconnected arrive once reconnected.
+        server.publish(&kernel_id, stream("while disconnected\n"));
+        kernel
+            .request_tx()
+            .try_send(execute_request("print('again')"))
+            .unwrap();
+        cx.advance_clock(RECONNECT_DELAYS[0]);
+        cx.run_until_parked();
+        assert_eq!(server.connected_clients(&kernel_id), 1);
+        assert_eq!(server.received_messages(&kernel_id).len(), 1);
+        session.read_with(cx, |session, _| {
+            assert_eq!(session.stream_text(), ["before\n", "while disconnected\n"]);
+            assert!(session.errors.is_empty());
+        });
+
+        // Reconnecting is given up on when the server stays unreachable.
+        server.set_reachable(false);
+        server.drop_connections(&kernel_id);
+        cx.run_until_parked();
+        for delay in RECONNECT_DELAYS {
+            cx.advance_clock(*delay);
+            cx.run_until_parked();
+        }
+        session.read_with(cx, |session, _| {
+            assert_eq!(session.errors.len(), 1);
+            assert!(
+                session.errors[0].contains("Lost connection"),
+                "{:?}",
+                session.errors
+            );
+        });
+    }
+}
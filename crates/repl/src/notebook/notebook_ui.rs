@@ -501,6 +501,11 @@ impl NotebookEditor {
     fn restart_kernel(&mut self, _: &RestartKernel, window: &mut Window, cx: &mut Context<Self>) {
         if let Some(spec) = self.kernel_specification.clone() {
             if let Kernel::RunningKernel(kernel) = &mut self.kernel {
+                if let Some(restart) = kernel.restart(cx) {
+                    restart.detach_and_log_err(cx);
+                    cx.notify();
+                    return;
+                }
                 kernel.force_shutdown(window, cx).detach();
             }
 
@@ -517,7 +522,12 @@ impl NotebookEditor {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Kernel::RunningKernel(kernel) = &self.kernel {
+        if let Kernel::RunningKernel(kernel) = &mut self.kernel {
+            if let Some(interrupt) = kernel.interrupt(cx) {
+                interrupt.detach_and_log_err(cx);
+                cx.notify();
+                return;
+            }
             let interrupt_request = runtimelib::InterruptRequest {};
             let message: JupyterMessage = interrupt_request.into();
             kernel.request_tx().try_send(message).ok();
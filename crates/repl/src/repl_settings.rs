@@ -1,4 +1,4 @@
-use settings::{RegisterSetting, Settings};
+use settings::{JupyterServerContent, RegisterSetting, Settings};
 
 /// Settings for configuring REPL display and behavior.
 #[derive(Clone, Debug, RegisterSetting)]
@@ -27,6 +27,10 @@ pub struct ReplSettings {
     ///
     /// Default: 0
     pub output_max_height_lines: usize,
+    /// Jupyter servers whose kernels and sessions are offered in the kernel picker.
+    ///
+    /// Default: []
+    pub jupyter_servers: Vec<JupyterServerContent>,
 }
 
 impl Settings for ReplSettings {
@@ -39,6 +43,23 @@ impl Settings for ReplSettings {
             inline_output: repl.inline_output.unwrap_or(true),
             inline_output_max_length: repl.inline_output_max_length.unwrap_or(50),
             output_max_height_lines: repl.output_max_height_lines.unwrap_or(0),
+            jupyter_servers: repl.jupyter_servers.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl ReplSettings {
+    /// Warns about Jupyter servers configured with a `token`, which is no longer read.
+    #[allow(deprecated)]
+    pub(crate) fn warn_about_settings_tokens(&self) {
+        for server in &self.jupyter_servers {
+            if server.token.is_some() {
+                log::warn!(
+                    "ignoring the `token` of Jupyter server {}: set `token_env` to the name of an \
+                    environment variable holding the token instead",
+                    server.url
+                );
+            }
         }
     }
 }
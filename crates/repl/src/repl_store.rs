@@ -9,13 +9,13 @@ use jupyter_websocket_client::RemoteServer;
 use language::{Language, LanguageName};
 use project::{Fs, Project, ProjectPath, WorktreeId};
 use settings::{Settings, SettingsStore};
-use util::rel_path::RelPath;
+use util::{ResultExt as _, rel_path::RelPath};
 
 use crate::kernels::{
-    Kernel, list_remote_kernelspecs, local_kernel_specifications, python_env_kernel_specifications,
-    wsl_kernel_specifications,
+    Kernel, list_remote_kernelspecs, list_running_remote_kernels, local_kernel_specifications,
+    python_env_kernel_specifications, wsl_kernel_specifications,
 };
-use crate::{JupyterSettings, KernelSpecification, Session};
+use crate::{JupyterSettings, KernelSpecification, ReplSettings, Session};
 
 struct GlobalReplStore(Entity<ReplStore>);
 
@@ -52,9 +52,16 @@ impl ReplStore {
     }
 
     pub fn new(fs: Arc<dyn Fs>, cx: &mut Context<Self>) -> Self {
+        let mut jupyter_servers = ReplSettings::get_global(cx).jupyter_servers.clone();
         let subscriptions = vec![
             cx.observe_global::<SettingsStore>(move |this, cx| {
                 this.set_enabled(JupyterSettings::enabled(cx), cx);
+
+                let new_jupyter_servers = &ReplSettings::get_global(cx).jupyter_servers;
+                if *new_jupyter_servers != jupyter_servers {
+                    jupyter_servers = new_jupyter_servers.clone();
+                    this.refresh_kernelspecs(cx).detach();
+                }
             }),
             cx.on_app_quit(Self::shutdown_all_sessions),
         ];
@@ -182,29 +189,72 @@ impl ReplStore {
         &self,
         cx: &mut Context<Self>,
     ) -> Option<Task<Result<Vec<KernelSpecification>>>> {
-        match (
-            std::env::var("JUPYTER_SERVER"),
-            std::env::var("JUPYTER_TOKEN"),
-        ) {
-            (Ok(server), Ok(token)) => {
-                let remote_server = RemoteServer {
-                    base_url: server,
-                    token,
-                };
-                let http_client = cx.http_client();
-                Some(cx.spawn(async move |_, _| {
-                    list_remote_kernelspecs(remote_server, http_client)
+        let env_token = std::env::var("JUPYTER_TOKEN").ok();
+        let settings = ReplSettings::get_global(cx);
+        settings.warn_about_settings_tokens();
+        let mut remote_servers = settings
+            .jupyter_servers
+            .iter()
+            .map(|server| RemoteServer {
+                base_url: server.url.trim_end_matches('/').to_string(),
+                token: match &server.token_env {
+                    Some(token_env) => std::env::var(token_env).ok(),
+                    None => env_token.clone(),
+                }
+                .unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        if let (Ok(server), Some(token)) = (std::env::var("JUPYTER_SERVER"), env_token)
+            && !remote_servers
+                .iter()
+                .any(|remote_server| remote_server.base_url == server)
+        {
+            remote_servers.push(RemoteServer {
+                base_url: server,
+                token,
+            });
+        }
+        if remote_servers.is_empty() {
+            return None;
+        }
+
+        let http_client = cx.http_client();
+        Some(cx.spawn(async move |_, _| {
+            let mut specs = Vec::new();
+            for remote_server in remote_servers {
+                let kernelspecs =
+                    match list_remote_kernelspecs(&remote_server, http_client.clone()).await {
+                        Ok(kernelspecs) => kernelspecs,
+                        Err(err) => {
+                            log::error!(
+                                "failed to list kernels on {}: {err:#}",
+                                remote_server.base_url
+                            );
+                            continue;
+                        }
+                    };
+
+                // Kernels that are already running come first, so that joining a shared
+                // session is preferred over starting yet another kernel.
+                if let Some(running_kernels) =
+                    list_running_remote_kernels(&remote_server, http_client.clone(), &kernelspecs)
                         .await
-                        .map(|specs| {
-                            specs
-                                .into_iter()
-                                .map(KernelSpecification::JupyterServer)
-                                .collect()
-                        })
-                }))
+                        .log_err()
+                {
+                    specs.extend(
+                        running_kernels
+                            .into_iter()
+                            .map(KernelSpecification::JupyterServer),
+                    );
+                }
+                specs.extend(
+                    kernelspecs
+                        .into_iter()
+                        .map(KernelSpecification::JupyterServer),
+                );
             }
-            _ => None,
-        }
+            anyhow::Ok(specs)
+        }))
     }
 
     pub fn refresh_kernelspecs(&mut self, cx: &mut Context<Self>) -> Task<Result<()>> {
@@ -808,8 +808,12 @@ impl Session {
 
     pub fn interrupt(&mut self, cx: &mut Context<Self>) {
         match &mut self.kernel {
-            Kernel::RunningKernel(_kernel) => {
-                self.send(InterruptRequest {}.into(), cx).ok();
+            Kernel::RunningKernel(kernel) => {
+                if let Some(interrupt) = kernel.interrupt(cx) {
+                    interrupt.detach_and_log_err(cx);
+                } else {
+                    self.send(InterruptRequest {}.into(), cx).ok();
+                }
             }
             Kernel::StartingKernel(_task) => {
                 // NOTE: If we switch to a literal queue instead of chaining on to the task, clear all queued executions
@@ -878,6 +882,15 @@ impl Session {
                 // Do nothing if already restarting
             }
             Kernel::RunningKernel(mut kernel) => {
+                if let Some(restart) = kernel.restart(cx) {
+                    // The kernel restarts in place and reports its progress over the existing connection.
+                    restart.detach_and_log_err(cx);
+                    self.clear_outputs(cx);
+                    self.kernel = Kernel::RunningKernel(kernel);
+                    cx.notify();
+                    return;
+                }
+
                 let mut request_tx = kernel.request_tx();
 
                 let forced = kernel.force_shutdown(window, cx);
@@ -346,6 +346,9 @@ impl AnyProtoClient {
                             Response::GetDocumentColorResponse(response) => {
                                 to_any_envelope(&envelope, response)
                             }
+                            Response::GetDocumentLinksResponse(response) => {
+                                to_any_envelope(&envelope, response)
+                            }
                             Response::GetHoverResponse(response) => {
                                 to_any_envelope(&envelope, response)
                             }
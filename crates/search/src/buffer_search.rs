@@ -1,10 +1,12 @@
 mod registrar;
 
 use crate::{
-    FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOption,
-    SearchOptions, SearchSource, SelectAllMatches, SelectNextMatch, SelectPreviousMatch,
-    ToggleCaseSensitive, ToggleRegex, ToggleReplace, ToggleSelection, ToggleWholeWord,
+    CycleSearchScope, FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext,
+    SearchOption, SearchOptions, SearchSource, SelectAllMatches, SelectNextMatch,
+    SelectPreviousMatch, ToggleCaseSensitive, ToggleRegex, ToggleReplace, ToggleSelection,
+    ToggleWholeWord,
     buffer_search::registrar::WithResultsOrExternalQuery,
+    next_search_scope, render_search_scope_button,
     search_bar::{
         ActionButtonState, alignment_element, filter_search_results_input, input_base_styles,
         render_action_button, render_text_input,
@@ -24,7 +26,7 @@ use gpui::{
 };
 use language::{Language, LanguageRegistry};
 use project::{
-    search::SearchQuery,
+    search::{SearchQuery, SearchScope},
     search_history::{SearchHistory, SearchHistoryCursor},
 };
 
@@ -84,6 +86,7 @@ pub struct BufferSearchBar {
     search_options: SearchOptions,
     default_options: SearchOptions,
     configured_options: SearchOptions,
+    search_scope: SearchScope,
     query_error: Option<String>,
     dismissed: bool,
     search_history: SearchHistory,
@@ -280,6 +283,7 @@ impl Render for BufferSearchBar {
             replacement,
             selection,
             find_in_results,
+            scope,
         } = self.supported_options(cx);
 
         self.query_editor.update(cx, |query_editor, cx| {
@@ -368,6 +372,13 @@ impl Render for BufferSearchBar {
                             SearchSource::Buffer,
                             focus_handle.clone(),
                         ))
+                    })
+                    .when(scope, |div| {
+                        div.child(render_search_scope_button(
+                            self.search_scope,
+                            SearchSource::Buffer,
+                            focus_handle.clone(),
+                        ))
                     }),
             );
 
@@ -751,6 +762,11 @@ impl BufferSearchBar {
                 this.toggle_regex(action, window, cx);
             }
         }));
+        registrar.register_handler(ForDeployed(|this, _: &CycleSearchScope, window, cx| {
+            if this.supported_options(cx).scope {
+                this.cycle_search_scope(window, cx);
+            }
+        }));
         registrar.register_handler(ForDeployed(|this, action: &ToggleSelection, window, cx| {
             if this.supported_options(cx).selection {
                 this.toggle_selection(action, window, cx);
@@ -887,6 +903,7 @@ impl BufferSearchBar {
             default_options: search_options,
             configured_options: search_options,
             search_options,
+            search_scope: SearchScope::All,
             pending_search: None,
             query_error: None,
             dismissed: true,
@@ -1225,6 +1242,12 @@ impl BufferSearchBar {
         cx.notify();
     }
 
+    fn cycle_search_scope(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_scope = next_search_scope(self.search_scope);
+        drop(self.update_matches(false, false, window, cx));
+        cx.notify();
+    }
+
     pub fn has_search_option(&mut self, search_option: SearchOptions) -> bool {
         self.search_options.contains(search_option)
     }
@@ -1549,7 +1572,9 @@ impl BufferSearchBar {
                             false,
                             None,
                         ) {
-                            Ok(query) => query.with_replacement(self.replacement(cx)),
+                            Ok(query) => query
+                                .with_replacement(self.replacement(cx))
+                                .with_scope(self.search_scope),
                             Err(e) => {
                                 self.query_error = Some(e.to_string());
                                 self.clear_active_searchable_item_matches(window, cx);
@@ -1568,7 +1593,9 @@ impl BufferSearchBar {
                             false,
                             None,
                         ) {
-                            Ok(query) => query.with_replacement(self.replacement(cx)),
+                            Ok(query) => query
+                                .with_replacement(self.replacement(cx))
+                                .with_scope(self.search_scope),
                             Err(e) => {
                                 self.query_error = Some(e.to_string());
                                 self.clear_active_searchable_item_matches(window, cx);
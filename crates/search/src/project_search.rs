@@ -1,8 +1,10 @@
 use crate::{
-    BufferSearchBar, FocusSearch, HighlightKey, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll,
-    ReplaceNext, SearchOption, SearchOptions, SearchSource, SelectNextMatch, SelectPreviousMatch,
-    ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex, ToggleReplace, ToggleWholeWord,
+    BufferSearchBar, CycleSearchScope, FocusSearch, HighlightKey, NextHistoryQuery,
+    PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOption, SearchOptions, SearchSource,
+    SelectNextMatch, SelectPreviousMatch, ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex,
+    ToggleReplace, ToggleStructural, ToggleWholeWord,
     buffer_search::Deploy,
+    next_search_scope, render_search_scope_button,
     search_bar::{
         ActionButtonState, alignment_element, input_base_styles, render_action_button,
         render_text_input,
@@ -29,7 +31,7 @@ use language::{Buffer, Language};
 use menu::Confirm;
 use project::{
     Project, ProjectPath, SearchResults,
-    search::{SearchInputKind, SearchQuery},
+    search::{SearchInputKind, SearchQuery, SearchScope},
     search_history::SearchHistoryCursor,
 };
 use settings::Settings;
@@ -129,6 +131,18 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, window, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, window, cx);
         });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ToggleStructural, window, cx| {
+                search_bar.toggle_search_option(SearchOptions::STRUCTURAL, window, cx);
+            },
+        );
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &CycleSearchScope, window, cx| {
+                search_bar.cycle_search_scope(window, cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
@@ -257,6 +271,7 @@ pub struct ProjectSearchView {
     replacement_editor: Entity<Editor>,
     results_editor: Entity<Editor>,
     search_options: SearchOptions,
+    search_scope: SearchScope,
     panels_with_errors: HashMap<InputPanel, String>,
     active_match_index: Option<usize>,
     search_id: usize,
@@ -272,6 +287,7 @@ pub struct ProjectSearchView {
 #[derive(Debug, Clone)]
 pub struct ProjectSearchSettings {
     search_options: SearchOptions,
+    search_scope: SearchScope,
     filters_enabled: bool,
 }
 
@@ -711,12 +727,13 @@ impl ProjectSearchView {
     fn current_settings(&self) -> ProjectSearchSettings {
         ProjectSearchSettings {
             search_options: self.search_options,
+            search_scope: self.search_scope,
             filters_enabled: self.filters_enabled,
         }
     }
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut Context<Self>) {
-        self.search_options.toggle(option);
+        self.search_options.toggle_exclusive(option);
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
                 self.entity.read(cx).project.downgrade(),
@@ -726,6 +743,16 @@ impl ProjectSearchView {
         self.adjust_query_regex_language(cx);
     }
 
+    fn cycle_search_scope(&mut self, cx: &mut Context<Self>) {
+        self.search_scope = next_search_scope(self.search_scope);
+        ActiveSettings::update_global(cx, |settings, cx| {
+            settings.0.insert(
+                self.entity.read(cx).project.downgrade(),
+                self.current_settings(),
+            );
+        });
+    }
+
     fn toggle_opened_only(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
         self.included_opened_only = !self.included_opened_only;
     }
@@ -835,12 +862,16 @@ impl ProjectSearchView {
         let mut subscriptions = Vec::new();
 
         // Read in settings if available
-        let (mut options, filters_enabled) = if let Some(settings) = settings {
-            (settings.search_options, settings.filters_enabled)
+        let (mut options, mut scope, filters_enabled) = if let Some(settings) = settings {
+            (
+                settings.search_options,
+                settings.search_scope,
+                settings.filters_enabled,
+            )
         } else {
             let search_options =
                 SearchOptions::from_settings(&EditorSettings::get_global(cx).search);
-            (search_options, false)
+            (search_options, SearchScope::All, false)
         };
 
         {
@@ -851,6 +882,7 @@ impl ProjectSearchView {
                 query_text = active_query.as_str().to_string();
                 replacement_text = active_query.replacement().map(ToOwned::to_owned);
                 options = SearchOptions::from_query(active_query);
+                scope = active_query.scope();
             }
         }
         subscriptions.push(cx.observe_in(&entity, window, |this, _, window, cx| {
@@ -975,6 +1007,7 @@ impl ProjectSearchView {
             query_editor,
             results_editor,
             search_options: options,
+            search_scope: scope,
             panels_with_errors: HashMap::default(),
             active_match_index: None,
             included_files_editor,
@@ -1053,6 +1086,7 @@ impl ProjectSearchView {
                         editor.set_text(old_query.as_str(), window, cx);
                     });
                     search_view.search_options = SearchOptions::from_query(&old_query);
+                    search_view.search_scope = old_query.scope();
                     search_view.adjust_query_regex_language(cx);
                 }
                 new_query
@@ -1308,7 +1342,35 @@ impl ProjectSearchView {
             .count()
             > 1;
 
-        let query = if self.search_options.contains(SearchOptions::REGEX) {
+        let query = if self.search_options.contains(SearchOptions::STRUCTURAL) {
+            match SearchQuery::structural(
+                text,
+                self.search_options.contains(SearchOptions::INCLUDE_IGNORED),
+                included_files,
+                excluded_files,
+                match_full_paths,
+                open_buffers,
+            ) {
+                Ok(query) => {
+                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
+                    if should_unmark_error.is_some() {
+                        cx.notify();
+                    }
+
+                    Some(query)
+                }
+                Err(e) => {
+                    let should_mark_error = self
+                        .panels_with_errors
+                        .insert(InputPanel::Query, e.to_string());
+                    if should_mark_error.is_none() {
+                        cx.notify();
+                    }
+
+                    None
+                }
+            }
+        } else if self.search_options.contains(SearchOptions::REGEX) {
             match SearchQuery::regex(
                 text,
                 self.search_options.contains(SearchOptions::WHOLE_WORD),
@@ -1377,7 +1439,7 @@ impl ProjectSearchView {
         if query.as_ref().is_some_and(|query| query.is_empty()) {
             return None;
         }
-        query
+        query.map(|query| query.with_scope(self.search_scope))
     }
 
     fn open_buffers(&self, cx: &App, workspace: &Workspace) -> Vec<Entity<Buffer>> {
@@ -1794,6 +1856,28 @@ impl ProjectSearchBar {
         option: SearchOptions,
         window: &mut Window,
         cx: &mut Context<Self>,
+    ) -> bool {
+        self.update_search_view_and_search(window, cx, move |search_view, cx| {
+            search_view.toggle_search_option(option, cx)
+        })
+    }
+
+    pub(crate) fn cycle_search_scope(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.update_search_view_and_search(window, cx, |search_view, cx| {
+            search_view.cycle_search_scope(cx)
+        })
+    }
+
+    /// Updates the active search view's options, then searches again if it had searched before.
+    fn update_search_view_and_search(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut ProjectSearchView, &mut Context<ProjectSearchView>) + 'static,
     ) -> bool {
         if self.active_project_search.is_none() {
             return false;
@@ -1803,7 +1887,7 @@ impl ProjectSearchBar {
             let task = this.update_in(cx, |this, window, cx| {
                 let search_view = this.active_project_search.as_ref()?;
                 search_view.update(cx, |search_view, cx| {
-                    search_view.toggle_search_option(option, cx);
+                    update(search_view, cx);
                     search_view
                         .entity
                         .read(cx)
@@ -2104,6 +2188,16 @@ impl Render for ProjectSearchBar {
                         search.search_options,
                         SearchSource::Project(cx),
                         focus_handle.clone(),
+                    ))
+                    .child(SearchOption::Structural.as_button(
+                        search.search_options,
+                        SearchSource::Project(cx),
+                        focus_handle.clone(),
+                    ))
+                    .child(render_search_scope_button(
+                        search.search_scope,
+                        SearchSource::Project(cx),
+                        focus_handle.clone(),
                     )),
             );
 
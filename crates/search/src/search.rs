@@ -3,7 +3,7 @@ pub use buffer_search::BufferSearchBar;
 pub use editor::HighlightKey;
 use editor::SearchSettings;
 use gpui::{Action, App, ClickEvent, FocusHandle, IntoElement, actions};
-use project::search::SearchQuery;
+use project::search::{SearchQuery, SearchScope};
 pub use project_search::ProjectSearchView;
 use ui::{ButtonStyle, IconButton, IconButtonShape};
 use ui::{Tooltip, prelude::*};
@@ -37,6 +37,11 @@ actions!(
         ToggleCaseSensitive,
         /// Toggles regular expression mode.
         ToggleRegex,
+        /// Toggles structural search, which matches tree-sitter queries and code patterns
+        /// against syntax trees.
+        ToggleStructural,
+        /// Cycles between searching everywhere, and only in code, comments or strings.
+        CycleSearchScope,
         /// Toggles the replace interface.
         ToggleReplace,
         /// Toggles searching within selection only.
@@ -71,6 +76,7 @@ bitflags! {
         const ONE_MATCH_PER_LINE = 1 << SearchOption::OneMatchPerLine as u8;
         /// If set, reverse direction when finding the active match
         const BACKWARDS = 1 << SearchOption::Backwards as u8;
+        const STRUCTURAL = 1 << SearchOption::Structural as u8;
     }
 }
 
@@ -83,6 +89,7 @@ pub enum SearchOption {
     Regex,
     OneMatchPerLine,
     Backwards,
+    Structural,
 }
 
 pub(crate) enum SearchSource<'a, 'b> {
@@ -103,6 +110,7 @@ impl SearchOption {
             SearchOption::Regex => "Use Regular Expressions",
             SearchOption::OneMatchPerLine => "One Match Per Line",
             SearchOption::Backwards => "Search Backwards",
+            SearchOption::Structural => "Use Structural Search",
         }
     }
 
@@ -112,6 +120,7 @@ impl SearchOption {
             SearchOption::CaseSensitive => ui::IconName::CaseSensitive,
             SearchOption::IncludeIgnored => ui::IconName::Sliders,
             SearchOption::Regex => ui::IconName::Regex,
+            SearchOption::Structural => ui::IconName::ListTree,
             _ => panic!("{self:?} is not a named SearchOption"),
         }
     }
@@ -122,6 +131,7 @@ impl SearchOption {
             SearchOption::CaseSensitive => &ToggleCaseSensitive,
             SearchOption::IncludeIgnored => &ToggleIncludeIgnored,
             SearchOption::Regex => &ToggleRegex,
+            SearchOption::Structural => &ToggleStructural,
             _ => panic!("{self:?} is not a toggle action"),
         }
     }
@@ -173,6 +183,7 @@ impl SearchOptions {
         options.set(SearchOptions::CASE_SENSITIVE, query.case_sensitive());
         options.set(SearchOptions::INCLUDE_IGNORED, query.include_ignored());
         options.set(SearchOptions::REGEX, query.is_regex());
+        options.set(SearchOptions::STRUCTURAL, query.is_structural());
         options
     }
 
@@ -184,6 +195,65 @@ impl SearchOptions {
         options.set(SearchOptions::REGEX, settings.regex);
         options
     }
+
+    /// Toggles `option`, turning off the options it can't be combined with.
+    pub(crate) fn toggle_exclusive(&mut self, option: SearchOptions) {
+        self.toggle(option);
+        if option.contains(SearchOptions::STRUCTURAL) && self.contains(SearchOptions::STRUCTURAL) {
+            self.remove(SearchOptions::REGEX);
+        } else if option.contains(SearchOptions::REGEX) && self.contains(SearchOptions::REGEX) {
+            self.remove(SearchOptions::STRUCTURAL);
+        }
+    }
+}
+
+pub(crate) fn next_search_scope(scope: SearchScope) -> SearchScope {
+    match scope {
+        SearchScope::All => SearchScope::Code,
+        SearchScope::Code => SearchScope::Comments,
+        SearchScope::Comments => SearchScope::Strings,
+        SearchScope::Strings => SearchScope::All,
+    }
+}
+
+pub(crate) fn render_search_scope_button(
+    scope: SearchScope,
+    search_source: SearchSource,
+    focus_handle: FocusHandle,
+) -> impl IntoElement {
+    let label = match scope {
+        SearchScope::All => "Search Everywhere",
+        SearchScope::Code => "Search Only Code",
+        SearchScope::Comments => "Search Only Comments",
+        SearchScope::Strings => "Search Only Strings",
+    };
+    IconButton::new(
+        (
+            "search-scope",
+            matches!(search_source, SearchSource::Buffer) as u32,
+        ),
+        ui::IconName::Code,
+    )
+    .map(|button| match search_source {
+        SearchSource::Buffer => {
+            let focus_handle = focus_handle.clone();
+            button.on_click(move |_: &ClickEvent, window, cx| {
+                if !focus_handle.is_focused(window) {
+                    window.focus(&focus_handle, cx);
+                }
+                window.dispatch_action(CycleSearchScope.boxed_clone(), cx);
+            })
+        }
+        SearchSource::Project(cx) => {
+            button.on_click(cx.listener(|this, _: &ClickEvent, window, cx| {
+                this.cycle_search_scope(window, cx);
+            }))
+        }
+    })
+    .style(ButtonStyle::Subtle)
+    .shape(IconButtonShape::Square)
+    .toggle_state(scope != SearchScope::All)
+    .tooltip(move |_window, cx| Tooltip::for_action_in(label, &CycleSearchScope, &focus_handle, cx))
 }
 
 pub(crate) fn show_no_more_matches(window: &mut Window, cx: &mut App) {
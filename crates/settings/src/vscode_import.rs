@@ -177,8 +177,10 @@ impl VsCodeSettings {
             audio: None,
             auto_update: None,
             base_keymap: Some(BaseKeymapContent::VSCode),
+            bookmarks_panel: None,
             calls: None,
             collaboration_panel: None,
+            csv_preview: None,
             debugger: None,
             diagnostics: None,
             editor: self.editor_settings_content(),
@@ -191,11 +193,14 @@ impl VsCodeSettings {
                 ..GlobalLspSettingsContent::default()
             }),
             helix_mode: None,
+            hierarchy_panel: None,
             image_viewer: None,
             journal: None,
             language_models: None,
             line_indicator_format: None,
+            local_history: None,
             log: None,
+            macros: None,
             message_editor: None,
             node: self.node_binary_settings(),
             notification_panel: None,
@@ -326,6 +331,7 @@ impl VsCodeSettings {
             min_line_number_digits: None,
             runnables: None,
             breakpoints: None,
+            bookmarks: None,
             folds: self.read_enum("editor.showFoldingControls", |s| match s {
                 "always" | "mouseover" => Some(true),
                 "never" => Some(false),
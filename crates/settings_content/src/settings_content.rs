@@ -125,9 +125,14 @@ pub struct SettingsContent {
     /// Default: VSCode
     pub base_keymap: Option<BaseKeymapContent>,
 
+    pub bookmarks_panel: Option<BookmarksPanelSettingsContent>,
+
     /// Configuration for the collab panel visual settings.
     pub collaboration_panel: Option<PanelSettingsContent>,
 
+    /// The settings for the preview of CSV and other tabular files.
+    pub csv_preview: Option<CsvPreviewSettingsContent>,
+
     pub debugger: Option<DebuggerSettingsContent>,
 
     /// Configuration for Diagnostics-related features.
@@ -139,6 +144,8 @@ pub struct SettingsContent {
     /// Common language server settings.
     pub global_lsp_settings: Option<GlobalLspSettingsContent>,
 
+    pub hierarchy_panel: Option<HierarchyPanelSettingsContent>,
+
     /// The settings for the image viewer.
     pub image_viewer: Option<ImageViewerSettingsContent>,
 
@@ -151,12 +158,20 @@ pub struct SettingsContent {
 
     pub journal: Option<JournalSettingsContent>,
 
+    /// Configuration for the local history of files.
+    pub local_history: Option<LocalHistorySettingsContent>,
+
     /// A map of log scopes to the desired log level.
     /// Useful for filtering out noisy logs or enabling more verbose logging.
     ///
     /// Example: {"log": {"client": "warn"}}
     pub log: Option<HashMap<String, String>>,
 
+    /// Named editor macros, which are replayed with the `editor_macros::Replay` action.
+    ///
+    /// Example: {"macros": {"bullet": [{"text": "- "}, {"action": "editor::MoveDown"}]}}
+    pub macros: Option<HashMap<String, Vec<MacroStepContent>>>,
+
     pub line_indicator_format: Option<LineIndicatorFormat>,
 
     pub language_models: Option<AllLanguageModelSettingsContent>,
@@ -897,6 +912,37 @@ pub struct JournalSettingsContent {
     pub hour_format: Option<HourFormat>,
 }
 
+/// Settings for the local history of files.
+#[with_fallible_options]
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to keep snapshots of files when they are saved, reloaded from disk,
+    /// or edited by the agent.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The maximum number of snapshots kept for a single file.
+    ///
+    /// Default: 50
+    pub max_entries_per_file: Option<usize>,
+    /// The maximum total size of all snapshots, in megabytes.
+    /// The oldest snapshots are removed when this is exceeded.
+    ///
+    /// Default: 256
+    pub max_total_size_mb: Option<u64>,
+}
+
+/// A step of an editor macro.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
+#[serde(untagged)]
+pub enum MacroStepContent {
+    /// Dispatches an action, written as in the keymap: either the name of the action,
+    /// or an array of its name and its arguments.
+    Action { action: serde_json::Value },
+    /// Types the given text.
+    Text { text: String },
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum HourFormat {
@@ -905,6 +951,47 @@ pub enum HourFormat {
     Hour24,
 }
 
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
+pub struct BookmarksPanelSettingsContent {
+    /// Whether to show the bookmarks panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customize default width (in pixels) taken by bookmarks panel
+    ///
+    /// Default: 300
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+    /// The position of bookmarks panel
+    ///
+    /// Default: right
+    pub dock: Option<DockSide>,
+}
+
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
+pub struct HierarchyPanelSettingsContent {
+    /// Whether to show the hierarchy panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customize default width (in pixels) taken by hierarchy panel
+    ///
+    /// Default: 300
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+    /// The position of hierarchy panel
+    ///
+    /// Default: left
+    pub dock: Option<DockSide>,
+    /// Amount of indentation (in pixels) for nested calls.
+    ///
+    /// Default: 20
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub indent_size: Option<f32>,
+}
+
 #[with_fallible_options]
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
 pub struct OutlinePanelSettingsContent {
@@ -1050,6 +1137,17 @@ pub enum ImageFileSizeUnit {
     Decimal,
 }
 
+/// The settings for the preview of CSV and other tabular files.
+#[with_fallible_options]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, MergeFrom, Default, PartialEq)]
+pub struct CsvPreviewSettingsContent {
+    /// The character separating the fields of a row: "auto" to detect it from the
+    /// contents of each file, or a single character, such as ",", ";", "\t" or "|".
+    ///
+    /// Default: "auto"
+    pub delimiter: Option<String>,
+}
+
 #[with_fallible_options]
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
 pub struct RemoteSettingsContent {
@@ -1148,6 +1246,27 @@ pub struct ReplSettingsContent {
     ///
     /// Default: 0
     pub output_max_height_lines: Option<usize>,
+    /// Jupyter servers whose kernels and sessions are offered in the kernel picker.
+    ///
+    /// Default: []
+    pub jupyter_servers: Option<Vec<JupyterServerContent>>,
+}
+
+/// A Jupyter server reachable over its REST and WebSocket API.
+#[with_fallible_options]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, MergeFrom)]
+pub struct JupyterServerContent {
+    /// The base URL of the server, such as `http://localhost:8888`.
+    pub url: String,
+    /// The name of the environment variable holding the token to authenticate with, so that
+    /// the token isn't stored in plain text in the settings.
+    ///
+    /// Default: "JUPYTER_TOKEN"
+    pub token_env: Option<String>,
+    /// Deprecated: the token is no longer read from the settings. Use `token_env` instead.
+    #[deprecated]
+    #[schemars(skip)]
+    pub token: Option<String>,
 }
 
 /// Settings for configuring the which-key popup behaviour.
@@ -1,6 +1,10 @@
 use anyhow::{Context as _, Result};
+use regex::{Captures, RegexBuilder};
 use smallvec::SmallVec;
-use std::{collections::BTreeMap, ops::Range};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Snippet {
@@ -14,12 +18,108 @@ pub struct TabStop {
     pub choices: Option<Vec<String>>,
 }
 
+/// A node of a parsed snippet, as described by the `Grammar` section of the LSP spec:
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#snippet_syntax
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnippetNode {
+    Text(String),
+    /// `$1` or `${1}`, or a transformed mirror of a tabstop such as `${1/(.*)/${1:/upcase}/}`.
+    Tabstop {
+        index: usize,
+        transform: Option<Transform>,
+    },
+    /// `${1:default}`
+    Placeholder {
+        index: usize,
+        children: Vec<SnippetNode>,
+    },
+    /// `${1|one,two,three|}`
+    Choice {
+        index: usize,
+        choices: Vec<String>,
+    },
+    /// `$NAME`, `${NAME}`, `${NAME:default}` or `${NAME/regex/format/options}`.
+    Variable {
+        name: String,
+        default: Option<Vec<SnippetNode>>,
+        transform: Option<Transform>,
+    },
+}
+
+/// A regex transform applied to a variable value or to the text of a mirrored tabstop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub regex: String,
+    pub format: Vec<FormatItem>,
+    pub options: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatItem {
+    Text(String),
+    /// `$1`, `${1}` or `${1:/upcase}`
+    Capture {
+        index: usize,
+        modifier: Option<CaseModifier>,
+    },
+    /// `${1:+if}`, `${1:?if:else}`, `${1:-else}` or `${1:else}`
+    Conditional {
+        index: usize,
+        if_matched: Option<String>,
+        otherwise: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaseModifier {
+    Upcase,
+    Downcase,
+    Capitalize,
+    Camelcase,
+    Pascalcase,
+}
+
+/// Provides values for snippet variables such as `TM_FILENAME` or `CLIPBOARD`.
+pub trait VariableResolver {
+    /// Returns the value of the variable, or `None` if the variable is unknown.
+    ///
+    /// Unknown variables are inserted as placeholders containing their name, or their default
+    /// value if the snippet specifies one.
+    fn resolve(&self, name: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String>> VariableResolver for F {
+    fn resolve(&self, name: &str) -> Option<String> {
+        self(name)
+    }
+}
+
 impl Snippet {
     pub fn parse(source: &str) -> Result<Self> {
-        let mut text = String::with_capacity(source.len());
-        let mut tabstops = BTreeMap::new();
-        parse_snippet(source, false, &mut text, &mut tabstops)
-            .context("failed to parse snippet")?;
+        Self::parse_with_variables(source, &|_: &str| None)
+    }
+
+    pub fn parse_with_variables(source: &str, variables: &dyn VariableResolver) -> Result<Self> {
+        let nodes = parse_nodes(source).context("failed to parse snippet")?;
+        Self::from_nodes(&nodes, variables).context("failed to expand snippet")
+    }
+
+    pub fn from_nodes(nodes: &[SnippetNode], variables: &dyn VariableResolver) -> Result<Self> {
+        let mut tabstop_definitions = HashMap::default();
+        collect_tabstop_definitions(nodes, &mut tabstop_definitions);
+        let next_variable_index = max_tabstop_index(nodes) + 1;
+        let mut renderer = Renderer {
+            variables,
+            tabstop_definitions: &tabstop_definitions,
+            resolving: Vec::new(),
+            text: String::new(),
+            tabstops: BTreeMap::new(),
+            next_variable_index,
+        };
+        renderer.render(nodes)?;
+        let Renderer {
+            text, mut tabstops, ..
+        } = renderer;
 
         let len = text.len() as isize;
         let final_tabstop = tabstops.remove(&0);
@@ -42,17 +142,257 @@ impl Snippet {
     }
 }
 
+impl Transform {
+    pub fn apply(&self, value: &str) -> Result<String> {
+        let regex = RegexBuilder::new(&self.regex)
+            .case_insensitive(self.options.contains('i'))
+            .multi_line(self.options.contains('m'))
+            .dot_matches_new_line(self.options.contains('s'))
+            .build()
+            .with_context(|| format!("invalid transform regex {:?}", self.regex))?;
+        let limit = if self.options.contains('g') { 0 } else { 1 };
+        Ok(regex
+            .replacen(value, limit, |captures: &Captures| self.format(captures))
+            .into_owned())
+    }
+
+    fn format(&self, captures: &Captures) -> String {
+        let mut result = String::new();
+        for item in &self.format {
+            match item {
+                FormatItem::Text(text) => result.push_str(text),
+                FormatItem::Capture { index, modifier } => {
+                    if let Some(capture) = captures.get(*index) {
+                        match modifier {
+                            Some(modifier) => result.push_str(&modifier.apply(capture.as_str())),
+                            None => result.push_str(capture.as_str()),
+                        }
+                    }
+                }
+                FormatItem::Conditional {
+                    index,
+                    if_matched,
+                    otherwise,
+                } => {
+                    let matched = captures
+                        .get(*index)
+                        .is_some_and(|capture| !capture.is_empty());
+                    let text = if matched { if_matched } else { otherwise };
+                    if let Some(text) = text {
+                        result.push_str(text);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+impl CaseModifier {
+    pub fn apply(&self, value: &str) -> String {
+        let words = || {
+            value
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+        };
+        match self {
+            CaseModifier::Upcase => value.to_uppercase(),
+            CaseModifier::Downcase => value.to_lowercase(),
+            CaseModifier::Capitalize => capitalize(value),
+            CaseModifier::Pascalcase => words().map(capitalize).collect(),
+            CaseModifier::Camelcase => words()
+                .enumerate()
+                .map(|(ix, word)| {
+                    if ix == 0 {
+                        uncapitalize(word)
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn uncapitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+struct Renderer<'a> {
+    variables: &'a dyn VariableResolver,
+    tabstop_definitions: &'a HashMap<usize, &'a SnippetNode>,
+    resolving: Vec<usize>,
+    text: String,
+    tabstops: BTreeMap<usize, TabStop>,
+    next_variable_index: usize,
+}
+
+impl Renderer<'_> {
+    fn render(&mut self, nodes: &[SnippetNode]) -> Result<()> {
+        for node in nodes {
+            match node {
+                SnippetNode::Text(text) => self.text.push_str(text),
+                SnippetNode::Tabstop {
+                    index,
+                    transform: None,
+                } => {
+                    let start = self.text.len();
+                    self.push_tabstop(*index, start, None);
+                }
+                SnippetNode::Tabstop {
+                    index,
+                    transform: Some(transform),
+                } => {
+                    // Transformed mirrors are not editable, so they are expanded to plain text.
+                    let value = self.tabstop_value(*index)?;
+                    self.text.push_str(&transform.apply(&value)?);
+                }
+                SnippetNode::Placeholder { index, children } => {
+                    let start = self.text.len();
+                    self.resolving.push(*index);
+                    self.render(children)?;
+                    self.resolving.pop();
+                    self.push_tabstop(*index, start, None);
+                }
+                SnippetNode::Choice { index, choices } => {
+                    let start = self.text.len();
+                    if let Some(choice) = choices.first() {
+                        self.text.push_str(choice);
+                    }
+                    self.push_tabstop(*index, start, Some(choices.clone()));
+                }
+                SnippetNode::Variable {
+                    name,
+                    default,
+                    transform,
+                } => match (self.variables.resolve(name), default) {
+                    (Some(value), Some(default)) if value.is_empty() => self.render(default)?,
+                    (Some(value), _) => match transform {
+                        Some(transform) => self.text.push_str(&transform.apply(&value)?),
+                        None => self.text.push_str(&value),
+                    },
+                    (None, default) => {
+                        let start = self.text.len();
+                        match default {
+                            Some(default) => self.render(default)?,
+                            None => self.text.push_str(name),
+                        }
+                        let index = self.next_variable_index;
+                        self.next_variable_index += 1;
+                        self.push_tabstop(index, start, None);
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn push_tabstop(&mut self, index: usize, start: usize, choices: Option<Vec<String>>) {
+        self.tabstops
+            .entry(index)
+            .or_insert_with(|| TabStop {
+                ranges: Default::default(),
+                choices,
+            })
+            .ranges
+            .push(start as isize..self.text.len() as isize);
+    }
+
+    /// Returns the initial text of the tabstop with the given index, i.e. the text of its
+    /// first placeholder or the first option of its first choice.
+    fn tabstop_value(&self, index: usize) -> Result<String> {
+        if self.resolving.contains(&index) {
+            return Ok(String::new());
+        }
+        match self.tabstop_definitions.get(&index) {
+            Some(SnippetNode::Placeholder { children, .. }) => {
+                let mut renderer = Renderer {
+                    variables: self.variables,
+                    tabstop_definitions: self.tabstop_definitions,
+                    resolving: self.resolving.iter().copied().chain([index]).collect(),
+                    text: String::new(),
+                    tabstops: BTreeMap::new(),
+                    next_variable_index: self.next_variable_index,
+                };
+                renderer.render(children)?;
+                Ok(renderer.text)
+            }
+            Some(SnippetNode::Choice { choices, .. }) => {
+                Ok(choices.first().cloned().unwrap_or_default())
+            }
+            _ => Ok(String::new()),
+        }
+    }
+}
+
+fn collect_tabstop_definitions<'a>(
+    nodes: &'a [SnippetNode],
+    definitions: &mut HashMap<usize, &'a SnippetNode>,
+) {
+    for node in nodes {
+        match node {
+            SnippetNode::Placeholder { index, children } => {
+                definitions.entry(*index).or_insert(node);
+                collect_tabstop_definitions(children, definitions);
+            }
+            SnippetNode::Choice { index, .. } => {
+                definitions.entry(*index).or_insert(node);
+            }
+            SnippetNode::Variable {
+                default: Some(default),
+                ..
+            } => collect_tabstop_definitions(default, definitions),
+            SnippetNode::Text(_) | SnippetNode::Tabstop { .. } | SnippetNode::Variable { .. } => {}
+        }
+    }
+}
+
+fn max_tabstop_index(nodes: &[SnippetNode]) -> usize {
+    nodes
+        .iter()
+        .map(|node| match node {
+            SnippetNode::Tabstop { index, .. } | SnippetNode::Choice { index, .. } => *index,
+            SnippetNode::Placeholder { index, children } => {
+                (*index).max(max_tabstop_index(children))
+            }
+            SnippetNode::Variable {
+                default: Some(default),
+                ..
+            } => max_tabstop_index(default),
+            SnippetNode::Text(_) | SnippetNode::Variable { .. } => 0,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn parse_nodes(source: &str) -> Result<Vec<SnippetNode>> {
+    let mut nodes = Vec::new();
+    parse_snippet(source, false, &mut nodes)?;
+    Ok(nodes)
+}
+
 fn parse_snippet<'a>(
     mut source: &'a str,
     nested: bool,
-    text: &mut String,
-    tabstops: &mut BTreeMap<usize, TabStop>,
+    nodes: &mut Vec<SnippetNode>,
 ) -> Result<&'a str> {
     loop {
         match source.chars().next() {
             None => return Ok(""),
             Some('$') => {
-                source = parse_tabstop(&source[1..], text, tabstops)?;
+                source = parse_dollar(&source[1..], nodes)?;
             }
             Some('\\') => {
                 // As specified in the LSP spec (`Grammar` section),
@@ -61,76 +401,116 @@ fn parse_snippet<'a>(
                 source = &source[1..];
                 if let Some(c) = source.chars().next() {
                     if c == '$' || c == '\\' || c == '}' {
-                        text.push(c);
+                        push_text(nodes, &source[..1]);
                         // All escapable characters are 1 byte long:
                         source = &source[1..];
                     } else {
-                        text.push('\\');
+                        push_text(nodes, "\\");
                     }
                 } else {
-                    text.push('\\');
+                    push_text(nodes, "\\");
                 }
             }
             Some('}') => {
                 if nested {
                     return Ok(source);
                 } else {
-                    text.push('}');
+                    push_text(nodes, "}");
                     source = &source[1..];
                 }
             }
             Some(_) => {
                 let chunk_end = source.find(['}', '$', '\\']).unwrap_or(source.len());
                 let (chunk, rest) = source.split_at(chunk_end);
-                text.push_str(chunk);
+                push_text(nodes, chunk);
                 source = rest;
             }
         }
     }
 }
 
-fn parse_tabstop<'a>(
-    mut source: &'a str,
-    text: &mut String,
-    tabstops: &mut BTreeMap<usize, TabStop>,
-) -> Result<&'a str> {
-    let tabstop_start = text.len();
-    let tabstop_index;
-    let mut choices = None;
-
-    if source.starts_with('{') {
-        let (index, rest) = parse_int(&source[1..])?;
-        tabstop_index = index;
-        source = rest;
-
-        if source.starts_with("|") {
-            (source, choices) = parse_choices(&source[1..], text)?;
-        }
+fn push_text(nodes: &mut Vec<SnippetNode>, text: &str) {
+    if let Some(SnippetNode::Text(last)) = nodes.last_mut() {
+        last.push_str(text);
+    } else {
+        nodes.push(SnippetNode::Text(text.to_string()));
+    }
+}
 
-        if source.starts_with(':') {
-            source = parse_snippet(&source[1..], true, text, tabstops)?;
+fn parse_dollar<'a>(source: &'a str, nodes: &mut Vec<SnippetNode>) -> Result<&'a str> {
+    if let Some(source) = source.strip_prefix('{') {
+        if source.starts_with(|c: char| c.is_ascii_digit()) {
+            parse_tabstop(source, nodes)
+        } else {
+            parse_variable(source, nodes)
         }
+    } else if source.starts_with(|c: char| c.is_ascii_digit()) {
+        let (index, rest) = parse_int(source)?;
+        nodes.push(SnippetNode::Tabstop {
+            index,
+            transform: None,
+        });
+        Ok(rest)
+    } else {
+        let (name, rest) = parse_variable_name(source)?;
+        nodes.push(SnippetNode::Variable {
+            name: name.to_string(),
+            default: None,
+            transform: None,
+        });
+        Ok(rest)
+    }
+}
 
-        if source.starts_with('}') {
-            source = &source[1..];
-        } else {
-            anyhow::bail!("expected a closing brace");
+fn parse_tabstop<'a>(source: &'a str, nodes: &mut Vec<SnippetNode>) -> Result<&'a str> {
+    let (index, mut source) = parse_int(source)?;
+    let node = if let Some(rest) = source.strip_prefix('|') {
+        let choices;
+        (source, choices) = parse_choices(rest)?;
+        SnippetNode::Choice { index, choices }
+    } else if let Some(rest) = source.strip_prefix(':') {
+        let mut children = Vec::new();
+        source = parse_snippet(rest, true, &mut children)?;
+        SnippetNode::Placeholder { index, children }
+    } else if let Some(rest) = source.strip_prefix('/') {
+        let transform;
+        (transform, source) = parse_transform(rest)?;
+        SnippetNode::Tabstop {
+            index,
+            transform: Some(transform),
         }
     } else {
-        let (index, rest) = parse_int(source)?;
-        tabstop_index = index;
-        source = rest;
+        SnippetNode::Tabstop {
+            index,
+            transform: None,
+        }
+    };
+
+    nodes.push(node);
+    source.strip_prefix('}').context("expected a closing brace")
+}
+
+fn parse_variable<'a>(source: &'a str, nodes: &mut Vec<SnippetNode>) -> Result<&'a str> {
+    let (name, mut source) = parse_variable_name(source)?;
+    let mut default = None;
+    let mut transform = None;
+
+    if let Some(rest) = source.strip_prefix(':') {
+        let mut children = Vec::new();
+        source = parse_snippet(rest, true, &mut children)?;
+        default = Some(children);
+    } else if let Some(rest) = source.strip_prefix('/') {
+        let parsed_transform;
+        (parsed_transform, source) = parse_transform(rest)?;
+        transform = Some(parsed_transform);
     }
 
-    tabstops
-        .entry(tabstop_index)
-        .or_insert_with(|| TabStop {
-            ranges: Default::default(),
-            choices,
-        })
-        .ranges
-        .push(tabstop_start as isize..text.len() as isize);
-    Ok(source)
+    nodes.push(SnippetNode::Variable {
+        name: name.to_string(),
+        default,
+        transform,
+    });
+    source.strip_prefix('}').context("expected a closing brace")
 }
 
 fn parse_int(source: &str) -> Result<(usize, &str)> {
@@ -142,60 +522,231 @@ fn parse_int(source: &str) -> Result<(usize, &str)> {
     Ok((prefix.parse()?, suffix))
 }
 
-fn parse_choices<'a>(
-    mut source: &'a str,
-    text: &mut String,
-) -> Result<(&'a str, Option<Vec<String>>)> {
-    let mut found_default_choice = false;
+fn parse_variable_name(source: &str) -> Result<(&str, &str)> {
+    anyhow::ensure!(
+        source.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic()),
+        "expected an integer or a variable name"
+    );
+    let len = source
+        .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+        .unwrap_or(source.len());
+    Ok(source.split_at(len))
+}
+
+fn parse_choices(mut source: &str) -> Result<(&str, Vec<String>)> {
     let mut current_choice = String::new();
     let mut choices = Vec::new();
 
     loop {
         match source.chars().next() {
-            None => return Ok(("", Some(choices))),
+            None => {
+                anyhow::bail!("Placeholder choice doesn't contain closing pipe-character '|'")
+            }
             Some('\\') => {
                 source = &source[1..];
 
                 if let Some(c) = source.chars().next() {
-                    if !found_default_choice {
-                        current_choice.push(c);
-                        text.push(c);
-                    }
+                    current_choice.push(c);
                     source = &source[c.len_utf8()..];
                 }
             }
             Some(',') => {
-                found_default_choice = true;
                 source = &source[1..];
-                choices.push(current_choice);
-                current_choice = String::new();
+                choices.push(std::mem::take(&mut current_choice));
             }
             Some('|') => {
                 source = &source[1..];
                 choices.push(current_choice);
-                return Ok((source, Some(choices)));
+                return Ok((source, choices));
             }
             Some(_) => {
-                let chunk_end = source.find([',', '|', '\\']);
-
-                anyhow::ensure!(
-                    chunk_end.is_some(),
-                    "Placeholder choice doesn't contain closing pipe-character '|'"
-                );
+                let chunk_end = source.find([',', '|', '\\']).unwrap_or(source.len());
+                let (chunk, rest) = source.split_at(chunk_end);
+                current_choice.push_str(chunk);
+                source = rest;
+            }
+        }
+    }
+}
 
-                let (chunk, rest) = source.split_at(chunk_end.unwrap());
+/// Parses `regex/format/options`, leaving the closing brace in the returned source.
+fn parse_transform(source: &str) -> Result<(Transform, &str)> {
+    let (regex, source) = parse_transform_regex(source)?;
+    let (format, source) = parse_transform_format(source)?;
+    let options_len = source.find('}').context("expected a closing brace")?;
+    let (options, source) = source.split_at(options_len);
+    let transform = Transform {
+        regex,
+        format,
+        options: options.to_string(),
+    };
+    Ok((transform, source))
+}
 
-                if !found_default_choice {
-                    text.push_str(chunk);
+fn parse_transform_regex(mut source: &str) -> Result<(String, &str)> {
+    let mut regex = String::new();
+    loop {
+        match source.chars().next() {
+            None => anyhow::bail!("expected a '/' after the transform regex"),
+            Some('/') => return Ok((regex, &source[1..])),
+            Some('\\') => {
+                source = &source[1..];
+                match source.chars().next() {
+                    Some('/') => {
+                        regex.push('/');
+                        source = &source[1..];
+                    }
+                    Some(c) => {
+                        regex.push('\\');
+                        regex.push(c);
+                        source = &source[c.len_utf8()..];
+                    }
+                    None => regex.push('\\'),
                 }
+            }
+            Some(_) => {
+                let chunk_end = source.find(['/', '\\']).unwrap_or(source.len());
+                let (chunk, rest) = source.split_at(chunk_end);
+                regex.push_str(chunk);
+                source = rest;
+            }
+        }
+    }
+}
 
-                current_choice.push_str(chunk);
+fn parse_transform_format(mut source: &str) -> Result<(Vec<FormatItem>, &str)> {
+    let mut items = Vec::new();
+    loop {
+        match source.chars().next() {
+            None => anyhow::bail!("expected a '/' after the transform format"),
+            Some('/') => return Ok((items, &source[1..])),
+            Some('\\') => {
+                source = &source[1..];
+                match source.chars().next() {
+                    Some(c @ ('/' | '\\' | '$')) => {
+                        push_format_text(&mut items, &c.to_string());
+                        source = &source[1..];
+                    }
+                    _ => push_format_text(&mut items, "\\"),
+                }
+            }
+            Some('$') => {
+                source = parse_format_item(&source[1..], &mut items)?;
+            }
+            Some(_) => {
+                let chunk_end = source.find(['/', '\\', '$']).unwrap_or(source.len());
+                let (chunk, rest) = source.split_at(chunk_end);
+                push_format_text(&mut items, chunk);
                 source = rest;
             }
         }
     }
 }
 
+fn push_format_text(items: &mut Vec<FormatItem>, text: &str) {
+    if let Some(FormatItem::Text(last)) = items.last_mut() {
+        last.push_str(text);
+    } else {
+        items.push(FormatItem::Text(text.to_string()));
+    }
+}
+
+fn parse_format_item<'a>(source: &'a str, items: &mut Vec<FormatItem>) -> Result<&'a str> {
+    let Some(source) = source.strip_prefix('{') else {
+        if source.starts_with(|c: char| c.is_ascii_digit()) {
+            let (index, rest) = parse_int(source)?;
+            items.push(FormatItem::Capture {
+                index,
+                modifier: None,
+            });
+            return Ok(rest);
+        } else {
+            push_format_text(items, "$");
+            return Ok(source);
+        }
+    };
+
+    let (index, source) = parse_int(source)?;
+    if let Some(rest) = source.strip_prefix('}') {
+        items.push(FormatItem::Capture {
+            index,
+            modifier: None,
+        });
+        return Ok(rest);
+    }
+
+    let source = source
+        .strip_prefix(':')
+        .context("expected a closing brace")?;
+    let (item, rest) = if let Some(rest) = source.strip_prefix('/') {
+        let name_len = rest.find('}').context("expected a closing brace")?;
+        let modifier = match &rest[..name_len] {
+            "upcase" => CaseModifier::Upcase,
+            "downcase" => CaseModifier::Downcase,
+            "capitalize" => CaseModifier::Capitalize,
+            "camelcase" => CaseModifier::Camelcase,
+            "pascalcase" => CaseModifier::Pascalcase,
+            name => anyhow::bail!("unknown case modifier {name:?}"),
+        };
+        let item = FormatItem::Capture {
+            index,
+            modifier: Some(modifier),
+        };
+        (item, &rest[name_len + 1..])
+    } else if let Some(rest) = source.strip_prefix('+') {
+        let (if_matched, rest) = parse_conditional_text(rest, '}')?;
+        let item = FormatItem::Conditional {
+            index,
+            if_matched: Some(if_matched),
+            otherwise: None,
+        };
+        (item, rest)
+    } else if let Some(rest) = source.strip_prefix('?') {
+        let (if_matched, rest) = parse_conditional_text(rest, ':')?;
+        let (otherwise, rest) = parse_conditional_text(rest, '}')?;
+        let item = FormatItem::Conditional {
+            index,
+            if_matched: Some(if_matched),
+            otherwise: Some(otherwise),
+        };
+        (item, rest)
+    } else {
+        let source = source.strip_prefix('-').unwrap_or(source);
+        let (otherwise, rest) = parse_conditional_text(source, '}')?;
+        let item = FormatItem::Conditional {
+            index,
+            if_matched: None,
+            otherwise: Some(otherwise),
+        };
+        (item, rest)
+    };
+
+    items.push(item);
+    Ok(rest)
+}
+
+/// Parses text up to (and including) the given terminator, unescaping backslashes.
+fn parse_conditional_text(mut source: &str, terminator: char) -> Result<(String, &str)> {
+    let mut text = String::new();
+    loop {
+        match source.chars().next() {
+            None => anyhow::bail!("expected {terminator:?} in transform format"),
+            Some(c) if c == terminator => return Ok((text, &source[c.len_utf8()..])),
+            Some('\\') => {
+                source = &source[1..];
+                if let Some(c) = source.chars().next() {
+                    text.push(c);
+                    source = &source[c.len_utf8()..];
+                }
+            }
+            Some(c) => {
+                text.push(c);
+                source = &source[c.len_utf8()..];
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -324,6 +875,121 @@ mod tests {
         assert_eq!(tabstops(&snippet), &[vec![4..4], vec![7..7]]);
     }
 
+    #[test]
+    fn test_snippet_with_variables() {
+        let variables = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "TM_SELECTED_TEXT" => Some(String::new()),
+            _ => None,
+        };
+
+        let snippet = Snippet::parse_with_variables("// $TM_FILENAME: $1", &variables).unwrap();
+        assert_eq!(snippet.text, "// main.rs: ");
+        assert_eq!(tabstops(&snippet), &[vec![12..12]]);
+
+        // Empty variables fall back to their default value.
+        let snippet =
+            Snippet::parse_with_variables("(${TM_SELECTED_TEXT:${1:value}})", &variables).unwrap();
+        assert_eq!(snippet.text, "(value)");
+        assert_eq!(tabstops(&snippet), &[vec![1..6], vec![7..7]]);
+
+        // Unknown variables become placeholders after the numbered tabstops.
+        let snippet = Snippet::parse_with_variables("$UNKNOWN ${2:two} $0", &variables).unwrap();
+        assert_eq!(snippet.text, "UNKNOWN two ");
+        assert_eq!(tabstops(&snippet), &[vec![8..11], vec![0..7], vec![12..12]]);
+
+        let snippet = Snippet::parse("${CLIPBOARD:fallback}").unwrap();
+        assert_eq!(snippet.text, "fallback");
+        assert_eq!(tabstops(&snippet), &[vec![0..8], vec![8..8]]);
+
+        // A lone dollar sign is still an error.
+        assert!(Snippet::parse("$ 1").is_err());
+    }
+
+    #[test]
+    fn test_snippet_with_variable_transforms() {
+        let variables = |name: &str| match name {
+            "TM_FILENAME" => Some("my_module.rs".to_string()),
+            _ => None,
+        };
+
+        let snippet = Snippet::parse_with_variables(
+            "struct ${TM_FILENAME/(.*)\\.rs$/${1:/pascalcase}/};",
+            &variables,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "struct MyModule;");
+
+        let snippet = Snippet::parse_with_variables("${TM_FILENAME/[_.]/-/g}", &variables).unwrap();
+        assert_eq!(snippet.text, "my-module-rs");
+
+        let snippet = Snippet::parse_with_variables(
+            "${TM_FILENAME/(test_)?(.*)/${1:?test:module} ${2:/upcase}/}",
+            &variables,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "module MY_MODULE.RS");
+    }
+
+    #[test]
+    fn test_snippet_with_tabstop_transforms() {
+        let snippet = Snippet::parse("${1:foo_bar} ${1/(.*)/${1:/camelcase}/} $0").unwrap();
+        assert_eq!(snippet.text, "foo_bar fooBar ");
+        assert_eq!(tabstops(&snippet), &[vec![0..7], vec![15..15]]);
+
+        // Mirrors can precede the placeholder that defines them.
+        let snippet = Snippet::parse("${1/^(.)/${1:/upcase}/}: ${1|one,two|}").unwrap();
+        assert_eq!(snippet.text, "One: one");
+        assert_eq!(tabstops(&snippet), &[vec![5..8], vec![8..8]]);
+
+        let snippet = Snippet::parse("${1:x}${1/(y)?/${1:+yes}${1:-no}/}").unwrap();
+        assert_eq!(snippet.text, "xnox");
+
+        let snippet = Snippet::parse("${1:a/b}${1/\\//\\$/}").unwrap();
+        assert_eq!(snippet.text, "a/ba$b");
+
+        // Self-referencing mirrors resolve to an empty value instead of recursing.
+        let snippet = Snippet::parse("${1:a${1/(.*)/[$1]/}}").unwrap();
+        assert_eq!(snippet.text, "a[]");
+
+        assert!(Snippet::parse("${1/(.*)/${1:/unknown}/}").is_err());
+        assert!(Snippet::parse("${1/(/x/}").is_err());
+        assert!(Snippet::parse("${1/(.*)/x}").is_err());
+    }
+
+    #[test]
+    fn test_parse_nodes() {
+        let nodes = parse_nodes("a${1:b$TM_LINE_NUMBER}${2/x/${1:/upcase}/gi}").unwrap();
+        assert_eq!(
+            nodes,
+            &[
+                SnippetNode::Text("a".into()),
+                SnippetNode::Placeholder {
+                    index: 1,
+                    children: vec![
+                        SnippetNode::Text("b".into()),
+                        SnippetNode::Variable {
+                            name: "TM_LINE_NUMBER".into(),
+                            default: None,
+                            transform: None,
+                        },
+                    ],
+                },
+                SnippetNode::Tabstop {
+                    index: 2,
+                    transform: Some(Transform {
+                        regex: "x".into(),
+                        format: vec![FormatItem::Capture {
+                            index: 1,
+                            modifier: Some(CaseModifier::Upcase),
+                        }],
+                        options: "gi".into(),
+                    }),
+                },
+            ]
+        );
+    }
+
     fn tabstops(snippet: &Snippet) -> Vec<Vec<Range<isize>>> {
         snippet.tabstops.iter().map(|t| t.ranges.to_vec()).collect()
     }
@@ -4,6 +4,8 @@ pub use alacritty_terminal;
 
 mod pty_info;
 mod terminal_hyperlinks;
+pub mod terminal_images;
+mod terminal_output;
 pub mod terminal_settings;
 
 use alacritty_terminal::{
@@ -47,6 +49,8 @@ use settings::Settings;
 use smol::channel::{Receiver, Sender};
 use task::{HideStrategy, Shell, SpawnInTerminal};
 use terminal_hyperlinks::RegexSearches;
+use terminal_images::{ImagePty, TerminalImagePlacement, TerminalImages};
+use terminal_output::TerminalOutput;
 use terminal_settings::{AlternateScroll, CursorShape, TerminalSettings};
 use theme::{ActiveTheme, Theme};
 use urlencoding;
@@ -381,6 +385,8 @@ impl TerminalBuilder {
             completion_tx: None,
             term,
             term_config: config,
+            images: Arc::default(),
+            output: Arc::default(),
             title_override: None,
             events: VecDeque::with_capacity(10),
             last_content: Default::default(),
@@ -585,12 +591,18 @@ impl TerminalBuilder {
             let term = Arc::new(FairMutex::new(term));
 
             let pty_info = PtyProcessInfo::new(&pty);
+            let images = Arc::new(TerminalImages::default());
+            let output = Arc::new(if task.is_some() {
+                TerminalOutput::with_backlog()
+            } else {
+                TerminalOutput::default()
+            });
 
             //And connect them together
             let event_loop = EventLoop::new(
                 term.clone(),
-                ZedListener(events_tx),
-                pty,
+                ZedListener(events_tx.clone()),
+                ImagePty::new(pty, ZedListener(events_tx), images.clone(), output.clone()),
                 pty_options.drain_on_exit,
                 false,
             )
@@ -609,6 +621,8 @@ impl TerminalBuilder {
                 completion_tx,
                 term,
                 term_config: config,
+                images,
+                output,
                 title_override: terminal_title_override,
                 events: VecDeque::with_capacity(10), //Should never get this high.
                 last_content: Default::default(),
@@ -794,6 +808,7 @@ pub struct TerminalContent {
     pub last_hovered_word: Option<HoveredWord>,
     pub scrolled_to_top: bool,
     pub scrolled_to_bottom: bool,
+    pub images: Vec<TerminalImagePlacement>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -820,6 +835,7 @@ impl Default for TerminalContent {
             last_hovered_word: None,
             scrolled_to_top: false,
             scrolled_to_bottom: false,
+            images: Vec::new(),
         }
     }
 }
@@ -843,6 +859,8 @@ pub struct Terminal {
     completion_tx: Option<Sender<Option<ExitStatus>>>,
     term: Arc<FairMutex<Term<ZedListener>>>,
     term_config: Config,
+    images: Arc<TerminalImages>,
+    output: Arc<TerminalOutput>,
     events: VecDeque<InternalEvent>,
     /// This is only used for mouse mode cell change detection
     last_mouse: Option<(AlacPoint, AlacDirection)>,
@@ -1017,6 +1035,8 @@ impl Terminal {
                 new_bounds.bounds.size.width = cmp::max(new_bounds.cell_width, new_bounds.width());
 
                 self.last_content.terminal_bounds = new_bounds;
+                self.images
+                    .set_cell_size(Size::new(new_bounds.cell_width, new_bounds.line_height));
 
                 if let TerminalType::Pty { pty_tx, .. } = &self.terminal_type {
                     pty_tx.0.send(Msg::Resize(new_bounds.into())).ok();
@@ -1311,6 +1331,7 @@ impl Terminal {
             let mut term = self.term.lock();
             processor.advance(&mut *term, &converted);
         }
+        self.output.write(&converted);
         cx.emit(Event::Wakeup);
     }
 
@@ -1603,6 +1624,7 @@ impl Terminal {
         }
 
         self.last_content = Self::make_content(&terminal, &self.last_content);
+        self.last_content.images = self.images.placements(&mut self.last_content.cells);
     }
 
     fn make_content(term: &Term<ZedListener>, last_content: &TerminalContent) -> TerminalContent {
@@ -1635,9 +1657,19 @@ impl Terminal {
             last_hovered_word: last_content.last_hovered_word.clone(),
             scrolled_to_top: content.display_offset == term.history_size(),
             scrolled_to_bottom: content.display_offset == 0,
+            images: Vec::new(),
         }
     }
 
+    /// Returns a stream of the lines of text written to the terminal, without escape sequences.
+    ///
+    /// Unlike [`Self::get_content`], this is not limited to the scrollback. The first subscriber
+    /// of a task terminal receives the task's output from its start. The stream ends once the
+    /// terminal's process stops writing output.
+    pub fn subscribe_to_output(&self) -> UnboundedReceiver<Vec<String>> {
+        self.output.subscribe()
+    }
+
     pub fn get_content(&self) -> String {
         let term = self.term.lock_unfair();
         let start = AlacPoint::new(term.topmost_line(), Column(0));
@@ -2386,6 +2418,7 @@ unsafe fn append_text_to_term(term: &mut Term<ZedListener>, text_lines: &[&str])
 
 impl Drop for Terminal {
     fn drop(&mut self) {
+        self.output.close();
         if let TerminalType::Pty { pty_tx, info } =
             std::mem::replace(&mut self.terminal_type, TerminalType::DisplayOnly)
         {
@@ -3224,6 +3257,49 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_output_subscription_outlives_scrollback(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = settings::SettingsStore::test(cx);
+            cx.set_global(settings_store);
+        });
+
+        let terminal = cx.new(|cx| {
+            TerminalBuilder::new_display_only(
+                CursorShape::default(),
+                AlternateScroll::On,
+                Some(10),
+                0,
+                cx.background_executor(),
+                PathStyle::local(),
+            )
+            .unwrap()
+            .subscribe(cx)
+        });
+        let mut output = terminal.update(cx, |terminal, _| terminal.subscribe_to_output());
+
+        // Lines longer than the terminal is wide are soft wrapped in the grid, but not in the output.
+        let mut expected_lines = (1..=1000).map(|i| format!("line {i}")).collect::<Vec<_>>();
+        expected_lines.push("x".repeat(500));
+        terminal.update(cx, |terminal, cx| {
+            for line in &expected_lines {
+                terminal.write_output(format!("{line}\n").as_bytes(), cx);
+            }
+        });
+
+        let content = terminal.update(cx, |terminal, _| terminal.get_content());
+        assert!(
+            !content.lines().any(|line| line == "line 1"),
+            "the first line should have been evicted from the scrollback, got: {content}"
+        );
+
+        let mut received_lines = Vec::new();
+        while let Ok(Some(lines)) = output.try_next() {
+            received_lines.extend(lines);
+        }
+        assert_eq!(received_lines, expected_lines);
+    }
+
     /// Test that kill_active_task on a task that's not running is a no-op
     #[gpui::test]
     async fn test_kill_active_task_on_completed_task_is_noop(cx: &mut TestAppContext) {
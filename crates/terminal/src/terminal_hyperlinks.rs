@@ -18,6 +18,8 @@ use std::{
 use url::Url;
 use util::paths::{PathStyle, UrlExt};
 
+use crate::terminal_images::is_marker_uri;
+
 const URL_REGEX: &str = r#"(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file://|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>"\s{-}\^⟨⟩`']+"#;
 const WIDE_CHAR_SPACERS: Flags =
     Flags::from_bits(Flags::LEADING_WIDE_CHAR_SPACER.bits() | Flags::WIDE_CHAR_SPACER.bits())
@@ -74,7 +76,10 @@ pub(super) fn find_from_grid_point<T: EventListener>(
     path_style: PathStyle,
 ) -> Option<(String, bool, Match)> {
     let grid = term.grid();
-    let link = grid.index(point).hyperlink();
+    let link = grid
+        .index(point)
+        .hyperlink()
+        .filter(|link| !is_marker_uri(link.uri()));
     let found_word = if let Some(ref url) = link {
         let mut min_index = point;
         loop {
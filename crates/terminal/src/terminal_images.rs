@@ -0,0 +1,601 @@
+//! Support for images drawn by programs with the Kitty graphics protocol or Sixel sequences.
+//!
+//! Alacritty ignores both kinds of sequences, so they are filtered out of the PTY output before
+//! alacritty parses it. Each displayed image is replaced with a column of blank cells, one per
+//! image row, carrying an OSC 8 hyperlink with a private `zed-image:` URI. This anchors the
+//! image to the grid: it scrolls and is erased along with the surrounding text, and the marker
+//! cells are resolved back into [`TerminalImagePlacement`]s when the terminal content is built.
+
+mod kitty;
+mod sixel;
+
+use alacritty_terminal::{
+    event::{Event as AlacTermEvent, OnResize, WindowSize},
+    index::{Line, Point as AlacPoint},
+    tty::{ChildEvent, EventedPty, EventedReadWrite, Pty},
+};
+use collections::{BTreeMap, HashSet};
+use gpui::{Pixels, RenderImage, Size, px, size};
+use image::RgbaImage;
+use parking_lot::Mutex;
+use polling::{Event, PollMode, Poller};
+use std::{
+    io::{self, Read, Write as _},
+    mem,
+    sync::Arc,
+};
+
+use crate::{IndexedCell, ZedListener, terminal_output::TerminalOutput};
+
+const IMAGE_URI_PREFIX: &str = "zed-image:";
+/// The oldest images are dropped once the decoded images use more memory than this.
+const MAX_IMAGE_BYTES: usize = 256 * 1024 * 1024;
+/// Image sequences longer than this are discarded.
+const MAX_SEQUENCE_BYTES: usize = 64 * 1024 * 1024;
+
+const ESC: u8 = 0x1b;
+const CAN: u8 = 0x18;
+const SUB: u8 = 0x1a;
+
+/// An image displayed by a program running in the terminal.
+pub struct TerminalImage {
+    pub image: Arc<RenderImage>,
+    /// The number of columns covered by the image.
+    pub columns: usize,
+    /// The number of rows covered by the image.
+    pub rows: usize,
+    /// Whether the image is stretched over its cells, rather than drawn at its own size.
+    pub stretch: bool,
+    kitty_image_id: Option<u32>,
+}
+
+/// An image, and the grid point of its top left cell.
+#[derive(Clone)]
+pub struct TerminalImagePlacement {
+    pub image: Arc<TerminalImage>,
+    pub point: AlacPoint,
+}
+
+/// The images displayed in a terminal, shared between the thread reading its PTY and the UI.
+#[derive(Default)]
+pub struct TerminalImages(Mutex<TerminalImagesState>);
+
+#[derive(Default)]
+struct TerminalImagesState {
+    images: BTreeMap<u64, Arc<TerminalImage>>,
+    next_image_id: u64,
+    total_bytes: usize,
+    cell_size: Option<Size<Pixels>>,
+}
+
+/// Where the cursor is left after an image is displayed.
+#[derive(Clone, Copy)]
+enum CursorMovement {
+    /// After the last column of the image, on its last row.
+    AfterImage,
+    /// At the first column of the image, on its first row.
+    Stay,
+    /// At the start of the line following the image.
+    NextLine,
+}
+
+impl TerminalImages {
+    pub(crate) fn set_cell_size(&self, cell_size: Size<Pixels>) {
+        self.0.lock().cell_size = Some(cell_size);
+    }
+
+    fn cell_size(&self) -> Size<Pixels> {
+        self.0
+            .lock()
+            .cell_size
+            .unwrap_or_else(|| size(px(8.), px(16.)))
+    }
+
+    /// Stores an image and writes the marker cells anchoring it at the cursor.
+    fn display(&self, image: TerminalImage, movement: CursorMovement, output: &mut Vec<u8>) {
+        let columns = image.columns.max(1);
+        let rows = image.rows.max(1);
+        let image_id = {
+            let mut state = self.0.lock();
+            let image_id = state.next_image_id;
+            state.next_image_id += 1;
+            state.total_bytes += image_bytes(&image);
+            state.images.insert(image_id, Arc::new(image));
+            while state.total_bytes > MAX_IMAGE_BYTES && state.images.len() > 1 {
+                if let Some((_, evicted)) = state.images.pop_first() {
+                    state.total_bytes -= image_bytes(&evicted);
+                }
+            }
+            image_id
+        };
+
+        for row in 0..rows {
+            if row > 0 {
+                output.push(b'\n');
+            }
+            // A space with a hyperlink, after which the cursor is moved back onto it.
+            write!(
+                output,
+                "\x1b]8;;{IMAGE_URI_PREFIX}{image_id}/{row}\x1b\\ \x1b]8;;\x1b\\\x08"
+            )
+            .ok();
+        }
+        match movement {
+            CursorMovement::AfterImage => {
+                write!(output, "\x1b[{columns}C").ok();
+            }
+            CursorMovement::Stay if rows > 1 => {
+                write!(output, "\x1b[{}A", rows - 1).ok();
+            }
+            CursorMovement::Stay => {}
+            CursorMovement::NextLine => output.extend_from_slice(b"\r\n"),
+        }
+    }
+
+    fn remove(&self, mut predicate: impl FnMut(&TerminalImage) -> bool) {
+        let mut state = self.0.lock();
+        let mut removed_bytes = 0;
+        state.images.retain(|_, image| {
+            let remove = predicate(image);
+            if remove {
+                removed_bytes += image_bytes(image);
+            }
+            !remove
+        });
+        state.total_bytes -= removed_bytes;
+    }
+
+    /// Returns the images anchored in the given cells, clearing the hyperlinks of their
+    /// marker cells.
+    pub(crate) fn placements(&self, cells: &mut [IndexedCell]) -> Vec<TerminalImagePlacement> {
+        let mut placements = Vec::new();
+        let mut seen = HashSet::default();
+        let state = self.0.lock();
+        for cell in cells {
+            let Some((image_id, row)) = cell
+                .cell
+                .hyperlink()
+                .and_then(|hyperlink| parse_marker_uri(hyperlink.uri()))
+            else {
+                continue;
+            };
+            cell.cell.set_hyperlink(None);
+            if !seen.insert(image_id) {
+                continue;
+            }
+            if let Some(image) = state.images.get(&image_id) {
+                placements.push(TerminalImagePlacement {
+                    image: image.clone(),
+                    point: AlacPoint::new(Line(cell.point.line.0 - row as i32), cell.point.column),
+                });
+            }
+        }
+        placements
+    }
+}
+
+/// Whether the given hyperlink URI marks a cell covered by an image.
+pub(crate) fn is_marker_uri(uri: &str) -> bool {
+    uri.starts_with(IMAGE_URI_PREFIX)
+}
+
+fn parse_marker_uri(uri: &str) -> Option<(u64, usize)> {
+    let (image_id, row) = uri.strip_prefix(IMAGE_URI_PREFIX)?.split_once('/')?;
+    Some((image_id.parse().ok()?, row.parse().ok()?))
+}
+
+fn image_bytes(image: &TerminalImage) -> usize {
+    image.image.as_bytes(0).map_or(0, <[u8]>::len)
+}
+
+fn render_image(mut image: RgbaImage) -> Arc<RenderImage> {
+    // Convert from RGBA to BGRA.
+    for pixel in image.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    Arc::new(RenderImage::new(vec![image::Frame::new(image)]))
+}
+
+enum FilterState {
+    Ground,
+    Escape,
+    /// Collecting the parameters of a DCS sequence, to find out whether it holds a Sixel image.
+    DcsHeader(Vec<u8>),
+    /// Collecting the data of an APC or Sixel sequence until its string terminator.
+    String {
+        kind: StringKind,
+        data: Vec<u8>,
+        /// Whether the previous byte was an ESC, which may start the terminator.
+        escape: bool,
+        /// Whether the data was discarded for being too long.
+        overflowed: bool,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum StringKind {
+    Apc,
+    Sixel,
+}
+
+/// Removes Kitty graphics and Sixel sequences from the output of a program, replacing the
+/// images they display with marker cells.
+pub(crate) struct ImageFilter {
+    images: Arc<TerminalImages>,
+    state: FilterState,
+    kitty: kitty::KittyGraphics,
+}
+
+impl ImageFilter {
+    pub(crate) fn new(images: Arc<TerminalImages>) -> Self {
+        Self {
+            images,
+            state: FilterState::Ground,
+            kitty: Default::default(),
+        }
+    }
+
+    /// Filters `input` into `output`. Replies to Kitty graphics commands are written to
+    /// `responses`, to be sent back to the program.
+    pub(crate) fn process(&mut self, input: &[u8], output: &mut Vec<u8>, responses: &mut Vec<u8>) {
+        let mut ix = 0;
+        while ix < input.len() {
+            let byte = input[ix];
+            match &mut self.state {
+                FilterState::Ground => {
+                    let end = input[ix..]
+                        .iter()
+                        .position(|&byte| byte == ESC)
+                        .map_or(input.len(), |offset| ix + offset);
+                    output.extend_from_slice(&input[ix..end]);
+                    if end < input.len() {
+                        self.state = FilterState::Escape;
+                    }
+                    ix = end + 1;
+                    continue;
+                }
+                FilterState::Escape => match byte {
+                    b'_' => self.state = FilterState::string(StringKind::Apc),
+                    b'P' => self.state = FilterState::DcsHeader(Vec::new()),
+                    ESC => output.push(ESC),
+                    _ => {
+                        output.extend_from_slice(&[ESC, byte]);
+                        self.state = FilterState::Ground;
+                    }
+                },
+                FilterState::DcsHeader(header) => match byte {
+                    // Parameters, private markers and intermediates.
+                    0x20..=0x3f => header.push(byte),
+                    b'q' if header
+                        .iter()
+                        .all(|&byte| byte.is_ascii_digit() || byte == b';') =>
+                    {
+                        self.state = FilterState::string(StringKind::Sixel);
+                    }
+                    // Any other DCS sequence is left to alacritty.
+                    _ => {
+                        output.extend_from_slice(&[ESC, b'P']);
+                        output.extend_from_slice(header);
+                        self.state = FilterState::Ground;
+                        continue;
+                    }
+                },
+                FilterState::String {
+                    kind,
+                    data,
+                    escape,
+                    overflowed,
+                } => {
+                    if *escape {
+                        if byte == b'\\' {
+                            let (kind, data, overflowed) = (*kind, mem::take(data), *overflowed);
+                            self.state = FilterState::Ground;
+                            if !overflowed {
+                                self.finish(kind, &data, output, responses);
+                            }
+                        } else {
+                            // The sequence was interrupted by another escape sequence.
+                            self.state = FilterState::Escape;
+                            continue;
+                        }
+                    } else {
+                        let end = input[ix..]
+                            .iter()
+                            .position(|&byte| matches!(byte, ESC | CAN | SUB))
+                            .map_or(input.len(), |offset| ix + offset);
+                        if !*overflowed {
+                            if data.len() + (end - ix) > MAX_SEQUENCE_BYTES {
+                                *overflowed = true;
+                                *data = Vec::new();
+                            } else {
+                                data.extend_from_slice(&input[ix..end]);
+                            }
+                        }
+                        match input.get(end) {
+                            Some(&ESC) => *escape = true,
+                            Some(_) => self.state = FilterState::Ground,
+                            None => {}
+                        }
+                        ix = end + 1;
+                        continue;
+                    }
+                }
+            }
+            ix += 1;
+        }
+    }
+
+    fn finish(
+        &mut self,
+        kind: StringKind,
+        data: &[u8],
+        output: &mut Vec<u8>,
+        responses: &mut Vec<u8>,
+    ) {
+        match kind {
+            StringKind::Apc => {
+                // Other APC sequences are ignored by alacritty anyway.
+                if let Some(body) = data.strip_prefix(b"G") {
+                    self.kitty.handle(body, &self.images, output, responses);
+                }
+            }
+            StringKind::Sixel => {
+                let Some(image) = sixel::decode(data) else {
+                    return;
+                };
+                let cell_size = self.images.cell_size();
+                let (width, height) = image.dimensions();
+                let columns = (width as f32 / f32::from(cell_size.width).max(1.)).ceil();
+                let rows = (height as f32 / f32::from(cell_size.height).max(1.)).ceil();
+                self.images.display(
+                    TerminalImage {
+                        image: render_image(image),
+                        columns: columns as usize,
+                        rows: rows as usize,
+                        stretch: false,
+                        kitty_image_id: None,
+                    },
+                    CursorMovement::NextLine,
+                    output,
+                );
+            }
+        }
+    }
+}
+
+impl FilterState {
+    fn string(kind: StringKind) -> Self {
+        Self::String {
+            kind,
+            data: Vec::new(),
+            escape: false,
+            overflowed: false,
+        }
+    }
+}
+
+/// Wraps the PTY read by alacritty's event loop, filtering image sequences out of its output
+/// and copying the rest to the terminal's [`TerminalOutput`].
+pub(crate) struct ImagePty {
+    reader: ImagePtyReader,
+}
+
+pub(crate) struct ImagePtyReader {
+    pty: Pty,
+    /// Receives the replies to image queries, which are written back to the PTY by the terminal
+    /// along with the rest of its input.
+    listener: ZedListener,
+    filter: ImageFilter,
+    terminal_output: Arc<TerminalOutput>,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    output_offset: usize,
+}
+
+impl ImagePty {
+    pub(crate) fn new(
+        pty: Pty,
+        listener: ZedListener,
+        images: Arc<TerminalImages>,
+        terminal_output: Arc<TerminalOutput>,
+    ) -> Self {
+        Self {
+            reader: ImagePtyReader {
+                pty,
+                listener,
+                filter: ImageFilter::new(images),
+                terminal_output,
+                input: Vec::new(),
+                output: Vec::new(),
+                output_offset: 0,
+            },
+        }
+    }
+}
+
+impl Read for ImagePtyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if self.output_offset < self.output.len() {
+                let pending = &self.output[self.output_offset..];
+                let len = pending.len().min(buf.len());
+                buf[..len].copy_from_slice(&pending[..len]);
+                self.output_offset += len;
+                if self.output_offset == self.output.len() {
+                    self.output.clear();
+                    self.output_offset = 0;
+                }
+                return Ok(len);
+            }
+
+            self.input.resize(buf.len(), 0);
+            let len = self.pty.reader().read(&mut self.input)?;
+            if len == 0 {
+                self.terminal_output.close();
+                return Ok(0);
+            }
+            let mut responses = Vec::new();
+            self.filter
+                .process(&self.input[..len], &mut self.output, &mut responses);
+            self.terminal_output.write(&self.output);
+            if !responses.is_empty() {
+                let responses = String::from_utf8_lossy(&responses).into_owned();
+                if let Err(error) = self
+                    .listener
+                    .0
+                    .unbounded_send(AlacTermEvent::PtyWrite(responses))
+                {
+                    log::error!("failed to send image protocol response: {error}");
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ImagePtyReader {
+    fn drop(&mut self) {
+        self.terminal_output.close();
+    }
+}
+
+impl EventedReadWrite for ImagePty {
+    type Reader = ImagePtyReader;
+    type Writer = <Pty as EventedReadWrite>::Writer;
+
+    unsafe fn register(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        unsafe { self.reader.pty.register(poll, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: Event,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.reader.pty.reregister(poll, interest, mode)
+    }
+
+    fn deregister(&mut self, poll: &Arc<Poller>) -> io::Result<()> {
+        self.reader.pty.deregister(poll)
+    }
+
+    fn reader(&mut self) -> &mut Self::Reader {
+        &mut self.reader
+    }
+
+    fn writer(&mut self) -> &mut Self::Writer {
+        self.reader.pty.writer()
+    }
+}
+
+impl EventedPty for ImagePty {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.reader.pty.next_child_event()
+    }
+}
+
+impl OnResize for ImagePty {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.reader.pty.on_resize(window_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(filter: &mut ImageFilter, chunks: &[&[u8]]) -> (String, String) {
+        let mut output = Vec::new();
+        let mut responses = Vec::new();
+        for chunk in chunks {
+            filter.process(chunk, &mut output, &mut responses);
+        }
+        (
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(responses).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_other_sequences_pass_through() {
+        let images = Arc::new(TerminalImages::default());
+        let mut image_filter = ImageFilter::new(images.clone());
+        let input =
+            "plain \x1b[1mbold\x1b[0m \x1b]0;title\x07\x1b\x1b7 \x1bP=1s\x1b\\ \x1bP$qm\x1b\\";
+        let (output, responses) = filter(&mut image_filter, &[input.as_bytes()]);
+        assert_eq!(output, input);
+        assert_eq!(responses, "");
+
+        // Unrelated APC sequences are dropped, like alacritty would.
+        let (output, _) = filter(&mut image_filter, &[b"a\x1b_Xdata\x1b\\b"]);
+        assert_eq!(output, "ab");
+        assert!(images.0.lock().images.is_empty());
+    }
+
+    #[test]
+    fn test_kitty_image() {
+        let images = Arc::new(TerminalImages::default());
+        images.set_cell_size(size(px(1.), px(1.)));
+        let mut image_filter = ImageFilter::new(images.clone());
+
+        // A 2x2 RGB image, split into two chunks and across reads.
+        let (output, responses) = filter(
+            &mut image_filter,
+            &[
+                b"before\x1b_Ga=T,f=24,s=2,v=2,i=5,m=1;AAAAAAAA\x1b",
+                b"\\\x1b_Gm=0;AAAAAAAA\x1b\\after",
+            ],
+        );
+        assert_eq!(
+            output,
+            "before\
+             \x1b]8;;zed-image:0/0\x1b\\ \x1b]8;;\x1b\\\x08\n\
+             \x1b]8;;zed-image:0/1\x1b\\ \x1b]8;;\x1b\\\x08\x1b[2C\
+             after"
+        );
+        assert_eq!(responses, "\x1b_Gi=5;OK\x1b\\");
+        let image = images.0.lock().images[&0].clone();
+        assert_eq!((image.columns, image.rows), (2, 2));
+        assert_eq!(image.kitty_image_id, Some(5));
+
+        // Placing the stored image again, quietly.
+        let (output, responses) = filter(&mut image_filter, &[b"\x1b_Ga=p,i=5,c=3,C=1,q=1\x1b\\"]);
+        assert_eq!(
+            output,
+            "\x1b]8;;zed-image:1/0\x1b\\ \x1b]8;;\x1b\\\x08\n\
+             \x1b]8;;zed-image:1/1\x1b\\ \x1b]8;;\x1b\\\x08\n\
+             \x1b]8;;zed-image:1/2\x1b\\ \x1b]8;;\x1b\\\x08\x1b[2A"
+        );
+        assert_eq!(responses, "");
+
+        let (_, responses) = filter(&mut image_filter, &[b"\x1b_Ga=p,i=6\x1b\\"]);
+        assert_eq!(responses, "\x1b_Gi=6;ENOENT:image not found\x1b\\");
+
+        filter(&mut image_filter, &[b"\x1b_Ga=d,d=i,i=5\x1b\\"]);
+        assert!(images.0.lock().images.is_empty());
+    }
+
+    #[test]
+    fn test_sixel_image() {
+        let images = Arc::new(TerminalImages::default());
+        images.set_cell_size(size(px(2.), px(4.)));
+        let mut image_filter = ImageFilter::new(images.clone());
+
+        let (output, _) = filter(&mut image_filter, &[b"\x1bPq#0!3~-~\x1b\\next"]);
+        assert_eq!(
+            output,
+            "\x1b]8;;zed-image:0/0\x1b\\ \x1b]8;;\x1b\\\x08\n\
+             \x1b]8;;zed-image:0/1\x1b\\ \x1b]8;;\x1b\\\x08\n\
+             \x1b]8;;zed-image:0/2\x1b\\ \x1b]8;;\x1b\\\x08\r\n\
+             next"
+        );
+        let image = images.0.lock().images[&0].clone();
+        assert_eq!((image.columns, image.rows), (2, 3));
+        assert_eq!(parse_marker_uri("zed-image:0/2"), Some((0, 2)));
+    }
+}
@@ -0,0 +1,363 @@
+//! Handling of Kitty graphics protocol commands, as described in
+//! https://sw.kovidgoyal.net/kitty/graphics-protocol/
+//!
+//! Only direct transmission (`t=d`) of RGB, RGBA and PNG data is supported, optionally zlib
+//! compressed and split into chunks. Unicode placeholders, animation and deleting images by
+//! position are not supported.
+
+use super::{CursorMovement, MAX_SEQUENCE_BYTES, TerminalImage, TerminalImages, render_image};
+use base64::Engine as _;
+use collections::HashMap;
+use gpui::{Pixels, RenderImage, Size};
+use image::{ImageFormat, RgbaImage};
+use std::{fmt::Write as _, io::Read as _, sync::Arc};
+
+/// Transmitted images are dropped once more than this many are stored.
+const MAX_STORED_IMAGES: usize = 128;
+/// Images covering more rows than this are shrunk to fit.
+const MAX_ROWS: u32 = 500;
+
+#[derive(Clone)]
+struct StoredImage {
+    image: Arc<RenderImage>,
+    width: u32,
+    height: u32,
+}
+
+/// The state of the Kitty graphics protocol for a single terminal.
+#[derive(Default)]
+pub(super) struct KittyGraphics {
+    /// Images transmitted with an id, which can be displayed again with `a=p`.
+    images: HashMap<u32, StoredImage>,
+    /// A command whose payload is still being transmitted in chunks.
+    pending: Option<(Command, Vec<u8>)>,
+}
+
+#[derive(Clone)]
+struct Command {
+    action: u8,
+    quiet: u32,
+    format: u32,
+    medium: u8,
+    compression: Option<u8>,
+    width: u32,
+    height: u32,
+    image_id: u32,
+    placement_id: u32,
+    columns: u32,
+    rows: u32,
+    cursor_movement: u32,
+    more: bool,
+    delete: u8,
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Self {
+            action: b't',
+            quiet: 0,
+            format: 32,
+            medium: b'd',
+            compression: None,
+            width: 0,
+            height: 0,
+            image_id: 0,
+            placement_id: 0,
+            columns: 0,
+            rows: 0,
+            cursor_movement: 0,
+            more: false,
+            delete: b'a',
+        }
+    }
+}
+
+impl Command {
+    /// Parses the comma-separated `key=value` pairs preceding the payload.
+    fn parse(control: &[u8]) -> Self {
+        let mut command = Self::default();
+        for pair in control.split(|&byte| byte == b',') {
+            let [key, b'=', value @ ..] = pair else {
+                continue;
+            };
+            let char_value = value.first().copied().unwrap_or(0);
+            let number_value = || {
+                std::str::from_utf8(value)
+                    .ok()
+                    .and_then(|value| value.parse::<u32>().ok())
+                    .unwrap_or(0)
+            };
+            match key {
+                b'a' => command.action = char_value,
+                b'q' => command.quiet = number_value(),
+                b'f' => command.format = number_value(),
+                b't' => command.medium = char_value,
+                b'o' => command.compression = Some(char_value),
+                b's' => command.width = number_value(),
+                b'v' => command.height = number_value(),
+                b'i' => command.image_id = number_value(),
+                b'p' => command.placement_id = number_value(),
+                b'c' => command.columns = number_value(),
+                b'r' => command.rows = number_value(),
+                b'C' => command.cursor_movement = number_value(),
+                b'm' => command.more = number_value() == 1,
+                b'd' => command.delete = char_value,
+                _ => {}
+            }
+        }
+        command
+    }
+}
+
+impl KittyGraphics {
+    /// Handles the body of an APC sequence following its `G`. Images to display are written
+    /// to `output`, replies to the program to `responses`.
+    pub(super) fn handle(
+        &mut self,
+        body: &[u8],
+        images: &TerminalImages,
+        output: &mut Vec<u8>,
+        responses: &mut Vec<u8>,
+    ) {
+        let (control, payload) = match body.iter().position(|&byte| byte == b';') {
+            Some(ix) => (&body[..ix], &body[ix + 1..]),
+            None => (body, &[][..]),
+        };
+        let command = Command::parse(control);
+
+        // Programs must finish transmitting an image before sending other commands, so
+        // anything following a chunk with `m=1` continues its payload.
+        if let Some((first, mut data)) = self.pending.take() {
+            if data.len() + payload.len() > MAX_SEQUENCE_BYTES {
+                respond(&first, Err("EFBIG:image data is too large"), responses);
+                return;
+            }
+            data.extend_from_slice(payload);
+            if command.more {
+                self.pending = Some((first, data));
+            } else {
+                self.execute(first, &data, images, output, responses);
+            }
+        } else if command.more {
+            self.pending = Some((command, payload.to_vec()));
+        } else {
+            self.execute(command, payload, images, output, responses);
+        }
+    }
+
+    fn execute(
+        &mut self,
+        command: Command,
+        payload: &[u8],
+        images: &TerminalImages,
+        output: &mut Vec<u8>,
+        responses: &mut Vec<u8>,
+    ) {
+        match command.action {
+            b'q' => {
+                let result = load(&command, payload).map(|_| ());
+                respond(&command, result, responses);
+            }
+            b't' | b'T' => match load(&command, payload) {
+                Ok(image) => {
+                    if command.image_id != 0 {
+                        if self.images.len() >= MAX_STORED_IMAGES
+                            && let Some(&id) = self.images.keys().next()
+                        {
+                            self.images.remove(&id);
+                        }
+                        self.images.insert(command.image_id, image.clone());
+                    }
+                    if command.action == b'T' {
+                        display(&command, image, images, output);
+                    }
+                    respond(&command, Ok(()), responses);
+                }
+                Err(error) => respond(&command, Err(error), responses),
+            },
+            b'p' => match self.images.get(&command.image_id) {
+                Some(image) => {
+                    display(&command, image.clone(), images, output);
+                    respond(&command, Ok(()), responses);
+                }
+                None => respond(&command, Err("ENOENT:image not found"), responses),
+            },
+            b'd' => match command.delete {
+                b'a' | b'A' => {
+                    images.remove(|_| true);
+                    if command.delete == b'A' {
+                        self.images.clear();
+                    }
+                }
+                b'i' | b'I' => {
+                    images.remove(|image| image.kitty_image_id == Some(command.image_id));
+                    if command.delete == b'I' {
+                        self.images.remove(&command.image_id);
+                    }
+                }
+                _ => {}
+            },
+            _ => respond(&command, Err("EINVAL:unsupported action"), responses),
+        }
+    }
+}
+
+fn load(command: &Command, payload: &[u8]) -> Result<StoredImage, &'static str> {
+    if command.medium != b'd' {
+        return Err("EINVAL:unsupported transmission medium");
+    }
+    let payload = payload
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+    let mut data = base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .map_err(|_| "EINVAL:invalid base64 data")?;
+    match command.compression {
+        None => {}
+        Some(b'z') => {
+            let mut decompressed = Vec::new();
+            flate2::read::ZlibDecoder::new(data.as_slice())
+                .take(MAX_SEQUENCE_BYTES as u64)
+                .read_to_end(&mut decompressed)
+                .map_err(|_| "EINVAL:invalid zlib data")?;
+            data = decompressed;
+        }
+        Some(_) => return Err("EINVAL:unsupported compression"),
+    }
+
+    let image = match command.format {
+        100 => image::load_from_memory_with_format(&data, ImageFormat::Png)
+            .map_err(|_| "EBADPNG:invalid PNG data")?
+            .into_rgba8(),
+        32 => RgbaImage::from_raw(command.width, command.height, data)
+            .ok_or("EINVAL:data does not match the image size")?,
+        24 => {
+            let pixel_count = command.width as usize * command.height as usize;
+            if data.len() < pixel_count * 3 {
+                return Err("EINVAL:data does not match the image size");
+            }
+            let rgba = data
+                .chunks_exact(3)
+                .take(pixel_count)
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+                .collect();
+            RgbaImage::from_raw(command.width, command.height, rgba)
+                .ok_or("EINVAL:data does not match the image size")?
+        }
+        _ => return Err("EINVAL:unsupported format"),
+    };
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return Err("EINVAL:image is empty");
+    }
+    Ok(StoredImage {
+        image: render_image(image),
+        width,
+        height,
+    })
+}
+
+fn display(command: &Command, image: StoredImage, images: &TerminalImages, output: &mut Vec<u8>) {
+    let (columns, rows) = cell_extent(command, &image, images.cell_size());
+    let movement = if command.cursor_movement == 1 {
+        CursorMovement::Stay
+    } else {
+        CursorMovement::AfterImage
+    };
+    images.display(
+        TerminalImage {
+            image: image.image,
+            columns,
+            rows,
+            stretch: command.columns != 0 || command.rows != 0,
+            kitty_image_id: (command.image_id != 0).then_some(command.image_id),
+        },
+        movement,
+        output,
+    );
+}
+
+/// Returns the number of columns and rows covered by an image. When only one of them is
+/// given, the other is derived from the aspect ratio of the image.
+fn cell_extent(command: &Command, image: &StoredImage, cell_size: Size<Pixels>) -> (usize, usize) {
+    let cell_width = f32::from(cell_size.width).max(1.);
+    let cell_height = f32::from(cell_size.height).max(1.);
+    let (width, height) = (image.width as f32, image.height as f32);
+    let (columns, rows) = match (command.columns, command.rows) {
+        (0, 0) => ((width / cell_width).ceil(), (height / cell_height).ceil()),
+        (columns, 0) => {
+            let columns = columns as f32;
+            let rows = columns * cell_width * height / width / cell_height;
+            (columns, rows.ceil())
+        }
+        (0, rows) => {
+            let rows = rows as f32;
+            let columns = rows * cell_height * width / height / cell_width;
+            (columns.ceil(), rows)
+        }
+        (columns, rows) => (columns as f32, rows as f32),
+    };
+    (
+        (columns as usize).max(1),
+        (rows as usize).clamp(1, MAX_ROWS as usize),
+    )
+}
+
+/// Replies to a command that has an image id, unless the program asked for quiet mode.
+fn respond(command: &Command, result: Result<(), &str>, responses: &mut Vec<u8>) {
+    if command.image_id == 0 {
+        return;
+    }
+    let message = match result {
+        Ok(()) if command.quiet == 0 => "OK",
+        Err(error) if command.quiet < 2 => error,
+        _ => return,
+    };
+    let mut response = format!("\x1b_Gi={}", command.image_id);
+    if command.placement_id != 0 {
+        write!(response, ",p={}", command.placement_id).ok();
+    }
+    write!(response, ";{message}\x1b\\").ok();
+    responses.extend_from_slice(response.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{px, size};
+
+    #[test]
+    fn test_parse_command() {
+        let command = Command::parse(b"a=T,f=24,s=10,v=20,i=7,c=3,C=1,m=1,q=2");
+        assert_eq!(command.action, b'T');
+        assert_eq!(command.format, 24);
+        assert_eq!((command.width, command.height), (10, 20));
+        assert_eq!(command.image_id, 7);
+        assert_eq!(command.columns, 3);
+        assert_eq!(command.cursor_movement, 1);
+        assert!(command.more);
+        assert_eq!(command.quiet, 2);
+
+        let command = Command::parse(b"");
+        assert_eq!(command.action, b't');
+        assert_eq!(command.format, 32);
+        assert_eq!(command.medium, b'd');
+    }
+
+    #[test]
+    fn test_cell_extent() {
+        let image = StoredImage {
+            image: render_image(RgbaImage::new(100, 50)),
+            width: 100,
+            height: 50,
+        };
+        let cell_size = size(px(10.), px(20.));
+        let extent = |control: &[u8]| cell_extent(&Command::parse(control), &image, cell_size);
+        assert_eq!(extent(b""), (10, 3));
+        assert_eq!(extent(b"c=20"), (20, 5));
+        assert_eq!(extent(b"r=5"), (20, 5));
+        assert_eq!(extent(b"c=4,r=2"), (4, 2));
+    }
+}
@@ -0,0 +1,253 @@
+//! Decoder for the data of Sixel DCS sequences, as described in
+//! https://vt100.net/docs/vt3xx-gp/chapter14.html
+
+use image::{Rgba, RgbaImage};
+
+/// Images larger than this in either dimension are cropped.
+const MAX_DIMENSION: usize = 10_000;
+const PALETTE_SIZE: usize = 256;
+
+/// The default color registers of the VT340, as RGB percentages.
+const DEFAULT_PALETTE: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [20, 20, 80],
+    [80, 13, 13],
+    [20, 80, 20],
+    [80, 20, 80],
+    [20, 80, 80],
+    [80, 80, 20],
+    [53, 53, 53],
+    [26, 26, 26],
+    [33, 33, 60],
+    [60, 26, 26],
+    [33, 60, 33],
+    [60, 33, 60],
+    [33, 60, 60],
+    [60, 60, 33],
+    [80, 80, 80],
+];
+
+const TRANSPARENT: [u8; 4] = [0, 0, 0, 0];
+
+/// Decodes the data following the `q` of a Sixel sequence. Pixels that no sixel was drawn
+/// over are left transparent. Returns `None` if the image is empty.
+pub(super) fn decode(data: &[u8]) -> Option<RgbaImage> {
+    let mut decoder = Decoder::new();
+    let mut ix = 0;
+    while let Some(&byte) = data.get(ix) {
+        ix += 1;
+        match byte {
+            b'"' => {
+                let params = parse_params(data, &mut ix);
+                decoder.raster_width = params.get(2).copied().unwrap_or(0);
+                decoder.raster_height = params.get(3).copied().unwrap_or(0);
+            }
+            b'#' => {
+                let params = parse_params(data, &mut ix);
+                let Some(&register) = params.first() else {
+                    continue;
+                };
+                decoder.color = register % PALETTE_SIZE;
+                if let [_, space, x, y, z, ..] = params[..] {
+                    decoder.define_color(space, x, y, z);
+                }
+            }
+            b'!' => {
+                let count = parse_params(data, &mut ix).first().copied().unwrap_or(1);
+                if let Some(&sixel @ 0x3F..=0x7E) = data.get(ix) {
+                    ix += 1;
+                    decoder.draw(sixel - 0x3F, count.max(1));
+                }
+            }
+            b'$' => decoder.x = 0,
+            b'-' => {
+                decoder.x = 0;
+                decoder.y += 6;
+            }
+            0x3F..=0x7E => decoder.draw(byte - 0x3F, 1),
+            _ => {}
+        }
+    }
+    decoder.finish()
+}
+
+struct Decoder {
+    palette: Vec<[u8; 4]>,
+    color: usize,
+    x: usize,
+    y: usize,
+    raster_width: usize,
+    raster_height: usize,
+    rows: Vec<Vec<[u8; 4]>>,
+}
+
+impl Decoder {
+    fn new() -> Self {
+        let mut palette = vec![[0, 0, 0, 255]; PALETTE_SIZE];
+        for (register, [r, g, b]) in palette.iter_mut().zip(DEFAULT_PALETTE) {
+            *register = [
+                percent(r as usize),
+                percent(g as usize),
+                percent(b as usize),
+                255,
+            ];
+        }
+        Self {
+            palette,
+            color: 0,
+            x: 0,
+            y: 0,
+            raster_width: 0,
+            raster_height: 0,
+            rows: Vec::new(),
+        }
+    }
+
+    fn define_color(&mut self, space: usize, x: usize, y: usize, z: usize) {
+        let [r, g, b] = match space {
+            1 => hls_to_rgb(x, y, z),
+            2 => [percent(x), percent(y), percent(z)],
+            _ => return,
+        };
+        self.palette[self.color] = [r, g, b, 255];
+    }
+
+    fn draw(&mut self, bits: u8, count: usize) {
+        let start = self.x.min(MAX_DIMENSION);
+        let end = self.x.saturating_add(count).min(MAX_DIMENSION);
+        self.x = self.x.saturating_add(count);
+        if bits == 0 || start == end {
+            return;
+        }
+
+        let color = self.palette[self.color];
+        for bit in 0..6 {
+            let y = self.y + bit;
+            if bits & (1 << bit) == 0 || y >= MAX_DIMENSION {
+                continue;
+            }
+            if self.rows.len() <= y {
+                self.rows.resize_with(y + 1, Vec::new);
+            }
+            let row = &mut self.rows[y];
+            if row.len() < end {
+                row.resize(end, TRANSPARENT);
+            }
+            row[start..end].fill(color);
+        }
+    }
+
+    fn finish(self) -> Option<RgbaImage> {
+        let drawn_width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let width = drawn_width.max(self.raster_width).min(MAX_DIMENSION);
+        let height = self.rows.len().max(self.raster_height).min(MAX_DIMENSION);
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some(RgbaImage::from_fn(width as u32, height as u32, |x, y| {
+            let pixel = self
+                .rows
+                .get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                .copied()
+                .unwrap_or(TRANSPARENT);
+            Rgba(pixel)
+        }))
+    }
+}
+
+/// Parses a `;`-separated list of numbers, leaving `ix` at the first byte following it.
+fn parse_params(data: &[u8], ix: &mut usize) -> Vec<usize> {
+    let mut params = Vec::new();
+    let mut current = None;
+    while let Some(&byte) = data.get(*ix) {
+        match byte {
+            b'0'..=b'9' => {
+                let digit = (byte - b'0') as usize;
+                current = Some(
+                    current
+                        .unwrap_or(0usize)
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+            }
+            b';' => params.push(current.take().unwrap_or(0)),
+            _ => break,
+        }
+        *ix += 1;
+    }
+    params.extend(current);
+    params
+}
+
+fn percent(value: usize) -> u8 {
+    (value.min(100) * 255 / 100) as u8
+}
+
+/// Converts a Sixel HLS color, whose hues start at blue rather than red, to RGB.
+fn hls_to_rgb(hue: usize, lightness: usize, saturation: usize) -> [u8; 3] {
+    let hue = ((hue + 240) % 360) as f32 / 360.;
+    let lightness = lightness.min(100) as f32 / 100.;
+    let saturation = saturation.min(100) as f32 / 100.;
+    if saturation == 0. {
+        let value = (lightness * 255.).round() as u8;
+        return [value; 3];
+    }
+
+    let q = if lightness < 0.5 {
+        lightness * (1. + saturation)
+    } else {
+        lightness + saturation - lightness * saturation
+    };
+    let p = 2. * lightness - q;
+    let channel = |t: f32| {
+        let t = t.rem_euclid(1.);
+        let value = if t < 1. / 6. {
+            p + (q - p) * 6. * t
+        } else if t < 1. / 2. {
+            q
+        } else if t < 2. / 3. {
+            p + (q - p) * (2. / 3. - t) * 6.
+        } else {
+            p
+        };
+        (value * 255.).round() as u8
+    };
+    [channel(hue + 1. / 3.), channel(hue), channel(hue - 1. / 3.)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_sixels() {
+        // A 3x6 image: a red column, a skipped column, then a green column drawn over two
+        // passes of the same sixel row.
+        let image = decode(b"\"1;1;3;6#1;2;100;0;0#2;2;0;100;0#1~$!2?#2F-").unwrap();
+        assert_eq!(image.dimensions(), (3, 6));
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(0, 5).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 0).0, TRANSPARENT);
+        assert_eq!(image.get_pixel(2, 0).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(2, 2).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(2, 3).0, TRANSPARENT);
+
+        // Repeats and line feeds extend the image past its raster attributes.
+        let image = decode(b"\"1;1;1;1#0!4@-@").unwrap();
+        assert_eq!(image.dimensions(), (4, 7));
+        assert_eq!(image.get_pixel(3, 0).0, [0, 0, 0, 255]);
+        assert_eq!(image.get_pixel(0, 6).0, [0, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 6).0, TRANSPARENT);
+
+        assert!(decode(b"#0;2;0;0;0").is_none());
+    }
+
+    #[test]
+    fn test_hls_colors() {
+        assert_eq!(hls_to_rgb(0, 50, 100), [0, 0, 255]);
+        assert_eq!(hls_to_rgb(120, 50, 100), [255, 0, 0]);
+        assert_eq!(hls_to_rgb(240, 50, 100), [0, 255, 0]);
+        assert_eq!(hls_to_rgb(0, 100, 0), [255, 255, 255]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Copies the text written to a terminal to subscribers, such as a CLI waiting for a task.
+//!
+//! The text is taken from the PTY output as it is read, rather than from the terminal grid, so
+//! subscribers see every line even after it has been evicted from the scrollback, and lines
+//! that were soft wrapped by the grid are kept whole.
+
+use alacritty_terminal::vte::{Parser, Perform};
+use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
+use parking_lot::Mutex;
+use std::mem;
+
+/// Output is kept for the first subscriber of a task terminal, as the task starts running before
+/// anyone can subscribe. Once the kept output grows past this, its oldest lines are dropped.
+const MAX_BACKLOG_BYTES: usize = 4 * 1024 * 1024;
+
+/// The lines of text written to a terminal, shared between the thread reading its PTY and the UI.
+#[derive(Default)]
+pub(crate) struct TerminalOutput(Mutex<TerminalOutputState>);
+
+#[derive(Default)]
+struct TerminalOutputState {
+    parser: Parser,
+    text: OutputText,
+    subscribers: Vec<UnboundedSender<Vec<String>>>,
+    backlog: Option<Backlog>,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Backlog {
+    lines: Vec<String>,
+    bytes: usize,
+}
+
+/// Collects the printed characters of the output into lines, dropping escape sequences.
+#[derive(Default)]
+struct OutputText {
+    line: String,
+    /// Whether a carriage return was written, so that the line is overwritten by the next
+    /// character printed, like a progress bar would be.
+    carriage_return: bool,
+    lines: Vec<String>,
+}
+
+impl Perform for OutputText {
+    fn print(&mut self, c: char) {
+        if mem::take(&mut self.carriage_return) {
+            self.line.clear();
+        }
+        self.line.push(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' => {
+                self.carriage_return = false;
+                self.lines.push(mem::take(&mut self.line));
+            }
+            b'\r' => self.carriage_return = true,
+            b'\t' => self.print('\t'),
+            _ => {}
+        }
+    }
+}
+
+impl TerminalOutput {
+    /// Creates the output of a task terminal, which is kept until the first subscriber arrives.
+    pub(crate) fn with_backlog() -> Self {
+        Self(Mutex::new(TerminalOutputState {
+            backlog: Some(Backlog::default()),
+            ..TerminalOutputState::default()
+        }))
+    }
+
+    /// Returns a stream of the complete lines written to the terminal from now on, or since the
+    /// task started for the first subscriber of a task terminal.
+    ///
+    /// The stream ends once the terminal stops reading output, after sending its last line.
+    pub(crate) fn subscribe(&self) -> UnboundedReceiver<Vec<String>> {
+        let mut state = self.0.lock();
+        let (tx, rx) = unbounded();
+        if let Some(backlog) = state.backlog.take()
+            && !backlog.lines.is_empty()
+        {
+            tx.unbounded_send(backlog.lines).ok();
+        }
+        if !state.closed {
+            state.subscribers.push(tx);
+        }
+        rx
+    }
+
+    pub(crate) fn write(&self, bytes: &[u8]) {
+        let mut state = self.0.lock();
+        if state.closed || (state.subscribers.is_empty() && state.backlog.is_none()) {
+            return;
+        }
+        let TerminalOutputState { parser, text, .. } = &mut *state;
+        parser.advance(text, bytes);
+        let lines = mem::take(&mut state.text.lines);
+        state.send(lines);
+    }
+
+    /// Sends the last line, if it was not terminated, and ends the subscribers' streams.
+    pub(crate) fn close(&self) {
+        let mut state = self.0.lock();
+        if mem::replace(&mut state.closed, true) {
+            return;
+        }
+        let line = mem::take(&mut state.text.line);
+        if !line.is_empty() {
+            state.send(vec![line]);
+        }
+        state.subscribers.clear();
+    }
+}
+
+impl TerminalOutputState {
+    fn send(&mut self, lines: Vec<String>) {
+        if lines.is_empty() {
+            return;
+        }
+        if let Some(backlog) = &mut self.backlog {
+            backlog.bytes += lines.iter().map(String::len).sum::<usize>();
+            backlog.lines.extend(lines);
+            let mut dropped = 0;
+            while backlog.bytes > MAX_BACKLOG_BYTES {
+                backlog.bytes -= backlog.lines[dropped].len();
+                dropped += 1;
+            }
+            backlog.lines.drain(..dropped);
+            return;
+        }
+        self.subscribers
+            .retain(|subscriber| subscriber.unbounded_send(lines.clone()).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn received(rx: &mut UnboundedReceiver<Vec<String>>) -> Vec<String> {
+        let mut lines = Vec::new();
+        while let Ok(Some(chunk)) = rx.try_next() {
+            lines.extend(chunk);
+        }
+        lines
+    }
+
+    #[test]
+    fn test_output_lines() {
+        let output = TerminalOutput::default();
+        output.write(b"dropped\n");
+        let mut rx = output.subscribe();
+
+        output.write(b"\x1b[1mbold\x1b[0m text\r\nprogress 1%\rprogress 100%\nunfinished");
+        assert_eq!(received(&mut rx), ["bold text", "progress 100%"]);
+
+        output.write(b" line");
+        assert_eq!(received(&mut rx), Vec::<String>::new());
+
+        output.close();
+        assert_eq!(received(&mut rx), ["unfinished line"]);
+        assert_eq!(rx.try_next().unwrap(), None);
+    }
+
+    #[test]
+    fn test_output_backlog() {
+        let output = TerminalOutput::with_backlog();
+        output.write(b"first\nsecond\n");
+        let mut first = output.subscribe();
+        let mut second = output.subscribe();
+        output.write(b"third\n");
+        assert_eq!(received(&mut first), ["first", "second", "third"]);
+        assert_eq!(received(&mut second), ["third"]);
+    }
+}
@@ -4,14 +4,14 @@ use gpui::{
     Element, ElementId, Entity, FocusHandle, Font, FontFeatures, FontStyle, FontWeight,
     GlobalElementId, HighlightStyle, Hitbox, Hsla, InputHandler, InteractiveElement, Interactivity,
     IntoElement, LayoutId, Length, ModifiersChangedEvent, MouseButton, MouseMoveEvent, Pixels,
-    Point, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun, TextStyle,
+    Point, RenderImage, StatefulInteractiveElement, StrikethroughStyle, Styled, TextRun, TextStyle,
     UTF16Selection, UnderlineStyle, WeakEntity, WhiteSpace, Window, div, fill, point, px, relative,
     size,
 };
 use itertools::Itertools;
 use language::CursorShape;
 use settings::Settings;
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 use terminal::{
     IndexedCell, Terminal, TerminalBounds, TerminalContent,
     alacritty_terminal::{
@@ -23,6 +23,7 @@ use terminal::{
             CursorShape as AlacCursorShape, NamedColor,
         },
     },
+    terminal_images::TerminalImagePlacement,
     terminal_settings::TerminalSettings,
 };
 use theme::{ActiveTheme, Theme, ThemeSettings};
@@ -41,6 +42,7 @@ pub struct LayoutState {
     hitbox: Hitbox,
     batched_text_runs: Vec<BatchedTextRun>,
     rects: Vec<LayoutRect>,
+    images: Vec<(Bounds<Pixels>, Arc<RenderImage>)>,
     relative_highlighted_ranges: Vec<(RangeInclusive<AlacPoint>, Hsla)>,
     cursor: Option<CursorLayout>,
     ime_cursor_bounds: Option<Bounds<Pixels>>,
@@ -1030,10 +1032,15 @@ impl Element for TerminalElement {
                     cursor_char,
                     selection,
                     cursor,
+                    images,
                     ..
                 } = &self.terminal.read(cx).last_content;
                 let mode = *mode;
                 let display_offset = *display_offset;
+                let images = images
+                    .iter()
+                    .map(|placement| layout_image(placement, display_offset, &dimensions))
+                    .collect();
 
                 // searches, highlights to a single range representations
                 let mut relative_highlighted_ranges = Vec::new();
@@ -1214,6 +1221,7 @@ impl Element for TerminalElement {
                     background_color,
                     dimensions,
                     rects,
+                    images,
                     relative_highlighted_ranges,
                     mode,
                     display_offset,
@@ -1330,6 +1338,18 @@ impl Element for TerminalElement {
                     }
                     let text_paint_time = text_paint_start.elapsed();
 
+                    for (image_bounds, image) in &layout.images {
+                        window
+                            .paint_image(
+                                *image_bounds + origin,
+                                Default::default(),
+                                image.clone(),
+                                0,
+                                false,
+                            )
+                            .log_err();
+                    }
+
                     if let Some(text_to_mark) = &marked_text_cloned
                         && !text_to_mark.is_empty()
                         && let Some(ime_bounds) = layout.ime_cursor_bounds
@@ -1552,6 +1572,38 @@ pub fn is_blank(cell: &IndexedCell) -> bool {
     true
 }
 
+/// Returns the bounds of an image relative to the origin of the terminal content. Images that
+/// aren't stretched are drawn at their own size, shrunk to fit within their cells if needed.
+fn layout_image(
+    placement: &TerminalImagePlacement,
+    display_offset: usize,
+    dimensions: &TerminalBounds,
+) -> (Bounds<Pixels>, Arc<RenderImage>) {
+    let image = &placement.image;
+    let origin = point(
+        placement.point.column.0 as f32 * dimensions.cell_width,
+        (placement.point.line.0 + display_offset as i32) as f32 * dimensions.line_height,
+    );
+    let cells_size = size(
+        image.columns as f32 * dimensions.cell_width,
+        image.rows as f32 * dimensions.line_height,
+    );
+    let image_size = if image.stretch {
+        cells_size
+    } else {
+        let pixel_size = image.image.size(0);
+        let natural_size = size(
+            px(pixel_size.width.0 as f32),
+            px(pixel_size.height.0 as f32),
+        );
+        let scale = (cells_size.width / natural_size.width)
+            .min(cells_size.height / natural_size.height)
+            .min(1.);
+        size(natural_size.width * scale, natural_size.height * scale)
+    };
+    (Bounds::new(origin, image_size), image.image.clone())
+}
+
 fn to_highlighted_range_lines(
     range: &RangeInclusive<AlacPoint>,
     layout: &LayoutState,
@@ -1653,6 +1653,7 @@ impl SearchableItem for TerminalView {
             replacement: false,
             selection: false,
             find_in_results: false,
+            scope: false,
         }
     }
 
@@ -21,6 +21,7 @@ use db::{
 };
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use project::{
+    bookmark_store::SourceBookmark,
     debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
     trusted_worktrees::{DbTrustedPaths, RemoteHostLocation},
 };
@@ -970,6 +971,17 @@ impl Domain for WorkspaceDb {
         sql!(
             ALTER TABLE remote_connections ADD COLUMN use_podman BOOLEAN;
         ),
+        sql!(
+            CREATE TABLE bookmarks (
+                workspace_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                bookmark_row INTEGER NOT NULL,
+                label TEXT,
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            );
+        ),
     ];
 
     // Allow recovering from bad migration that was initially shipped to nightly
@@ -1105,6 +1117,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            bookmarks: self.bookmarks(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1195,6 +1208,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            bookmarks: self.bookmarks(workspace_id),
             window_id,
             user_toolchains: self.user_toolchains(workspace_id, remote_connection_id),
         })
@@ -1246,6 +1260,36 @@ impl WorkspaceDb {
         }
     }
 
+    fn bookmarks(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SourceBookmark>> {
+        let bookmarks: Result<Vec<(PathBuf, u32, Option<String>)>> = self
+            .select_bound(sql! {
+                SELECT path, bookmark_row, label
+                FROM bookmarks
+                WHERE workspace_id = ?
+                ORDER BY path, bookmark_row
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match bookmarks {
+            Ok(bookmarks) => {
+                let mut map: BTreeMap<Arc<Path>, Vec<SourceBookmark>> = Default::default();
+                for (path, row, label) in bookmarks {
+                    map.entry(Arc::from(path))
+                        .or_default()
+                        .push(SourceBookmark {
+                            row,
+                            label: label.map(Arc::from),
+                        });
+                }
+                map
+            }
+            Err(msg) => {
+                log::error!("Bookmarks query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     fn user_toolchains(
         &self,
         workspace_id: WorkspaceId,
@@ -1374,6 +1418,28 @@ impl WorkspaceDb {
                     }
                 }
 
+                conn.exec_bound(
+                    sql!(
+                        DELETE FROM bookmarks WHERE workspace_id = ?1;
+                    )
+                )?(workspace.id).context("Clearing old bookmarks")?;
+
+                for (path, bookmarks) in workspace.bookmarks {
+                    for bookmark in bookmarks {
+                        if let Err(err) = conn.exec_bound(sql!(
+                            INSERT INTO bookmarks (workspace_id, path, bookmark_row, label)
+                            VALUES (?1, ?2, ?3, ?4);))?
+                        ((
+                            workspace.id,
+                            path.as_ref(),
+                            bookmark.row,
+                            bookmark.label.as_deref(),
+                        )) {
+                            log::error!("{err}");
+                        }
+                    }
+                }
+
                 conn.exec_bound(
                     sql!(
                         DELETE FROM user_toolchains WHERE workspace_id = ?1;
@@ -2568,6 +2634,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2646,6 +2713,65 @@ mod tests {
         assert_eq!(loaded_breakpoints[4].path, Arc::from(path));
     }
 
+    #[gpui::test]
+    async fn test_bookmarks() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_bookmarks").await;
+        let id = db.next_id().await.unwrap();
+
+        let first_path: Arc<Path> = Arc::from(Path::new("/tmp/a.rs"));
+        let second_path: Arc<Path> = Arc::from(Path::new("/tmp/b.rs"));
+        let bookmarks = collections::BTreeMap::from_iter([
+            (
+                first_path.clone(),
+                vec![
+                    SourceBookmark {
+                        row: 3,
+                        label: None,
+                    },
+                    SourceBookmark {
+                        row: 10,
+                        label: Some("entry point".into()),
+                    },
+                ],
+            ),
+            (
+                second_path.clone(),
+                vec![SourceBookmark {
+                    row: 0,
+                    label: None,
+                }],
+            ),
+        ]);
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            paths: PathList::new(&["/tmp"]),
+            location: SerializedWorkspaceLocation::Local,
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            bookmarks: bookmarks.clone(),
+            session_id: None,
+            window_id: None,
+            user_toolchains: Default::default(),
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks, bookmarks);
+
+        workspace.bookmarks.remove(&first_path);
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.bookmarks.len(), 1);
+        assert!(loaded.bookmarks.contains_key(&second_path));
+    }
+
     #[gpui::test]
     async fn test_remove_last_breakpoint() {
         zlog::init_test();
@@ -2689,6 +2815,7 @@ mod tests {
             },
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2723,6 +2850,7 @@ mod tests {
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2821,6 +2949,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2836,6 +2965,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2943,6 +3073,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(999),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2977,6 +3108,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(1),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -2992,6 +3124,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3034,6 +3167,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: Some(3),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3072,6 +3206,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3087,6 +3222,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3102,6 +3238,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3117,6 +3254,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3143,6 +3281,7 @@ mod tests {
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3158,6 +3297,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("session-id-3".to_owned()),
             window_id: Some(60),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3215,6 +3355,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         }
     }
@@ -3256,6 +3397,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -3368,6 +3510,7 @@ mod tests {
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .collect::<Vec<_>>();
@@ -3729,6 +3872,7 @@ mod tests {
             centered_layout: false,
             session_id: None,
             window_id: None,
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         };
 
@@ -3805,6 +3949,7 @@ mod tests {
                 session_id: Some("test-session".to_owned()),
                 breakpoints: Default::default(),
                 window_id: Some(*window_id),
+                bookmarks: Default::default(),
                 user_toolchains: Default::default(),
             })
             .await;
@@ -4146,6 +4291,7 @@ mod tests {
             session_id: Some("remove-test-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(99),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4228,6 +4374,7 @@ mod tests {
             session_id: Some(session_id.to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4244,6 +4391,7 @@ mod tests {
             session_id: Some(session_id.to_owned()),
             breakpoints: Default::default(),
             window_id: Some(window_id_val),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
@@ -4322,6 +4470,7 @@ mod tests {
             session_id: Some("pending-removal-session".to_owned()),
             breakpoints: Default::default(),
             window_id: Some(88),
+            bookmarks: Default::default(),
             user_toolchains: Default::default(),
         })
         .await;
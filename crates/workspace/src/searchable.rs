@@ -56,6 +56,8 @@ pub struct SearchOptions {
     pub replacement: bool,
     pub selection: bool,
     pub find_in_results: bool,
+    /// Specifies whether matches can be restricted to code, comments or strings.
+    pub scope: bool,
 }
 
 // Whether to always select the current selection (even if empty)
@@ -79,6 +81,7 @@ pub trait SearchableItem: Item + EventEmitter<SearchEvent> {
             replacement: true,
             selection: true,
             find_in_results: false,
+            scope: false,
         }
     }
 
@@ -685,6 +685,10 @@ fn main() {
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        hierarchy_panel::init(cx);
+        bookmarks_panel::init(cx);
+        local_history::init(app_state.fs.clone(), cx);
+        editor_macros::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -0,0 +1,487 @@
+//! Handlers for the `zed` CLI requests that query or control a running instance
+//! instead of opening paths, e.g. `zed --list-buffers` or `zed --run-task build`.
+//!
+//! Results are printed by the CLI as JSON documents sent through [`CliResponse::Stdout`].
+
+use anyhow::{Context as _, Result};
+use cli::{CliResponse, ipc::IpcSender};
+use futures::StreamExt as _;
+use gpui::{App, AsyncApp, Entity, WindowHandle};
+use language::{DiagnosticSeverity, Point};
+use project::{Project, ProjectPath};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use task::TaskContext;
+use terminal_view::terminal_panel::TerminalPanel;
+use workspace::{MultiWorkspace, Workspace};
+
+#[derive(Serialize)]
+struct WorkspaceInfo {
+    window_id: u64,
+    active: bool,
+    paths: Vec<PathBuf>,
+    remote: bool,
+}
+
+#[derive(Serialize)]
+struct BufferInfo {
+    window_id: u64,
+    path: Option<PathBuf>,
+    language: Option<String>,
+    dirty: bool,
+    conflicted: bool,
+}
+
+/// Positions are one-based, matching the `path:line:column` syntax accepted by the CLI.
+#[derive(Serialize)]
+struct DiagnosticInfo {
+    severity: &'static str,
+    message: String,
+    source: Option<String>,
+    code: Option<String>,
+    start: Position,
+    end: Position,
+}
+
+#[derive(Serialize)]
+struct Position {
+    line: u32,
+    column: u32,
+}
+
+impl From<Point> for Position {
+    fn from(point: Point) -> Self {
+        Self {
+            line: point.row + 1,
+            column: point.column + 1,
+        }
+    }
+}
+
+/// Returns the windows containing workspaces, front-most first.
+fn multi_workspace_windows(cx: &App) -> Vec<WindowHandle<MultiWorkspace>> {
+    cx.window_stack()
+        .unwrap_or_else(|| cx.windows())
+        .into_iter()
+        .filter_map(|window| window.downcast::<MultiWorkspace>())
+        .collect()
+}
+
+fn all_workspaces(cx: &App) -> Vec<(WindowHandle<MultiWorkspace>, Entity<Workspace>)> {
+    multi_workspace_windows(cx)
+        .into_iter()
+        .filter_map(|window| {
+            let workspaces = window.read(cx).ok()?.workspaces().to_vec();
+            Some(
+                workspaces
+                    .into_iter()
+                    .map(move |workspace| (window, workspace)),
+            )
+        })
+        .flatten()
+        .collect()
+}
+
+fn active_workspace(cx: &App) -> Result<(WindowHandle<MultiWorkspace>, Entity<Workspace>)> {
+    multi_workspace_windows(cx)
+        .into_iter()
+        .find_map(|window| {
+            let workspace = window.read(cx).ok()?.workspace().clone();
+            Some((window, workspace))
+        })
+        .context("no workspace is open")
+}
+
+fn send_json(responses: &IpcSender<CliResponse>, value: &impl Serialize) -> Result<()> {
+    let message = serde_json::to_string_pretty(value)?;
+    responses
+        .send(CliResponse::Stdout { message })
+        .context("sending response to the CLI")
+}
+
+pub(crate) fn list_workspaces(
+    responses: &IpcSender<CliResponse>,
+    cx: &mut AsyncApp,
+) -> Result<i32> {
+    let workspaces = cx.update(|cx| {
+        let active_window = cx.active_window();
+        all_workspaces(cx)
+            .into_iter()
+            .map(|(window, workspace)| {
+                let is_active_workspace = window
+                    .read(cx)
+                    .is_ok_and(|multi_workspace| multi_workspace.workspace() == &workspace);
+                let workspace = workspace.read(cx);
+                WorkspaceInfo {
+                    window_id: window.window_id().as_u64(),
+                    active: is_active_workspace && active_window == Some(window.into()),
+                    paths: workspace
+                        .root_paths(cx)
+                        .into_iter()
+                        .map(|path| path.to_path_buf())
+                        .collect(),
+                    remote: !workspace.project().read(cx).is_local(),
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+    send_json(responses, &workspaces)?;
+    Ok(0)
+}
+
+pub(crate) fn list_buffers(responses: &IpcSender<CliResponse>, cx: &mut AsyncApp) -> Result<i32> {
+    let buffers = cx.update(|cx| {
+        all_workspaces(cx)
+            .into_iter()
+            .flat_map(|(window, workspace)| {
+                let project = workspace.read(cx).project().clone();
+                let buffers = project
+                    .read(cx)
+                    .buffer_store()
+                    .read(cx)
+                    .buffers()
+                    .collect::<Vec<_>>();
+                buffers
+                    .into_iter()
+                    .map(|buffer| {
+                        let buffer = buffer.read(cx);
+                        let path = buffer.file().and_then(|file| {
+                            project
+                                .read(cx)
+                                .absolute_path(&ProjectPath::from_file(file.as_ref(), cx), cx)
+                        });
+                        BufferInfo {
+                            window_id: window.window_id().as_u64(),
+                            path,
+                            language: buffer
+                                .language()
+                                .map(|language| language.name().to_string()),
+                            dirty: buffer.is_dirty(),
+                            conflicted: buffer.has_conflict(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    });
+    send_json(responses, &buffers)?;
+    Ok(0)
+}
+
+pub(crate) fn dispatch_action(
+    name: &str,
+    arguments: Option<&str>,
+    cx: &mut AsyncApp,
+) -> Result<i32> {
+    let arguments = arguments
+        .map(serde_json::from_str::<serde_json::Value>)
+        .transpose()
+        .context("parsing action arguments")?;
+    cx.update(|cx| {
+        let action = cx
+            .build_action(name, arguments)
+            .map_err(|error| anyhow::anyhow!("{error}"))?;
+        let window = cx
+            .window_stack()
+            .unwrap_or_else(|| cx.windows())
+            .into_iter()
+            .next()
+            .context("no window is open")?;
+        window.update(cx, |_, window, cx| window.dispatch_action(action, cx))
+    })?;
+    Ok(0)
+}
+
+pub(crate) async fn diagnostics(
+    path: &str,
+    responses: &IpcSender<CliResponse>,
+    cx: &mut AsyncApp,
+) -> Result<i32> {
+    let path = Path::new(path);
+    let (project, project_path) = cx
+        .update(|cx| find_project_path(path, cx))
+        .with_context(|| format!("{path:?} is not part of any open project"))?;
+    let buffer = project
+        .update(cx, |project, cx| project.open_buffer(project_path, cx))
+        .await?;
+    let diagnostics = buffer.read_with(cx, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        snapshot
+            .diagnostics_in_range::<_, Point>(0..snapshot.len(), false)
+            .map(|entry| {
+                let diagnostic = entry.diagnostic;
+                DiagnosticInfo {
+                    severity: match diagnostic.severity {
+                        DiagnosticSeverity::ERROR => "error",
+                        DiagnosticSeverity::WARNING => "warning",
+                        DiagnosticSeverity::INFORMATION => "information",
+                        _ => "hint",
+                    },
+                    message: diagnostic.message.clone(),
+                    source: diagnostic.source.clone(),
+                    code: diagnostic.code.as_ref().map(|code| match code {
+                        lsp::NumberOrString::Number(code) => code.to_string(),
+                        lsp::NumberOrString::String(code) => code.clone(),
+                    }),
+                    start: entry.range.start.into(),
+                    end: entry.range.end.into(),
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+    send_json(responses, &diagnostics)?;
+    Ok(0)
+}
+
+fn find_project_path(path: &Path, cx: &App) -> Option<(Entity<Project>, ProjectPath)> {
+    all_workspaces(cx).into_iter().find_map(|(_, workspace)| {
+        let project = workspace.read(cx).project().clone();
+        let project_path = project.read(cx).find_project_path(path, cx)?;
+        Some((project, project_path))
+    })
+}
+
+/// Runs the task with the given label in the front-most workspace, returning its exit status.
+pub(crate) async fn run_task(
+    label: &str,
+    responses: &IpcSender<CliResponse>,
+    cx: &mut AsyncApp,
+) -> Result<i32> {
+    let (window, workspace) = cx.update(|cx| active_workspace(cx))?;
+    let task_contexts = window.update(cx, |_, window, cx| {
+        workspace.update(cx, |workspace, cx| {
+            tasks_ui::task_contexts(workspace, window, cx)
+        })
+    })?;
+    let task_contexts = task_contexts.await;
+    let tasks = workspace
+        .update(cx, |workspace, cx| {
+            let Some(task_inventory) = workspace
+                .project()
+                .read(cx)
+                .task_store()
+                .read(cx)
+                .task_inventory()
+                .cloned()
+            else {
+                return gpui::Task::ready(Vec::new());
+            };
+            let (file, language) = task_contexts
+                .location()
+                .map(|location| {
+                    let buffer = location.buffer.read(cx);
+                    (
+                        buffer.file().cloned(),
+                        buffer.language_at(location.range.start),
+                    )
+                })
+                .unwrap_or_default();
+            task_inventory
+                .read(cx)
+                .list_tasks(file, language, task_contexts.worktree(), cx)
+        })
+        .await;
+
+    let (task_source_kind, task_template) = tasks
+        .into_iter()
+        .find(|(_, task)| task.label == label)
+        .with_context(|| format!("no task with label {label:?}"))?;
+    let task_context = task_contexts
+        .active_context()
+        .cloned()
+        .unwrap_or_else(TaskContext::default);
+    let resolved_task = task_template
+        .resolve_task(&task_source_kind.to_id_base(), &task_context)
+        .with_context(|| format!("failed to resolve task {label:?}"))?;
+
+    let terminal_panel = workspace
+        .read_with(cx, |workspace, cx| workspace.panel::<TerminalPanel>(cx))
+        .context("the terminal panel is not loaded")?;
+    workspace.update(cx, |workspace, cx| {
+        if let Some(task_inventory) = workspace
+            .project()
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        {
+            task_inventory.update(cx, |inventory, _| {
+                inventory.task_scheduled(task_source_kind, resolved_task.clone());
+            })
+        }
+    });
+    let terminal = window
+        .update(cx, |_, window, cx| {
+            terminal_panel.update(cx, |terminal_panel, cx| {
+                terminal_panel.spawn_task(&resolved_task.resolved, window, cx)
+            })
+        })?
+        .await
+        .with_context(|| format!("failed to spawn task {label:?}"))?;
+
+    // Forward the task's output as it is read from the terminal, rather than from its grid, as
+    // the scrollback may be shorter than the output.
+    let mut output = terminal.read_with(cx, |terminal, _| terminal.subscribe_to_output())?;
+    let completion = terminal.read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))?;
+    while let Some(lines) = output.next().await {
+        responses
+            .send(CliResponse::Stdout {
+                message: lines.join("\n"),
+            })
+            .context("sending response to the CLI")?;
+    }
+    let status = completion.await;
+
+    match status {
+        Some(status) => Ok(status.code().unwrap_or(1)),
+        None => anyhow::bail!("task {label:?} was cancelled"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zed::tests::init_test;
+    use cli::ipc::{self, IpcReceiver};
+    use gpui::TestAppContext;
+    use language::{Diagnostic, DiagnosticEntry, DiagnosticSourceKind, PointUtf16, Unclipped};
+    use lsp::LanguageServerId;
+    use serde_json::{Value, json};
+    use util::path;
+
+    async fn open_root(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(
+                path!("/root"),
+                json!({
+                    "a.rs": "fn a() {}\n",
+                    "b.rs": "",
+                }),
+            )
+            .await;
+        cx.update(|cx| {
+            workspace::open_paths(
+                &[
+                    PathBuf::from(path!("/root")),
+                    PathBuf::from(path!("/root/a.rs")),
+                ],
+                app_state,
+                workspace::OpenOptions::default(),
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+        cx.run_until_parked();
+    }
+
+    fn receive_json(responses: &IpcReceiver<CliResponse>) -> Value {
+        match responses.try_recv().unwrap() {
+            CliResponse::Stdout { message } => serde_json::from_str(&message).unwrap(),
+            response => panic!("unexpected response {response:?}"),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_list_workspaces_and_buffers(cx: &mut TestAppContext) {
+        open_root(cx).await;
+        let (response_tx, response_rx) = ipc::channel::<CliResponse>().unwrap();
+
+        assert_eq!(
+            list_workspaces(&response_tx, &mut cx.to_async()).unwrap(),
+            0
+        );
+        let workspaces = receive_json(&response_rx);
+        assert_eq!(workspaces.as_array().unwrap().len(), 1);
+        assert_eq!(workspaces[0]["paths"], json!([path!("/root")]));
+        assert_eq!(workspaces[0]["remote"], json!(false));
+
+        assert_eq!(list_buffers(&response_tx, &mut cx.to_async()).unwrap(), 0);
+        let buffers = receive_json(&response_rx);
+        assert_eq!(buffers.as_array().unwrap().len(), 1);
+        assert_eq!(buffers[0]["path"], json!(path!("/root/a.rs")));
+        assert_eq!(buffers[0]["dirty"], json!(false));
+    }
+
+    #[gpui::test]
+    async fn test_dispatch_unknown_action(cx: &mut TestAppContext) {
+        open_root(cx).await;
+
+        let error = dispatch_action("workspace::NotAnAction", None, &mut cx.to_async())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("workspace::NotAnAction"), "{error}");
+
+        dispatch_action("workspace::SaveAll", Some("{"), &mut cx.to_async())
+            .expect_err("invalid JSON arguments are rejected");
+    }
+
+    #[gpui::test]
+    async fn test_diagnostics(cx: &mut TestAppContext) {
+        open_root(cx).await;
+        let project = cx.update(|cx| active_workspace(cx).unwrap().1.read(cx).project().clone());
+        project.update(cx, |project, cx| {
+            project.lsp_store().update(cx, |lsp_store, cx| {
+                lsp_store
+                    .update_diagnostic_entries(
+                        LanguageServerId(0),
+                        PathBuf::from(path!("/root/a.rs")),
+                        None,
+                        None,
+                        vec![DiagnosticEntry {
+                            range: Unclipped(PointUtf16::new(0, 3))
+                                ..Unclipped(PointUtf16::new(0, 4)),
+                            diagnostic: Diagnostic {
+                                severity: DiagnosticSeverity::WARNING,
+                                message: "unused function".to_string(),
+                                is_primary: true,
+                                source_kind: DiagnosticSourceKind::Pushed,
+                                ..Diagnostic::default()
+                            },
+                        }],
+                        cx,
+                    )
+                    .unwrap();
+            });
+        });
+        let (response_tx, response_rx) = ipc::channel::<CliResponse>().unwrap();
+
+        let status = diagnostics(path!("/root/a.rs"), &response_tx, &mut cx.to_async())
+            .await
+            .unwrap();
+        assert_eq!(status, 0);
+        assert_eq!(
+            receive_json(&response_rx),
+            json!([{
+                "severity": "warning",
+                "message": "unused function",
+                "source": null,
+                "code": null,
+                "start": { "line": 1, "column": 4 },
+                "end": { "line": 1, "column": 5 },
+            }])
+        );
+
+        let error = diagnostics(path!("/other/c.rs"), &response_tx, &mut cx.to_async())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("is not part of any open project"), "{error}");
+    }
+
+    #[gpui::test]
+    async fn test_run_unknown_task(cx: &mut TestAppContext) {
+        open_root(cx).await;
+        let (response_tx, _response_rx) = ipc::channel::<CliResponse>().unwrap();
+
+        let error = run_task("missing", &response_tx, &mut cx.to_async())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("no task with label \"missing\""), "{error}");
+    }
+}
@@ -1,3 +1,4 @@
+use super::cli_queries;
 use crate::handle_open_request;
 use crate::restore_or_create_workspace;
 use anyhow::{Context as _, Result, anyhow};
@@ -453,10 +454,45 @@ pub async fn handle_cli_connection(
                 let status = if open_workspace_result.is_err() { 1 } else { 0 };
                 responses.send(CliResponse::Exit { status }).log_err();
             }
+            CliRequest::ListWorkspaces => {
+                let result = cli_queries::list_workspaces(&responses, cx);
+                finish_cli_query(result, &responses);
+            }
+            CliRequest::ListBuffers => {
+                let result = cli_queries::list_buffers(&responses, cx);
+                finish_cli_query(result, &responses);
+            }
+            CliRequest::DispatchAction { name, arguments } => {
+                let result = cli_queries::dispatch_action(&name, arguments.as_deref(), cx);
+                finish_cli_query(result, &responses);
+            }
+            CliRequest::Diagnostics { path } => {
+                let result = cli_queries::diagnostics(&path, &responses, cx).await;
+                finish_cli_query(result, &responses);
+            }
+            CliRequest::RunTask { label } => {
+                let result = cli_queries::run_task(&label, &responses, cx).await;
+                finish_cli_query(result, &responses);
+            }
         }
     }
 }
 
+fn finish_cli_query(result: Result<i32>, responses: &IpcSender<CliResponse>) {
+    let status = match result {
+        Ok(status) => status,
+        Err(error) => {
+            responses
+                .send(CliResponse::Stderr {
+                    message: format!("{error:#}"),
+                })
+                .log_err();
+            1
+        }
+    };
+    responses.send(CliResponse::Exit { status }).log_err();
+}
+
 async fn open_workspaces(
     paths: Vec<String>,
     diff_paths: Vec<[String; 2]>,